@@ -0,0 +1,35 @@
+//! Compares the nom-based FEN parser with the allocation-free `Fen::parse_fast`.
+//!
+//! Run with `cargo +nightly bench --bench fen`.
+
+#![feature(test)]
+
+extern crate test;
+
+use konig::io::Fen;
+use test::{black_box, Bencher};
+
+const POSITIONS: [&str; 4] = [
+    "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+    "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+    "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+    "rnbqkbnr/pp1ppppp/8/2p5/4P3/8/PPPP1PPP/RNBQKBNR w KQkq c6 0 2",
+];
+
+#[bench]
+fn parse_nom(bencher: &mut Bencher) {
+    bencher.iter(|| {
+        for source in POSITIONS {
+            black_box(Fen::try_from(black_box(source)).unwrap());
+        }
+    });
+}
+
+#[bench]
+fn parse_fast(bencher: &mut Bencher) {
+    bencher.iter(|| {
+        for source in POSITIONS {
+            black_box(Fen::parse_fast(black_box(source)).unwrap());
+        }
+    });
+}
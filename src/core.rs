@@ -1,17 +1,26 @@
 //! Abstract traits for implementing chess and chess variants.
 
+pub mod game;
 pub mod index;
 pub mod r#move;
 pub mod piece;
 pub mod position;
 
 // reexported traits
+pub use index::Colored;
+pub use index::Coordinates;
+pub use game::Game;
 pub use index::Index;
 pub use index::PieceMetric;
 pub use piece::Piece;
+pub use position::Keyed;
 pub use position::Position;
 pub use position::Process;
+pub use position::ProcessNull;
 pub use position::Standard;
 pub use position::Validate;
 pub use r#move::LegalMove;
 pub use r#move::Move;
+pub use r#move::PieceDrop;
+pub use r#move::Play;
+pub use r#move::Promote;
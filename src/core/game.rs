@@ -0,0 +1,51 @@
+//! Traits for representing whole games.
+
+use super::position::{Process, Validate};
+
+/// The legal moves of the positions of a [`Game`].
+pub type GameMove<G> = <<G as Game>::Position as Validate>::LegalMove;
+
+/// Represents a game: a starting position and the sequence of legal moves played from it.
+///
+/// Where [`Position`](super::Position), [`Validate`] and [`Process`] describe a single
+/// state and the moves acting on it, a [`Game`] describes the history of those states,
+/// so that tools like PGN writers and adjudicators can be written generically over
+/// standard chess and its variants alike.
+pub trait Game {
+    /// The positions through which the game passes.
+    type Position: Process;
+
+    /// The possible results of a finished game.
+    type Outcome;
+
+    /// Returns the position in which the game started.
+    fn start(&self) -> &Self::Position;
+
+    /// Returns the current position of the game.
+    fn position(&self) -> &Self::Position;
+
+    /// Returns the moves played so far, in order.
+    fn moves(&self) -> &[GameMove<Self>];
+
+    /// Returns the outcome of the game if it is over, and `None` otherwise.
+    fn outcome(&self) -> Option<Self::Outcome>;
+
+    /// Validates `candidate` in the current position and plays it.
+    fn play(
+        &mut self,
+        candidate: <Self::Position as Validate>::Move,
+    ) -> Result<GameMove<Self>, <Self::Position as Validate>::ValidationError>;
+
+    /// Takes back the last move, returning it.
+    fn undo(&mut self) -> Option<GameMove<Self>>;
+
+    /// Returns `true` if the game is over.
+    fn is_over(&self) -> bool {
+        self.outcome().is_some()
+    }
+
+    /// Returns the number of plies (i.e. half-moves) played so far.
+    fn ply_count(&self) -> usize {
+        self.moves().len()
+    }
+}
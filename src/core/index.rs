@@ -4,14 +4,83 @@ use thiserror::Error;
 
 /// The result of the incorrect creation or usage of
 /// a particular index.
-#[derive(Error, Debug, Eq, PartialEq)]
+///
+/// The erroneous value has type `T`, and errors over the common choices
+/// of `T` convert into one another with [`From`], e.g. an `IndexError<u8>`
+/// into an `IndexError<usize>` or an `IndexError<String>`.
+#[derive(Error, Debug, Clone, Eq, PartialEq)]
 pub enum IndexError<T> {
     /// The result of using a valid index in an invalid context.
-    #[error("Received an out-of-bounds index: {0}")]
-    OutOfBounds(T),
+    #[error("Received an out-of-bounds index: {index} (expected less than {bound})")]
+    OutOfBounds {
+        /// The invalid index.
+        index: T,
+        /// The exclusive upper bound of the valid indices.
+        bound: usize,
+    },
     /// The result of attempting to construct an invalid index.
     #[error("Received an index with invalid formatting: {0}")]
     InvalidFormat(T),
+    /// The result of giving coordinates which lie off a rectangular board.
+    #[error("Received coordinates ({file}, {rank}) off a {files}x{ranks} board")]
+    OffBoard {
+        /// The invalid file coordinate.
+        file: usize,
+        /// The invalid rank coordinate.
+        rank: usize,
+        /// The number of files on the board.
+        files: usize,
+        /// The number of ranks on the board.
+        ranks: usize,
+    },
+}
+
+impl<T> IndexError<T> {
+    /// Converts the erroneous value of `self` with `f`, keeping its context.
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> IndexError<U> {
+        match self {
+            IndexError::OutOfBounds { index, bound } => IndexError::OutOfBounds {
+                index: f(index),
+                bound,
+            },
+            IndexError::InvalidFormat(value) => IndexError::InvalidFormat(f(value)),
+            IndexError::OffBoard {
+                file,
+                rank,
+                files,
+                ranks,
+            } => IndexError::OffBoard {
+                file,
+                rank,
+                files,
+                ranks,
+            },
+        }
+    }
+}
+
+impl From<IndexError<u8>> for IndexError<usize> {
+    fn from(value: IndexError<u8>) -> Self {
+        value.map(usize::from)
+    }
+}
+
+impl From<IndexError<u8>> for IndexError<String> {
+    fn from(value: IndexError<u8>) -> Self {
+        value.map(|index| index.to_string())
+    }
+}
+
+impl From<IndexError<usize>> for IndexError<String> {
+    fn from(value: IndexError<usize>) -> Self {
+        value.map(|index| index.to_string())
+    }
+}
+
+impl From<IndexError<&str>> for IndexError<String> {
+    fn from(value: IndexError<&str>) -> Self {
+        value.map(String::from)
+    }
 }
 
 /// Represents a particular position on a given board.
@@ -47,6 +116,57 @@ pub trait Index {
         Self: Sized;
 }
 
+/// Represents an index on a rectangular board, addressed by integer
+/// (file, rank) coordinates.
+///
+/// Coordinates start at `(0, 0)`, which on a standard board is a1, and
+/// increase towards the last file and rank. This lets generic algorithms,
+/// e.g. for geometry or rendering, work across boards of any dimensions
+/// without knowing how their indices are stored.
+pub trait Coordinates: Index {
+    /// The number of files on the board.
+    const FILES: usize;
+    /// The number of ranks on the board.
+    const RANKS: usize;
+
+    /// Returns the (file, rank) coordinates of `self`.
+    fn coordinates(&self) -> (usize, usize);
+
+    /// Constructs the index at the given coordinates, or returns `None`
+    /// if they lie off the board.
+    fn from_coordinates(file: usize, rank: usize) -> Option<Self>
+    where
+        Self: Sized;
+
+    /// Constructs the index at the given coordinates, as in [`Coordinates::from_coordinates`],
+    /// but describes the dimensions of the board if they lie off it.
+    fn try_from_coordinates(file: usize, rank: usize) -> Result<Self, IndexError<usize>>
+    where
+        Self: Sized,
+    {
+        Self::from_coordinates(file, rank).ok_or(IndexError::OffBoard {
+            file,
+            rank,
+            files: Self::FILES,
+            ranks: Self::RANKS,
+        })
+    }
+
+    /// Returns the number of king moves between `a` and `b`, which is
+    /// the [Chebyshev distance](https://en.wikipedia.org/wiki/Chebyshev_distance).
+    fn chebyshev_distance(a: &Self, b: &Self) -> usize {
+        let ((a_file, a_rank), (b_file, b_rank)) = (a.coordinates(), b.coordinates());
+        a_file.abs_diff(b_file).max(a_rank.abs_diff(b_rank))
+    }
+
+    /// Returns the number of rook steps between `a` and `b`, which is
+    /// the [Manhattan distance](https://en.wikipedia.org/wiki/Taxicab_geometry).
+    fn manhattan_distance(a: &Self, b: &Self) -> usize {
+        let ((a_file, a_rank), (b_file, b_rank)) = (a.coordinates(), b.coordinates());
+        a_file.abs_diff(b_file) + a_rank.abs_diff(b_rank)
+    }
+}
+
 /// Represents an index with a distinct per-piece
 /// notion of distance.
 ///
@@ -75,3 +195,15 @@ pub trait PieceMetric: Index {
     where
         Self: Sized;
 }
+
+/// Represents a value with an intrinsic color, such as a square of a checkered board.
+///
+/// This is distinct from the color of a [`Piece`](super::Piece), which identifies
+/// the side it belongs to; the two need not even share a type.
+pub trait Colored {
+    /// The set of colors a value may have.
+    type Color: Eq;
+
+    /// Returns the color of `self`.
+    fn color(&self) -> Self::Color;
+}
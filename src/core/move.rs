@@ -17,11 +17,46 @@ pub trait IllegalMoveError: Error {
     /// The associated [`Index`] type (metric space) in which moves act.
     type Index: Index;
     /// The potentially illegal candidate moves.
-    type Move: Move<Index = Self::Index, Board = Self::Board>;
+    type Move: Play<Index = Self::Index, Board = Self::Board>;
     /// The verified-legal moves.
     type LegalMove: LegalMove<Index = Self::Index, Move = Self::Move>;
 }
 
+/// Represents anything which a side may play on its turn on the associated [`Validate`].
+///
+/// Every [`Move`] is a play which takes a piece from its source index. A play with
+/// no [`origin`](Play::origin) instead puts a new piece onto the board, as piece
+/// drops do in Crazyhouse and Bughouse; boards with drops should implement this
+/// trait for their moves directly, rather than fabricating a source index for each drop.
+pub trait Play {
+    /// A [`Validate`] against which plays can be checked.
+    type Board: Validate<Index = Self::Index>;
+    /// An [`Index`] metric space in which plays act.
+    type Index: Index;
+
+    /// Returns the [`Index`] from which a piece is taken, or `None` if a piece is dropped.
+    fn origin(&self) -> Option<Self::Index>;
+    /// Returns the [`Index`] on which the played piece ends up.
+    fn destination(&self) -> Self::Index;
+    /// Returns `true` if the play drops a piece onto the board.
+    fn is_drop(&self) -> bool {
+        self.origin().is_none()
+    }
+}
+
+impl<M: Move> Play for M {
+    type Board = M::Board;
+    type Index = M::Index;
+
+    fn origin(&self) -> Option<Self::Index> {
+        Some(self.source())
+    }
+
+    fn destination(&self) -> Self::Index {
+        self.target()
+    }
+}
+
 /// Represents a (potentially illegal) move on the associated [`Validate`].
 ///
 /// A [`Move`] is essentially a pair of points in the metric space defined
@@ -50,9 +85,38 @@ pub trait Move {
 /// method should be considered the source of truth for "completely legal"
 /// moves within an implementation. In any other context, when receiving a
 /// [`LegalMove`], you can and *should* assume it to be valid.
-pub trait LegalMove: Move {
-    /// The associated [`Move`] type of which [`LegalMove`]s are a subset.
-    type Move: Move<Index = Self::Index, Board = Self::Board>;
+pub trait LegalMove: Play {
+    /// The associated [`Play`] type of which [`LegalMove`]s are a subset.
+    type Move: Play<Index = Self::Index, Board = Self::Board>;
+}
+
+/// Represents a [`Move`] which may promote the moving piece, as pawns do in standard chess.
+///
+/// Promotions are not part of [`Move`] itself, since a move is only a pair of
+/// [indices](Index) and many variants have no notion of promotion at all.
+pub trait Promote: Move {
+    /// The kinds of piece to which a move can promote.
+    type Kind: Eq;
+
+    /// Returns the kind of piece to which the move promotes, if any.
+    fn promotion(&self) -> Option<Self::Kind>;
+
+    /// Returns `true` if the move promotes the moving piece.
+    fn is_promotion(&self) -> bool {
+        self.promotion().is_some()
+    }
+}
+
+/// Represents a [`Play`] which may drop a piece onto the board, as in Crazyhouse.
+///
+/// Like [`Promote`], this is kept apart from [`Play`] itself, so that only the
+/// variants which have drops need to describe the dropped pieces.
+pub trait PieceDrop: Play {
+    /// The kinds of piece which can be dropped.
+    type Kind: Eq;
+
+    /// Returns the kind of piece which the play drops, or `None` if it moves a piece.
+    fn dropped(&self) -> Option<Self::Kind>;
 }
 
 /// Crate-internal constructor trait for [`LegalMove`]s.
@@ -60,7 +124,8 @@ pub trait LegalMove: Move {
 /// The visibility modifier here prevents a crate consumer from
 /// constructing an invalid [`LegalMove`].
 pub(crate) trait WrapMove: LegalMove {
-    /// Directly wraps a [`Move`] with a [`LegalMove`],
+    /// Directly wraps a [`Move`] with a [`LegalMove`], recording whatever
+    /// the [`LegalMove`] needs from the `board` on which it is played,
     /// without a validation step.
-    fn wrap(value: Self::Move) -> Self;
+    fn wrap(board: &Self::Board, value: Self::Move) -> Self;
 }
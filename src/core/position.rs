@@ -4,7 +4,7 @@ use crate::io::San;
 
 use super::index::Index;
 use super::piece::Piece;
-use super::r#move::{IllegalMoveError, LegalMove, Move};
+use super::r#move::{IllegalMoveError, LegalMove, Play};
 
 /// Represents a static view into a single board position, with
 /// no notion of moves or move legality.
@@ -60,8 +60,8 @@ pub trait Standard: Position<Piece: Piece<Color = Self::Color>> {
 /// manually, but is most effectively implemented with an
 /// opaque type.
 pub trait Validate: Position {
-    /// Represents a move which may or may not be legal.
-    type Move: Move<Board = Self, Index = Self::Index>;
+    /// Represents a move which may or may not be legal, and which may drop a piece.
+    type Move: Play<Board = Self, Index = Self::Index>;
     /// Represents a move which has been confirmed to be legal.
     type LegalMove: LegalMove<Board = Self, Index = Self::Index>;
     /// The error created when move validation fails.
@@ -93,7 +93,7 @@ pub trait Process: Validate {
     ///
     /// Note that the only valid source for the candidate move is from [`Validate`]'s
     /// `validate` method, and in general you should prefer `validate_and_process` for
-    /// updating the board's state with a single [`Move`].
+    /// updating the board's state with a single [`Move`](Validate::Move).
     fn process(&self, candidate: Self::LegalMove) -> Self
     where
         Self: Sized;
@@ -109,6 +109,35 @@ pub trait Process: Validate {
     }
 }
 
+/// Represents a board on which the side to move may pass, i.e. play a null move.
+///
+/// Null moves are never legal in a game, but are needed by engines performing
+/// null-move pruning and by analysis tools asking what the opponent would do if
+/// it were their turn.
+pub trait ProcessNull: Process {
+    /// Passes the turn to the other side and returns the new state.
+    ///
+    /// Implementations should clear any en passant target square and advance any
+    /// move clocks they track, exactly as a quiet move would. The result is only a
+    /// legal position if the side to move was not in check.
+    fn process_null(&self) -> Self
+    where
+        Self: Sized;
+}
+
+/// Represents a position which can be summarised by a compact key, usually a hash.
+///
+/// Equal positions must have equal keys, but distinct positions may share a key;
+/// transposition tables and repetition trackers written against this trait should
+/// either tolerate such collisions or confirm matches by other means.
+pub trait Keyed: Position {
+    /// The type of the keys, e.g. `u64` for a Zobrist hash.
+    type Key: Copy + Eq + std::hash::Hash;
+
+    /// Returns the key of the position.
+    fn key(&self) -> Self::Key;
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -145,6 +174,34 @@ mod tests {
         > = Box::new(Board::default());
     }
 
+    #[test]
+    fn promote_is_object_safe() {
+        use crate::core::Promote;
+        use crate::standard::piece::PieceKind;
+
+        let square = |name| Square::try_from(name).unwrap();
+        let moves: [Box<dyn Promote<Board = Board, Index = Square, Kind = PieceKind>>; 2] = [
+            Box::new(Move::from((square("e7"), square("e8"))).with_promotion(PieceKind::Knight)),
+            Box::new(Move::from((square("e2"), square("e4")))),
+        ];
+
+        assert_eq!(moves[0].promotion(), Some(PieceKind::Knight));
+        assert!(!moves[1].is_promotion());
+    }
+
+    #[test]
+    fn play_is_object_safe() {
+        use crate::core::Play;
+
+        let square = |name| Square::try_from(name).unwrap();
+        let play: Box<dyn Play<Board = Board, Index = Square>> =
+            Box::new(Move::from((square("g1"), square("f3"))));
+
+        assert_eq!(play.origin(), Some(square("g1")));
+        assert_eq!(play.destination(), square("f3"));
+        assert!(!play.is_drop());
+    }
+
     #[test]
     fn process_is_object_safe() {
         let _process: Box<
@@ -0,0 +1,138 @@
+//! A single error type for applications using several parts of the crate.
+
+use std::fmt::Display;
+
+use nom::error::VerboseError;
+use thiserror::Error;
+
+use crate::core::index::IndexError;
+use crate::io::epd::{EpdError, EpdReadError};
+use crate::io::fen::{fast::FastFenError, FenBuildError, FenError};
+use crate::io::pgn::PgnError;
+use crate::io::san;
+use crate::standard::{r#move::IllegalMoveError, ParsePieceError, SetupError};
+
+/// Any error produced by this crate's parsers and rules.
+///
+/// The errors of the FEN, EPD, SAN and PGN parsers, of indices, and of position and
+/// move validation convert into an `Error` with [`From`], so the `?` operator can
+/// mix them in a function returning `Result<T, Error>`.
+/// Errors which borrow their input, such as [`FenError`] and [`san::ParseError`],
+/// are converted into their messages, so that an `Error` never borrows.
+#[derive(Debug, Error)]
+pub enum Error {
+    /// Results when reading or writing fails.
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    /// Results when a FEN string is invalid.
+    #[error("invalid FEN string: {0}")]
+    Fen(String),
+    /// Results when an EPD record is invalid.
+    #[error(transparent)]
+    Epd(#[from] EpdError),
+    /// Results when an EPD file cannot be read.
+    #[error(transparent)]
+    EpdRead(#[from] EpdReadError),
+    /// Results when a SAN literal is invalid.
+    #[error("invalid SAN literal: {0}")]
+    San(String),
+    /// Results when a PGN game cannot be read or replayed.
+    #[error(transparent)]
+    Pgn(#[from] PgnError),
+    /// Results when an index, such as a square, is invalid.
+    #[error("invalid index: {0}")]
+    Index(String),
+    /// Results when a piece cannot be parsed.
+    #[error(transparent)]
+    Piece(#[from] ParsePieceError),
+    /// Results when a position could not arise in a game.
+    #[error(transparent)]
+    Setup(#[from] SetupError),
+    /// Results when a move is not legal.
+    #[error(transparent)]
+    IllegalMove(#[from] IllegalMoveError),
+}
+
+impl From<VerboseError<&str>> for Error {
+    /// Converts the error of [`Fen::try_from`](crate::io::Fen) and [`Fen::parse_placement`](crate::io::Fen::parse_placement).
+    fn from(value: VerboseError<&str>) -> Self {
+        Self::Fen(value.to_string())
+    }
+}
+
+impl From<FenError<'_>> for Error {
+    fn from(value: FenError<'_>) -> Self {
+        Self::Fen(value.to_string())
+    }
+}
+
+impl From<FastFenError> for Error {
+    fn from(value: FastFenError) -> Self {
+        Self::Fen(value.to_string())
+    }
+}
+
+impl From<FenBuildError> for Error {
+    fn from(value: FenBuildError) -> Self {
+        Self::Fen(value.to_string())
+    }
+}
+
+impl From<san::ParseError<'_>> for Error {
+    fn from(value: san::ParseError<'_>) -> Self {
+        Self::San(value.to_string())
+    }
+}
+
+impl<T: Display> From<IndexError<T>> for Error {
+    fn from(value: IndexError<T>) -> Self {
+        Self::Index(value.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::Validate;
+    use crate::io::epd::{Epd, EpdReader};
+    use crate::io::{Fen, San};
+    use crate::standard::{Game, Square};
+
+    /// Plays `san` from the position of `fen`, using `?` on the errors of every step.
+    fn play(fen: &str, san: &str) -> Result<Game, Error> {
+        let board = Fen::try_from(fen)?.to_validated_board()?;
+        let mut game = Game::from_board(board);
+        let legal_move = game.board().validate_san(San::try_from(san)?)?;
+        game.play(legal_move.into())?;
+        Ok(game)
+    }
+
+    #[test]
+    fn errors_of_every_subsystem_convert() {
+        let start = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+        assert!(play(start, "e4").is_ok());
+        assert!(matches!(play("8/8 w - - 0 1", "e4"), Err(Error::Fen(_))));
+        assert!(matches!(
+            play("8/8/8/8/8/8/8/8 w - - 0 1", "e4"),
+            Err(Error::Setup(_))
+        ));
+        assert!(matches!(play(start, "Zf3"), Err(Error::San(_))));
+        assert!(matches!(play(start, "e5"), Err(Error::IllegalMove(_))));
+
+        let error = Error::from(Square::try_from("z9").unwrap_err());
+        assert!(matches!(error, Error::Index(_)));
+        assert_eq!(
+            Error::from(Fen::parse_fast("").unwrap_err()).to_string(),
+            "invalid FEN string: invalid piece placement at byte 0"
+        );
+
+        let epd = |source: &str| -> Result<Epd, Error> { Ok(Epd::try_from(source)?) };
+        assert!(matches!(epd("8/8 w - -"), Err(Error::Epd(_))));
+        let mut reader = EpdReader::new("8/8 w - -\n".as_bytes());
+        let error = Error::from(reader.next().unwrap().unwrap_err());
+        assert!(matches!(error, Error::EpdRead(_)));
+        assert!(error
+            .to_string()
+            .starts_with("invalid EPD record on line 1"));
+    }
+}
@@ -2,26 +2,54 @@
 
 // some modules temporarily hidden while refactoring
 
+/// Provides utilities for the Arena opening book format (ABK).
+pub mod abk;
+
+/// Provides a common interface over opening book formats.
+pub mod book;
+
 /// Provides utilities for the Extended Position Description (EPD) format.
-mod epd;
+pub mod epd;
+
+/// Provides the serial protocol of DGT electronic boards.
+#[cfg(feature = "dgt")]
+pub mod dgt;
 
 /// Provides utilities for Forsyth-Edwards Notation (FEN).
 pub mod fen;
 
+/// Provides types mirroring the JSON of the Lichess API.
+#[cfg(feature = "lichess")]
+pub mod lichess;
+
+/// Provides a common interface over the notations of moves, e.g. SAN and UCI.
+pub mod notation;
+
 /// Provides utilities for Portable Game Notation (PGN).
-mod pgn;
+pub mod pgn;
+
+/// Provides utilities for the Polyglot opening book format.
+pub mod polyglot;
 
 /// Provides utilities for Standard Algebraic Notation (SAN).
 pub mod san;
 
+/// Provides decoding of the TCN move encoding used by chess.com.
+pub mod tcn;
+
+/// Provides utilities for the Universal Chess Interface (UCI).
+pub mod uci;
+
 // NOTE: this is a list of standards to look at implementing after the core four
 // - FEEN: https://github.com/sashite/specs/blob/main/forsyth-edwards-expanded-notation.md
 // - X-FEN: https://en.wikipedia.org/wiki/X-FEN
 // - Shredder-FEN: https://www.chessprogramming.org/Forsyth-Edwards_Notation#Shredder-FEN
 //      - This probably requires an implementation of Chess960
-// - UCI: https://www.chessprogramming.org/UCI
-//      - This really requires a full game implementation with a playing AI first.
-// - ICCF numeric notation: https://en.wikipedia.org/wiki/ICCF_numeric_notation
 
 pub use fen::Fen;
-pub use san::San;
+pub use fen::FenBuilder;
+pub use notation::Notation;
+#[cfg(feature = "rayon")]
+pub use fen::ParsedFens;
+pub use pgn::{PgnGame, PgnReader};
+pub use san::{San, SanLocale, SanVariant};
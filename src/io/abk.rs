@@ -0,0 +1,324 @@
+use std::cmp::Reverse;
+use std::collections::{HashMap, HashSet};
+use std::io::Read;
+
+use super::book::{BookError, BookMove, OpeningBook};
+use crate::core::{Process, Validate};
+use crate::standard::{Board, LegalMove, Move, PieceKind, Square};
+
+/// The size of a single ABK entry in bytes.
+const ENTRY_SIZE: usize = 28;
+
+/// The index of the entry holding the first move from the starting position;
+/// the entries before it hold the header of the book.
+const ROOT_INDEX: usize = 900;
+
+/// A single 28-byte entry of an ABK book.
+///
+/// ABK books are trees of moves from the starting position: each entry holds a
+/// move, statistics about the games in which it was played, and the indices of
+/// its first reply and of the next alternative to it. Every integer is stored
+/// in little-endian byte order, and a negative index marks the end of a chain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AbkEntry {
+    /// The source square of the move, counting from a1 along the ranks.
+    pub from: u8,
+    /// The target square of the move, counting from a1 along the ranks.
+    pub to: u8,
+    /// The promotion piece, or 0 if the move is not a promotion; otherwise its
+    /// absolute value is 1 for a rook, 2 for a knight, 3 for a bishop, or 4 for
+    /// a queen, and some books make it negative for black's promotions.
+    pub promotion: i8,
+    /// The priority assigned to the move by the author of the book.
+    pub priority: u8,
+    /// The number of games in which the move was played.
+    pub games: i32,
+    /// The number of those games which were won.
+    pub won_games: i32,
+    /// The number of those games which were lost.
+    pub lost_games: i32,
+    /// Format-specific flags.
+    pub flags: i32,
+    /// The index of the first entry replying to this move.
+    pub first_child: i32,
+    /// The index of the next alternative to this move.
+    pub next_sibling: i32,
+}
+
+impl AbkEntry {
+    /// Decodes an entry from the on-disk format.
+    pub fn from_bytes(bytes: [u8; ENTRY_SIZE]) -> Self {
+        let int = |offset: usize| {
+            i32::from_le_bytes([
+                bytes[offset],
+                bytes[offset + 1],
+                bytes[offset + 2],
+                bytes[offset + 3],
+            ])
+        };
+
+        Self {
+            from: bytes[0],
+            to: bytes[1],
+            promotion: bytes[2] as i8,
+            priority: bytes[3],
+            games: int(4),
+            won_games: int(8),
+            lost_games: int(12),
+            flags: int(16),
+            first_child: int(20),
+            next_sibling: int(24),
+        }
+    }
+
+    /// Returns the move described by `self`, or `None` if its squares or
+    /// promotion piece are out of range.
+    pub fn to_move(&self) -> Option<Move> {
+        let source = Square::try_from(self.from).ok()?;
+        let target = Square::try_from(self.to).ok()?;
+        let candidate = Move::from((source, target));
+        match self.promotion.unsigned_abs() {
+            0 => Some(candidate),
+            1 => Some(candidate.with_promotion(PieceKind::Rook)),
+            2 => Some(candidate.with_promotion(PieceKind::Knight)),
+            3 => Some(candidate.with_promotion(PieceKind::Bishop)),
+            4 => Some(candidate.with_promotion(PieceKind::Queen)),
+            _ => None,
+        }
+    }
+
+    /// Encodes `self` in the on-disk format.
+    pub fn to_bytes(&self) -> [u8; ENTRY_SIZE] {
+        let mut bytes = [0; ENTRY_SIZE];
+        bytes[0] = self.from;
+        bytes[1] = self.to;
+        bytes[2] = self.promotion as u8;
+        bytes[3] = self.priority;
+
+        let ints = [
+            self.games,
+            self.won_games,
+            self.lost_games,
+            self.flags,
+            self.first_child,
+            self.next_sibling,
+        ];
+
+        for (index, value) in ints.into_iter().enumerate() {
+            let offset = 4 + 4 * index;
+            bytes[offset..offset + 4].copy_from_slice(&value.to_le_bytes());
+        }
+
+        bytes
+    }
+}
+
+/// An Arena ABK opening book held in memory.
+///
+/// Since ABK books are trees rather than tables of positions, the tree is walked
+/// once when the book is read, and every position it reaches is indexed by its
+/// [Zobrist hash](Board::zobrist). Positions reached by several move orders
+/// therefore combine the moves of every branch, and the weight of each move is
+/// the total number of games in which it was played.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AbkBook {
+    positions: HashMap<u64, Vec<BookMove>>,
+}
+
+impl AbkBook {
+    /// Constructs a book from the raw bytes of an `.abk` file.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, BookError> {
+        if !bytes.chunks_exact(ENTRY_SIZE).remainder().is_empty()
+            || bytes.len() < ROOT_INDEX * ENTRY_SIZE
+        {
+            return Err(BookError::InvalidLength(bytes.len()));
+        }
+
+        let entry_count = bytes.len() / ENTRY_SIZE;
+        let entry = |index: i32| -> Result<Option<AbkEntry>, BookError> {
+            let Ok(index) = usize::try_from(index) else {
+                return Ok(None);
+            };
+
+            if index < ROOT_INDEX || index >= entry_count {
+                return Err(BookError::InvalidEntry(index));
+            }
+
+            let offset = index * ENTRY_SIZE;
+            let chunk = bytes[offset..offset + ENTRY_SIZE].try_into().unwrap();
+            Ok(Some(AbkEntry::from_bytes(chunk)))
+        };
+
+        let mut book = Self::default();
+        if entry_count == ROOT_INDEX {
+            return Ok(book);
+        }
+
+        let mut visited = HashSet::new();
+        let mut pending = vec![(ROOT_INDEX as i32, Board::default())];
+
+        while let Some((first, board)) = pending.pop() {
+            let mut index = first;
+            while let Some(current) = entry(index)? {
+                // a malformed book could link its entries into a cycle
+                if !visited.insert(index) {
+                    break;
+                }
+
+                let invalid = || BookError::InvalidEntry(index as usize);
+                let candidate = current.to_move().ok_or_else(invalid)?;
+                let legal_move = board.validate(candidate).map_err(|_| invalid())?;
+
+                book.add(&board, legal_move, current.games.max(0) as u32);
+                if current.first_child >= 0 {
+                    pending.push((current.first_child, board.process(legal_move)));
+                }
+
+                index = current.next_sibling;
+            }
+        }
+
+        for moves in book.positions.values_mut() {
+            moves.sort_by_key(|book_move| Reverse(book_move.weight));
+        }
+
+        Ok(book)
+    }
+
+    /// Reads a book in the `.abk` format from `reader`.
+    pub fn read_from<R: Read>(mut reader: R) -> Result<Self, BookError> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes)?;
+        Self::from_bytes(&bytes)
+    }
+
+    /// Returns the number of distinct positions in the book.
+    pub fn len(&self) -> usize {
+        self.positions.len()
+    }
+
+    /// Returns `true` if the book has no positions.
+    pub fn is_empty(&self) -> bool {
+        self.positions.is_empty()
+    }
+
+    /// Records `legal_move` in the position of `board`, merging it
+    /// with the same move if it was reached by another branch.
+    fn add(&mut self, board: &Board, legal_move: LegalMove, weight: u32) {
+        let moves = self.positions.entry(board.zobrist()).or_default();
+        match moves
+            .iter_mut()
+            .find(|known| known.legal_move == legal_move)
+        {
+            Some(known) => known.weight = known.weight.saturating_add(weight),
+            None => moves.push(BookMove { legal_move, weight }),
+        }
+    }
+}
+
+impl OpeningBook for AbkBook {
+    fn moves(&self, board: &Board) -> Vec<BookMove> {
+        self.positions
+            .get(&board.zobrist())
+            .cloned()
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::Fen;
+    use crate::standard::r#move::mv;
+
+    fn entry(from: &str, to: &str, games: i32, first_child: i32, next_sibling: i32) -> AbkEntry {
+        AbkEntry {
+            from: usize::from(Square::try_from(from).unwrap()) as u8,
+            to: usize::from(Square::try_from(to).unwrap()) as u8,
+            promotion: 0,
+            priority: 0,
+            games,
+            won_games: 0,
+            lost_games: 0,
+            flags: 0,
+            first_child,
+            next_sibling,
+        }
+    }
+
+    fn book_bytes(entries: &[AbkEntry]) -> Vec<u8> {
+        let mut bytes = vec![0; ROOT_INDEX * ENTRY_SIZE];
+        for entry in entries {
+            bytes.extend_from_slice(&entry.to_bytes());
+        }
+
+        bytes
+    }
+
+    #[test]
+    fn tree_is_mapped_onto_positions() {
+        // 1. e4 (1... e5, 1... c5) and 1. d4
+        let bytes = book_bytes(&[
+            entry("e2", "e4", 10, 902, 901),
+            entry("d2", "d4", 4, -1, -1),
+            entry("e7", "e5", 3, -1, 903),
+            entry("c7", "c5", 6, -1, -1),
+        ]);
+
+        let book = AbkBook::read_from(bytes.as_slice()).unwrap();
+        assert_eq!(book.len(), 2);
+
+        let start = Board::default();
+        let moves = book.moves(&start);
+        assert_eq!(moves.len(), 2);
+        assert_eq!(Move::from(moves[0].legal_move), mv("e2", "e4"));
+        assert_eq!(moves[0].weight, 10);
+
+        let after_e4 = start.process(moves[0].legal_move);
+        let replies = book.moves(&after_e4);
+        assert_eq!(Move::from(replies[0].legal_move), mv("c7", "c5"));
+        assert_eq!(replies[1].weight, 3);
+        assert!(!book.contains(&after_e4.process(replies[0].legal_move)));
+    }
+
+    #[test]
+    fn promotions_are_decoded() {
+        let board = Fen::try_from("n3k3/1P6/8/8/8/8/8/4K3 w - - 0 1")
+            .unwrap()
+            .to_standard_board();
+
+        let mut underpromotion = entry("b7", "a8", 1, -1, -1);
+        underpromotion.promotion = 2;
+        let expected = mv("b7", "a8").with_promotion(PieceKind::Knight);
+        assert_eq!(underpromotion.to_move(), Some(expected));
+        assert_eq!(
+            board
+                .validate(underpromotion.to_move().unwrap())
+                .unwrap()
+                .promotion(),
+            Some(PieceKind::Knight)
+        );
+
+        underpromotion.promotion = -1;
+        assert_eq!(
+            underpromotion.to_move().unwrap().promotion(),
+            Some(PieceKind::Rook)
+        );
+        underpromotion.promotion = 5;
+        assert_eq!(underpromotion.to_move(), None);
+    }
+
+    #[test]
+    fn malformed_books_are_rejected() {
+        assert!(AbkBook::from_bytes(&[0; 100]).is_err());
+        // the reply refers to an entry past the end of the book
+        let dangling = book_bytes(&[entry("e2", "e4", 1, 950, -1)]);
+        assert!(AbkBook::from_bytes(&dangling).is_err());
+        // e2e5 is not a legal move
+        let illegal = book_bytes(&[entry("e2", "e5", 1, -1, -1)]);
+        assert!(AbkBook::from_bytes(&illegal).is_err());
+        // a header on its own is an empty book
+        let empty = AbkBook::from_bytes(&book_bytes(&[])).unwrap();
+        assert!(empty.is_empty());
+    }
+}
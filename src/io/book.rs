@@ -0,0 +1,45 @@
+use crate::standard::{Board, LegalMove};
+use thiserror::Error;
+
+/// Results when an opening book cannot be read.
+#[derive(Debug, Error)]
+pub enum BookError {
+    /// Results when the underlying reader fails.
+    #[error("failed to read the opening book: {0}")]
+    Io(#[from] std::io::Error),
+    /// Results when the book has a length which is not valid for its format.
+    #[error("invalid opening book length: {0} bytes")]
+    InvalidLength(usize),
+    /// Results when an entry of the book is malformed, e.g. if it
+    /// describes an illegal move or refers to a nonexistent entry.
+    #[error("invalid opening book entry at index {0}")]
+    InvalidEntry(usize),
+}
+
+/// A move suggested by an [`OpeningBook`], along with its relative weight.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BookMove {
+    /// The suggested move, which is legal in the queried position.
+    pub legal_move: LegalMove,
+    /// The weight of the move relative to the other moves in the same position;
+    /// the meaning of the scale depends on the format of the book.
+    pub weight: u32,
+}
+
+/// A common interface over opening book formats, so that
+/// applications can switch between formats transparently.
+pub trait OpeningBook {
+    /// Returns every move the book suggests in the position of `board`, in descending
+    /// order of weight. If the book has no entries for the position, this is empty.
+    fn moves(&self, board: &Board) -> Vec<BookMove>;
+
+    /// Returns the move with the highest weight in the position of `board`, if any.
+    fn best_move(&self, board: &Board) -> Option<BookMove> {
+        self.moves(board).into_iter().next()
+    }
+
+    /// Returns `true` if the book has any entries for the position of `board`.
+    fn contains(&self, board: &Board) -> bool {
+        !self.moves(board).is_empty()
+    }
+}
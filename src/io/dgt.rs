@@ -0,0 +1,339 @@
+use std::io::{self, Read, Write};
+
+use thiserror::Error;
+
+use crate::core::Standard as _;
+use crate::standard::{Board, Game, LegalMove, Piece, Square};
+
+/// The bit set in the first byte of every message sent by a board.
+const MESSAGE_BIT: u8 = 0x80;
+
+/// The identifier of a [`DgtMessage::BoardDump`].
+const BOARD_DUMP: u8 = MESSAGE_BIT | 0x06;
+
+/// The identifier of a [`DgtMessage::FieldUpdate`].
+const FIELD_UPDATE: u8 = MESSAGE_BIT | 0x0e;
+
+/// The identifier of a [`DgtMessage::Version`].
+const VERSION: u8 = MESSAGE_BIT | 0x13;
+
+/// The number of bytes in the header of a message: its identifier and its length.
+const HEADER_LENGTH: usize = 3;
+
+/// The pieces in the order of their codes, which start from 1.
+const PIECES: [Piece; 12] = [
+    Piece::WhitePawn,
+    Piece::WhiteRook,
+    Piece::WhiteKnight,
+    Piece::WhiteBishop,
+    Piece::WhiteKing,
+    Piece::WhiteQueen,
+    Piece::BlackPawn,
+    Piece::BlackRook,
+    Piece::BlackKnight,
+    Piece::BlackBishop,
+    Piece::BlackKing,
+    Piece::BlackQueen,
+];
+
+/// Results when a message from a DGT board cannot be read.
+#[derive(Debug, Error)]
+pub enum DgtError {
+    /// Results when the connection to the board fails.
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    /// Results when the first byte of a message is not a message identifier.
+    #[error("invalid DGT message identifier {0:#04x}")]
+    InvalidIdentifier(u8),
+    /// Results when a message does not have the length its identifier requires.
+    #[error("invalid length {length} for DGT message {id:#04x}")]
+    InvalidLength {
+        /// The identifier of the message.
+        id: u8,
+        /// The length of the message, including its header.
+        length: usize,
+    },
+    /// Results when a field is not one of the 64 squares.
+    #[error("invalid DGT field {0}")]
+    InvalidField(u8),
+    /// Results when a piece code is not that of a standard piece.
+    #[error("invalid DGT piece code {0:#04x}")]
+    InvalidPiece(u8),
+}
+
+/// The commands which may be sent to a DGT board.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u8)]
+pub enum DgtCommand {
+    /// Puts the board into its idle mode.
+    Reset = 0x40,
+    /// Asks for a [`DgtMessage::BoardDump`].
+    SendBoard = 0x42,
+    /// Asks for a [`DgtMessage::FieldUpdate`] whenever a piece is lifted or placed.
+    SendUpdate = 0x43,
+    /// Asks for field updates, as with [`DgtCommand::SendUpdate`], and a board dump.
+    SendUpdateBoard = 0x44,
+    /// Asks for field updates, as with [`DgtCommand::SendUpdate`], but without clock updates.
+    SendUpdateNice = 0x4b,
+    /// Asks for a [`DgtMessage::Version`].
+    SendVersion = 0x4d,
+}
+
+/// A message sent by a DGT board.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DgtMessage {
+    /// The pieces on every square, indexed from a1 to h8.
+    BoardDump([Option<Piece>; 64]),
+    /// A piece was placed on `square`, or lifted from it if `piece` is `None`.
+    FieldUpdate {
+        /// The square which changed.
+        square: Square,
+        /// The piece now on the square.
+        piece: Option<Piece>,
+    },
+    /// The version of the board's firmware.
+    Version {
+        /// The major version.
+        major: u8,
+        /// The minor version.
+        minor: u8,
+    },
+    /// Any other message, such as a clock update, with its header removed.
+    Other {
+        /// The identifier of the message.
+        id: u8,
+        /// The body of the message.
+        data: Vec<u8>,
+    },
+}
+
+/// Sends `command` to the board behind `writer`.
+pub fn send_command<W: Write + ?Sized>(writer: &mut W, command: DgtCommand) -> io::Result<()> {
+    writer.write_all(&[command as u8])?;
+    writer.flush()
+}
+
+/// Reads the next message from the board behind `reader`, blocking until it arrives.
+pub fn read_message<R: Read + ?Sized>(reader: &mut R) -> Result<DgtMessage, DgtError> {
+    let mut header = [0; HEADER_LENGTH];
+    reader.read_exact(&mut header)?;
+
+    let [id, high, low] = header;
+    if id & MESSAGE_BIT == 0 {
+        return Err(DgtError::InvalidIdentifier(id));
+    }
+
+    // the length is sent as two 7-bit bytes, and includes the header
+    let length = (usize::from(high & 0x7f) << 7) | usize::from(low & 0x7f);
+    let expected = match id {
+        BOARD_DUMP => Some(HEADER_LENGTH + 64),
+        FIELD_UPDATE | VERSION => Some(HEADER_LENGTH + 2),
+        _ => None,
+    };
+
+    if length < HEADER_LENGTH || expected.is_some_and(|expected| expected != length) {
+        return Err(DgtError::InvalidLength { id, length });
+    }
+
+    let mut data = vec![0; length - HEADER_LENGTH];
+    reader.read_exact(&mut data)?;
+
+    Ok(match id {
+        BOARD_DUMP => {
+            let mut pieces = [None; 64];
+            for (field, &code) in data.iter().enumerate() {
+                pieces[usize::from(field_square(field as u8)?)] = decode_piece(code)?;
+            }
+
+            DgtMessage::BoardDump(pieces)
+        }
+        FIELD_UPDATE => DgtMessage::FieldUpdate {
+            square: field_square(data[0])?,
+            piece: decode_piece(data[1])?,
+        },
+        VERSION => DgtMessage::Version {
+            major: data[0],
+            minor: data[1],
+        },
+        _ => DgtMessage::Other { id, data },
+    })
+}
+
+/// Returns the square of a DGT field, which are numbered from a8 to h1.
+fn field_square(field: u8) -> Result<Square, DgtError> {
+    match field {
+        0..=63 => Ok(Square::at((7 - field / 8) * 8 + field % 8)),
+        _ => Err(DgtError::InvalidField(field)),
+    }
+}
+
+/// Returns the piece denoted by a DGT piece code, or `None` for an empty square.
+fn decode_piece(code: u8) -> Result<Option<Piece>, DgtError> {
+    match code {
+        0 => Ok(None),
+        1..=12 => Ok(Some(PIECES[usize::from(code) - 1])),
+        _ => Err(DgtError::InvalidPiece(code)),
+    }
+}
+
+/// Follows a game played on a DGT board, recognising moves from its messages.
+///
+/// The tracker keeps the placement reported by the board, which is updated as
+/// pieces are lifted and placed. Whenever the placement is that reached by
+/// exactly one legal move, as found by [`Board::diff`], the move is played;
+/// intermediate placements, such as a lifted piece, are simply waited out.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DgtTracker {
+    game: Game,
+    placement: [Option<Piece>; 64],
+}
+
+impl DgtTracker {
+    /// Constructs a tracker following `game`, assuming that the
+    /// board shows its current position until told otherwise.
+    pub fn new(game: Game) -> Self {
+        let mut placement = [None; 64];
+        for (index, piece) in placement.iter_mut().enumerate() {
+            *piece = game.board()[Square::at(index as u8)];
+        }
+
+        Self { game, placement }
+    }
+
+    /// Returns the game played so far.
+    pub fn game(&self) -> &Game {
+        &self.game
+    }
+
+    /// Returns `true` if the pieces on the board are those of the current position.
+    pub fn is_synchronized(&self) -> bool {
+        (0..64).all(|index| {
+            let square = Square::at(index);
+            self.game.board()[square] == self.placement[usize::from(square)]
+        })
+    }
+
+    /// Updates the placement with `message`, returning the move
+    /// played if the new placement completes one.
+    pub fn update(&mut self, message: &DgtMessage) -> Option<LegalMove> {
+        match *message {
+            DgtMessage::BoardDump(pieces) => self.placement = pieces,
+            DgtMessage::FieldUpdate { square, piece } => {
+                self.placement[usize::from(square)] = piece
+            }
+            _ => return None,
+        }
+
+        let side = self.game.board().side_to_move().opposite();
+        let after = Board::from_pieces(self.placement, side);
+        let candidate = self.game.board().diff(&after)?;
+        self.game.play(candidate).ok()
+    }
+}
+
+impl Default for DgtTracker {
+    fn default() -> Self {
+        Self::new(Game::new())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::standard::r#move::mv;
+    use crate::standard::Move;
+
+    /// Encodes a field update for `square` as a board would send it.
+    fn field_update(square: &str, code: u8) -> Vec<u8> {
+        let square = Square::try_from(square).unwrap();
+        let field = (7 - square.rank()) * 8 + square.file();
+        vec![FIELD_UPDATE, 0, 5, field, code]
+    }
+
+    #[test]
+    fn messages_are_decoded() {
+        let mut dump = vec![BOARD_DUMP, 0, 67];
+        dump.extend([8, 9, 10, 12, 11, 10, 9, 8]);
+        dump.extend([7; 8]);
+        dump.extend([0; 32]);
+        dump.extend([1; 8]);
+        dump.extend([2, 3, 4, 6, 5, 4, 3, 2]);
+        dump.extend([VERSION, 0, 5, 1, 7]);
+        dump.extend([MESSAGE_BIT | 0x0d, 0, 4, 42]);
+
+        let mut reader = io::Cursor::new(dump);
+        let DgtMessage::BoardDump(pieces) = read_message(&mut reader).unwrap() else {
+            panic!("expected a board dump");
+        };
+        let start = Board::default();
+        for index in 0..64 {
+            let square = Square::at(index);
+            assert_eq!(pieces[usize::from(square)], start[square]);
+        }
+
+        assert_eq!(
+            read_message(&mut reader).unwrap(),
+            DgtMessage::Version { major: 1, minor: 7 }
+        );
+        assert_eq!(
+            read_message(&mut reader).unwrap(),
+            DgtMessage::Other {
+                id: MESSAGE_BIT | 0x0d,
+                data: vec![42]
+            }
+        );
+        assert!(matches!(read_message(&mut reader), Err(DgtError::Io(_))));
+
+        let mut invalid = io::Cursor::new([FIELD_UPDATE, 0, 5, 64, 0]);
+        assert!(matches!(
+            read_message(&mut invalid),
+            Err(DgtError::InvalidField(64))
+        ));
+        let mut invalid = io::Cursor::new([FIELD_UPDATE, 0, 6, 0, 0, 0]);
+        assert!(matches!(
+            read_message(&mut invalid),
+            Err(DgtError::InvalidLength { length: 6, .. })
+        ));
+    }
+
+    #[test]
+    fn moves_are_recognised_from_field_updates() {
+        let mut tracker = DgtTracker::default();
+        let mut stream = Vec::new();
+        for (square, code) in [
+            // 1. e4, with the pawn lifted and then placed
+            ("e2", 0),
+            ("e4", 1),
+            // 1... d5
+            ("d7", 0),
+            ("d5", 7),
+            // 2. exd5, lifting the captured pawn first
+            ("d5", 0),
+            ("e4", 0),
+            ("d5", 1),
+        ] {
+            stream.extend(field_update(square, code));
+        }
+
+        let mut reader = io::Cursor::new(stream);
+        let mut played = Vec::new();
+        while let Ok(message) = read_message(&mut reader) {
+            let legal_move = tracker.update(&message);
+            // the board only matches the game once a move is completed
+            assert_eq!(tracker.is_synchronized(), legal_move.is_some());
+            played.extend(legal_move.map(Move::from));
+        }
+
+        assert_eq!(played, [mv("e2", "e4"), mv("d7", "d5"), mv("e4", "d5")]);
+        assert!(tracker.is_synchronized());
+        assert_eq!(tracker.game().moves().len(), 3);
+    }
+
+    #[test]
+    fn commands_are_sent_as_single_bytes() {
+        let mut output = Vec::new();
+        send_command(&mut output, DgtCommand::SendBoard).unwrap();
+        send_command(&mut output, DgtCommand::SendUpdateNice).unwrap();
+        assert_eq!(output, [0x42, 0x4b]);
+    }
+}
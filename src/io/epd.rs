@@ -0,0 +1,490 @@
+use std::io::{self, BufRead, BufReader, BufWriter, Read, Write};
+
+use thiserror::Error;
+
+use super::fen::{fast::FastFenError, Fen, FenBuildError, FenBuilder};
+
+/// Results when a string is not a valid EPD record.
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum EpdError {
+    /// Results when the four fields describing the position are invalid.
+    #[error("invalid EPD position: {0}")]
+    Position(#[from] FastFenError),
+    /// Results when the four fields parse, but describe a position which no
+    /// FEN string could represent, e.g. with an en passant square on the wrong rank.
+    #[error("invalid EPD position: {0}")]
+    Build(#[from] FenBuildError),
+    /// Results when an operation is malformed, e.g. by an unclosed string
+    /// operand or a missing `;`, carrying the byte offset at which it begins.
+    #[error("malformed EPD operation at byte {0}")]
+    Operation(usize),
+    /// Results when the operand of a `hmvc` or `fmvn` operation is not a valid
+    /// halfmove clock or fullmove counter.
+    #[error("invalid operand of the {0} operation")]
+    Clock(String),
+}
+
+/// An operand of an [`EpdOperation`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum EpdOperand {
+    /// An unquoted operand, such as a move or a number.
+    Token(String),
+    /// A string operand, which is written between double quotes.
+    String(String),
+}
+
+impl EpdOperand {
+    /// Returns the value of the operand, without any quotes.
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Token(value) | Self::String(value) => value,
+        }
+    }
+}
+
+impl std::fmt::Display for EpdOperand {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Token(token) => write!(f, "{token}"),
+            Self::String(string) => {
+                write!(f, "\"")?;
+                for c in string.chars() {
+                    if matches!(c, '"' | '\\') {
+                        write!(f, "\\")?;
+                    }
+
+                    write!(f, "{c}")?;
+                }
+
+                write!(f, "\"")
+            }
+        }
+    }
+}
+
+/// An operation of an [`Epd`] record, e.g. `bm Nf3;` or `id "BK.01";`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct EpdOperation {
+    /// The opcode of the operation, e.g. `bm`.
+    pub opcode: String,
+    /// The operands of the operation, in order.
+    pub operands: Vec<EpdOperand>,
+}
+
+impl std::fmt::Display for EpdOperation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.opcode)?;
+        for operand in &self.operands {
+            write!(f, " {operand}")?;
+        }
+
+        write!(f, ";")
+    }
+}
+
+/// A record in Extended Position Description (EPD): the first four fields of a
+/// FEN string, followed by a list of operations such as `bm` (best move) or `id`.
+///
+/// The halfmove clock and fullmove counter of the position are taken from the
+/// `hmvc` and `fmvn` operations if they are present, and are otherwise 0 and 1.
+///
+/// ```
+/// use konig::io::epd::Epd;
+///
+/// let epd = Epd::try_from(r#"4k3/8/8/8/8/8/8/4K2R w K - bm Rh8+; id "mate.1";"#).unwrap();
+/// assert_eq!(epd.id(), Some("mate.1"));
+/// assert_eq!(epd.operation("bm").unwrap().operands[0].as_str(), "Rh8+");
+/// assert_eq!(epd.fen().fullmove_counter(), 1);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Epd {
+    fen: Fen,
+    operations: Vec<EpdOperation>,
+}
+
+impl Epd {
+    /// Returns the position of the record, as a [`Fen`].
+    pub fn fen(&self) -> Fen {
+        self.fen
+    }
+
+    /// Returns the operations of the record, in order.
+    pub fn operations(&self) -> &[EpdOperation] {
+        &self.operations
+    }
+
+    /// Returns the first operation with the given `opcode`.
+    pub fn operation(&self, opcode: &str) -> Option<&EpdOperation> {
+        self.operations
+            .iter()
+            .find(|operation| operation.opcode == opcode)
+    }
+
+    /// Returns the value of the first operand of the `id` operation, if there is one.
+    pub fn id(&self) -> Option<&str> {
+        let operand = self.operation("id")?.operands.first()?;
+        Some(operand.as_str())
+    }
+
+    /// Adds an operation after the others.
+    pub fn push_operation(&mut self, operation: EpdOperation) {
+        self.operations.push(operation);
+    }
+}
+
+impl From<Fen> for Epd {
+    /// Constructs a record of the position of `fen`, whose halfmove clock and
+    /// fullmove counter are kept as `hmvc` and `fmvn` operations unless they
+    /// are 0 and 1.
+    fn from(value: Fen) -> Self {
+        let clock = |opcode: &str, value: u16| EpdOperation {
+            opcode: opcode.to_string(),
+            operands: vec![EpdOperand::Token(value.to_string())],
+        };
+
+        let mut operations = Vec::new();
+        if value.halfmove_clock() != 0 {
+            operations.push(clock("hmvc", value.halfmove_clock().into()));
+        }
+
+        if value.fullmove_counter() != 1 {
+            operations.push(clock("fmvn", value.fullmove_counter()));
+        }
+
+        Self {
+            fen: value,
+            operations,
+        }
+    }
+}
+
+impl TryFrom<&str> for Epd {
+    type Error = EpdError;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let position_end = field_end(value, 4);
+        let mut position = value[..position_end].to_string();
+        position.push_str(" 0 1");
+        let fen = Fen::parse_fast(&position)?;
+
+        let operations = operations(value, position_end)?;
+        let mut builder = FenBuilder::from(fen);
+        for operation in &operations {
+            let operand = operation.operands.first().map(EpdOperand::as_str);
+            let invalid = || EpdError::Clock(operation.opcode.clone());
+            builder = match operation.opcode.as_str() {
+                "hmvc" => builder.halfmove_clock(
+                    operand
+                        .and_then(|clock| clock.parse().ok())
+                        .ok_or_else(invalid)?,
+                ),
+                "fmvn" => builder.fullmove_counter(
+                    operand
+                        .and_then(|counter| counter.parse().ok())
+                        .ok_or_else(invalid)?,
+                ),
+                _ => builder,
+            };
+        }
+
+        // the clocks of the parsed position are 0 and 1, so only the operations can
+        // have put them out of range
+        let fen = builder.build().map_err(|err| match err {
+            FenBuildError::HalfmoveClockOutOfRange(_) => EpdError::Clock(String::from("hmvc")),
+            FenBuildError::ZeroFullmoveCounter => EpdError::Clock(String::from("fmvn")),
+            FenBuildError::InvalidEnPassantSquare(..) => EpdError::Build(err),
+        })?;
+
+        Ok(Self { fen, operations })
+    }
+}
+
+impl std::fmt::Display for Epd {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let fen = self.fen.to_string();
+        write!(f, "{}", &fen[..field_end(&fen, 4)])?;
+        for operation in &self.operations {
+            write!(f, " {operation}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns the byte offset at which the `count`th whitespace-separated field of
+/// `source` ends, or the length of `source` if it has fewer fields.
+fn field_end(source: &str, count: usize) -> usize {
+    let mut fields = 0;
+    let mut in_field = false;
+    for (offset, c) in source.char_indices() {
+        match (c.is_whitespace(), in_field) {
+            (true, true) if fields == count => return offset,
+            (true, _) => in_field = false,
+            (false, false) => {
+                in_field = true;
+                fields += 1;
+            }
+            (false, true) => {}
+        }
+    }
+
+    source.len()
+}
+
+/// Parses the operations of an EPD record, which begin at byte `offset` of `source`.
+fn operations(source: &str, offset: usize) -> Result<Vec<EpdOperation>, EpdError> {
+    let mut operations = Vec::new();
+    let mut chars = source[offset..]
+        .char_indices()
+        .map(|(index, c)| (offset + index, c))
+        .peekable();
+
+    loop {
+        while chars.next_if(|(_, c)| c.is_whitespace()).is_some() {}
+        let Some(&(start, _)) = chars.peek() else {
+            return Ok(operations);
+        };
+
+        let mut opcode = String::new();
+        while let Some((_, c)) = chars.next_if(|(_, c)| !c.is_whitespace() && *c != ';') {
+            opcode.push(c);
+        }
+
+        let mut operands = Vec::new();
+        loop {
+            while chars.next_if(|(_, c)| c.is_whitespace()).is_some() {}
+            match chars.next() {
+                Some((_, ';')) => break,
+                Some((_, '"')) => {
+                    let mut string = String::new();
+                    loop {
+                        match chars.next() {
+                            Some((_, '"')) => break,
+                            Some((_, '\\')) => match chars.next() {
+                                Some((_, c)) => string.push(c),
+                                None => return Err(EpdError::Operation(start)),
+                            },
+                            Some((_, c)) => string.push(c),
+                            None => return Err(EpdError::Operation(start)),
+                        }
+                    }
+
+                    operands.push(EpdOperand::String(string));
+                }
+                Some((_, c)) => {
+                    let mut token = String::from(c);
+                    while let Some((_, c)) = chars.next_if(|(_, c)| !c.is_whitespace() && *c != ';')
+                    {
+                        token.push(c);
+                    }
+
+                    operands.push(EpdOperand::Token(token));
+                }
+                None => return Err(EpdError::Operation(start)),
+            }
+        }
+
+        if opcode.is_empty() {
+            return Err(EpdError::Operation(start));
+        }
+
+        operations.push(EpdOperation { opcode, operands });
+    }
+}
+
+/// Results when a line of an EPD file cannot be read.
+#[derive(Debug, Error)]
+pub enum EpdReadError {
+    /// Results when the underlying reader fails.
+    #[error("failed to read EPD: {0}")]
+    Io(#[from] io::Error),
+    /// Results when a line is not a valid EPD record.
+    #[error("invalid EPD record on line {line}: {epd}")]
+    InvalidEpd {
+        /// The number of the line, counting from 1.
+        line: usize,
+        /// The contents of the line, with surrounding whitespace removed.
+        epd: String,
+        /// Why the line is not a valid EPD record.
+        #[source]
+        source: EpdError,
+    },
+}
+
+/// A streaming reader of a file with one EPD record per line, such as a test suite.
+///
+/// Lines are read one at a time, so files of any size can be processed
+/// without being loaded into memory. Empty lines and lines starting with `#`
+/// are skipped, and surrounding whitespace is ignored.
+#[derive(Debug)]
+pub struct EpdReader<R> {
+    reader: R,
+    line: usize,
+    buffer: String,
+}
+
+impl<R: BufRead> EpdReader<R> {
+    /// Constructs a reader of the EPD records in `reader`.
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            line: 0,
+            buffer: String::new(),
+        }
+    }
+}
+
+impl<R: Read> EpdReader<BufReader<R>> {
+    /// Constructs a reader of the EPD records in `reader`, which is buffered
+    /// internally, e.g. a [`File`](std::fs::File).
+    pub fn from_reader(reader: R) -> Self {
+        Self::new(BufReader::new(reader))
+    }
+}
+
+impl<R: BufRead> Iterator for EpdReader<R> {
+    type Item = Result<Epd, EpdReadError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            self.buffer.clear();
+            match self.reader.read_line(&mut self.buffer) {
+                Ok(0) => return None,
+                Ok(_) => self.line += 1,
+                Err(err) => return Some(Err(err.into())),
+            }
+
+            let source = self.buffer.trim();
+            if source.is_empty() || source.starts_with('#') {
+                continue;
+            }
+
+            return Some(
+                Epd::try_from(source).map_err(|source_error| EpdReadError::InvalidEpd {
+                    line: self.line,
+                    epd: source.to_string(),
+                    source: source_error,
+                }),
+            );
+        }
+    }
+}
+
+/// Writes each of `records` to `writer` on its own line, buffering the output internally.
+pub fn to_writer<'a, W: Write>(
+    writer: W,
+    records: impl IntoIterator<Item = &'a Epd>,
+) -> io::Result<()> {
+    let mut writer = BufWriter::new(writer);
+    for record in records {
+        writeln!(writer, "{record}")?;
+    }
+
+    writer.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn records_are_parsed_and_displayed() {
+        let source = r#"r1b1k2r/ppppnppp/2n2q2/2b5/3NP3/2P1B3/PP3PPP/RN1QKB1R w KQkq - bm Qd2 Qe2; id "BK.06"; c0 "a \"quoted\" comment"; hmvc 3; fmvn 9;"#;
+        let epd = Epd::try_from(source).unwrap();
+
+        assert_eq!(epd.operations().len(), 5);
+        assert_eq!(epd.id(), Some("BK.06"));
+        let best: Vec<_> = epd
+            .operation("bm")
+            .unwrap()
+            .operands
+            .iter()
+            .map(EpdOperand::as_str)
+            .collect();
+        assert_eq!(best, ["Qd2", "Qe2"]);
+        assert_eq!(
+            epd.operation("c0").unwrap().operands[0].as_str(),
+            r#"a "quoted" comment"#
+        );
+        assert_eq!(epd.fen().halfmove_clock(), 3);
+        assert_eq!(epd.fen().fullmove_counter(), 9);
+        assert_eq!(epd.to_string(), source);
+
+        let bare = Epd::from(Fen::default());
+        assert_eq!(
+            bare.to_string(),
+            "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq -"
+        );
+        assert_eq!(Epd::try_from(bare.to_string().as_str()).unwrap(), bare);
+
+        let clocked = Epd::from(Fen::parse_fast("4k3/8/8/8/8/8/8/4K3 b - - 7 40").unwrap());
+        assert_eq!(
+            clocked.to_string(),
+            "4k3/8/8/8/8/8/8/4K3 b - - hmvc 7; fmvn 40;"
+        );
+        assert_eq!(
+            Epd::try_from(clocked.to_string().as_str()).unwrap(),
+            clocked
+        );
+    }
+
+    #[test]
+    fn malformed_records_are_rejected() {
+        assert!(matches!(
+            Epd::try_from("4k3/8/8/8/8/8/8/4K3 x - -"),
+            Err(EpdError::Position(FastFenError::SideToMove(20)))
+        ));
+        assert_eq!(
+            Epd::try_from("4k3/8/8/8/8/8/8/4K3 w - - bm Kd2"),
+            Err(EpdError::Operation(26))
+        );
+        assert_eq!(
+            Epd::try_from("4k3/8/8/8/8/8/8/4K3 w - - id \"unclosed;"),
+            Err(EpdError::Operation(26))
+        );
+        assert_eq!(
+            Epd::try_from("4k3/8/8/8/8/8/8/4K3 w - - hmvc 101;"),
+            Err(EpdError::Clock(String::from("hmvc")))
+        );
+        assert_eq!(
+            Epd::try_from("4k3/8/8/8/8/8/8/4K3 w - - id \"x\"; fmvn 0;"),
+            Err(EpdError::Clock(String::from("fmvn")))
+        );
+
+        // an en passant square behind the pawn of the side to move is a position error
+        for source in [
+            "4k3/8/8/8/4P3/8/8/4K3 w - e3",
+            "4k3/8/8/8/4P3/8/8/4K3 w - e3 id \"x\";",
+        ] {
+            assert!(matches!(
+                Epd::try_from(source),
+                Err(EpdError::Build(FenBuildError::InvalidEnPassantSquare(..)))
+            ));
+        }
+    }
+
+    #[test]
+    fn epd_files_are_streamed() {
+        let records = [
+            Epd::from(Fen::default()),
+            Epd::try_from("4k3/8/8/8/8/8/8/4K2R w K - bm Rh8+; id \"mate\";").unwrap(),
+        ];
+
+        let mut file = b"# a test suite\n\n".to_vec();
+        to_writer(&mut file, &records).unwrap();
+        file.extend_from_slice(b"  4k3/8/8/8/8/8/8/4K3 w - - bm  \n");
+
+        let mut reader = EpdReader::from_reader(file.as_slice());
+        assert_eq!(reader.next().unwrap().unwrap(), records[0]);
+        assert_eq!(reader.next().unwrap().unwrap(), records[1]);
+        assert!(matches!(
+            reader.next(),
+            Some(Err(EpdReadError::InvalidEpd {
+                line: 5,
+                source: EpdError::Operation(26),
+                ..
+            }))
+        ));
+        assert!(reader.next().is_none());
+    }
+}
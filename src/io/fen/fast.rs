@@ -0,0 +1,121 @@
+use thiserror::Error;
+
+use super::{literal, Fen, MAX_HALFMOVE_CLOCK};
+
+/// Results when [`Fen::parse_fast`] rejects a FEN string, carrying
+/// the byte offset at which the invalid field begins.
+#[derive(Debug, Error, Clone, Copy, PartialEq, Eq)]
+pub enum FastFenError {
+    /// Results when the piece placement field is invalid.
+    #[error("invalid piece placement at byte {0}")]
+    Placement(usize),
+    /// Results when the side to move is neither `w` nor `b`.
+    #[error("invalid side to move at byte {0}")]
+    SideToMove(usize),
+    /// Results when the castling ability field is invalid.
+    #[error("invalid castling ability at byte {0}")]
+    CastlingAbility(usize),
+    /// Results when the en passant target square is invalid.
+    #[error("invalid en passant target square at byte {0}")]
+    EnPassantSquare(usize),
+    /// Results when the halfmove clock is not a number up to the largest which is
+    /// accepted, which is [`MAX_HALFMOVE_CLOCK`] unless configured otherwise.
+    #[error("invalid halfmove clock at byte {0}")]
+    HalfmoveClock(usize),
+    /// Results when the fullmove counter is not a number which fits in a `u16`.
+    #[error("invalid fullmove counter at byte {0}")]
+    FullmoveCounter(usize),
+    /// Results when the string does not separate its fields with whitespace,
+    /// or continues after the fullmove counter.
+    #[error("unexpected character at byte {0}")]
+    Separator(usize),
+}
+
+impl FastFenError {
+    /// Returns a description of the error without its offset, which unlike
+    /// [`Display`](std::fmt::Display) is available in a `const` context.
+    pub const fn description(&self) -> &'static str {
+        match self {
+            Self::Placement(_) => {
+                "invalid FEN piece placement: expected 8 ranks of 8 squares separated by `/`"
+            }
+            Self::SideToMove(_) => "invalid FEN side to move: expected `w` or `b`",
+            Self::CastlingAbility(_) => {
+                "invalid FEN castling ability: expected `-` or some of `KQkq`, in that order"
+            }
+            Self::EnPassantSquare(_) => {
+                "invalid FEN en passant target square: expected `-` or a square on the third or sixth rank"
+            }
+            Self::HalfmoveClock(_) => {
+                "invalid FEN halfmove clock: expected a number no greater than the maximum"
+            }
+            Self::FullmoveCounter(_) => {
+                "invalid FEN fullmove counter: expected a number which fits in a `u16`"
+            }
+            Self::Separator(_) => {
+                "invalid FEN string: expected six fields separated by whitespace"
+            }
+        }
+    }
+}
+
+/// Parses `source` as [`Fen::try_from`] does, without allocating.
+pub(super) fn parse(source: &str) -> Result<Fen, FastFenError> {
+    literal::parse(source.as_bytes(), MAX_HALFMOVE_CLOCK)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::fen::FEN_STARTING_POSITION;
+
+    #[test]
+    fn fast_parser_agrees_with_the_nom_parser() {
+        for source in [
+            FEN_STARTING_POSITION,
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+            "rnbqkbnr/pp1ppppp/8/2p5/4P3/8/PPPP1PPP/RNBQKBNR w KQkq c6 0 2",
+            "4k3/8/8/8/8/8/8/4K2R b Kq e3 100 65535",
+            "8/8/8/8/8/8/8/44 w -\t- 007 1",
+            "4k3/8/8/8/8/8/8/4K3 w Kk - 0 1",
+            // each of these is rejected by both parsers
+            "4k3/8/8/8/8/8/8/4K3 w - - 101 1",
+            "4k3/8/8/8/8/8/8/4K3 w - - 0 65536",
+            "4k3/8/8/8/8/8/8/4K3 w kK - 0 1",
+            "4k3/8/8/8/8/8/8/4K3 w KK - 0 1",
+            "4k3/8/8/8/8/8/8/4K3 w - e4 0 1",
+            "4k3/8/8/8/8/8/8/4K3 w - - 0 1 ",
+            "4k3/8/8/8/8/8/8/4K3 x - - 0 1",
+            "4k3/8/8/8/8/8/8/4K3 w - - 0",
+            "4k3/8/8/8/8/8/8/4K4 w - - 0 1",
+            "4k3/8/8/8/8/8/8/4K2 w - - 0 1",
+            "4k3/8/8/8/8/8/8/8/4K3 w - - 0 1",
+            "4k3/8/8/8/8/8/4K3 w - - 0 1",
+            "4k3/8/8/8/8/8/8/4X3 w - - 0 1",
+            "4k3/8/8/8/8/8/8/4K3 w - - -1 1",
+            "",
+        ] {
+            assert_eq!(
+                Fen::parse_fast(source).ok(),
+                Fen::try_from(source).ok(),
+                "{source:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn fast_parser_errors_locate_the_invalid_field() {
+        assert_eq!(
+            Fen::parse_fast("4k3/8/8/8/8/8/8/4K3 w - - 101 1"),
+            Err(FastFenError::HalfmoveClock(26))
+        );
+        assert_eq!(
+            Fen::parse_fast("4k3/8/8/9/8/8/8/4K3 w - - 0 1"),
+            Err(FastFenError::Placement(8))
+        );
+        assert_eq!(
+            Fen::parse_fast("4k3/8/8/8/8/8/8/4K3 w QK - 0 1"),
+            Err(FastFenError::Separator(23))
+        );
+    }
+}
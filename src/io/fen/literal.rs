@@ -0,0 +1,302 @@
+use super::{fast::FastFenError, Fen, MAX_HALFMOVE_CLOCK};
+use crate::standard::board::CastlingPermissions;
+use crate::standard::piece::{Color, Piece};
+use crate::standard::Square;
+
+/// Parses a FEN string at compile time, expanding to a constant [`Fen`].
+///
+/// The argument may be any constant expression of type `&str`. A string which
+/// [`Fen::try_from`] would reject is reported as a compile error, so typos in
+/// fixtures are caught before any test runs.
+///
+/// ```
+/// use konig::{fen, io::Fen};
+///
+/// let fen = fen!("rnbqkbnr/pppp1ppp/8/4p3/4P3/8/PPPP1PPP/RNBQKBNR w KQkq e6 0 2");
+/// assert_eq!(fen.fullmove_counter(), 2);
+/// assert_eq!(fen, Fen::try_from(fen.to_string().as_str()).unwrap());
+/// ```
+///
+/// ```compile_fail
+/// # use konig::fen;
+/// // the seventh rank has nine squares
+/// let fen = fen!("rnbqkbnr/ppppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1");
+/// ```
+///
+/// Only the syntax of the string is checked; use the [`board!`](crate::board) macro
+/// to also reject positions which could not arise in a game.
+#[macro_export]
+macro_rules! fen {
+    ($fen:expr) => {{
+        const FEN: $crate::io::Fen = $crate::io::Fen::from_literal($fen);
+        FEN
+    }};
+}
+
+impl Fen {
+    /// Parses `source` as a FEN string in a `const` context, accepting exactly
+    /// the strings accepted by [`Fen::try_from`].
+    ///
+    /// # Panics
+    ///
+    /// Panics with a description of the first invalid field if `source` is not a
+    /// valid FEN string; when evaluated at compile time, e.g. through the
+    /// [`fen!`](crate::fen) macro, this is reported as a compile error.
+    pub const fn from_literal(source: &str) -> Self {
+        match parse(source.as_bytes(), MAX_HALFMOVE_CLOCK) {
+            Ok(fen) => fen,
+            Err(err) => panic!("{}", err.description()),
+        }
+    }
+}
+
+/// Returns the value of a `Result`, or returns its error from the enclosing
+/// function, as `?` does outside of a `const` context.
+macro_rules! attempt {
+    ($result:expr) => {
+        match $result {
+            Ok(value) => value,
+            Err(err) => return Err(err),
+        }
+    };
+}
+
+/// Parses `source` as a FEN string whose halfmove clock is at most `max_halfmove_clock`.
+///
+/// This is the hand-written parser behind both [`Fen::from_literal`] and
+/// [`Fen::parse_fast`]; it does not allocate, and so it can run at compile time.
+pub(super) const fn parse(source: &[u8], max_halfmove_clock: u8) -> Result<Fen, FastFenError> {
+    let mut cursor = 0;
+
+    let pieces = attempt!(piece_placement(source, &mut cursor));
+    attempt!(spaces(source, &mut cursor));
+
+    let start = cursor;
+    let side_to_move = match next(source, &mut cursor) {
+        Some(b'w') => Color::White,
+        Some(b'b') => Color::Black,
+        _ => return Err(FastFenError::SideToMove(start)),
+    };
+    attempt!(spaces(source, &mut cursor));
+
+    let castling_permissions = attempt!(castling_ability(source, &mut cursor));
+    attempt!(spaces(source, &mut cursor));
+
+    let en_passant_square = attempt!(en_passant_target_square(source, &mut cursor));
+    attempt!(spaces(source, &mut cursor));
+
+    let start = cursor;
+    let halfmove_clock = match number(source, &mut cursor) {
+        Some(clock) if clock <= max_halfmove_clock as u16 => clock as u8,
+        _ => return Err(FastFenError::HalfmoveClock(start)),
+    };
+    attempt!(spaces(source, &mut cursor));
+
+    let start = cursor;
+    let fullmove_counter = match number(source, &mut cursor) {
+        Some(counter) => counter,
+        None => return Err(FastFenError::FullmoveCounter(start)),
+    };
+
+    if cursor != source.len() {
+        return Err(FastFenError::Separator(cursor));
+    }
+
+    Ok(Fen {
+        pieces,
+        side_to_move,
+        castling_permissions,
+        en_passant_square,
+        halfmove_clock,
+        fullmove_counter,
+    })
+}
+
+/// Returns the byte at `cursor` and advances past it, if there is one.
+const fn next(source: &[u8], cursor: &mut usize) -> Option<u8> {
+    if *cursor < source.len() {
+        *cursor += 1;
+        Some(source[*cursor - 1])
+    } else {
+        None
+    }
+}
+
+/// Returns the byte at `cursor` without advancing, if there is one.
+const fn peek(source: &[u8], cursor: usize) -> Option<u8> {
+    if cursor < source.len() {
+        Some(source[cursor])
+    } else {
+        None
+    }
+}
+
+/// Skips the whitespace separating two fields, of which there must be some.
+const fn spaces(source: &[u8], cursor: &mut usize) -> Result<(), FastFenError> {
+    let start = *cursor;
+    while let Some(b' ' | b'\t') = peek(source, *cursor) {
+        *cursor += 1;
+    }
+
+    match *cursor == start {
+        true => Err(FastFenError::Separator(start)),
+        false => Ok(()),
+    }
+}
+
+/// Parses the piece placement field, indexing the squares from a1 to h8.
+const fn piece_placement(
+    source: &[u8],
+    cursor: &mut usize,
+) -> Result<[Option<Piece>; 64], FastFenError> {
+    let mut pieces = [None; 64];
+    let mut rank = 8;
+
+    while rank > 0 {
+        rank -= 1;
+        let start = *cursor;
+        if rank < 7 && !matches!(next(source, cursor), Some(b'/')) {
+            return Err(FastFenError::Placement(start));
+        }
+
+        let mut file = 0;
+        while file < 8 {
+            let start = *cursor;
+            let piece = match next(source, cursor) {
+                Some(digit @ b'1'..=b'8') if file + (digit - b'0') as usize <= 8 => {
+                    file += (digit - b'0') as usize;
+                    continue;
+                }
+                Some(b'P') => Piece::WhitePawn,
+                Some(b'N') => Piece::WhiteKnight,
+                Some(b'B') => Piece::WhiteBishop,
+                Some(b'R') => Piece::WhiteRook,
+                Some(b'Q') => Piece::WhiteQueen,
+                Some(b'K') => Piece::WhiteKing,
+                Some(b'p') => Piece::BlackPawn,
+                Some(b'n') => Piece::BlackKnight,
+                Some(b'b') => Piece::BlackBishop,
+                Some(b'r') => Piece::BlackRook,
+                Some(b'q') => Piece::BlackQueen,
+                Some(b'k') => Piece::BlackKing,
+                _ => return Err(FastFenError::Placement(start)),
+            };
+
+            pieces[rank * 8 + file] = Some(piece);
+            file += 1;
+        }
+    }
+
+    Ok(pieces)
+}
+
+/// Parses the castling ability field, which lists its permissions in the order `KQkq`.
+const fn castling_ability(
+    source: &[u8],
+    cursor: &mut usize,
+) -> Result<CastlingPermissions, FastFenError> {
+    let mut permissions = CastlingPermissions {
+        white_king_side: false,
+        white_queen_side: false,
+        black_king_side: false,
+        black_queen_side: false,
+    };
+
+    if let Some(b'-') = peek(source, *cursor) {
+        *cursor += 1;
+        return Ok(permissions);
+    }
+
+    let start = *cursor;
+    if let Some(b'K') = peek(source, *cursor) {
+        permissions.white_king_side = true;
+        *cursor += 1;
+    }
+    if let Some(b'Q') = peek(source, *cursor) {
+        permissions.white_queen_side = true;
+        *cursor += 1;
+    }
+    if let Some(b'k') = peek(source, *cursor) {
+        permissions.black_king_side = true;
+        *cursor += 1;
+    }
+    if let Some(b'q') = peek(source, *cursor) {
+        permissions.black_queen_side = true;
+        *cursor += 1;
+    }
+
+    match *cursor == start {
+        true => Err(FastFenError::CastlingAbility(start)),
+        false => Ok(permissions),
+    }
+}
+
+/// Parses the en passant target square field, which is `-` or a square on the third
+/// or sixth rank.
+const fn en_passant_target_square(
+    source: &[u8],
+    cursor: &mut usize,
+) -> Result<Option<Square>, FastFenError> {
+    let start = *cursor;
+    match next(source, cursor) {
+        Some(b'-') => Ok(None),
+        Some(file @ b'a'..=b'h') => match next(source, cursor) {
+            Some(b'3') => Ok(Some(Square::at(16 + file - b'a'))),
+            Some(b'6') => Ok(Some(Square::at(40 + file - b'a'))),
+            _ => Err(FastFenError::EnPassantSquare(start)),
+        },
+        _ => Err(FastFenError::EnPassantSquare(start)),
+    }
+}
+
+/// Parses a decimal number, returning `None` if there is none or it does not fit in a `u16`.
+const fn number(source: &[u8], cursor: &mut usize) -> Option<u16> {
+    let start = *cursor;
+    let mut value: u32 = 0;
+    while let Some(digit @ b'0'..=b'9') = peek(source, *cursor) {
+        value = value * 10 + (digit - b'0') as u32;
+        if value > u16::MAX as u32 {
+            return None;
+        }
+
+        *cursor += 1;
+    }
+
+    match *cursor == start {
+        true => None,
+        false => Some(value as u16),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::io::fen::FEN_STARTING_POSITION;
+    use crate::io::Fen;
+
+    #[test]
+    fn literals_are_parsed_like_runtime_strings() {
+        for source in [
+            FEN_STARTING_POSITION,
+            "r3k2r/8/8/8/8/8/8/R3K2R b Kq - 12 40",
+            "rnbqkbnr/pp1ppppp/8/2p5/4P3/8/PPPP1PPP/RNBQKBNR w KQkq c6 0 2",
+            "8/8/8/8/8/8/8/8  w  -  -  100  65535",
+        ] {
+            assert_eq!(Fen::from_literal(source), Fen::try_from(source).unwrap());
+        }
+
+        const START: Fen = fen!(FEN_STARTING_POSITION);
+        assert_eq!(START, Fen::default());
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid FEN castling ability")]
+    fn invalid_literals_panic() {
+        Fen::from_literal("4k3/8/8/8/8/8/8/4K3 w x - 0 1");
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid FEN halfmove clock")]
+    fn out_of_range_halfmove_clocks_panic() {
+        Fen::from_literal("4k3/8/8/8/8/8/8/4K3 w - - 101 1");
+    }
+}
@@ -0,0 +1,126 @@
+use std::io::{self, BufRead, BufReader, BufWriter, Read, Write};
+
+use thiserror::Error;
+
+use super::{fast::FastFenError, Fen};
+
+/// Results when a line of a FEN file cannot be read.
+#[derive(Debug, Error)]
+pub enum FenReadError {
+    /// Results when the underlying reader fails.
+    #[error("failed to read FEN: {0}")]
+    Io(#[from] io::Error),
+    /// Results when a line is not a valid FEN string.
+    #[error("invalid FEN string on line {line}: {fen}")]
+    InvalidFen {
+        /// The number of the line, counting from 1.
+        line: usize,
+        /// The contents of the line, with surrounding whitespace removed.
+        fen: String,
+        /// Why the line is not a valid FEN string.
+        #[source]
+        source: FastFenError,
+    },
+}
+
+/// A streaming reader of a file with one FEN string per line, such as a
+/// test suite or a dataset of positions.
+///
+/// Lines are read one at a time, so files of any size can be processed
+/// without being loaded into memory. Empty lines and lines starting with `#`
+/// are skipped, and surrounding whitespace is ignored.
+#[derive(Debug)]
+pub struct FenReader<R> {
+    reader: R,
+    line: usize,
+    buffer: String,
+}
+
+impl<R: BufRead> FenReader<R> {
+    /// Constructs a reader of the FEN strings in `reader`.
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            line: 0,
+            buffer: String::new(),
+        }
+    }
+}
+
+impl<R: Read> FenReader<BufReader<R>> {
+    /// Constructs a reader of the FEN strings in `reader`, which is buffered
+    /// internally, e.g. a [`File`](std::fs::File).
+    pub fn from_reader(reader: R) -> Self {
+        Self::new(BufReader::new(reader))
+    }
+}
+
+impl<R: BufRead> Iterator for FenReader<R> {
+    type Item = Result<Fen, FenReadError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            self.buffer.clear();
+            match self.reader.read_line(&mut self.buffer) {
+                Ok(0) => return None,
+                Ok(_) => self.line += 1,
+                Err(err) => return Some(Err(err.into())),
+            }
+
+            let source = self.buffer.trim();
+            if source.is_empty() || source.starts_with('#') {
+                continue;
+            }
+
+            return Some(Fen::parse_fast(source).map_err(|source_error| {
+                FenReadError::InvalidFen {
+                    line: self.line,
+                    fen: source.to_string(),
+                    source: source_error,
+                }
+            }));
+        }
+    }
+}
+
+/// Writes each of `fens` to `writer` on its own line, buffering the output internally.
+pub fn to_writer<'a, W: Write>(
+    writer: W,
+    fens: impl IntoIterator<Item = &'a Fen>,
+) -> io::Result<()> {
+    let mut writer = BufWriter::new(writer);
+    for fen in fens {
+        writeln!(writer, "{fen}")?;
+    }
+
+    writer.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::fen::FEN_STARTING_POSITION;
+
+    #[test]
+    fn fen_files_are_streamed() {
+        let fens = [
+            Fen::default(),
+            Fen::try_from("4k3/8/8/8/8/8/8/4K2R w K - 3 40").unwrap(),
+        ];
+
+        let mut file = b"# a test suite\n\n".to_vec();
+        to_writer(&mut file, &fens).unwrap();
+        file.extend_from_slice(b"  not a fen  \n");
+        file.extend_from_slice(FEN_STARTING_POSITION.as_bytes());
+
+        let mut reader = FenReader::from_reader(file.as_slice());
+        assert_eq!(reader.next().unwrap().unwrap(), fens[0]);
+        assert_eq!(reader.next().unwrap().unwrap(), fens[1]);
+        assert!(matches!(
+            reader.next(),
+            Some(Err(FenReadError::InvalidFen { line: 5, ref fen, source: FastFenError::Placement(1) })) if fen == "not a fen"
+        ));
+        assert_eq!(reader.next().unwrap().unwrap(), Fen::default());
+        assert!(reader.next().is_none());
+    }
+}
@@ -0,0 +1,378 @@
+use thiserror::Error;
+
+use super::Fen;
+use crate::core::Piece as _;
+use crate::standard::{BitBoard, Color, Piece, PieceKind, Square};
+
+/// The order in which the pieces in a pocket are written.
+const POCKET_ORDER: &str = "QRBNPqrbnp";
+
+/// The variants whose FEN extensions are understood by [`VariantFen`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Variant {
+    /// Standard chess, which admits no extensions.
+    #[default]
+    Standard,
+    /// Crazyhouse, which adds the pockets of captured pieces and
+    /// marks promoted pieces with a `~`.
+    Crazyhouse,
+    /// Three-check, which adds the number of checks given by each side.
+    ThreeCheck,
+}
+
+/// Results when a FEN string cannot be parsed as a [`VariantFen`].
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum VariantFenError {
+    /// Results when the standard fields of the string are not valid FEN.
+    #[error("invalid FEN: {0}")]
+    InvalidFen(String),
+    /// Results when the pocket of a crazyhouse position is malformed or contains a king.
+    #[error("invalid crazyhouse pocket: {0}")]
+    InvalidPocket(String),
+    /// Results when a `~` does not follow a piece.
+    #[error("misplaced promotion marker in the piece placement field")]
+    InvalidPromotionMarker,
+    /// Results when the check counters of a three-check position are malformed.
+    #[error("invalid three-check counter: {0}")]
+    InvalidCheckCounter(String),
+}
+
+/// A FEN string extended with the state of a chess variant.
+///
+/// Parsing with [`Variant::Standard`] is exactly as strict as parsing a [`Fen`],
+/// while the other variants additionally accept their own extensions:
+///
+/// - crazyhouse pockets, written either in brackets after the piece placement
+///   (`.../RNBQKBNR[QRq] w ...`) or as a ninth rank (`.../RNBQKBNR/QRq w ...`),
+///   along with `~` markers after promoted pieces;
+/// - three-check counters, written either as the number of checks given by each
+///   side after the fullmove counter (`... 0 1 +1+0`), or as the number of checks
+///   remaining before the halfmove clock (`... - 2+3 0 1`).
+///
+/// Positions are always written in the first of each pair of forms.
+///
+/// ```
+/// use konig::io::fen::variant::{Variant, VariantFen};
+/// use konig::standard::Color;
+///
+/// let fen = "rnbqkbnr/pppp1ppp/8/4p3/4P3/8/PPPP1PPP/RNBQKBNR w KQkq - 0 2 +1+0";
+/// let parsed = VariantFen::parse(fen, Variant::ThreeCheck).unwrap();
+/// assert_eq!(parsed.checks_given(Color::White), 1);
+/// assert_eq!(parsed.to_string(), fen);
+/// assert!(VariantFen::parse(fen, Variant::Standard).is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VariantFen {
+    variant: Variant,
+    fen: Fen,
+    pocket: Vec<Piece>,
+    promoted: BitBoard,
+    checks: [u8; 2],
+}
+
+impl VariantFen {
+    /// Parses `source` as a FEN string of the given `variant`.
+    pub fn parse(source: &str, variant: Variant) -> Result<Self, VariantFenError> {
+        let mut fields: Vec<&str> = source.split_whitespace().collect();
+        let mut pocket = Vec::new();
+        let mut promoted = BitBoard::from_bits(0);
+        let mut checks = [0; 2];
+
+        let mut placement = fields.first().copied().unwrap_or_default().to_string();
+        if variant == Variant::Crazyhouse {
+            let (board, pieces) = split_pocket(&placement)?;
+            pocket = pieces;
+            (placement, promoted) = strip_promotion_markers(&board)?;
+        }
+
+        if variant == Variant::ThreeCheck {
+            if fields.len() == 7 && fields[6].starts_with('+') {
+                checks = parse_checks_given(fields[6])?;
+                fields.pop();
+            } else if fields.len() == 7 {
+                checks = parse_checks_remaining(fields[4])?;
+                fields.remove(4);
+            }
+        }
+
+        if let Some(first) = fields.first_mut() {
+            *first = &placement;
+        }
+
+        let standard = fields.join(" ");
+        let fen = Fen::try_from(standard.as_str())
+            .map_err(|_| VariantFenError::InvalidFen(standard.clone()))?;
+
+        Ok(Self {
+            variant,
+            fen,
+            pocket,
+            promoted,
+            checks,
+        })
+    }
+
+    /// Returns the variant of the position.
+    pub fn variant(&self) -> Variant {
+        self.variant
+    }
+
+    /// Returns the standard fields of the position.
+    pub fn fen(&self) -> &Fen {
+        &self.fen
+    }
+
+    /// Returns the pieces in the pockets of a crazyhouse position,
+    /// with white's pieces first and each side ordered from queens to pawns.
+    pub fn pocket(&self) -> &[Piece] {
+        &self.pocket
+    }
+
+    /// Returns the squares holding promoted pieces in a crazyhouse position.
+    pub fn promoted(&self) -> BitBoard {
+        self.promoted
+    }
+
+    /// Returns the number of checks given by `color` in a three-check position.
+    pub fn checks_given(&self, color: Color) -> u8 {
+        match color {
+            Color::White => self.checks[0],
+            Color::Black => self.checks[1],
+        }
+    }
+}
+
+impl From<Fen> for VariantFen {
+    fn from(value: Fen) -> Self {
+        Self {
+            variant: Variant::Standard,
+            fen: value,
+            pocket: Vec::new(),
+            promoted: BitBoard::from_bits(0),
+            checks: [0; 2],
+        }
+    }
+}
+
+impl std::fmt::Display for VariantFen {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let standard = self.fen.to_string();
+        let (placement, rest) = standard.split_once(' ').unwrap_or((&standard, ""));
+
+        // the placement is written from a8 to h1
+        let mut rank = 7;
+        let mut file = 0;
+        for c in placement.chars() {
+            write!(f, "{c}")?;
+            match c {
+                '/' => {
+                    rank -= 1;
+                    file = 0;
+                }
+                '1'..='8' => file += c as u8 - b'0',
+                _ => {
+                    if self.promoted.contains(Square::at(rank * 8 + file)) {
+                        write!(f, "~")?;
+                    }
+
+                    file += 1;
+                }
+            }
+        }
+
+        if self.variant == Variant::Crazyhouse {
+            write!(f, "[")?;
+            for &piece in &self.pocket {
+                let c: char = piece.into();
+                write!(f, "{c}")?;
+            }
+
+            write!(f, "]")?;
+        }
+
+        write!(f, " {rest}")?;
+        if self.variant == Variant::ThreeCheck {
+            write!(f, " +{}+{}", self.checks[0], self.checks[1])?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Splits a crazyhouse piece placement field into the board and its pocket.
+fn split_pocket(placement: &str) -> Result<(String, Vec<Piece>), VariantFenError> {
+    let (board, pocket) = match placement.strip_suffix(']') {
+        Some(rest) => rest
+            .split_once('[')
+            .ok_or_else(|| VariantFenError::InvalidPocket(placement.to_string()))?,
+        None => match placement.match_indices('/').nth(7) {
+            Some((index, _)) => (&placement[..index], &placement[index + 1..]),
+            None => (placement, ""),
+        },
+    };
+
+    let mut pieces = pocket
+        .chars()
+        .filter(|&c| c != '-')
+        .map(|c| {
+            Piece::try_from(c)
+                .ok()
+                .filter(|piece| piece.kind() != PieceKind::King)
+                .ok_or_else(|| VariantFenError::InvalidPocket(pocket.to_string()))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    pieces.sort_by_key(|&piece| {
+        let c: char = piece.into();
+        POCKET_ORDER.find(c)
+    });
+
+    Ok((board.to_string(), pieces))
+}
+
+/// Removes the `~` markers from a piece placement field, returning the
+/// squares of the pieces they followed.
+fn strip_promotion_markers(placement: &str) -> Result<(String, BitBoard), VariantFenError> {
+    let mut stripped = String::with_capacity(placement.len());
+    let mut bits = 0u64;
+    let mut rank: u8 = 7;
+    let mut file: u8 = 0;
+    let mut after_piece = false;
+
+    for c in placement.chars() {
+        match c {
+            '~' if after_piece && file <= 8 => {
+                bits |= 1 << (rank as u64 * 8 + file as u64 - 1);
+                after_piece = false;
+                continue;
+            }
+            '~' => return Err(VariantFenError::InvalidPromotionMarker),
+            '/' => {
+                rank = rank.saturating_sub(1);
+                file = 0;
+                after_piece = false;
+            }
+            '1'..='8' => {
+                file = file.saturating_add(c as u8 - b'0');
+                after_piece = false;
+            }
+            _ => {
+                file = file.saturating_add(1);
+                after_piece = true;
+            }
+        }
+
+        stripped.push(c);
+    }
+
+    Ok((stripped, BitBoard::from_bits(bits)))
+}
+
+/// Parses a counter of the form `+W+B`, giving the checks given by each side.
+fn parse_checks_given(field: &str) -> Result<[u8; 2], VariantFenError> {
+    let invalid = || VariantFenError::InvalidCheckCounter(field.to_string());
+    let (white, black) = field
+        .strip_prefix('+')
+        .and_then(|rest| rest.split_once('+'))
+        .ok_or_else(invalid)?;
+
+    let count = |value: &str| value.parse::<u8>().ok().filter(|&n| n <= 3);
+    Ok([
+        count(white).ok_or_else(invalid)?,
+        count(black).ok_or_else(invalid)?,
+    ])
+}
+
+/// Parses a counter of the form `W+B`, giving the checks remaining for each side.
+fn parse_checks_remaining(field: &str) -> Result<[u8; 2], VariantFenError> {
+    let invalid = || VariantFenError::InvalidCheckCounter(field.to_string());
+    let (white, black) = field.split_once('+').ok_or_else(invalid)?;
+    let given = |value: &str| {
+        value
+            .parse::<u8>()
+            .ok()
+            .and_then(|remaining| 3u8.checked_sub(remaining))
+    };
+
+    Ok([
+        given(white).ok_or_else(invalid)?,
+        given(black).ok_or_else(invalid)?,
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::fen::FEN_STARTING_POSITION;
+
+    #[test]
+    fn standard_parsing_stays_strict() {
+        let parsed = VariantFen::parse(FEN_STARTING_POSITION, Variant::Standard).unwrap();
+        assert_eq!(parsed, VariantFen::from(Fen::default()));
+        assert_eq!(parsed.to_string(), FEN_STARTING_POSITION);
+
+        let pocket = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR[] w KQkq - 0 1";
+        assert!(VariantFen::parse(pocket, Variant::Standard).is_err());
+        assert!(VariantFen::parse(pocket, Variant::Crazyhouse).is_ok());
+    }
+
+    #[test]
+    fn crazyhouse_extensions_are_parsed() {
+        let bracketed = "r1bk3r/ppp2Q~pp/8/8/8/8/PPP2PPP/RNB1K2R[NPqp] b KQ - 0 12";
+        let parsed = VariantFen::parse(bracketed, Variant::Crazyhouse).unwrap();
+        assert_eq!(
+            parsed.pocket(),
+            [
+                Piece::WhiteKnight,
+                Piece::WhitePawn,
+                Piece::BlackQueen,
+                Piece::BlackPawn
+            ]
+        );
+        assert!(parsed.promoted().contains(Square::try_from("f7").unwrap()));
+        assert_eq!(parsed.promoted().count(), 1);
+        assert_eq!(parsed.to_string(), bracketed);
+
+        let ninth_rank = "r1bk3r/ppp2Q~pp/8/8/8/8/PPP2PPP/RNB1K2R/pqPN b KQ - 0 12";
+        assert_eq!(
+            VariantFen::parse(ninth_rank, Variant::Crazyhouse).unwrap(),
+            parsed
+        );
+
+        let king = "r1bk3r/ppp2Qpp/8/8/8/8/PPP2PPP/RNB1K2R[K] b KQ - 0 12";
+        assert_eq!(
+            VariantFen::parse(king, Variant::Crazyhouse),
+            Err(VariantFenError::InvalidPocket(String::from("K")))
+        );
+        let marker = "r1bk3r/ppp2~Qpp/8/8/8/8/PPP2PPP/RNB1K2R[] b KQ - 0 12";
+        assert_eq!(
+            VariantFen::parse(marker, Variant::Crazyhouse),
+            Err(VariantFenError::InvalidPromotionMarker)
+        );
+    }
+
+    #[test]
+    fn three_check_counters_are_parsed() {
+        let remaining = "rnbqkbnr/pppp1ppp/8/4p3/4P3/8/PPPP1PPP/RNBQKBNR w KQkq - 2+3 0 2";
+        let parsed = VariantFen::parse(remaining, Variant::ThreeCheck).unwrap();
+        assert_eq!(parsed.checks_given(Color::White), 1);
+        assert_eq!(parsed.checks_given(Color::Black), 0);
+        assert_eq!(
+            parsed.to_string(),
+            "rnbqkbnr/pppp1ppp/8/4p3/4P3/8/PPPP1PPP/RNBQKBNR w KQkq - 0 2 +1+0"
+        );
+
+        let missing = VariantFen::parse(FEN_STARTING_POSITION, Variant::ThreeCheck).unwrap();
+        assert_eq!(missing.checks_given(Color::Black), 0);
+        assert!(VariantFen::parse(
+            &format!("{FEN_STARTING_POSITION} +4+0"),
+            Variant::ThreeCheck
+        )
+        .is_err());
+        assert!(VariantFen::parse(
+            &format!("{FEN_STARTING_POSITION} +1+0"),
+            Variant::Crazyhouse
+        )
+        .is_err());
+    }
+}
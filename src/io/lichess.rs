@@ -0,0 +1,506 @@
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use super::{uci, Fen, San};
+use crate::core::{Move as _, Piece as _, Process as _, Validate as _};
+use crate::standard::{Board, Game, LegalMove, Move, PieceKind, Square};
+
+/// Results when Lichess JSON cannot be decoded or converted into a [`Board`].
+#[derive(Debug, Error)]
+pub enum LichessError {
+    /// Results when the JSON does not describe the expected type.
+    #[error("invalid Lichess JSON: {0}")]
+    Json(#[from] serde_json::Error),
+    /// Results when the initial position of a game is not valid FEN.
+    #[error("invalid initial FEN: {0}")]
+    InvalidFen(String),
+    /// Results when the game is not standard chess.
+    #[error("unsupported variant: {0}")]
+    UnsupportedVariant(String),
+    /// Results when a move of the game cannot be played.
+    #[error("illegal move {literal} at ply {ply}")]
+    IllegalMove {
+        /// The index of the move in the game.
+        ply: usize,
+        /// The move as it appeared in the JSON.
+        literal: String,
+    },
+}
+
+/// A game as returned by the Lichess game export endpoints, such as
+/// `/game/export/{id}` or the `application/x-ndjson` responses of
+/// `/api/games/user/{username}`.
+///
+/// Only the fields which are useful for reconstructing and annotating
+/// games are kept, and unknown fields are ignored.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LichessGame {
+    /// The eight-character identifier of the game.
+    pub id: String,
+    /// Whether the game was rated.
+    #[serde(default)]
+    pub rated: bool,
+    /// The key of the variant, e.g. `"standard"` or `"chess960"`.
+    #[serde(default = "standard_variant")]
+    pub variant: String,
+    /// The speed category of the game, e.g. `"blitz"`.
+    pub speed: Option<String>,
+    /// The time of creation, in milliseconds since the Unix epoch.
+    pub created_at: Option<u64>,
+    /// The time of the last move, in milliseconds since the Unix epoch.
+    pub last_move_at: Option<u64>,
+    /// The status of the game, e.g. `"mate"` or `"resign"`.
+    pub status: String,
+    /// The players of either side.
+    pub players: Players,
+    /// The winning side (`"white"` or `"black"`), if the game was decided.
+    pub winner: Option<String>,
+    /// The opening of the game, if it was requested.
+    pub opening: Option<Opening>,
+    /// The moves of the game as space-separated SAN literals.
+    #[serde(default)]
+    pub moves: String,
+    /// The initial position, if the game did not start from the standard position.
+    pub initial_fen: Option<String>,
+    /// The time control of the game, if it had one.
+    pub clock: Option<Clock>,
+    /// The remaining clock time after each move, in centiseconds.
+    #[serde(default)]
+    pub clocks: Vec<u32>,
+    /// The computer analysis of each ply, if it was requested and is available.
+    #[serde(default)]
+    pub analysis: Vec<PlyAnalysis>,
+}
+
+/// The players of a [`LichessGame`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Players {
+    /// The player of the white pieces.
+    pub white: Player,
+    /// The player of the black pieces.
+    pub black: Player,
+}
+
+/// A player of a [`LichessGame`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Player {
+    /// The account of the player, or `None` for anonymous players and the AI.
+    pub user: Option<User>,
+    /// The rating of the player at the start of the game.
+    pub rating: Option<u32>,
+    /// The change in rating caused by the game.
+    pub rating_diff: Option<i32>,
+    /// The level of the Lichess AI, if it played this side.
+    pub ai_level: Option<u8>,
+    /// A summary of the player's mistakes, if the game was analysed.
+    pub analysis: Option<PlayerAnalysis>,
+}
+
+/// A Lichess account.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct User {
+    /// The display name of the account.
+    pub name: String,
+    /// The lowercase identifier of the account.
+    pub id: String,
+    /// The title of the account, e.g. `"GM"` or `"BOT"`.
+    pub title: Option<String>,
+}
+
+/// The summary of a player's mistakes in an analysed game.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlayerAnalysis {
+    /// The number of inaccuracies.
+    pub inaccuracy: u32,
+    /// The number of mistakes.
+    pub mistake: u32,
+    /// The number of blunders.
+    pub blunder: u32,
+    /// The average centipawn loss.
+    pub acpl: u32,
+}
+
+/// The opening of a [`LichessGame`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Opening {
+    /// The ECO code of the opening, e.g. `"C20"`.
+    pub eco: String,
+    /// The name of the opening.
+    pub name: String,
+    /// The number of plies in the opening.
+    pub ply: u32,
+}
+
+/// The time control of a [`LichessGame`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Clock {
+    /// The initial time of each player, in seconds.
+    pub initial: u32,
+    /// The increment after each move, in seconds.
+    pub increment: u32,
+    /// The estimated total time of the game, in seconds.
+    pub total_time: Option<u32>,
+}
+
+/// The computer analysis of a single ply of a [`LichessGame`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlyAnalysis {
+    /// The evaluation in centipawns, from white's perspective.
+    pub eval: Option<i32>,
+    /// The number of moves to mate, negative if black is mating.
+    pub mate: Option<i32>,
+    /// The best move in UCI notation, if the played move was a mistake.
+    pub best: Option<String>,
+    /// The best line in SAN, if the played move was a mistake.
+    pub variation: Option<String>,
+    /// The judgment of the played move, if it was a mistake.
+    pub judgment: Option<Judgment>,
+}
+
+/// The judgment of a move in a [`PlyAnalysis`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Judgment {
+    /// The kind of the judgment, e.g. `"Blunder"`.
+    pub name: String,
+    /// A description of the judgment.
+    pub comment: String,
+}
+
+/// An event of a Lichess bot or board game stream, such as
+/// `/api/bot/game/stream/{id}`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum GameEvent {
+    /// The complete description of the game, sent first.
+    #[serde(rename_all = "camelCase")]
+    GameFull {
+        /// The identifier of the game.
+        id: String,
+        /// Whether the game is rated.
+        #[serde(default)]
+        rated: bool,
+        /// The initial position, either `"startpos"` or a FEN string.
+        initial_fen: String,
+        /// The current state of the game.
+        state: GameState,
+    },
+    /// The current state of the game, sent after each move.
+    GameState(GameState),
+    /// A message sent to one of the game's chat rooms.
+    ChatLine {
+        /// The chat room, e.g. `"player"` or `"spectator"`.
+        room: String,
+        /// The name of the sender.
+        username: String,
+        /// The text of the message.
+        text: String,
+    },
+    /// An announcement that the opponent has left the game.
+    OpponentGone {
+        /// Whether the opponent is gone.
+        gone: bool,
+    },
+}
+
+/// The state of a streamed game.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GameState {
+    /// The moves of the game as space-separated UCI moves.
+    pub moves: String,
+    /// The remaining time of white, in milliseconds.
+    pub wtime: u64,
+    /// The remaining time of black, in milliseconds.
+    pub btime: u64,
+    /// The increment of white, in milliseconds.
+    pub winc: u64,
+    /// The increment of black, in milliseconds.
+    pub binc: u64,
+    /// The status of the game, e.g. `"started"` or `"mate"`.
+    pub status: String,
+    /// The winning side, if the game was decided.
+    pub winner: Option<String>,
+}
+
+impl LichessGame {
+    /// Decodes a game from a single JSON object.
+    pub fn from_json(json: &str) -> Result<Self, LichessError> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    /// Decodes each non-empty line of `ndjson` as a game, as in the
+    /// `application/x-ndjson` responses of the export endpoints.
+    pub fn from_ndjson(ndjson: &str) -> impl Iterator<Item = Result<Self, LichessError>> + '_ {
+        ndjson
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(Self::from_json)
+    }
+
+    /// Returns the position in which the game started.
+    pub fn initial_board(&self) -> Result<Board, LichessError> {
+        if !matches!(self.variant.as_str(), "standard" | "fromPosition") {
+            return Err(LichessError::UnsupportedVariant(self.variant.clone()));
+        }
+
+        initial_board(self.initial_fen.as_deref())
+    }
+
+    /// Validates the moves of the game, in order, from its initial position.
+    pub fn legal_moves(&self) -> Result<Vec<LegalMove>, LichessError> {
+        let mut board = self.initial_board()?;
+        let mut legal_moves = Vec::new();
+
+        for (ply, literal) in self.moves.split_whitespace().enumerate() {
+            let illegal = || LichessError::IllegalMove {
+                ply,
+                literal: literal.to_string(),
+            };
+
+            let san = San::try_from(literal).map_err(|_| illegal())?;
+            let legal_move = board.validate_san(san).map_err(|_| illegal())?;
+            board = board.process(legal_move);
+            legal_moves.push(legal_move);
+        }
+
+        Ok(legal_moves)
+    }
+}
+
+impl TryFrom<&LichessGame> for Board {
+    type Error = LichessError;
+
+    /// Returns the final position of `game`.
+    fn try_from(game: &LichessGame) -> Result<Self, Self::Error> {
+        let board = game.initial_board()?;
+        Ok(game
+            .legal_moves()?
+            .into_iter()
+            .fold(board, |board, legal_move| board.process(legal_move)))
+    }
+}
+
+impl TryFrom<&LichessGame> for Game {
+    type Error = LichessError;
+
+    /// Replays `game` from its initial position, keeping each of its moves.
+    fn try_from(game: &LichessGame) -> Result<Self, Self::Error> {
+        let mut replay = Game::from_board(game.initial_board()?);
+        for legal_move in game.legal_moves()? {
+            replay
+                .play(legal_move.into())
+                .expect("a validated move should be legal when replayed");
+        }
+
+        Ok(replay)
+    }
+}
+
+impl GameEvent {
+    /// Decodes an event from a single line of a game stream.
+    pub fn from_json(json: &str) -> Result<Self, LichessError> {
+        Ok(serde_json::from_str(json)?)
+    }
+}
+
+impl GameState {
+    /// Validates the moves of the game, in order, from `initial`.
+    pub fn legal_moves(&self, initial: &Board) -> Result<Vec<LegalMove>, LichessError> {
+        let mut board = *initial;
+        let mut legal_moves = Vec::new();
+
+        for (ply, literal) in self.moves.split_whitespace().enumerate() {
+            let legal_move =
+                parse_uci(&board, literal).ok_or_else(|| LichessError::IllegalMove {
+                    ply,
+                    literal: literal.to_string(),
+                })?;
+
+            board = board.process(legal_move);
+            legal_moves.push(legal_move);
+        }
+
+        Ok(legal_moves)
+    }
+
+    /// Returns the current position of the game, which started from `initial`.
+    pub fn board(&self, initial: &Board) -> Result<Board, LichessError> {
+        Ok(self
+            .legal_moves(initial)?
+            .into_iter()
+            .fold(*initial, |board, legal_move| board.process(legal_move)))
+    }
+}
+
+impl GameEvent {
+    /// Returns the current position of the game described by a
+    /// [`GameEvent::GameFull`] event, or `None` for any other event.
+    pub fn board(&self) -> Option<Result<Board, LichessError>> {
+        match self {
+            Self::GameFull {
+                initial_fen, state, ..
+            } => {
+                let fen = Some(initial_fen.as_str()).filter(|&fen| fen != "startpos");
+                Some(initial_board(fen).and_then(|initial| state.board(&initial)))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// The default value of [`LichessGame::variant`].
+fn standard_variant() -> String {
+    String::from("standard")
+}
+
+/// Returns the board described by `fen`, or the standard
+/// starting position if there is none.
+fn initial_board(fen: Option<&str>) -> Result<Board, LichessError> {
+    match fen {
+        None => Ok(Board::default()),
+        Some(fen) => Fen::try_from(fen)
+            .map(Fen::to_standard_board)
+            .map_err(|_| LichessError::InvalidFen(fen.to_string())),
+    }
+}
+
+/// Validates a move in UCI notation (e.g. `e2e4` or `e7e8q`) on `board`.
+///
+/// Lichess may also describe castling as the king capturing its own rook,
+/// so such moves are converted into the usual two-square king move.
+fn parse_uci(board: &Board, literal: &str) -> Option<LegalMove> {
+    let candidate = uci::parse_move(literal).ok()?;
+    let (source, target) = (candidate.source(), candidate.target());
+
+    let moving = board[source]?;
+    let castles = moving.kind() == PieceKind::King
+        && board[target].is_some_and(|piece| {
+            piece.kind() == PieceKind::Rook && piece.color() == moving.color()
+        });
+
+    board
+        .validate(match castles {
+            true => {
+                let file = if target.file() > source.file() { 6 } else { 2 };
+                Move::from((source, Square::at(source.rank() * 8 + file)))
+            }
+            false => candidate,
+        })
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXPORT: &str = r#"{
+        "id": "q7ZvsdUF",
+        "rated": true,
+        "variant": "standard",
+        "speed": "blitz",
+        "createdAt": 1514505150384,
+        "lastMoveAt": 1514505592843,
+        "status": "mate",
+        "players": {
+            "white": {
+                "user": {"name": "Lance5500", "title": "LM", "id": "lance5500"},
+                "rating": 2389,
+                "ratingDiff": 4,
+                "analysis": {"inaccuracy": 1, "mistake": 0, "blunder": 0, "acpl": 12}
+            },
+            "black": {"aiLevel": 3}
+        },
+        "winner": "white",
+        "opening": {"eco": "C50", "name": "Italian Game", "ply": 5},
+        "moves": "e4 e5 Bc4 Nc6 Qh5 Nf6 Qxf7#",
+        "clock": {"initial": 300, "increment": 3, "totalTime": 420},
+        "analysis": [{"eval": 20}, {"eval": 25}, {"eval": 18},
+            {"eval": 30, "best": "g8f6", "variation": "Nf6",
+             "judgment": {"name": "Inaccuracy", "comment": "Nf6 was best."}}]
+    }"#;
+
+    #[test]
+    fn exported_games_are_replayed() {
+        let game = LichessGame::from_json(EXPORT).unwrap();
+        assert_eq!(game.players.white.rating, Some(2389));
+        assert_eq!(game.players.black.ai_level, Some(3));
+        assert_eq!(game.opening.as_ref().unwrap().eco, "C50");
+        assert_eq!(game.clock.unwrap().increment, 3);
+        assert_eq!(
+            game.analysis[3].judgment.as_ref().unwrap().name,
+            "Inaccuracy"
+        );
+
+        assert_eq!(game.legal_moves().unwrap().len(), 7);
+        let board = Board::try_from(&game).unwrap();
+        assert!(board.is_check());
+        assert_eq!(board.legal_moves().count(), 0);
+
+        let replay = Game::try_from(&game).unwrap();
+        assert_eq!(replay.moves(), game.legal_moves().unwrap().as_slice());
+        assert_eq!(replay.board(), &board);
+        assert_eq!(replay.result(), "1-0");
+
+        let ndjson = format!(
+            "{}\n\n{}\n",
+            EXPORT.replace('\n', ""),
+            EXPORT.replace('\n', "")
+        );
+        assert_eq!(LichessGame::from_ndjson(&ndjson).count(), 2);
+
+        let mut broken = game.clone();
+        broken.moves = String::from("e4 e4");
+        assert!(matches!(
+            broken.legal_moves(),
+            Err(LichessError::IllegalMove { ply: 1, .. })
+        ));
+
+        assert!(matches!(
+            Game::try_from(&broken),
+            Err(LichessError::IllegalMove { ply: 1, .. })
+        ));
+
+        broken.variant = String::from("atomic");
+        assert!(matches!(
+            broken.initial_board(),
+            Err(LichessError::UnsupportedVariant(_))
+        ));
+    }
+
+    #[test]
+    fn stream_events_are_decoded() {
+        let full = r#"{"type": "gameFull", "id": "5IrD6Gzz", "rated": false,
+            "initialFen": "r3k3/8/8/8/8/8/8/4K2R w Kq - 0 1",
+            "state": {"type": "gameState", "moves": "e1h1 e8c8", "wtime": 900000,
+                "btime": 900000, "winc": 0, "binc": 0, "status": "started"}}"#;
+
+        let event = GameEvent::from_json(full).unwrap();
+        let board = event.board().unwrap().unwrap();
+        assert_eq!(
+            Fen::from(&board).to_string(),
+            "2kr4/8/8/8/8/8/8/5RK1 w - - 0 1"
+        );
+
+        let chat = r#"{"type": "chatLine", "room": "player", "username": "bot", "text": "gg"}"#;
+        let event = GameEvent::from_json(chat).unwrap();
+        assert!(matches!(event, GameEvent::ChatLine { ref text, .. } if text == "gg"));
+        assert!(event.board().is_none());
+
+        let state = GameState {
+            moves: String::from("e2e4 e7e5 g1f3 b8c6 e4e5"),
+            wtime: 0,
+            btime: 0,
+            winc: 0,
+            binc: 0,
+            status: String::from("started"),
+            winner: None,
+        };
+
+        assert!(matches!(
+            state.legal_moves(&Board::default()),
+            Err(LichessError::IllegalMove { ply: 4, .. })
+        ));
+    }
+}
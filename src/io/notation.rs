@@ -0,0 +1,399 @@
+use thiserror::Error;
+
+use super::san::{self, San, SanLocale};
+use super::uci::{self, UciError};
+use crate::core::{Move as _, Piece as _, Process as _, Standard as _, Validate as _};
+use crate::standard::r#move::IllegalMoveError;
+use crate::standard::{Board, Color, LegalMove, Move, MoveKind, PieceKind, Square};
+
+/// A notation in which moves of standard chess are read and written.
+///
+/// Reading a move happens in two steps, since most notations cannot describe a
+/// move without reference to a position: [`Notation::parse`] checks the syntax
+/// of a literal, and [`Notation::resolve`] finds the legal move it describes on
+/// a [`Board`]. [`Notation::read`] combines the two.
+///
+/// ```
+/// use konig::io::notation::{Iccf, Lan, Notation, Uci};
+/// use konig::io::San;
+/// use konig::standard::Board;
+///
+/// fn reply<N: Notation>(board: &Board, literal: &str) -> Option<String> {
+///     let legal_move = N::read(literal, board)?;
+///     Some(N::format(&legal_move, board))
+/// }
+///
+/// let board = Board::default();
+/// assert_eq!(reply::<San>(&board, "Nf3").as_deref(), Some("Nf3"));
+/// assert_eq!(reply::<Lan>(&board, "Ng1-f3").as_deref(), Some("Ng1-f3"));
+/// assert_eq!(reply::<Uci>(&board, "g1f3").as_deref(), Some("g1f3"));
+/// assert_eq!(reply::<Iccf>(&board, "7163").as_deref(), Some("7163"));
+/// ```
+pub trait Notation {
+    /// A move as parsed from a literal, before it is resolved against a position.
+    type Parsed;
+
+    /// Results when a literal is not valid in this notation.
+    type Error<'a>: std::error::Error;
+
+    /// Parses `literal` without reference to a position.
+    fn parse(literal: &str) -> Result<Self::Parsed, Self::Error<'_>>;
+
+    /// Returns the legal move described by `parsed` on `board`, if there is exactly one.
+    fn resolve(parsed: Self::Parsed, board: &Board) -> Option<LegalMove>;
+
+    /// Formats `legal_move`, which must be legal on `board`, in this notation.
+    fn format(legal_move: &LegalMove, board: &Board) -> String;
+
+    /// Parses `literal` and resolves it against `board`.
+    fn read(literal: &str, board: &Board) -> Option<LegalMove> {
+        Self::parse(literal)
+            .ok()
+            .and_then(|parsed| Self::resolve(parsed, board))
+    }
+}
+
+/// Results when a literal is not a valid move in [`Lan`] or [`Iccf`] notation.
+#[derive(Debug, Error, Clone, Copy, PartialEq, Eq)]
+#[error("invalid {notation} move: {literal}")]
+pub struct NotationError<'a> {
+    /// The name of the notation.
+    pub notation: &'static str,
+    /// The invalid literal.
+    pub literal: &'a str,
+}
+
+impl Notation for San {
+    type Parsed = San;
+    type Error<'a> = san::ParseError<'a>;
+
+    fn parse(literal: &str) -> Result<Self::Parsed, Self::Error<'_>> {
+        San::try_from(literal)
+    }
+
+    fn resolve(parsed: Self::Parsed, board: &Board) -> Option<LegalMove> {
+        board.validate_san(parsed).ok()
+    }
+
+    fn format(legal_move: &LegalMove, board: &Board) -> String {
+        San::format(board, *legal_move)
+    }
+}
+
+/// The coordinate notation of UCI, e.g. `e2e4`, `e1g1` or `e7e8q`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Uci;
+
+impl Notation for Uci {
+    type Parsed = Move;
+    type Error<'a> = UciError;
+
+    fn parse(literal: &str) -> Result<Self::Parsed, Self::Error<'_>> {
+        uci::parse_move(literal)
+    }
+
+    fn resolve(parsed: Self::Parsed, board: &Board) -> Option<LegalMove> {
+        board.validate(parsed).ok()
+    }
+
+    fn format(legal_move: &LegalMove, _board: &Board) -> String {
+        legal_move.to_string()
+    }
+}
+
+/// Long algebraic notation, e.g. `e2-e4`, `Ng1xf3+`, `e7-e8=Q` or `O-O`.
+///
+/// Every move names its source and target squares, separated by `-` or `x`,
+/// and every move but a pawn's is preceded by the letter of its piece.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Lan;
+
+/// A move parsed from a literal in [`Lan`] notation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LanMove {
+    /// A move of the piece of the given kind, which must stand on the source square.
+    Move(PieceKind, Move),
+    /// The king castling towards the h-file, written `O-O`.
+    KingSideCastle,
+    /// The king castling towards the a-file, written `O-O-O`.
+    QueenSideCastle,
+}
+
+impl Notation for Lan {
+    type Parsed = LanMove;
+    type Error<'a> = NotationError<'a>;
+
+    fn parse(literal: &str) -> Result<Self::Parsed, Self::Error<'_>> {
+        let invalid = NotationError {
+            notation: "LAN",
+            literal,
+        };
+
+        let body = literal.trim_end_matches(['+', '#']);
+        match body {
+            "O-O" | "0-0" => return Ok(LanMove::KingSideCastle),
+            "O-O-O" | "0-0-0" => return Ok(LanMove::QueenSideCastle),
+            _ => (),
+        }
+
+        let (piece, body) = match body
+            .chars()
+            .next()
+            .and_then(|c| SanLocale::ENGLISH.from_char(c))
+        {
+            Some(kind) => (kind, &body[1..]),
+            None => (PieceKind::Pawn, body),
+        };
+
+        let square = |name: Option<&str>| name.and_then(|name| Square::try_from(name).ok());
+        let (Some(source), Some("-" | "x"), Some(target)) = (
+            square(body.get(0..2)),
+            body.get(2..3),
+            square(body.get(3..5)),
+        ) else {
+            return Err(invalid);
+        };
+
+        let candidate = Move::from((source, target));
+        let candidate = match body.get(5..) {
+            Some("") => candidate,
+            Some(suffix) if piece == PieceKind::Pawn => {
+                // the promotion piece may follow a single `=`, which cannot stand alone
+                let letter = suffix.strip_prefix('=').unwrap_or(suffix);
+                let mut chars = letter.chars();
+                match (
+                    chars.next().and_then(|c| SanLocale::ENGLISH.from_char(c)),
+                    chars.next(),
+                ) {
+                    (Some(kind), None) if kind != PieceKind::King => candidate.with_promotion(kind),
+                    _ => return Err(invalid),
+                }
+            }
+            _ => return Err(invalid),
+        };
+
+        Ok(LanMove::Move(piece, candidate))
+    }
+
+    fn resolve(parsed: Self::Parsed, board: &Board) -> Option<LegalMove> {
+        Lan::validate(parsed, board).ok()
+    }
+
+    fn format(legal_move: &LegalMove, board: &Board) -> String {
+        let candidate = Move::from(*legal_move);
+        let mut lan = String::new();
+        match board[candidate.source()] {
+            _ if legal_move.kind() == MoveKind::Castle => {
+                lan.push_str(match candidate.target().file() {
+                    6 => "O-O",
+                    _ => "O-O-O",
+                });
+            }
+            Some(piece) => {
+                lan.extend(piece.kind().to_san_char());
+                lan.push_str(&candidate.source().to_string());
+                lan.push(if legal_move.is_capture() { 'x' } else { '-' });
+                lan.push_str(&candidate.target().to_string());
+                if let Some(kind) = legal_move.promotion() {
+                    lan.push('=');
+                    lan.extend(kind.to_san_char());
+                }
+            }
+            None => return String::new(),
+        }
+
+        lan.push_str(check_suffix(board, *legal_move));
+        lan
+    }
+}
+
+impl Lan {
+    /// Resolves `parsed` against `board` as [`Notation::resolve`] does, describing
+    /// why it fails if it does.
+    ///
+    /// A move whose source square holds a piece other than the one named is
+    /// rejected with [`IllegalMoveError::InvalidSource`], and castling written
+    /// as a move of the king is rejected with [`IllegalMoveError::InvalidTarget`].
+    pub(crate) fn validate(parsed: LanMove, board: &Board) -> Result<LegalMove, IllegalMoveError> {
+        // the king castles from the e-file of its back rank, two squares towards the rook
+        let home = match board.side_to_move() {
+            Color::White => 4,
+            Color::Black => 60,
+        };
+        let castle = |target| {
+            (
+                PieceKind::King,
+                Move::from((Square::at(home), Square::at(target))),
+            )
+        };
+        let (kind, candidate) = match parsed {
+            LanMove::Move(kind, candidate) => (kind, candidate),
+            LanMove::KingSideCastle => castle(home + 2),
+            LanMove::QueenSideCastle => castle(home - 2),
+        };
+
+        // castling must be written as such, rather than as a move of the king
+        let legal_move = board.validate(candidate)?;
+        let castles = legal_move.kind() == MoveKind::Castle;
+        let written_as_castle = !matches!(parsed, LanMove::Move(..));
+        let matches_kind = board[candidate.source()].is_some_and(|piece| piece.kind() == kind);
+        match (matches_kind, castles == written_as_castle) {
+            (false, _) => Err(IllegalMoveError::InvalidSource(candidate.source())),
+            (true, false) => Err(IllegalMoveError::InvalidTarget(candidate.target())),
+            (true, true) => Ok(legal_move),
+        }
+    }
+}
+
+/// ICCF numeric notation, used in correspondence chess, e.g. `5254` for e2-e4.
+///
+/// Files and ranks are both numbered from 1 to 8, so that the source and target
+/// squares are written as two digits each. Castling is written as the move of the
+/// king, and a promotion is followed by a digit for its piece: 1 for a queen, 2 for
+/// a rook, 3 for a bishop and 4 for a knight.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Iccf;
+
+/// The pieces to which a pawn may promote, in the order of their ICCF digits.
+const ICCF_PROMOTIONS: [PieceKind; 4] = [
+    PieceKind::Queen,
+    PieceKind::Rook,
+    PieceKind::Bishop,
+    PieceKind::Knight,
+];
+
+impl Notation for Iccf {
+    type Parsed = Move;
+    type Error<'a> = NotationError<'a>;
+
+    fn parse(literal: &str) -> Result<Self::Parsed, Self::Error<'_>> {
+        let invalid = NotationError {
+            notation: "ICCF",
+            literal,
+        };
+
+        let digits: Vec<u8> = literal
+            .bytes()
+            .map(|byte| match byte {
+                b'1'..=b'8' => Ok(byte - b'1'),
+                _ => Err(invalid),
+            })
+            .collect::<Result<_, _>>()?;
+
+        let square = |file: u8, rank: u8| Square::at(rank * 8 + file);
+        match digits[..] {
+            [sf, sr, tf, tr] => Ok(Move::from((square(sf, sr), square(tf, tr)))),
+            [sf, sr, tf, tr, promotion @ 0..=3] => Ok(Move::from((square(sf, sr), square(tf, tr)))
+                .with_promotion(ICCF_PROMOTIONS[usize::from(promotion)])),
+            _ => Err(invalid),
+        }
+    }
+
+    fn resolve(parsed: Self::Parsed, board: &Board) -> Option<LegalMove> {
+        board.validate(parsed).ok()
+    }
+
+    fn format(legal_move: &LegalMove, _board: &Board) -> String {
+        let candidate = Move::from(*legal_move);
+        let mut iccf = String::new();
+        for square in [candidate.source(), candidate.target()] {
+            iccf.push(char::from(b'1' + square.file()));
+            iccf.push(char::from(b'1' + square.rank()));
+        }
+
+        if let Some(index) = legal_move.promotion().and_then(|kind| {
+            ICCF_PROMOTIONS
+                .iter()
+                .position(|&promotion| promotion == kind)
+        }) {
+            iccf.push(char::from(b'1' + index as u8));
+        }
+
+        iccf
+    }
+}
+
+/// Returns the suffix marking `legal_move` as a check (`+`) or a checkmate (`#`).
+fn check_suffix(board: &Board, legal_move: LegalMove) -> &'static str {
+    if !board.gives_check(&legal_move) {
+        return "";
+    }
+
+    match board.process(legal_move).has_legal_moves() {
+        true => "+",
+        false => "#",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::Fen;
+
+    fn board(fen: &str) -> Board {
+        Fen::try_from(fen).unwrap().to_standard_board()
+    }
+
+    /// Reads `literal` in the notation `N` and writes it back out.
+    fn round_trip<N: Notation>(board: &Board, literal: &str) -> Option<String> {
+        N::read(literal, board).map(|legal_move| N::format(&legal_move, board))
+    }
+
+    #[test]
+    fn moves_are_read_and_written_in_every_notation() {
+        let castling = board("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1");
+        assert_eq!(round_trip::<Lan>(&castling, "O-O").as_deref(), Some("O-O"));
+        assert_eq!(
+            round_trip::<Lan>(&castling, "0-0-0").as_deref(),
+            Some("O-O-O")
+        );
+        assert_eq!(round_trip::<Lan>(&castling, "Ke1-g1"), None);
+        assert_eq!(
+            round_trip::<Lan>(&castling, "Ra1xa8+").as_deref(),
+            Some("Ra1xa8+")
+        );
+        assert_eq!(round_trip::<Lan>(&castling, "Qa1-a8"), None);
+        assert_eq!(
+            round_trip::<Iccf>(&castling, "5171").as_deref(),
+            Some("5171")
+        );
+        assert_eq!(
+            round_trip::<Uci>(&castling, "e1c1").as_deref(),
+            Some("e1c1")
+        );
+
+        let promotion = board("8/1P4k1/8/8/8/8/8/4K3 w - - 0 1");
+        assert_eq!(
+            round_trip::<Lan>(&promotion, "b7-b8=N").as_deref(),
+            Some("b7-b8=N")
+        );
+        assert_eq!(
+            round_trip::<Lan>(&promotion, "b7-b8Q").as_deref(),
+            Some("b7-b8=Q")
+        );
+        assert_eq!(
+            round_trip::<Iccf>(&promotion, "27284").as_deref(),
+            Some("27284")
+        );
+        assert_eq!(
+            round_trip::<Iccf>(&promotion, "2728").as_deref(),
+            Some("27281")
+        );
+        assert_eq!(
+            round_trip::<San>(&promotion, "b8=R").as_deref(),
+            Some("b8=R")
+        );
+
+        assert!(Lan::parse("e2e4").is_err());
+        assert!(Lan::parse("Ke2-e4=Q").is_err());
+        assert!(Lan::parse("e7-e8=").is_err());
+        assert!(Lan::parse("e7-e8==Q").is_err());
+        assert!(Lan::parse("e7-e8Q").is_ok());
+        assert!(Iccf::parse("5259").is_err());
+        assert!(Iccf::parse("525445").is_err());
+        assert_eq!(
+            Iccf::parse("52").unwrap_err().to_string(),
+            "invalid ICCF move: 52"
+        );
+    }
+}
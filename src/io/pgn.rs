@@ -0,0 +1,731 @@
+use std::io::{BufRead, BufReader, Read};
+use std::ops::Range;
+
+use thiserror::Error;
+
+use super::{Fen, San};
+use crate::core::{Process as _, Standard as _, Validate as _};
+use crate::standard::{Board, Color, LegalMove};
+
+/// Provides a [`PgnIndex`] of the games in a PGN file, for random access.
+pub mod index;
+
+/// Provides [`PgnDatabase`], an in-memory collection of PGN games.
+pub mod database;
+
+/// Provides [`PgnStream`], which reads PGN games asynchronously.
+#[cfg(feature = "async")]
+pub mod stream;
+
+/// Provides filtering of PGN games by their tags and the positions they reach.
+pub mod query;
+
+pub use database::PgnDatabase;
+pub use index::{PgnIndex, PgnIndexEntry, SEVEN_TAG_ROSTER};
+pub use query::{PgnDate, PgnQuery};
+#[cfg(feature = "async")]
+pub use stream::PgnStream;
+
+/// Results when a PGN game cannot be read or replayed.
+#[derive(Debug, Error)]
+pub enum PgnError {
+    /// Results when the underlying reader fails.
+    #[error("failed to read PGN: {0}")]
+    Io(#[from] std::io::Error),
+    /// Results when a line of the input is not valid UTF-8.
+    #[error("line {0} is not valid UTF-8")]
+    InvalidUtf8(usize),
+    /// Results when a tag pair is malformed.
+    #[error("malformed tag pair on line {0}")]
+    InvalidTag(usize),
+    /// Results when the movetext of a game is malformed, e.g. by an unclosed comment.
+    #[error("malformed movetext on line {0}")]
+    InvalidMovetext(usize),
+    /// Results when the `FEN` tag of a game is not valid FEN.
+    #[error("invalid FEN tag: {0}")]
+    InvalidFen(String),
+    /// Results when a move of a game cannot be played.
+    #[error("illegal move {san} at ply {ply}")]
+    IllegalMove {
+        /// The index of the move in the game.
+        ply: usize,
+        /// The move as it appeared in the movetext.
+        san: String,
+    },
+}
+
+/// The code points of the bytes 0x80 to 0x9F in windows-1252, where Latin-1 has
+/// control characters; the five bytes which windows-1252 leaves undefined are
+/// replaced with U+FFFD.
+const WINDOWS_1252_HIGH: [char; 32] = [
+    '\u{20ac}', '\u{fffd}', '\u{201a}', '\u{0192}', '\u{201e}', '\u{2026}', '\u{2020}', '\u{2021}',
+    '\u{02c6}', '\u{2030}', '\u{0160}', '\u{2039}', '\u{0152}', '\u{fffd}', '\u{017d}', '\u{fffd}',
+    '\u{fffd}', '\u{2018}', '\u{2019}', '\u{201c}', '\u{201d}', '\u{2022}', '\u{2013}', '\u{2014}',
+    '\u{02dc}', '\u{2122}', '\u{0161}', '\u{203a}', '\u{0153}', '\u{fffd}', '\u{017e}', '\u{0178}',
+];
+
+/// How a PGN reader decodes the lines of its input which are not valid UTF-8.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum PgnEncoding {
+    /// Such lines are rejected with [`PgnError::InvalidUtf8`].
+    #[default]
+    Utf8,
+    /// Such lines are decoded as windows-1252, which extends Latin-1 and is the
+    /// encoding of many old databases, so that their tag values and comments are
+    /// transcoded rather than rejected. Bytes which windows-1252 leaves undefined
+    /// become U+FFFD.
+    ///
+    /// Lines which are valid UTF-8 are still read as UTF-8, so files which mix
+    /// the two encodings are read as well as they can be.
+    Windows1252,
+}
+
+impl PgnEncoding {
+    /// Decodes `bytes`, the line numbered `line` of the input.
+    pub(crate) fn decode(self, bytes: Vec<u8>, line: usize) -> Result<String, PgnError> {
+        match (String::from_utf8(bytes), self) {
+            (Ok(decoded), _) => Ok(decoded),
+            (Err(_), Self::Utf8) => Err(PgnError::InvalidUtf8(line)),
+            (Err(err), Self::Windows1252) => Ok(err
+                .as_bytes()
+                .iter()
+                .map(|&byte| match byte {
+                    0x80..=0x9f => WINDOWS_1252_HIGH[usize::from(byte - 0x80)],
+                    byte => char::from(byte),
+                })
+                .collect()),
+        }
+    }
+}
+
+/// The tag pairs of a PGN game, in the order in which they appeared.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Tags {
+    pairs: Vec<(String, String)>,
+}
+
+impl Tags {
+    /// Constructs an empty set of tags.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the value of the tag called `name`, if it is present.
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.pairs
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Sets the tag called `name` to `value`, returning its previous value.
+    pub fn insert(&mut self, name: impl Into<String>, value: impl Into<String>) -> Option<String> {
+        let name = name.into();
+        let value = value.into();
+        match self.pairs.iter_mut().find(|(key, _)| *key == name) {
+            Some((_, known)) => Some(std::mem::replace(known, value)),
+            None => {
+                self.pairs.push((name, value));
+                None
+            }
+        }
+    }
+
+    /// Removes the tag called `name`, returning its value.
+    pub fn remove(&mut self, name: &str) -> Option<String> {
+        let index = self.pairs.iter().position(|(key, _)| key == name)?;
+        Some(self.pairs.remove(index).1)
+    }
+
+    /// Returns the number of tags.
+    pub fn len(&self) -> usize {
+        self.pairs.len()
+    }
+
+    /// Returns `true` if there are no tags.
+    pub fn is_empty(&self) -> bool {
+        self.pairs.is_empty()
+    }
+
+    /// Returns an iterator over the names and values of the tags.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.pairs
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+    }
+}
+
+/// A move in the movetext of a PGN game, along with its annotations.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PgnMove {
+    /// The move as a SAN literal.
+    pub san: String,
+    /// The numeric annotation glyphs following the move, e.g. 1 for `$1`.
+    pub nags: Vec<u8>,
+    /// The comment following the move, if any.
+    pub comment: Option<String>,
+}
+
+/// A single game read from a PGN file.
+///
+/// Recursive annotation variations are skipped when a game is read,
+/// so only the main line of the game is kept.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct PgnGame {
+    /// The tag pairs of the game.
+    pub tags: Tags,
+    /// The comment before the first move, if any.
+    pub comment: Option<String>,
+    /// The moves of the main line.
+    pub moves: Vec<PgnMove>,
+    /// The game termination marker: `1-0`, `0-1`, `1/2-1/2`, or `*`.
+    pub result: String,
+}
+
+impl PgnGame {
+    /// Returns the position in which the game started, given by its `FEN` tag
+    /// or the standard starting position.
+    pub fn initial_board(&self) -> Result<Board, PgnError> {
+        match self.tags.get("FEN") {
+            None => Ok(Board::default()),
+            Some(fen) => Fen::try_from(fen)
+                .map(Fen::to_standard_board)
+                .map_err(|_| PgnError::InvalidFen(fen.to_string())),
+        }
+    }
+
+    /// Validates the moves of the game, in order, from its initial position.
+    pub fn legal_moves(&self) -> Result<Vec<LegalMove>, PgnError> {
+        let mut legal_moves = Vec::with_capacity(self.moves.len());
+        self.replay(|_, legal_move| legal_moves.push(legal_move))?;
+        Ok(legal_moves)
+    }
+
+    /// Returns the position at the end of the game.
+    pub fn final_board(&self) -> Result<Board, PgnError> {
+        self.replay(|_, _| ())
+    }
+
+    /// Plays the moves of the game from its initial position, calling `visit` with
+    /// each position and the move played from it, and returns the final position.
+    pub(crate) fn replay(
+        &self,
+        mut visit: impl FnMut(&Board, LegalMove),
+    ) -> Result<Board, PgnError> {
+        let mut board = self.initial_board()?;
+        for (ply, pgn_move) in self.moves.iter().enumerate() {
+            let illegal = || PgnError::IllegalMove {
+                ply,
+                san: pgn_move.san.clone(),
+            };
+
+            let san = San::try_from(pgn_move.san.as_str()).map_err(|_| illegal())?;
+            let legal_move = board.validate_san(san).map_err(|_| illegal())?;
+            visit(&board, legal_move);
+            board = board.process(legal_move);
+        }
+
+        Ok(board)
+    }
+}
+
+/// A streaming reader of PGN games, yielding one [`PgnGame`] at a time.
+///
+/// Games are read line by line from the underlying [`BufRead`], so files
+/// of any size can be processed without being loaded into memory.
+#[derive(Debug)]
+pub struct PgnReader<R> {
+    reader: R,
+    line: usize,
+    offset: u64,
+    pending: Option<(String, u64)>,
+    encoding: PgnEncoding,
+}
+
+impl<R: BufRead> PgnReader<R> {
+    /// Constructs a reader of the games in `reader`.
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            line: 0,
+            offset: 0,
+            pending: None,
+            encoding: PgnEncoding::default(),
+        }
+    }
+
+    /// Sets how lines of the input which are not valid UTF-8 are decoded,
+    /// which by default fails with [`PgnError::InvalidUtf8`].
+    pub fn with_encoding(mut self, encoding: PgnEncoding) -> Self {
+        self.encoding = encoding;
+        self
+    }
+
+    /// Returns the number of bytes consumed from the underlying reader.
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    /// Reads the next game, returning `None` at the end of the input,
+    /// along with the range of bytes which it spans.
+    pub(crate) fn next_game(&mut self) -> Option<Result<(Range<u64>, PgnGame), PgnError>> {
+        let mut builder = GameBuilder::default();
+        loop {
+            let (line, line_offset) = match self.next_line() {
+                Ok(Some(line)) => line,
+                Ok(None) => break,
+                Err(err) => return Some(Err(err)),
+            };
+
+            match builder.feed(&line, self.line, line_offset) {
+                Ok(Fed::Consumed) => continue,
+                Ok(Fed::Complete) => break,
+                Ok(Fed::Rejected) => {
+                    self.pending = Some((line, line_offset));
+                    self.line -= 1;
+                    break;
+                }
+                Err(err) => return Some(Err(err)),
+            }
+        }
+
+        let end = match &self.pending {
+            Some((_, pending_offset)) => *pending_offset,
+            None => self.offset,
+        };
+
+        Some(
+            builder
+                .finish(self.line)?
+                .map(|(start, game)| (start..end, game)),
+        )
+    }
+
+    /// Reads the next line, returning it along with its byte offset.
+    fn next_line(&mut self) -> Result<Option<(String, u64)>, PgnError> {
+        self.line += 1;
+        if let Some(pending) = self.pending.take() {
+            return Ok(Some(pending));
+        }
+
+        let mut bytes = Vec::new();
+        let offset = self.offset;
+        let read = self.reader.read_until(b'\n', &mut bytes)?;
+        if read == 0 {
+            return Ok(None);
+        }
+
+        self.offset += read as u64;
+        let line = self.encoding.decode(bytes, self.line)?;
+        Ok(Some((line, offset)))
+    }
+}
+
+impl<R: Read> PgnReader<BufReader<R>> {
+    /// Constructs a reader of the games in `reader`, which is buffered internally,
+    /// e.g. a [`File`](std::fs::File).
+    pub fn from_reader(reader: R) -> Self {
+        Self::new(BufReader::new(reader))
+    }
+}
+
+impl<R: BufRead> Iterator for PgnReader<R> {
+    type Item = Result<PgnGame, PgnError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_game().map(|game| game.map(|(_, game)| game))
+    }
+}
+
+/// A game being assembled from the lines of a PGN file, which lets the
+/// blocking and asynchronous readers share a parser.
+#[derive(Debug, Default)]
+pub(crate) struct GameBuilder {
+    game: PgnGame,
+    movetext: Movetext,
+    start: Option<u64>,
+}
+
+/// The effect of feeding a line to a [`GameBuilder`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Fed {
+    /// The line belongs to the game, which may continue.
+    Consumed,
+    /// The line belongs to the game, which has ended.
+    Complete,
+    /// The line begins the next game, and so was not consumed.
+    Rejected,
+}
+
+impl GameBuilder {
+    /// Feeds the line numbered `number`, which begins at byte `offset` of the input.
+    pub(crate) fn feed(&mut self, line: &str, number: usize, offset: u64) -> Result<Fed, PgnError> {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('%') {
+            return Ok(Fed::Consumed);
+        }
+
+        let movetext = &mut self.movetext;
+        if trimmed.starts_with('[') && movetext.depth == 0 && !movetext.in_comment {
+            // a tag pair after the movetext begins the next game
+            if movetext.has_started {
+                return Ok(Fed::Rejected);
+            }
+
+            self.start.get_or_insert(offset);
+            let (name, value) = parse_tag(trimmed).ok_or(PgnError::InvalidTag(number))?;
+            self.game.tags.insert(name, value);
+            return Ok(Fed::Consumed);
+        }
+
+        self.start.get_or_insert(offset);
+        movetext.has_started = true;
+        movetext.feed(line, &mut self.game);
+        match !self.game.result.is_empty() && movetext.depth == 0 && !movetext.in_comment {
+            true => Ok(Fed::Complete),
+            false => Ok(Fed::Consumed),
+        }
+    }
+
+    /// Finishes the game after its last line, which is numbered `number`, returning
+    /// the offset at which it began, or `None` if no line belonged to it.
+    pub(crate) fn finish(self, number: usize) -> Option<Result<(u64, PgnGame), PgnError>> {
+        let Self {
+            mut game,
+            movetext,
+            start,
+        } = self;
+
+        let start = start?;
+        if movetext.in_comment || movetext.depth != 0 {
+            return Some(Err(PgnError::InvalidMovetext(number)));
+        }
+
+        if game.result.is_empty() {
+            game.result = game.tags.get("Result").unwrap_or("*").to_string();
+        }
+
+        Some(Ok((start, game)))
+    }
+}
+
+/// The state of the movetext parser, which may span several lines.
+#[derive(Debug, Default)]
+struct Movetext {
+    has_started: bool,
+    in_comment: bool,
+    comment: String,
+    depth: usize,
+}
+
+impl Movetext {
+    /// Parses the tokens of `line` into `game`.
+    fn feed(&mut self, line: &str, game: &mut PgnGame) {
+        let mut chars = line.char_indices().peekable();
+        while let Some((index, c)) = chars.next() {
+            if self.in_comment {
+                if c == '}' {
+                    self.in_comment = false;
+                    let comment = std::mem::take(&mut self.comment).trim().to_string();
+                    if self.depth == 0 {
+                        attach_comment(game, comment);
+                    }
+                } else {
+                    self.comment.push(c);
+                }
+
+                continue;
+            }
+
+            match c {
+                '{' => self.in_comment = true,
+                ';' => {
+                    if self.depth == 0 {
+                        attach_comment(game, line[index + 1..].trim().to_string());
+                    }
+
+                    return;
+                }
+                '(' => self.depth += 1,
+                ')' => self.depth = self.depth.saturating_sub(1),
+                c if c.is_whitespace() => (),
+                _ => {
+                    let mut end = index + c.len_utf8();
+                    while let Some(&(next, c)) = chars.peek() {
+                        if c.is_whitespace() || "{}();".contains(c) {
+                            break;
+                        }
+
+                        end = next + c.len_utf8();
+                        chars.next();
+                    }
+
+                    if self.depth == 0 {
+                        push_token(game, &line[index..end]);
+                    }
+                }
+            }
+        }
+
+        if self.in_comment {
+            self.comment.push('\n');
+        }
+    }
+}
+
+/// Adds `comment` to the last move of `game`, or to the game itself.
+fn attach_comment(game: &mut PgnGame, comment: String) {
+    let target = match game.moves.last_mut() {
+        Some(pgn_move) => &mut pgn_move.comment,
+        None => &mut game.comment,
+    };
+
+    match target {
+        Some(existing) => {
+            existing.push(' ');
+            existing.push_str(&comment);
+        }
+        None => *target = Some(comment),
+    }
+}
+
+/// Adds a single movetext token to `game`.
+fn push_token(game: &mut PgnGame, token: &str) {
+    if matches!(token, "1-0" | "0-1" | "1/2-1/2" | "*") {
+        game.result = token.to_string();
+        return;
+    }
+
+    if let Some(nag) = token.strip_prefix('$') {
+        if let (Ok(nag), Some(pgn_move)) = (nag.parse(), game.moves.last_mut()) {
+            pgn_move.nags.push(nag);
+        }
+
+        return;
+    }
+
+    // strip move number indications, which may be attached to the move itself
+    let san = token.trim_start_matches(|c: char| c.is_ascii_digit() || c == '.');
+    if !san.is_empty() {
+        game.moves.push(PgnMove {
+            san: san.to_string(),
+            nags: Vec::new(),
+            comment: None,
+        });
+    }
+}
+
+/// Formats a game as PGN: the Seven Tag Roster (with `?` for missing tags) and
+/// the other `tags`, followed by the movetext of `moves` played from `start`
+/// and the `result` token, wrapped at `width` characters.
+pub(crate) fn format_game(
+    start: &Board,
+    moves: &[LegalMove],
+    tags: &Tags,
+    result: &str,
+    width: usize,
+) -> String {
+    let mut pgn = String::new();
+    for name in SEVEN_TAG_ROSTER {
+        let default = match name {
+            "Date" => "????.??.??",
+            _ => "?",
+        };
+
+        push_tag(&mut pgn, name, tags.get(name).unwrap_or(default));
+    }
+
+    for (name, value) in tags.iter() {
+        if !SEVEN_TAG_ROSTER.contains(&name) {
+            push_tag(&mut pgn, name, value);
+        }
+    }
+
+    pgn.push('\n');
+
+    let mut tokens = Vec::with_capacity(3 * moves.len() / 2 + 2);
+    let mut board = *start;
+    let mut number = 1;
+    for (ply, &legal_move) in moves.iter().enumerate() {
+        match board.side_to_move() {
+            Color::White => tokens.push(format!("{number}.")),
+            Color::Black if ply == 0 => tokens.push(format!("{number}...")),
+            Color::Black => {}
+        }
+
+        if board.side_to_move() == Color::Black {
+            number += 1;
+        }
+
+        tokens.push(San::format(&board, legal_move));
+        board = board.process(legal_move);
+    }
+
+    tokens.push(result.to_string());
+
+    let mut line_len = 0;
+    for token in tokens {
+        if line_len > 0 && line_len + 1 + token.len() > width {
+            pgn.push('\n');
+            line_len = 0;
+        } else if line_len > 0 {
+            pgn.push(' ');
+            line_len += 1;
+        }
+
+        line_len += token.len();
+        pgn.push_str(&token);
+    }
+
+    pgn.push('\n');
+    pgn
+}
+
+/// Writes a tag pair of the form `[Name "Value"]`, escaping the value.
+fn push_tag(pgn: &mut String, name: &str, value: &str) {
+    pgn.push('[');
+    pgn.push_str(name);
+    pgn.push_str(" \"");
+    for c in value.chars() {
+        if matches!(c, '"' | '\\') {
+            pgn.push('\\');
+        }
+
+        pgn.push(c);
+    }
+
+    pgn.push_str("\"]\n");
+}
+
+/// Parses a tag pair of the form `[Name "Value"]`.
+fn parse_tag(line: &str) -> Option<(String, String)> {
+    let inner = line.strip_prefix('[')?.strip_suffix(']')?.trim();
+    let (name, rest) = inner.split_once(char::is_whitespace)?;
+    let quoted = rest.trim().strip_prefix('"')?.strip_suffix('"')?;
+
+    let mut value = String::with_capacity(quoted.len());
+    let mut chars = quoted.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => value.push(chars.next()?),
+            c => value.push(c),
+        }
+    }
+
+    Some((name.to_string(), value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GAMES: &str = r#"[Event "Casual Game"]
+[Site "London"]
+[White "Anderssen, Adolf"]
+[Black "Kieseritzky, Lionel"]
+[Result "1-0"]
+
+1. e4 e5 2. f4 exf4 3. Bc4 Qh4+ {Black gives up castling} 4. Kf1 b5?! $6 5. Bxb5
+Nf6 (5... Qf6 6. Nc3) 6. Nf3 Qh6 7. d3 Nh5 8. Nh4 Qg5 9. Nf5 c6 10. g4 Nf6 11.
+Rg1 cxb5 12. h4 Qg6 13. h5 Qg5 14. Qf3 Ng8 15. Bxf4 Qf6 16. Nc3 Bc5 17. Nd5
+Qxb2 18. Bd6 Bxg1 19. e5 Qxa1+ 20. Ke2 Na6 21. Nxg7+ Kd8 22. Qf6+ Nxf6 23. Be7# 1-0
+
+[Event "Fragment"]
+[FEN "4k3/8/8/8/8/8/4P3/4K3 w - - 0 1"]
+[Result "*"]
+
+{An endgame} 1. e4 Kd7 ; the king approaches
+2. e5 *
+"#;
+
+    #[test]
+    fn games_are_read_one_at_a_time() {
+        let mut reader = PgnReader::new(GAMES.as_bytes());
+        let immortal = reader.next().unwrap().unwrap();
+        assert_eq!(immortal.tags.get("White"), Some("Anderssen, Adolf"));
+        assert_eq!(immortal.result, "1-0");
+        assert_eq!(immortal.moves.len(), 45);
+        assert_eq!(
+            immortal.moves[5].comment.as_deref(),
+            Some("Black gives up castling")
+        );
+        assert_eq!(immortal.moves[7].nags, vec![6]);
+        assert_eq!(immortal.moves[9].san, "Nf6");
+
+        let board = immortal.final_board().unwrap();
+        assert!(board.is_check());
+        assert_eq!(board.legal_moves().count(), 0);
+
+        let fragment = reader.next().unwrap().unwrap();
+        assert_eq!(fragment.comment.as_deref(), Some("An endgame"));
+        assert_eq!(
+            fragment.moves[1].comment.as_deref(),
+            Some("the king approaches")
+        );
+        assert_eq!(fragment.legal_moves().unwrap().len(), 3);
+        assert!(reader.next().is_none());
+    }
+
+    #[test]
+    fn windows_1252_input_is_transcoded_when_enabled() {
+        let mut pgn = b"[White \"M\xfcller, J\xf6rg\"]\n[Result \"*\"]\n\n".to_vec();
+        pgn.extend_from_slice(b"1. e4 {\x93costs \x80100\x94\x81} *\n");
+
+        assert!(matches!(
+            PgnReader::new(pgn.as_slice()).next(),
+            Some(Err(PgnError::InvalidUtf8(1)))
+        ));
+
+        let game = PgnReader::new(pgn.as_slice())
+            .with_encoding(PgnEncoding::Windows1252)
+            .next()
+            .unwrap()
+            .unwrap();
+        assert_eq!(game.tags.get("White"), Some("Müller, Jörg"));
+        assert_eq!(
+            game.moves[0].comment.as_deref(),
+            Some("\u{201c}costs \u{20ac}100\u{201d}\u{fffd}")
+        );
+
+        // lines which are already UTF-8 are left as they are
+        let utf8 = "[White \"Müller\"]\n\n1. e4 *\n";
+        let game = PgnReader::new(utf8.as_bytes())
+            .with_encoding(PgnEncoding::Windows1252)
+            .next()
+            .unwrap()
+            .unwrap();
+        assert_eq!(game.tags.get("White"), Some("Müller"));
+    }
+
+    #[test]
+    fn malformed_games_are_reported() {
+        let unclosed = "[Event \"?\"]\n\n1. e4 {never closed\n";
+        assert!(matches!(
+            PgnReader::new(unclosed.as_bytes()).next(),
+            Some(Err(PgnError::InvalidMovetext(_)))
+        ));
+
+        let bad_tag = "[Event ?]\n\n1. e4 *\n";
+        assert!(matches!(
+            PgnReader::new(bad_tag.as_bytes()).next(),
+            Some(Err(PgnError::InvalidTag(1)))
+        ));
+
+        let illegal = PgnReader::new("1. e4 e4 *".as_bytes())
+            .next()
+            .unwrap()
+            .unwrap();
+        assert!(matches!(
+            illegal.legal_moves(),
+            Err(PgnError::IllegalMove { ply: 1, .. })
+        ));
+
+        let mut tags = Tags::new();
+        assert_eq!(tags.insert("White", "?"), None);
+        assert_eq!(tags.insert("White", "Tal"), Some(String::from("?")));
+        assert_eq!(
+            parse_tag(r#"[Annotator "A \"quoted\" name"]"#),
+            Some(("Annotator".into(), r#"A "quoted" name"#.into()))
+        );
+    }
+}
@@ -0,0 +1,216 @@
+use std::collections::{BTreeMap, HashSet};
+use std::io::BufRead;
+use std::ops::{Index, IndexMut};
+
+use super::{PgnError, PgnGame, PgnQuery, PgnReader};
+
+/// An in-memory collection of parsed PGN games.
+///
+/// Unlike a [`PgnReader`], which yields one game at a time, a database holds
+/// every game at once, so they can be accessed in any order, grouped, and
+/// combined with the games of other databases.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PgnDatabase {
+    games: Vec<PgnGame>,
+}
+
+impl PgnDatabase {
+    /// Constructs an empty database.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reads every game in `reader` into a database, failing at the first game
+    /// which cannot be read.
+    pub fn read<R: BufRead>(reader: R) -> Result<Self, PgnError> {
+        PgnReader::new(reader).collect()
+    }
+
+    /// Returns the number of games.
+    pub fn len(&self) -> usize {
+        self.games.len()
+    }
+
+    /// Returns `true` if there are no games.
+    pub fn is_empty(&self) -> bool {
+        self.games.is_empty()
+    }
+
+    /// Returns the games, in the order in which they were added.
+    pub fn games(&self) -> &[PgnGame] {
+        &self.games
+    }
+
+    /// Returns the game at `index`.
+    pub fn get(&self, index: usize) -> Option<&PgnGame> {
+        self.games.get(index)
+    }
+
+    /// Returns a mutable reference to the game at `index`.
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut PgnGame> {
+        self.games.get_mut(index)
+    }
+
+    /// Returns an iterator over the games.
+    pub fn iter(&self) -> std::slice::Iter<'_, PgnGame> {
+        self.games.iter()
+    }
+
+    /// Returns an iterator over the games which match `query`.
+    pub fn query<'a>(&'a self, query: &'a PgnQuery) -> impl Iterator<Item = &'a PgnGame> {
+        self.games.iter().filter(|game| query.matches(game))
+    }
+
+    /// Groups the games by the value of the tag called `name`, in order of the
+    /// values; the games which do not have the tag are grouped under `None`.
+    ///
+    /// Within each group, the games keep their order in the database.
+    pub fn group_by_tag(&self, name: &str) -> BTreeMap<Option<&str>, Vec<&PgnGame>> {
+        let mut groups: BTreeMap<_, Vec<_>> = BTreeMap::new();
+        for game in &self.games {
+            groups.entry(game.tags.get(name)).or_default().push(game);
+        }
+
+        groups
+    }
+
+    /// Adds `game` after the other games.
+    pub fn push(&mut self, game: PgnGame) {
+        self.games.push(game);
+    }
+
+    /// Moves every game of `other` after the games of `self`, leaving `other` empty.
+    pub fn append(&mut self, other: &mut PgnDatabase) {
+        self.games.append(&mut other.games);
+    }
+
+    /// Adds the games of `other` after the games of `self`, skipping those
+    /// which are equal to a game already present.
+    pub fn merge(&mut self, other: PgnDatabase) {
+        let mut seen: HashSet<&PgnGame> = self.games.iter().collect();
+        let fresh: Vec<bool> = other.games.iter().map(|game| seen.insert(game)).collect();
+
+        let games = other.games.into_iter().zip(fresh);
+        self.games
+            .extend(games.filter_map(|(game, fresh)| fresh.then_some(game)));
+    }
+}
+
+impl Index<usize> for PgnDatabase {
+    type Output = PgnGame;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.games[index]
+    }
+}
+
+impl IndexMut<usize> for PgnDatabase {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        &mut self.games[index]
+    }
+}
+
+impl From<Vec<PgnGame>> for PgnDatabase {
+    fn from(games: Vec<PgnGame>) -> Self {
+        Self { games }
+    }
+}
+
+impl From<PgnDatabase> for Vec<PgnGame> {
+    fn from(value: PgnDatabase) -> Self {
+        value.games
+    }
+}
+
+impl FromIterator<PgnGame> for PgnDatabase {
+    fn from_iter<T: IntoIterator<Item = PgnGame>>(iter: T) -> Self {
+        Self {
+            games: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl Extend<PgnGame> for PgnDatabase {
+    fn extend<T: IntoIterator<Item = PgnGame>>(&mut self, iter: T) {
+        self.games.extend(iter);
+    }
+}
+
+impl IntoIterator for PgnDatabase {
+    type Item = PgnGame;
+    type IntoIter = std::vec::IntoIter<PgnGame>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.games.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a PgnDatabase {
+    type Item = &'a PgnGame;
+    type IntoIter = std::slice::Iter<'a, PgnGame>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.games.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GAMES: &str = r#"[Event "Club Championship"]
+[White "Alice"]
+[Black "Bob"]
+[Result "1-0"]
+
+1. e4 e5 2. Qh5 Nc6 3. Bc4 Nf6 4. Qxf7# 1-0
+
+[Event "Club Championship"]
+[White "Bob"]
+[Black "Alice"]
+[Result "0-1"]
+
+1. f3 e5 2. g4 Qh4# 0-1
+
+[White "Carol"]
+[Black "Alice"]
+[Result "*"]
+
+1. d4 *
+"#;
+
+    #[test]
+    fn games_are_accessed_and_grouped() {
+        let database = PgnDatabase::read(GAMES.as_bytes()).unwrap();
+        assert_eq!(database.len(), 3);
+        assert_eq!(database[1].tags.get("White"), Some("Bob"));
+        assert_eq!(database.get(2).map(|game| game.moves.len()), Some(1));
+        assert!(database.get(3).is_none());
+
+        let groups = database.group_by_tag("Event");
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[&Some("Club Championship")].len(), 2);
+        assert_eq!(groups[&None][0].tags.get("White"), Some("Carol"));
+
+        let alice = PgnQuery::new().black("Alice");
+        assert_eq!(database.query(&alice).count(), 2);
+        assert_eq!((&database).into_iter().count(), 3);
+    }
+
+    #[test]
+    fn databases_are_merged_and_appended() {
+        let database = PgnDatabase::read(GAMES.as_bytes()).unwrap();
+        let mut first: PgnDatabase = database.iter().take(2).cloned().collect();
+        let mut second: PgnDatabase = database.iter().skip(1).cloned().collect();
+
+        // the game common to both databases is only kept once
+        let mut merged = first.clone();
+        merged.merge(second.clone());
+        assert_eq!(merged, database);
+
+        first.append(&mut second);
+        assert_eq!(first.len(), 4);
+        assert!(second.is_empty());
+        assert_eq!(first[2], first[1]);
+    }
+}
@@ -0,0 +1,294 @@
+use std::io::{BufRead, Read, Seek, SeekFrom, Write};
+
+use super::{PgnError, PgnGame, PgnReader};
+
+/// The magic bytes at the start of a serialized [`PgnIndex`].
+const MAGIC: [u8; 4] = *b"KPGI";
+
+/// The version of the serialized format of a [`PgnIndex`].
+const VERSION: u8 = 1;
+
+/// The length written in place of a tag which a game does not have.
+const ABSENT: u16 = u16::MAX;
+
+/// The tags recorded by [`PgnIndex::build`]: the Seven Tag Roster.
+pub const SEVEN_TAG_ROSTER: [&str; 7] =
+    ["Event", "Site", "Date", "Round", "White", "Black", "Result"];
+
+/// The entry of a single game in a [`PgnIndex`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PgnIndexEntry {
+    /// The byte offset at which the game starts.
+    pub offset: u64,
+    /// The number of bytes spanned by the game.
+    pub len: u64,
+    /// The values of the indexed tags, in the order of [`PgnIndex::tag_names`].
+    pub tags: Vec<Option<String>>,
+    /// The [Zobrist hash](crate::standard::Board::zobrist) of the final position
+    /// of the game, or `None` if the game could not be replayed.
+    pub final_hash: Option<u64>,
+}
+
+/// A compact index of the games in a PGN file, enabling random access to them.
+///
+/// An index is built by reading the file once, and records the byte range of each
+/// game along with a few of its tags and the hash of its final position. It can be
+/// written to and read back from a small binary file, so that large databases need
+/// only be scanned once.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PgnIndex {
+    tag_names: Vec<String>,
+    entries: Vec<PgnIndexEntry>,
+}
+
+impl PgnIndex {
+    /// Builds an index of the games in `reader`, recording the [`SEVEN_TAG_ROSTER`].
+    pub fn build<R: BufRead>(reader: R) -> Result<Self, PgnError> {
+        Self::build_with_tags(reader, &SEVEN_TAG_ROSTER)
+    }
+
+    /// Builds an index of the games in `reader`, recording the tags called `tag_names`.
+    ///
+    /// Reading stops at the first game which cannot be read; games which
+    /// cannot be replayed are indexed without a final hash.
+    pub fn build_with_tags<R: BufRead>(reader: R, tag_names: &[&str]) -> Result<Self, PgnError> {
+        let mut reader = PgnReader::new(reader);
+        let mut index = Self {
+            tag_names: tag_names.iter().map(|name| name.to_string()).collect(),
+            entries: Vec::new(),
+        };
+
+        while let Some(game) = reader.next_game() {
+            let (range, game) = game?;
+            let tags = tag_names
+                .iter()
+                .map(|name| game.tags.get(name).map(str::to_string))
+                .collect();
+
+            index.entries.push(PgnIndexEntry {
+                offset: range.start,
+                len: range.end - range.start,
+                tags,
+                final_hash: game.final_board().ok().map(|board| board.zobrist()),
+            });
+        }
+
+        Ok(index)
+    }
+
+    /// Returns the number of indexed games.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if no games are indexed.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the names of the indexed tags.
+    pub fn tag_names(&self) -> &[String] {
+        &self.tag_names
+    }
+
+    /// Returns the entries of the indexed games, in the order of the file.
+    pub fn entries(&self) -> &[PgnIndexEntry] {
+        &self.entries
+    }
+
+    /// Returns the entry of the game at `index`.
+    pub fn get(&self, index: usize) -> Option<&PgnIndexEntry> {
+        self.entries.get(index)
+    }
+
+    /// Returns the value of the tag called `name` in the game at `index`,
+    /// if the tag is indexed and the game has it.
+    pub fn tag(&self, index: usize, name: &str) -> Option<&str> {
+        let position = self.tag_names.iter().position(|known| known == name)?;
+        self.entries.get(index)?.tags[position].as_deref()
+    }
+
+    /// Returns the indices of the games which ended in the position with the given `hash`.
+    pub fn find_final_hash(&self, hash: u64) -> impl Iterator<Item = usize> + '_ {
+        self.entries
+            .iter()
+            .enumerate()
+            .filter(move |(_, entry)| entry.final_hash == Some(hash))
+            .map(|(index, _)| index)
+    }
+
+    /// Reads the game at `index` from `source`, which must be the file the index was built from.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn read_game<R: Read + Seek>(
+        &self,
+        source: &mut R,
+        index: usize,
+    ) -> Result<PgnGame, PgnError> {
+        let entry = &self.entries[index];
+        source.seek(SeekFrom::Start(entry.offset))?;
+
+        let mut bytes = vec![0; entry.len as usize];
+        source.read_exact(&mut bytes)?;
+
+        PgnReader::new(bytes.as_slice())
+            .next()
+            .unwrap_or_else(|| Ok(PgnGame::default()))
+    }
+
+    /// Writes the index to `writer` in a compact binary format.
+    pub fn write_to<W: Write>(&self, mut writer: W) -> Result<(), PgnError> {
+        writer.write_all(&MAGIC)?;
+        writer.write_all(&[VERSION])?;
+        writer.write_all(&(self.tag_names.len() as u16).to_le_bytes())?;
+        for name in &self.tag_names {
+            write_string(&mut writer, Some(name))?;
+        }
+
+        writer.write_all(&(self.entries.len() as u64).to_le_bytes())?;
+        for entry in &self.entries {
+            writer.write_all(&entry.offset.to_le_bytes())?;
+            writer.write_all(&entry.len.to_le_bytes())?;
+            writer.write_all(&[u8::from(entry.final_hash.is_some())])?;
+            writer.write_all(&entry.final_hash.unwrap_or(0).to_le_bytes())?;
+            for tag in &entry.tags {
+                write_string(&mut writer, tag.as_deref())?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Reads an index written by [`PgnIndex::write_to`] from `reader`.
+    pub fn read_from<R: Read>(mut reader: R) -> Result<Self, PgnError> {
+        let invalid = || {
+            PgnError::Io(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "not a PGN index",
+            ))
+        };
+
+        let mut header = [0; 5];
+        reader.read_exact(&mut header)?;
+        if header[..4] != MAGIC || header[4] != VERSION {
+            return Err(invalid());
+        }
+
+        let tag_count = u16::from_le_bytes(read_array(&mut reader)?);
+        let tag_names = (0..tag_count)
+            .map(|_| read_string(&mut reader)?.ok_or_else(invalid))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let entry_count = u64::from_le_bytes(read_array(&mut reader)?);
+        let mut entries = Vec::new();
+        for _ in 0..entry_count {
+            let offset = u64::from_le_bytes(read_array(&mut reader)?);
+            let len = u64::from_le_bytes(read_array(&mut reader)?);
+            let [has_hash] = read_array(&mut reader)?;
+            let hash = u64::from_le_bytes(read_array(&mut reader)?);
+            let tags = (0..tag_count)
+                .map(|_| read_string(&mut reader))
+                .collect::<Result<_, _>>()?;
+
+            entries.push(PgnIndexEntry {
+                offset,
+                len,
+                tags,
+                final_hash: (has_hash != 0).then_some(hash),
+            });
+        }
+
+        Ok(Self { tag_names, entries })
+    }
+}
+
+/// Writes `value` prefixed by its length, or [`ABSENT`] if there is no value.
+fn write_string<W: Write>(writer: &mut W, value: Option<&str>) -> Result<(), PgnError> {
+    match value {
+        // values too long to be indexed are truncated at a character boundary
+        Some(value) => {
+            let mut len = value.len().min(ABSENT as usize - 1);
+            while !value.is_char_boundary(len) {
+                len -= 1;
+            }
+
+            writer.write_all(&(len as u16).to_le_bytes())?;
+            writer.write_all(&value.as_bytes()[..len])?;
+        }
+        None => writer.write_all(&ABSENT.to_le_bytes())?,
+    }
+
+    Ok(())
+}
+
+/// Reads a value written by [`write_string`].
+fn read_string<R: Read>(reader: &mut R) -> Result<Option<String>, PgnError> {
+    let len = u16::from_le_bytes(read_array(reader)?);
+    if len == ABSENT {
+        return Ok(None);
+    }
+
+    let mut bytes = vec![0; len as usize];
+    reader.read_exact(&mut bytes)?;
+    String::from_utf8(bytes)
+        .map(Some)
+        .map_err(|err| PgnError::Io(std::io::Error::new(std::io::ErrorKind::InvalidData, err)))
+}
+
+/// Reads exactly `N` bytes from `reader`.
+fn read_array<R: Read, const N: usize>(reader: &mut R) -> Result<[u8; N], PgnError> {
+    let mut bytes = [0; N];
+    reader.read_exact(&mut bytes)?;
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    const GAMES: &str = "[White \"Morphy, Paul\"]\n[Black \"Duke Karl\"]\n\n1. e4 e5 1-0\n\n\
+        [White \"Fischer, Robert\"]\n\n1. e4 c5 2. Nf3\nd6 3. d4 *\n\n\
+        [White \"Broken\"]\n\n1. e4 e4 *\n";
+
+    #[test]
+    fn games_are_indexed_and_read_back() {
+        let index = PgnIndex::build(GAMES.as_bytes()).unwrap();
+        assert_eq!(index.len(), 3);
+        assert_eq!(index.tag(0, "Black"), Some("Duke Karl"));
+        assert_eq!(index.tag(1, "Black"), None);
+        assert_eq!(index.tag(1, "ECO"), None);
+        assert_eq!(index.get(2).unwrap().final_hash, None);
+
+        let mut source = Cursor::new(GAMES.as_bytes());
+        let fischer = index.read_game(&mut source, 1).unwrap();
+        assert_eq!(fischer.tags.get("White"), Some("Fischer, Robert"));
+        assert_eq!(fischer.moves.len(), 5);
+
+        let final_hash = fischer.final_board().unwrap().zobrist();
+        assert_eq!(
+            index.find_final_hash(final_hash).collect::<Vec<_>>(),
+            vec![1]
+        );
+
+        let morphy = index.read_game(&mut source, 0).unwrap();
+        assert_eq!(morphy.result, "1-0");
+    }
+
+    #[test]
+    fn indices_round_trip_through_bytes() {
+        let index = PgnIndex::build_with_tags(GAMES.as_bytes(), &["White", "Black"]).unwrap();
+        let mut bytes = Vec::new();
+        index.write_to(&mut bytes).unwrap();
+
+        let decoded = PgnIndex::read_from(bytes.as_slice()).unwrap();
+        assert_eq!(decoded, index);
+        assert_eq!(decoded.tag_names(), ["White", "Black"]);
+
+        bytes[0] = b'X';
+        assert!(PgnIndex::read_from(bytes.as_slice()).is_err());
+        assert!(PgnIndex::read_from(&bytes[..10]).is_err());
+    }
+}
@@ -0,0 +1,287 @@
+use std::collections::HashSet;
+
+use super::{PgnError, PgnGame};
+use crate::standard::Board;
+
+/// A date in the format of the PGN `Date` tag, where any unknown
+/// components are written as question marks (e.g. `1851.??.??`).
+///
+/// Dates are ordered by year, then month, then day, with an unknown
+/// component ordered before every known one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PgnDate {
+    /// The year, if it is known.
+    pub year: Option<u16>,
+    /// The month, from 1 to 12, if it is known.
+    pub month: Option<u8>,
+    /// The day of the month, from 1 to 31, if it is known.
+    pub day: Option<u8>,
+}
+
+impl PgnDate {
+    /// Parses a date in the `YYYY.MM.DD` format, returning `None` if it is malformed.
+    pub fn parse(date: &str) -> Option<Self> {
+        let mut parts = date.split('.');
+        let year = parse_component(parts.next()?, 4)?;
+        let month = parse_component(parts.next()?, 2)?;
+        let day = parse_component(parts.next()?, 2)?;
+        if parts.next().is_some() {
+            return None;
+        }
+
+        Some(Self {
+            year,
+            month: month.map(|month| month as u8),
+            day: day.map(|day| day as u8),
+        })
+    }
+}
+
+/// Parses a date component of `width` digits, or of `width` question marks if it is unknown.
+fn parse_component(component: &str, width: usize) -> Option<Option<u16>> {
+    if component.len() != width {
+        return None;
+    }
+
+    match component.chars().all(|c| c == '?') {
+        true => Some(None),
+        false => component.parse().ok().map(Some),
+    }
+}
+
+/// A predicate over a [`PgnGame`].
+type Predicate = Box<dyn Fn(&PgnGame) -> bool + Send + Sync>;
+
+/// A filter over PGN games, built from predicates on their tags and
+/// the positions they reach.
+///
+/// A game matches a query if it satisfies every predicate added to it,
+/// and so an empty query matches every game. Queries are applied lazily
+/// to a stream of games with [`PgnQuery::filter`]; positional predicates
+/// replay each game, and so are only checked once every tag predicate
+/// has been satisfied.
+///
+/// ```
+/// use konig::io::pgn::{PgnQuery, PgnReader};
+///
+/// let pgn = "[White \"Tal, Mikhail\"]\n[ECO \"B20\"]\n\n1. e4 c5 *\n";
+/// let query = PgnQuery::new().player("Tal, Mikhail").eco("B20", "B99");
+/// let games: Vec<_> = query.filter(PgnReader::new(pgn.as_bytes())).collect();
+/// assert_eq!(games.len(), 1);
+/// ```
+#[derive(Default)]
+pub struct PgnQuery {
+    tags: Vec<Predicate>,
+    positions: HashSet<u64>,
+}
+
+impl std::fmt::Debug for PgnQuery {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PgnQuery")
+            .field("tags", &self.tags.len())
+            .field("positions", &self.positions)
+            .finish()
+    }
+}
+
+impl PgnQuery {
+    /// Constructs a query which matches every game.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Requires the tag called `name` to be present and satisfy `predicate`.
+    pub fn tag(
+        mut self,
+        name: impl Into<String>,
+        predicate: impl Fn(&str) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        let name = name.into();
+        self.tags.push(Box::new(move |game| {
+            game.tags.get(&name).is_some_and(&predicate)
+        }));
+        self
+    }
+
+    /// Requires `name` to have played either side.
+    pub fn player(mut self, name: impl Into<String>) -> Self {
+        let name = name.into();
+        self.tags.push(Box::new(move |game| {
+            game.tags.get("White") == Some(name.as_str())
+                || game.tags.get("Black") == Some(name.as_str())
+        }));
+        self
+    }
+
+    /// Requires `name` to have played the white pieces.
+    pub fn white(self, name: impl Into<String>) -> Self {
+        let name = name.into();
+        self.tag("White", move |value| value == name)
+    }
+
+    /// Requires `name` to have played the black pieces.
+    pub fn black(self, name: impl Into<String>) -> Self {
+        let name = name.into();
+        self.tag("Black", move |value| value == name)
+    }
+
+    /// Requires the `ECO` code of the game to lie between `first` and `last`,
+    /// inclusive (e.g. `"B20"` and `"B99"` for the Sicilian Defence).
+    pub fn eco(self, first: impl Into<String>, last: impl Into<String>) -> Self {
+        let first = first.into();
+        let last = last.into();
+        self.tag("ECO", move |eco| {
+            first.as_str() <= eco && eco <= last.as_str()
+        })
+    }
+
+    /// Requires the game to have ended with `result`, e.g. `"1-0"`.
+    pub fn result(mut self, result: impl Into<String>) -> Self {
+        let result = result.into();
+        self.tags.push(Box::new(move |game| game.result == result));
+        self
+    }
+
+    /// Requires the `Date` tag of the game to lie between `first` and `last`, inclusive.
+    pub fn date_range(self, first: PgnDate, last: PgnDate) -> Self {
+        self.tag("Date", move |date| {
+            PgnDate::parse(date).is_some_and(|date| first <= date && date <= last)
+        })
+    }
+
+    /// Requires the game to reach the position of `board`, as identified by its
+    /// [Zobrist hash](Board::zobrist). Adding several positions requires the game
+    /// to reach any one of them.
+    pub fn reaches(mut self, board: &Board) -> Self {
+        self.positions.insert(board.zobrist());
+        self
+    }
+
+    /// Returns `true` if `game` satisfies every predicate of the query.
+    ///
+    /// Games which cannot be replayed never satisfy a positional predicate.
+    pub fn matches(&self, game: &PgnGame) -> bool {
+        if !self.tags.iter().all(|predicate| predicate(game)) {
+            return false;
+        }
+
+        if self.positions.is_empty() {
+            return true;
+        }
+
+        let mut reached = false;
+        let final_board = game.replay(|board, _| {
+            reached |= self.positions.contains(&board.zobrist());
+        });
+
+        match final_board {
+            Ok(board) => reached || self.positions.contains(&board.zobrist()),
+            Err(_) => false,
+        }
+    }
+
+    /// Lazily filters `games`, yielding the games which match the query
+    /// along with any errors encountered while reading them.
+    pub fn filter<I>(self, games: I) -> impl Iterator<Item = Result<PgnGame, PgnError>>
+    where
+        I: IntoIterator<Item = Result<PgnGame, PgnError>>,
+    {
+        games.into_iter().filter(move |game| match game {
+            Ok(game) => self.matches(game),
+            Err(_) => true,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::pgn::PgnReader;
+    use crate::io::Fen;
+
+    const GAMES: &str = r#"[White "Morphy, Paul"]
+[Black "Duke Karl"]
+[Date "1858.??.??"]
+[ECO "C41"]
+[Result "1-0"]
+
+1. e4 e5 2. Nf3 d6 3. d4 Bg4 1-0
+
+[White "Fischer, Robert"]
+[Black "Spassky, Boris"]
+[Date "1972.07.23"]
+[ECO "B44"]
+[Result "1/2-1/2"]
+
+1. e4 c5 2. Nf3 e6 3. d4 cxd4 1/2-1/2
+
+[White "Spassky, Boris"]
+[Black "Fischer, Robert"]
+[Date "1972.08.31"]
+[ECO "E17"]
+[Result "0-1"]
+
+1. d4 Nf6 2. Nf3 e6 3. c4 b6 0-1
+"#;
+
+    fn white_players(query: PgnQuery) -> Vec<String> {
+        query
+            .filter(PgnReader::new(GAMES.as_bytes()))
+            .map(|game| game.unwrap().tags.get("White").unwrap().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn games_are_filtered_by_tags() {
+        assert_eq!(white_players(PgnQuery::new()).len(), 3);
+        assert_eq!(
+            white_players(PgnQuery::new().player("Fischer, Robert")),
+            vec!["Fischer, Robert", "Spassky, Boris"]
+        );
+        assert_eq!(
+            white_players(PgnQuery::new().black("Fischer, Robert")),
+            vec!["Spassky, Boris"]
+        );
+        assert_eq!(
+            white_players(PgnQuery::new().eco("B20", "B99")),
+            vec!["Fischer, Robert"]
+        );
+        assert_eq!(
+            white_players(PgnQuery::new().result("1-0")),
+            vec!["Morphy, Paul"]
+        );
+
+        let summer = PgnQuery::new().date_range(
+            PgnDate::parse("1972.07.01").unwrap(),
+            PgnDate::parse("1972.07.31").unwrap(),
+        );
+        assert_eq!(white_players(summer), vec!["Fischer, Robert"]);
+
+        let nineteenth_century = PgnQuery::new().date_range(
+            PgnDate::parse("1800.??.??").unwrap(),
+            PgnDate::parse("1899.12.31").unwrap(),
+        );
+        assert_eq!(white_players(nineteenth_century), vec!["Morphy, Paul"]);
+        assert!(PgnDate::parse("1972.7.23").is_none());
+    }
+
+    #[test]
+    fn games_are_filtered_by_position() {
+        // the position after 1. e4 c5 2. Nf3 e6 3. d4 cxd4
+        let open_sicilian =
+            Fen::try_from("rnbqkbnr/pp1p1ppp/4p3/8/3pP3/5N2/PPP2PPP/RNBQKB1R w KQkq - 0 4")
+                .unwrap()
+                .to_standard_board();
+        assert_eq!(
+            white_players(PgnQuery::new().reaches(&open_sicilian)),
+            vec!["Fischer, Robert"]
+        );
+
+        let after_e4 = Board::default().make(crate::standard::Move::from((
+            crate::standard::Square::try_from("e2").unwrap(),
+            crate::standard::Square::try_from("e4").unwrap(),
+        )));
+        let query = PgnQuery::new().reaches(&after_e4).player("Spassky, Boris");
+        assert_eq!(white_players(query), vec!["Fischer, Robert"]);
+    }
+}
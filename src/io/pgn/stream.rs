@@ -0,0 +1,183 @@
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+
+use futures_core::Stream;
+use tokio::io::{AsyncBufRead, AsyncRead, BufReader};
+
+use super::{Fed, GameBuilder, PgnEncoding, PgnError, PgnGame};
+
+/// An asynchronous reader of PGN games, yielding one [`PgnGame`] at a time
+/// as a [`Stream`].
+///
+/// This is the asynchronous counterpart of [`PgnReader`](super::PgnReader),
+/// for servers which ingest live feeds of games, e.g. over a socket, without
+/// blocking their runtime. Games are yielded as soon as their last line has
+/// been read, so a game is never delayed until the next one begins.
+#[derive(Debug)]
+pub struct PgnStream<R> {
+    reader: R,
+    line: usize,
+    offset: u64,
+    bytes: Vec<u8>,
+    pending: Option<(String, u64)>,
+    builder: GameBuilder,
+    encoding: PgnEncoding,
+}
+
+impl<R: AsyncBufRead + Unpin> PgnStream<R> {
+    /// Constructs a stream of the games in `reader`.
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            line: 0,
+            offset: 0,
+            bytes: Vec::new(),
+            pending: None,
+            builder: GameBuilder::default(),
+            encoding: PgnEncoding::default(),
+        }
+    }
+
+    /// Sets how lines of the input which are not valid UTF-8 are decoded,
+    /// which by default fails with [`PgnError::InvalidUtf8`].
+    pub fn with_encoding(mut self, encoding: PgnEncoding) -> Self {
+        self.encoding = encoding;
+        self
+    }
+
+    /// Returns the number of bytes consumed from the underlying reader.
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    /// Polls for the next line, returning it along with its byte offset.
+    ///
+    /// A partially read line is kept between calls, so no input is lost
+    /// if the reader is not ready.
+    fn poll_next_line(
+        &mut self,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<Option<(String, u64)>, PgnError>> {
+        if let Some(pending) = self.pending.take() {
+            self.line += 1;
+            return Poll::Ready(Ok(Some(pending)));
+        }
+
+        loop {
+            let available = ready!(Pin::new(&mut self.reader).poll_fill_buf(cx))?;
+            if available.is_empty() {
+                break;
+            }
+
+            let (used, done) = match available.iter().position(|&byte| byte == b'\n') {
+                Some(index) => (index + 1, true),
+                None => (available.len(), false),
+            };
+
+            self.bytes.extend_from_slice(&available[..used]);
+            Pin::new(&mut self.reader).consume(used);
+            if done {
+                break;
+            }
+        }
+
+        self.line += 1;
+        if self.bytes.is_empty() {
+            return Poll::Ready(Ok(None));
+        }
+
+        let offset = self.offset;
+        self.offset += self.bytes.len() as u64;
+        let bytes = std::mem::take(&mut self.bytes);
+        let line = self.encoding.decode(bytes, self.line)?;
+        Poll::Ready(Ok(Some((line, offset))))
+    }
+}
+
+impl<R: AsyncRead + Unpin> PgnStream<BufReader<R>> {
+    /// Constructs a stream of the games in `reader`, which is buffered internally,
+    /// e.g. a TCP socket.
+    pub fn from_reader(reader: R) -> Self {
+        Self::new(BufReader::new(reader))
+    }
+}
+
+impl<R: AsyncBufRead + Unpin> Stream for PgnStream<R> {
+    type Item = Result<PgnGame, PgnError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        loop {
+            let (line, line_offset) = match ready!(this.poll_next_line(cx)) {
+                Ok(Some(line)) => line,
+                Ok(None) => break,
+                Err(err) => return Poll::Ready(Some(Err(err))),
+            };
+
+            match this.builder.feed(&line, this.line, line_offset) {
+                Ok(Fed::Consumed) => continue,
+                Ok(Fed::Complete) => break,
+                Ok(Fed::Rejected) => {
+                    this.pending = Some((line, line_offset));
+                    this.line -= 1;
+                    break;
+                }
+                Err(err) => {
+                    this.builder = GameBuilder::default();
+                    return Poll::Ready(Some(Err(err)));
+                }
+            }
+        }
+
+        let builder = std::mem::take(&mut this.builder);
+        let game = builder.finish(this.line);
+        Poll::Ready(game.map(|game| game.map(|(_, game)| game)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::task::Waker;
+
+    use super::*;
+    use crate::io::pgn::PgnReader;
+
+    /// Polls `stream` to completion, which never waits on an in-memory reader.
+    fn collect(mut stream: PgnStream<&[u8]>) -> Vec<Result<PgnGame, PgnError>> {
+        let mut cx = Context::from_waker(Waker::noop());
+        let mut games = Vec::new();
+        while let Poll::Ready(Some(game)) = Pin::new(&mut stream).poll_next(&mut cx) {
+            games.push(game);
+        }
+
+        games
+    }
+
+    #[test]
+    fn streamed_games_match_the_blocking_reader() {
+        let pgn = concat!(
+            "[Event \"First\"]\n[Result \"1-0\"]\n\n1. e4 e5 2. Qh5 Nc6 3. Bc4 Nf6 4. Qxf7# 1-0\n\n",
+            "[Event \"Second\"]\n\n1. d4 {a comment\nover two lines} d5 *\n",
+            "[Event \"Third\"]\n\n1. c4 e5",
+        );
+
+        let streamed = collect(PgnStream::new(pgn.as_bytes()));
+        let read: Vec<_> = PgnReader::new(pgn.as_bytes()).collect();
+        assert_eq!(streamed.len(), 3);
+        for (streamed, read) in streamed.iter().zip(&read) {
+            assert_eq!(streamed.as_ref().unwrap(), read.as_ref().unwrap());
+        }
+
+        let comment = streamed[1].as_ref().unwrap().moves[0].comment.as_deref();
+        assert!(comment.is_some_and(|comment| comment.ends_with("over two lines")));
+        assert_eq!(streamed[2].as_ref().unwrap().result, "*");
+    }
+
+    #[test]
+    fn streamed_errors_do_not_end_the_stream() {
+        let pgn = "[Event ?]\n\n1. e4 *\n\n[Event \"Valid\"]\n\n1. e4 *\n";
+        let games = collect(PgnStream::new(pgn.as_bytes()));
+        assert!(matches!(games[0], Err(PgnError::InvalidTag(1))));
+        assert_eq!(games.last().unwrap().as_ref().unwrap().moves.len(), 1);
+    }
+}
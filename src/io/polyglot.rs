@@ -0,0 +1,382 @@
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::io::{Read, Write};
+
+use super::book::{BookError, BookMove, OpeningBook};
+use crate::core::{Move as _, Piece as _, Process, Validate};
+use crate::standard::piece::PieceKind;
+use crate::standard::r#move::IllegalMoveError;
+use crate::standard::{Board, Move, Square};
+
+/// A single 16-byte entry of a Polyglot `.bin` book.
+///
+/// On disk every field is stored in big-endian byte order, and
+/// the entries of a book are sorted in ascending order of `key`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PolyglotEntry {
+    /// The [Zobrist hash](Board::zobrist) of the position.
+    pub key: u64,
+    /// The move to play, in the encoding produced by [`encode_move`].
+    pub raw_move: u16,
+    /// The relative weight of the move among the entries for the same position.
+    pub weight: u16,
+    /// Learning data, which is unused by most books.
+    pub learn: u32,
+}
+
+impl PolyglotEntry {
+    /// The size of a single entry in bytes.
+    pub const SIZE: usize = 16;
+
+    /// Encodes `self` in the on-disk format.
+    pub fn to_bytes(&self) -> [u8; Self::SIZE] {
+        let mut bytes = [0; Self::SIZE];
+        bytes[0..8].copy_from_slice(&self.key.to_be_bytes());
+        bytes[8..10].copy_from_slice(&self.raw_move.to_be_bytes());
+        bytes[10..12].copy_from_slice(&self.weight.to_be_bytes());
+        bytes[12..16].copy_from_slice(&self.learn.to_be_bytes());
+        bytes
+    }
+
+    /// Decodes an entry from the on-disk format.
+    pub fn from_bytes(bytes: [u8; Self::SIZE]) -> Self {
+        let [k0, k1, k2, k3, k4, k5, k6, k7, m0, m1, w0, w1, l0, l1, l2, l3] = bytes;
+        Self {
+            key: u64::from_be_bytes([k0, k1, k2, k3, k4, k5, k6, k7]),
+            raw_move: u16::from_be_bytes([m0, m1]),
+            weight: u16::from_be_bytes([w0, w1]),
+            learn: u32::from_be_bytes([l0, l1, l2, l3]),
+        }
+    }
+}
+
+/// Encodes `candidate`, played on `board`, as a Polyglot move.
+///
+/// The target and source squares occupy bits 0-5 and 6-11 respectively, and bits
+/// 12-14 hold the promotion piece. Castling is encoded as the king capturing its
+/// own rook, e.g. e1h1 rather than e1g1, as the format requires.
+pub fn encode_move(board: &Board, candidate: Move) -> u16 {
+    let (source, mut target) = candidate.as_pair();
+    let piece = board[source].map(|piece| piece.kind());
+
+    if piece == Some(PieceKind::King) && source.file_distance(target) == 2 {
+        let rook_file = if target.file() > source.file() { 7 } else { 0 };
+        target = Square::at(source.rank() * 8 + rook_file);
+    }
+
+    // a pawn move to the last rank without a promotion piece promotes to a queen
+    let promotion = match (piece, target.rank(), candidate.promotion()) {
+        (_, _, Some(PieceKind::Knight)) => 1,
+        (_, _, Some(PieceKind::Bishop)) => 2,
+        (_, _, Some(PieceKind::Rook)) => 3,
+        (_, _, Some(_)) | (Some(PieceKind::Pawn), 0 | 7, None) => 4,
+        _ => 0,
+    };
+
+    (promotion << 12) | ((usize::from(source) as u16) << 6) | usize::from(target) as u16
+}
+
+/// Decodes `raw_move`, a Polyglot move for the position of `board`, into a [`Move`].
+///
+/// This is the inverse of [`encode_move`]: a king capturing its own rook is decoded
+/// as the corresponding castling move.
+pub fn decode_move(board: &Board, raw_move: u16) -> Move {
+    let source = Square::at(((raw_move >> 6) & 0x3f) as u8);
+    let mut target = Square::at((raw_move & 0x3f) as u8);
+
+    if let (Some(king), Some(rook)) = (board[source], board[target]) {
+        let is_castling = king.kind() == PieceKind::King
+            && rook.kind() == PieceKind::Rook
+            && king.color() == rook.color();
+
+        if is_castling {
+            let file = if target.file() > source.file() { 6 } else { 2 };
+            target = Square::at(source.rank() * 8 + file);
+        }
+    }
+
+    let candidate = Move::from((source, target));
+    match (raw_move >> 12) & 0x7 {
+        1 => candidate.with_promotion(PieceKind::Knight),
+        2 => candidate.with_promotion(PieceKind::Bishop),
+        3 => candidate.with_promotion(PieceKind::Rook),
+        4 => candidate.with_promotion(PieceKind::Queen),
+        _ => candidate,
+    }
+}
+
+/// A Polyglot `.bin` opening book held in memory.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PolyglotBook {
+    entries: Vec<PolyglotEntry>,
+}
+
+impl PolyglotBook {
+    /// Constructs a book from the raw bytes of a `.bin` file.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, BookError> {
+        if !bytes
+            .chunks_exact(PolyglotEntry::SIZE)
+            .remainder()
+            .is_empty()
+        {
+            return Err(BookError::InvalidLength(bytes.len()));
+        }
+
+        let mut entries: Vec<_> = bytes
+            .chunks_exact(PolyglotEntry::SIZE)
+            .map(|chunk| PolyglotEntry::from_bytes(chunk.try_into().unwrap()))
+            .collect();
+
+        // lookups rely on the entries being sorted, so repair books which aren't
+        if !entries.windows(2).all(|pair| pair[0].key <= pair[1].key) {
+            entries.sort_by_key(|entry| entry.key);
+        }
+
+        Ok(Self { entries })
+    }
+
+    /// Reads a book in the `.bin` format from `reader`.
+    pub fn read_from<R: Read>(mut reader: R) -> Result<Self, BookError> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes)?;
+        Self::from_bytes(&bytes)
+    }
+
+    /// Returns the entries of the book, sorted by key.
+    pub fn entries(&self) -> &[PolyglotEntry] {
+        &self.entries
+    }
+
+    /// Returns the entries of the book whose key is `key`.
+    pub fn entries_for(&self, key: u64) -> &[PolyglotEntry] {
+        let start = self.entries.partition_point(|entry| entry.key < key);
+        let end = self.entries.partition_point(|entry| entry.key <= key);
+        &self.entries[start..end]
+    }
+}
+
+impl OpeningBook for PolyglotBook {
+    fn moves(&self, board: &Board) -> Vec<BookMove> {
+        let mut moves: Vec<_> = self
+            .entries_for(board.zobrist())
+            .iter()
+            .filter_map(|entry| {
+                // a hash collision may produce moves which are illegal here
+                let legal_move = board.validate(decode_move(board, entry.raw_move)).ok()?;
+                Some(BookMove {
+                    legal_move,
+                    weight: entry.weight.into(),
+                })
+            })
+            .collect();
+
+        moves.sort_by_key(|book_move| Reverse(book_move.weight));
+        moves
+    }
+}
+
+/// Builds a Polyglot `.bin` book from individual moves or from whole games.
+///
+/// Weights for the same move in the same position are accumulated,
+/// saturating at [`u16::MAX`].
+#[derive(Debug, Clone, Default)]
+pub struct PolyglotWriter {
+    weights: HashMap<(u64, u16), u16>,
+}
+
+impl PolyglotWriter {
+    /// Constructs an empty [`PolyglotWriter`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds `weight` to the weight of `candidate` in the position of `board`.
+    ///
+    /// The move is not validated, so callers are responsible for only
+    /// adding moves which are legal on `board`.
+    pub fn add(&mut self, board: &Board, candidate: Move, weight: u16) {
+        let key = (board.zobrist(), encode_move(board, candidate));
+        let current = self.weights.entry(key).or_default();
+        *current = current.saturating_add(weight);
+    }
+
+    /// Adds every move of a game played from the starting position,
+    /// each with a weight of 1.
+    ///
+    /// If a move is illegal, the error is returned and the moves of
+    /// the game which preceded it remain in the book.
+    pub fn add_game(
+        &mut self,
+        moves: impl IntoIterator<Item = Move>,
+    ) -> Result<(), IllegalMoveError> {
+        let mut board = Board::default();
+        for candidate in moves {
+            let legal = board.validate(candidate)?;
+            self.add(&board, candidate, 1);
+            board = board.process(legal);
+        }
+
+        Ok(())
+    }
+
+    /// Returns the number of distinct entries in the book.
+    pub fn len(&self) -> usize {
+        self.weights.len()
+    }
+
+    /// Returns `true` if the book has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.weights.is_empty()
+    }
+
+    /// Returns the entries of the book, sorted by key and then by descending weight.
+    pub fn entries(&self) -> Vec<PolyglotEntry> {
+        let mut entries: Vec<_> = self
+            .weights
+            .iter()
+            .map(|(&(key, raw_move), &weight)| PolyglotEntry {
+                key,
+                raw_move,
+                weight,
+                learn: 0,
+            })
+            .collect();
+
+        entries.sort_by(|a, b| {
+            a.key
+                .cmp(&b.key)
+                .then(b.weight.cmp(&a.weight))
+                .then(a.raw_move.cmp(&b.raw_move))
+        });
+        entries
+    }
+
+    /// Writes the book to `writer` in the Polyglot `.bin` format.
+    pub fn write_to<W: Write>(&self, mut writer: W) -> std::io::Result<()> {
+        for entry in self.entries() {
+            writer.write_all(&entry.to_bytes())?;
+        }
+
+        writer.flush()
+    }
+
+    /// Returns the book encoded in the Polyglot `.bin` format.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.entries()
+            .iter()
+            .flat_map(|entry| entry.to_bytes())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::Fen;
+    use crate::standard::r#move::mv;
+
+    #[test]
+    fn entries_round_trip_through_bytes() {
+        let entry = PolyglotEntry {
+            key: 0x463b96181691fc9c,
+            raw_move: 0x031c,
+            weight: 12,
+            learn: 7,
+        };
+
+        assert_eq!(
+            entry.to_bytes(),
+            [0x46, 0x3b, 0x96, 0x18, 0x16, 0x91, 0xfc, 0x9c, 0x03, 0x1c, 0, 12, 0, 0, 0, 7]
+        );
+        assert_eq!(PolyglotEntry::from_bytes(entry.to_bytes()), entry);
+    }
+
+    #[test]
+    fn moves_are_encoded_like_polyglot() {
+        let board = Board::default();
+        // e2e4: from 12, to 28
+        assert_eq!(encode_move(&board, mv("e2", "e4")), (12 << 6) | 28);
+
+        let board = Fen::try_from("r3k2r/1P6/8/8/8/8/8/R3K2R w KQkq - 0 1")
+            .unwrap()
+            .to_standard_board();
+        assert_eq!(encode_move(&board, mv("e1", "g1")), (4 << 6) | 7);
+        assert_eq!(encode_move(&board, mv("e1", "c1")), 4 << 6);
+        assert_eq!(encode_move(&board, mv("e1", "f1")), (4 << 6) | 5);
+        assert_eq!(
+            encode_move(&board, mv("b7", "a8")),
+            (4 << 12) | (49 << 6) | 56
+        );
+
+        let underpromotion = mv("b7", "a8").with_promotion(PieceKind::Knight);
+        let raw_move = encode_move(&board, underpromotion);
+        assert_eq!(raw_move, (1 << 12) | (49 << 6) | 56);
+        assert_eq!(decode_move(&board, raw_move), underpromotion);
+    }
+
+    #[test]
+    fn games_are_accumulated_and_sorted_by_key() {
+        let mut writer = PolyglotWriter::new();
+        writer.add_game([mv("e2", "e4"), mv("e7", "e5")]).unwrap();
+        writer.add_game([mv("e2", "e4"), mv("c7", "c5")]).unwrap();
+        writer.add_game([mv("d2", "d4")]).unwrap();
+        assert!(writer.add_game([mv("e2", "e5")]).is_err());
+
+        let entries = writer.entries();
+        assert_eq!(entries.len(), 4);
+        assert!(entries.windows(2).all(|pair| pair[0].key <= pair[1].key));
+
+        let start = Board::default().zobrist();
+        let opening: Vec<_> = entries.iter().filter(|entry| entry.key == start).collect();
+        assert_eq!(opening.len(), 2);
+        assert_eq!(opening[0].raw_move, (12 << 6) | 28);
+        assert_eq!(opening[0].weight, 2);
+        assert_eq!(opening[1].weight, 1);
+
+        let bytes = writer.to_bytes();
+        assert_eq!(bytes.len(), 4 * PolyglotEntry::SIZE);
+        let mut written = Vec::new();
+        writer.write_to(&mut written).unwrap();
+        assert_eq!(written, bytes);
+    }
+
+    #[test]
+    fn written_books_can_be_read_back() {
+        let mut writer = PolyglotWriter::new();
+        writer.add_game([mv("e2", "e4"), mv("e7", "e5")]).unwrap();
+        writer.add_game([mv("e2", "e4"), mv("c7", "c5")]).unwrap();
+        writer.add_game([mv("d2", "d4")]).unwrap();
+
+        let book = PolyglotBook::read_from(writer.to_bytes().as_slice()).unwrap();
+        let moves = book.moves(&Board::default());
+        assert_eq!(moves.len(), 2);
+        assert_eq!(Move::from(moves[0].legal_move), mv("e2", "e4"));
+        assert_eq!(moves[0].weight, 2);
+        assert_eq!(
+            book.best_move(&Board::default())
+                .map(|book_move| book_move.weight),
+            Some(2)
+        );
+
+        let empty = Fen::try_from("8/8/8/8/8/8/8/4K2k w - - 0 1")
+            .unwrap()
+            .to_standard_board();
+        assert!(!book.contains(&empty));
+        assert!(PolyglotBook::from_bytes(&[0; 17]).is_err());
+    }
+
+    #[test]
+    fn castling_is_decoded_from_king_captures_rook() {
+        let board = Fen::try_from("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1")
+            .unwrap()
+            .to_standard_board();
+
+        for (source, target) in [("e1", "g1"), ("e1", "c1"), ("e1", "f1")] {
+            let candidate = mv(source, target);
+            assert_eq!(
+                decode_move(&board, encode_move(&board, candidate)),
+                candidate
+            );
+        }
+    }
+}
@@ -3,100 +3,698 @@
 //
 // This is unfortunate, but this implementation will seek to
 // accept as many variants of this notation as is reasonable,
-// and to produce a single standardised variant. For now, this
-// is limited to the English variant of SAN.
+// and to produce a single standardised variant. The English piece
+// letters are used by default, but other locales can be given as a
+// SanLocale to both the parser and the formatter.
 //
 // The canonical version of SAN here will be FIDE's, as described in
 // appendix C of their Laws of Chess document: https://handbook.fide.com/chapter/E012023
+//
+// Variants extend the notation in ways which FIDE does not describe, and so
+// their extensions are only accepted when a SanVariant is given as context,
+// in the same way as a SanLocale: piece drops (e.g. N@f3) in crazyhouse, and
+// gating suffixes (e.g. Nf3/H) in Seirawan chess. Chess960 is written exactly
+// as standard chess, except that the side of a castling move is given by the
+// rook rather than by the king's target (see CastleSide::of_rook).
 
 use nom::combinator::success;
 use nom::{
     branch::{alt, permutation},
     bytes::complete::tag,
-    character::complete::one_of,
-    combinator::{complete, cut, opt, rest},
-    error::{ContextError, VerboseError},
+    character::complete::{anychar, one_of},
+    combinator::{complete, cond, cut, fail, map_opt, opt, rest},
+    error::{context, ContextError, VerboseError, VerboseErrorKind},
     sequence::{pair, preceded, tuple},
     Finish, IResult, Parser,
 };
 
-use crate::standard::piece::PieceKind;
+use thiserror::Error;
+
+use crate::core::{Move as _, Piece as _, Process as _};
+use crate::standard::{piece::PieceKind, Board, LegalMove, Move, MoveKind, Square};
+
+/// The letters denoting the pieces in SAN, which differ between languages.
+///
+/// Pawns are never denoted by a letter. The letters of a locale should be
+/// distinct uppercase letters other than `O`, so that they cannot be confused
+/// with files or castling moves.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SanLocale {
+    king: char,
+    queen: char,
+    rook: char,
+    bishop: char,
+    knight: char,
+}
+
+impl SanLocale {
+    /// The English letters `KQRBN`, which FIDE uses and which are the default.
+    pub const ENGLISH: Self = Self::new('K', 'Q', 'R', 'B', 'N');
+    /// The German letters `KDTLS`: König, Dame, Turm, Läufer, Springer.
+    pub const GERMAN: Self = Self::new('K', 'D', 'T', 'L', 'S');
+    /// The French letters `RDTFC`: roi, dame, tour, fou, cavalier.
+    pub const FRENCH: Self = Self::new('R', 'D', 'T', 'F', 'C');
+    /// The Spanish letters `RDTAC`: rey, dama, torre, alfil, caballo.
+    pub const SPANISH: Self = Self::new('R', 'D', 'T', 'A', 'C');
+    /// The Dutch letters `KDTLP`: koning, dame, toren, loper, paard.
+    pub const DUTCH: Self = Self::new('K', 'D', 'T', 'L', 'P');
+
+    /// Constructs a locale from the letters of the king, queen, rook, bishop and knight.
+    pub const fn new(king: char, queen: char, rook: char, bishop: char, knight: char) -> Self {
+        Self {
+            king,
+            queen,
+            rook,
+            bishop,
+            knight,
+        }
+    }
+
+    /// Returns the letter denoting `kind`, or `None` for a pawn.
+    pub fn to_char(&self, kind: PieceKind) -> Option<char> {
+        match kind {
+            PieceKind::Pawn => None,
+            PieceKind::Rook => Some(self.rook),
+            PieceKind::Knight => Some(self.knight),
+            PieceKind::Bishop => Some(self.bishop),
+            PieceKind::Queen => Some(self.queen),
+            PieceKind::King => Some(self.king),
+        }
+    }
+
+    /// Returns the kind of piece denoted by `letter`, if there is one.
+    pub fn from_char(&self, letter: char) -> Option<PieceKind> {
+        match letter {
+            _ if letter == self.king => Some(PieceKind::King),
+            _ if letter == self.queen => Some(PieceKind::Queen),
+            _ if letter == self.rook => Some(PieceKind::Rook),
+            _ if letter == self.bishop => Some(PieceKind::Bishop),
+            _ if letter == self.knight => Some(PieceKind::Knight),
+            _ => None,
+        }
+    }
+}
+
+impl Default for SanLocale {
+    fn default() -> Self {
+        Self::ENGLISH
+    }
+}
+
+/// The chess variants whose extensions to SAN are understood.
+///
+/// Like a [`SanLocale`], a variant is given as context when parsing, so that
+/// the literals of other variants are rejected. Chess960 has no variant here,
+/// since its moves are written as in standard chess; see [`CastleSide::of_rook`]
+/// for how the side of its castling moves is decided.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum SanVariant {
+    /// Standard chess, as described by FIDE.
+    #[default]
+    Standard,
+    /// Crazyhouse, which adds piece drops such as `N@f3`; pawn drops are written
+    /// without a letter (`@e4`), though `P@e4` is also accepted.
+    Crazyhouse,
+    /// Seirawan chess, which adds gating suffixes such as `Nf3/H` or `O-O/Eh1`.
+    Seirawan,
+}
+
+/// The pieces which can be gated onto the board in Seirawan chess.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GatedPiece {
+    /// The hawk, which moves as a bishop or a knight, written `H`.
+    Hawk,
+    /// The elephant, which moves as a rook or a knight, written `E`.
+    Elephant,
+}
 
-// #[derive(Error, Debug)]
-// enum ParseError<'a> {
-//     /// Returned if the optional leading character of the literal is invalid
-//     #[error("Expected one of 'O', 'K', 'Q', 'B', 'R', 'N'; got {0}")]
-//     InvalidLeadingPiece(char),
+/// The gating suffix of a move in Seirawan chess, which brings a piece onto
+/// a square vacated by the move, e.g. `/H` in `Nf3/H`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Gate {
+    /// The piece which is gated.
+    pub piece: GatedPiece,
+    /// The square onto which the piece is gated, which is only written when the
+    /// move vacates more than one square, as castling does (e.g. `O-O/Hh1`).
+    pub square: Option<Square>,
+}
 
-//     /// Returned if the mandatory target square field is invalid.
-//     #[error("Expected a valid target square; got {0}")]
-//     InvalidTargetSquare(&'a str),
+/// Represents the ways in which a SAN literal may be invalid.
+#[derive(Error, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseError<'a> {
+    /// Returned if the optional leading character of the literal is invalid.
+    #[error("Expected one of 'O', 'K', 'Q', 'B', 'R', 'N' or a file; got {0}")]
+    InvalidLeadingPiece(char),
 
-//     /// Returned if the optional capture field is invalid.
-//     #[error("Expected one of [x, X, -, :]; got {0}")]
-//     InvalidCaptureField(char),
+    /// Returned if the mandatory target square field is invalid.
+    #[error("Expected a valid target square; got {0}")]
+    InvalidTargetSquare(&'a str),
 
-//     /// Returned if the optional disambiguation field is invalid.
-//     #[error("Expected a value fulfilling [a-h]?[1-8]?; got {0}")]
-//     InvalidDisambiguationField(&'a str),
+    /// Returned if the optional capture field is invalid.
+    #[error("Expected one of [x, X, ×, :]; got {0}")]
+    InvalidCaptureField(char),
 
-//     /// Returned if the optional annotation suffix field is invalid.
-//     #[error("Expected a value fulfilling [?!]?[?!]?; got {0}")]
-//     InvalidAnnotationSuffixField(&'a str),
+    /// Returned if the optional annotation suffix field is invalid.
+    #[error("Expected a value fulfilling [?!]?[?!]?; got {0}")]
+    InvalidAnnotationSuffixField(&'a str),
 
-//     /// Returned if the optional en passant suffix is invalid.
-//     #[error("Expected a value equal to \"e.p\"; got {0}")]
-//     InvalidEnPassantSuffix(&'a str),
+    /// Returned if the optional check field is invalid.
+    #[error("Expected a value fulfilling [+]?; got {0}")]
+    InvalidCheckField(char),
 
-//     /// Returned if the optional check field is invalid.
-//     #[error("Expected a value fulfilling [+]?; got {0}")]
-//     InvalidCheckField(char),
+    /// Returned if the optional checkmate field is invalid.
+    #[error("Expected a value fulfilling [#]?; got {0}")]
+    InvalidCheckmateField(char),
 
-//     /// Returned if the optional checkmate field is invalid.
-//     #[error("Expected a value fulfilling [#]? or [++]?; got {0}")]
-//     InvalidCheckmateField(char),
+    /// Returned if the optional promotion field is invalid.
+    #[error("Expected a value fulfilling =[NBRQ]; got {0}")]
+    InvalidPromotionField(&'a str),
 
-//     /// Returned if the optional promotion field is invalid.
-//     #[error("Expected a value fulfilling [=/]?[NBRQ] or ([NBRQ]); got {0}")]
-//     InvalidPromotionField(&'a str),
+    /// Returned if the optional castling field is invalid.
+    #[error("Expected either [0O]-[0O] or [0O]-[0O]-[0O]; got {0}")]
+    InvalidCastlingField(&'a str),
 
-//     /// Returned if the optional castling field is invalid.
-//     #[error("Expected either [0O]-[0O] or [0O]-[0O]-[0O]; got {0}")]
-//     InvalidCastlingField(&'a str),
+    /// Returned if the piece of a drop is invalid, e.g. a king.
+    #[error("Expected one of 'Q', 'B', 'R', 'N', 'P' or nothing before '@'; got {0}")]
+    InvalidDropPiece(char),
 
-//     /// Returned if the length of the literal is invalid.
-//     #[error("Expected a literal with at least 2 and at most 12 characters; got {0} characters")]
-//     InvalidLiteralLength(u8),
+    /// Returned if the optional gating field is invalid.
+    #[error("Expected a value fulfilling /[HE]([a-h][1-8])?; got {0}")]
+    InvalidGatingField(&'a str),
 
-//     /// Returned if a literal is valid, but then ends in garbage.
-//     #[error("Got trailing garbage after a valid SAN literal: {0}")]
-//     TrailingGarbage(&'a str),
+    /// Returned if the length of the literal is invalid.
+    #[error("Expected a literal with at least 2 and at most 12 characters, or 16 with a gating suffix; got {0} characters")]
+    InvalidLiteralLength(usize),
 
-//     /// Returned if an unknown error occurs while parsing a SAN literal.
-//     #[error("Failed to parse the provided SAN literal")]
-//     Unknown,
-// }
+    /// Returned if a literal is valid, but then ends in garbage.
+    #[error("Got trailing garbage after a valid SAN literal: {0}")]
+    TrailingGarbage(&'a str),
+
+    /// Returned if an unknown error occurs while parsing a SAN literal.
+    #[error("Failed to parse the provided SAN literal")]
+    Unknown,
+}
 
 /// Represents the data derived from parsing a
 /// valid SAN literal.
 ///
 /// Parsing is provided via the `TryFrom<&'a str>` impl.
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, PartialEq, Eq, Clone)]
 pub struct San {
     data: SanData,
+    gate: Option<Gate>,
     is_check: bool,
     is_checkmate: bool,
     annotation: Option<SuffixAnnotation>,
 }
 
 impl<'a> TryFrom<&'a str> for San {
-    type Error = VerboseError<&'a str>;
+    type Error = ParseError<'a>;
 
     fn try_from(value: &'a str) -> Result<Self, Self::Error> {
-        san_literal(value).finish().map(|(_, san)| san)
+        San::parse_localized(value, SanLocale::ENGLISH)
+    }
+}
+
+impl San {
+    /// Parses `source` as a SAN literal whose pieces are denoted by the letters of `locale`.
+    ///
+    /// If `source` is invalid, the returned [`ParseError`] describes the first field
+    /// which could not be parsed.
+    pub fn parse_localized(source: &str, locale: SanLocale) -> Result<Self, ParseError<'_>> {
+        San::parse_variant(source, locale, SanVariant::Standard)
+    }
+
+    /// Parses `source` as a SAN literal of the given `variant`, whose pieces are
+    /// denoted by the letters of `locale`.
+    ///
+    /// The extensions of other variants are rejected as in [`San::parse_localized`],
+    /// e.g. a drop is not a valid literal of [`SanVariant::Standard`].
+    pub fn parse_variant(
+        source: &str,
+        locale: SanLocale,
+        variant: SanVariant,
+    ) -> Result<Self, ParseError<'_>> {
+        let length = source.chars().count();
+        let max_length = match variant {
+            SanVariant::Seirawan => 16,
+            SanVariant::Standard | SanVariant::Crazyhouse => 12,
+        };
+
+        if !(2..=max_length).contains(&length) {
+            return Err(ParseError::InvalidLiteralLength(length));
+        }
+
+        san_literal(locale, variant, source)
+            .finish()
+            .map(|(_, san)| san)
+            .map_err(|err| {
+                let context = err.errors.iter().find_map(|&(rest, ref kind)| match kind {
+                    VerboseErrorKind::Context(context) => Some((rest, *context)),
+                    _ => None,
+                });
+
+                match context {
+                    Some((rest, TRAILING_GARBAGE)) => trailing_error(rest),
+                    Some((rest, INVALID_GATE)) => ParseError::InvalidGatingField(rest),
+                    _ => diagnose(locale, variant, source),
+                }
+            })
+    }
+
+    /// Formats `legal_move` as a SAN literal in the position of `board`, following
+    /// FIDE's conventions: castling as `O-O` or `O-O-O`, disambiguation by file,
+    /// then rank, then square, and a `+` or `#` suffix for checks and mates.
+    pub fn format(board: &Board, legal_move: LegalMove) -> String {
+        San::format_localized(board, legal_move, SanLocale::ENGLISH)
+    }
+
+    /// Formats `legal_move` as in [`San::format`], denoting the pieces by the letters of `locale`.
+    pub fn format_localized(board: &Board, legal_move: LegalMove, locale: SanLocale) -> String {
+        match board[Move::from(legal_move).source()] {
+            Some(_) => San::from_legal_move(board, legal_move).to_localized_string(locale),
+            None => String::new(),
+        }
+    }
+
+    /// Describes `legal_move` in the position of `board`, as [`San::format`] writes it.
+    ///
+    /// The literal can be extended with the notation of a variant before it is
+    /// written, e.g. with [`San::with_gate`] in Seirawan chess.
+    ///
+    /// # Panics
+    /// Panics if no piece stands on the source square of `legal_move`, which
+    /// can only happen if it was validated against another board.
+    pub fn from_legal_move(board: &Board, legal_move: LegalMove) -> Self {
+        let candidate = Move::from(legal_move);
+        let source = candidate.source();
+        let target = candidate.target();
+        let piece = board[source].expect("a legal move should have a piece on its source square");
+        let is_capture = legal_move.is_capture();
+
+        let data = match piece.kind() {
+            _ if legal_move.kind() == MoveKind::Castle => {
+                SanData::CastleMove(match target.file() {
+                    6 => CastleSide::KingSide,
+                    _ => CastleSide::QueenSide,
+                })
+            }
+            PieceKind::Pawn => SanData::PawnMove(PawnMove {
+                target: square_chars(target),
+                is_capture,
+                capture_rank: is_capture.then(|| file_char(source.file())),
+                promotion_piece: legal_move.promotion(),
+            }),
+            kind => {
+                // other pieces of the same kind which could also move to the target
+                let rivals: Vec<Square> = board
+                    .legal_moves()
+                    .map(Move::from)
+                    .filter(|other| {
+                        other.target() == target
+                            && other.source() != source
+                            && board[other.source()] == Some(piece)
+                    })
+                    .map(|other| other.source())
+                    .collect();
+
+                let shares_file = rivals.iter().any(|rival| rival.file() == source.file());
+                let shares_rank = rivals.iter().any(|rival| rival.rank() == source.rank());
+                let disambiguation_field = match rivals.is_empty() {
+                    true => None,
+                    false if !shares_file => {
+                        Some(DisambiguationField::FileLetter(file_char(source.file())))
+                    }
+                    false if !shares_rank => {
+                        Some(DisambiguationField::RankDigit(square_chars(source).1))
+                    }
+                    false => Some(DisambiguationField::SourceSquare(square_chars(source))),
+                };
+
+                SanData::NormalMove(NormalMove {
+                    piece: kind,
+                    disambiguation_field,
+                    target: square_chars(target),
+                    is_capture,
+                })
+            }
+        };
+
+        let san = San::from_data(data);
+        match board.gives_check(&legal_move) {
+            true if board.process(legal_move).has_legal_moves() => san.with_check(),
+            true => san.with_checkmate(),
+            false => san,
+        }
+    }
+
+    /// Constructs the literal of a drop of a piece of the given `kind` onto
+    /// `target`, as in crazyhouse.
+    pub fn piece_drop(kind: PieceKind, target: Square) -> Self {
+        San::from_data(SanData::DropMove(DropMove {
+            piece: kind,
+            target: square_chars(target),
+        }))
+    }
+
+    /// Constructs the literal of a castling move towards `side`.
+    pub fn castling(side: CastleSide) -> Self {
+        San::from_data(SanData::CastleMove(side))
+    }
+
+    /// Returns `self` with the given gating suffix, as in Seirawan chess.
+    pub fn with_gate(self, gate: Gate) -> Self {
+        Self {
+            gate: Some(gate),
+            ..self
+        }
+    }
+
+    /// Returns `self` with a check suffix (`+`).
+    pub fn with_check(self) -> Self {
+        Self {
+            is_check: true,
+            is_checkmate: false,
+            ..self
+        }
+    }
+
+    /// Returns `self` with a checkmate suffix (`#`).
+    pub fn with_checkmate(self) -> Self {
+        Self {
+            is_check: false,
+            is_checkmate: true,
+            ..self
+        }
+    }
+
+    /// Constructs a literal with no suffixes from its `data`.
+    fn from_data(data: SanData) -> Self {
+        Self {
+            data,
+            gate: None,
+            is_check: false,
+            is_checkmate: false,
+            annotation: None,
+        }
+    }
+
+    /// Writes `self` as a SAN literal, denoting the pieces by the letters of `locale`.
+    ///
+    /// A literal with both a check and a checkmate suffix is written with the latter.
+    pub fn to_localized_string(&self, locale: SanLocale) -> String {
+        let mut san = String::new();
+        let push_square = |san: &mut String, (file, rank): (char, char)| {
+            san.push(file);
+            san.push(rank);
+        };
+
+        let push_promotion = |san: &mut String, promotion: Option<PieceKind>| {
+            if let Some(kind) = promotion {
+                san.push('=');
+                san.extend(locale.to_char(kind));
+            }
+        };
+
+        match &self.data {
+            SanData::CastleMove(CastleSide::KingSide) => san.push_str("O-O"),
+            SanData::CastleMove(CastleSide::QueenSide) => san.push_str("O-O-O"),
+            SanData::NormalMove(normal) => {
+                san.extend(locale.to_char(normal.piece));
+                match normal.disambiguation_field {
+                    None => {}
+                    Some(DisambiguationField::FileLetter(file)) => san.push(file),
+                    Some(DisambiguationField::RankDigit(rank)) => san.push(rank),
+                    Some(DisambiguationField::SourceSquare(square)) => {
+                        push_square(&mut san, square)
+                    }
+                }
+
+                if normal.is_capture {
+                    san.push('x');
+                }
+
+                push_square(&mut san, normal.target);
+            }
+            SanData::PawnMove(pawn) => {
+                if let Some(file) = pawn.capture_rank {
+                    san.push(file);
+                    san.push('x');
+                }
+
+                push_square(&mut san, pawn.target);
+                push_promotion(&mut san, pawn.promotion_piece);
+            }
+            SanData::AbbreviatedPawnMove(pawn) => {
+                san.push(pawn.source_rank);
+                if pawn.is_capture {
+                    san.push('x');
+                }
+
+                san.push(pawn.target_rank);
+                push_promotion(&mut san, pawn.promotion_piece);
+            }
+            SanData::DropMove(drop) => {
+                san.extend(locale.to_char(drop.piece));
+                san.push('@');
+                push_square(&mut san, drop.target);
+            }
+        }
+
+        if let Some(gate) = self.gate {
+            san.push('/');
+            san.push(match gate.piece {
+                GatedPiece::Hawk => 'H',
+                GatedPiece::Elephant => 'E',
+            });
+
+            if let Some(square) = gate.square {
+                push_square(&mut san, square_chars(square));
+            }
+        }
+
+        if self.is_checkmate {
+            san.push('#');
+        } else if self.is_check {
+            san.push('+');
+        }
+
+        san.push_str(match self.annotation {
+            None => "",
+            Some(SuffixAnnotation::Bang) => "!",
+            Some(SuffixAnnotation::Hook) => "?",
+            Some(SuffixAnnotation::BangBang) => "!!",
+            Some(SuffixAnnotation::BangHook) => "!?",
+            Some(SuffixAnnotation::HookBang) => "?!",
+            Some(SuffixAnnotation::HookHook) => "??",
+        });
+
+        san
+    }
+
+    /// Returns the kind of the moved or dropped piece, which is a king for castling moves.
+    pub fn piece(&self) -> PieceKind {
+        match &self.data {
+            SanData::CastleMove(_) => PieceKind::King,
+            SanData::NormalMove(normal) => normal.piece,
+            SanData::DropMove(drop) => drop.piece,
+            SanData::PawnMove(_) | SanData::AbbreviatedPawnMove(_) => PieceKind::Pawn,
+        }
+    }
+
+    /// Returns the target square, which is not given by castling moves
+    /// and abbreviated pawn moves (e.g. `ed`).
+    pub fn target(&self) -> Option<Square> {
+        match &self.data {
+            SanData::NormalMove(normal) => Some(square_from_chars(normal.target)),
+            SanData::PawnMove(pawn) => Some(square_from_chars(pawn.target)),
+            SanData::DropMove(drop) => Some(square_from_chars(drop.target)),
+            SanData::CastleMove(_) | SanData::AbbreviatedPawnMove(_) => None,
+        }
+    }
+
+    /// Returns the part of the source square given by the literal, if any.
+    ///
+    /// The source file of a pawn capture (e.g. `exd5`) or an abbreviated
+    /// pawn move (e.g. `ed`) is given as a [`Disambiguation::File`].
+    pub fn disambiguation(&self) -> Option<Disambiguation> {
+        match &self.data {
+            SanData::CastleMove(_) | SanData::DropMove(_) => None,
+            SanData::NormalMove(normal) => normal.disambiguation_field.as_ref().map(Into::into),
+            SanData::PawnMove(pawn) => pawn
+                .capture_rank
+                .map(|file| Disambiguation::File(file_index(file))),
+            SanData::AbbreviatedPawnMove(pawn) => {
+                Some(Disambiguation::File(file_index(pawn.source_rank)))
+            }
+        }
+    }
+
+    /// Returns `true` if the literal denotes a capture.
+    ///
+    /// Abbreviated pawn moves between different files are always captures,
+    /// whether or not they include a capture symbol.
+    pub fn is_capture(&self) -> bool {
+        match &self.data {
+            SanData::CastleMove(_) | SanData::DropMove(_) => false,
+            SanData::NormalMove(normal) => normal.is_capture,
+            SanData::PawnMove(pawn) => pawn.is_capture,
+            SanData::AbbreviatedPawnMove(pawn) => {
+                pawn.is_capture || pawn.source_rank != pawn.target_rank
+            }
+        }
+    }
+
+    /// Returns `true` if the literal has a check suffix (`+`).
+    pub fn is_check(&self) -> bool {
+        self.is_check
+    }
+
+    /// Returns `true` if the literal has a checkmate suffix (`#`).
+    pub fn is_checkmate(&self) -> bool {
+        self.is_checkmate
+    }
+
+    /// Returns the explicit promotion piece, if any.
+    ///
+    /// A promotion without a promotion piece (e.g. `e8`) returns `None`,
+    /// though it is taken to promote to a queen when resolved against a board.
+    pub fn promotion(&self) -> Option<PieceKind> {
+        match &self.data {
+            SanData::PawnMove(pawn) => pawn.promotion_piece,
+            SanData::AbbreviatedPawnMove(pawn) => pawn.promotion_piece,
+            SanData::CastleMove(_) | SanData::NormalMove(_) | SanData::DropMove(_) => None,
+        }
+    }
+
+    /// Returns the side towards which the literal castles, if it is a castling move.
+    pub fn castle(&self) -> Option<CastleSide> {
+        match &self.data {
+            SanData::CastleMove(side) => Some(*side),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if the literal drops a piece onto the board, as in crazyhouse.
+    pub fn is_drop(&self) -> bool {
+        matches!(self.data, SanData::DropMove(_))
+    }
+
+    /// Returns the gating suffix of the literal, as in Seirawan chess, if any.
+    pub fn gate(&self) -> Option<Gate> {
+        self.gate
+    }
+
+    /// Returns the suffix annotation (e.g. `!?`), if any.
+    pub fn annotation(&self) -> Option<SuffixAnnotation> {
+        self.annotation
+    }
+
+    /// Returns `true` if `candidate` is described by `self` on `board`,
+    /// assuming that `candidate` is a legal move.
+    ///
+    /// A promotion without a promotion piece (e.g. `e8`) is taken to promote to a queen.
+    pub(crate) fn describes(&self, board: &Board, candidate: Move) -> bool {
+        let source = candidate.source();
+        let target = candidate.target();
+        let Some(piece) = board[source] else {
+            return false;
+        };
+
+        // a standard board can neither drop nor gate pieces
+        if self.gate.is_some() {
+            return false;
+        }
+
+        let is_capture = board[target].is_some()
+            || (piece.kind() == PieceKind::Pawn && source.file() != target.file());
+        let promotes_correctly = |promotion_piece: &Option<PieceKind>| {
+            let implied = candidate.promotion().map(|_| PieceKind::Queen);
+            candidate.promotion() == promotion_piece.or(implied)
+        };
+
+        match &self.data {
+            SanData::CastleMove(castle) => {
+                let target_file = match castle {
+                    CastleSide::KingSide => 6,
+                    CastleSide::QueenSide => 2,
+                };
+
+                piece.kind() == PieceKind::King
+                    && source.file() == 4
+                    && target.file() == target_file
+                    && source.rank() == target.rank()
+            }
+            SanData::NormalMove(normal) => {
+                let matches_disambiguation = match normal.disambiguation_field {
+                    None => true,
+                    Some(DisambiguationField::FileLetter(file)) => {
+                        source.file() == file_index(file)
+                    }
+                    Some(DisambiguationField::RankDigit(rank)) => source.rank() == rank_index(rank),
+                    Some(DisambiguationField::SourceSquare(square)) => {
+                        source == square_from_chars(square)
+                    }
+                };
+
+                piece.kind() == normal.piece
+                    && target == square_from_chars(normal.target)
+                    && (is_capture || !normal.is_capture)
+                    && matches_disambiguation
+            }
+            SanData::PawnMove(pawn) => {
+                piece.kind() == PieceKind::Pawn
+                    && target == square_from_chars(pawn.target)
+                    && is_capture == pawn.is_capture
+                    && !pawn
+                        .capture_rank
+                        .is_some_and(|file| source.file() != file_index(file))
+                    && promotes_correctly(&pawn.promotion_piece)
+            }
+            SanData::AbbreviatedPawnMove(pawn) => {
+                piece.kind() == PieceKind::Pawn
+                    && source.file() == file_index(pawn.source_rank)
+                    && target.file() == file_index(pawn.target_rank)
+                    && is_capture == (source.file() != target.file())
+                    && promotes_correctly(&pawn.promotion_piece)
+            }
+            SanData::DropMove(_) => false,
+        }
+    }
+}
+
+impl std::fmt::Display for San {
+    /// Writes the literal with the English piece letters; see [`San::to_localized_string`].
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_localized_string(SanLocale::ENGLISH))
     }
 }
 
+/// Converts a file index into its letter (a-h).
+fn file_char(file: u8) -> char {
+    (b'a' + file) as char
+}
+
+/// Converts a file letter (a-h) into its index.
+fn file_index(file: char) -> u8 {
+    file as u8 - b'a'
+}
+
+/// Converts a rank digit (1-8) into its index.
+fn rank_index(rank: char) -> u8 {
+    rank as u8 - b'1'
+}
+
+/// Converts a parsed (file, rank) pair into a [`Square`].
+fn square_from_chars((file, rank): (char, char)) -> Square {
+    Square::at(rank_index(rank) * 8 + file_index(file))
+}
+
+/// Converts a [`Square`] into the (file, rank) pair which denotes it.
+fn square_chars(square: Square) -> (char, char) {
+    (file_char(square.file()), (b'1' + square.rank()) as char)
+}
+
 /// The distinct kinds of data conveyed by a SAN literal.
 ///
 /// Keep in mind that a SAN literal conveys information about
@@ -108,12 +706,13 @@ impl<'a> TryFrom<&'a str> for San {
 #[derive(Debug, Eq, PartialEq, Clone)]
 enum SanData {
     AbbreviatedPawnMove(AbbreviatedPawnMove),
-    CastleMove(CastleMove),
+    CastleMove(CastleSide),
+    DropMove(DropMove),
     NormalMove(NormalMove),
     PawnMove(PawnMove),
 }
 
-/// Represents a SAN literal denoting a castling move.
+/// The side towards which a SAN literal castles.
 ///
 /// ## Rough Specification
 /// Castle moves are entirely described by their direction,
@@ -126,12 +725,29 @@ enum SanData {
 /// and checkmate; hence when parsing a SAN-castle literal you
 /// still must check for the common check/checkmate suffixes. As
 /// usual, you also want to look for the annotation suffixes as well.
-#[derive(Debug, Eq, PartialEq, Clone)]
-enum CastleMove {
+#[derive(Debug, Eq, PartialEq, Clone, Copy, Hash)]
+pub enum CastleSide {
+    /// Castling towards the a-file, written `O-O-O`.
     QueenSide,
+    /// Castling towards the h-file, written `O-O`.
     KingSide,
 }
 
+impl CastleSide {
+    /// Returns the side towards which a king on `king` castles with the rook on `rook`.
+    ///
+    /// In Chess960 the king may start on any file between the rooks, and may even
+    /// stand on its target square already, so the side of a castling move is given
+    /// by the rook rather than by the king's target: `O-O` castles with the rook
+    /// towards the h-file, and `O-O-O` with the rook towards the a-file.
+    pub fn of_rook(king: Square, rook: Square) -> Self {
+        match rook.file() > king.file() {
+            true => Self::KingSide,
+            false => Self::QueenSide,
+        }
+    }
+}
+
 /// Represents a SAN literal denoting a normal (non-pawn) move.
 ///
 /// ## Rough Specification
@@ -148,6 +764,17 @@ struct NormalMove {
     is_capture: bool,
 }
 
+/// Represents a SAN literal denoting a piece drop, as in crazyhouse.
+///
+/// ## Rough Specification
+/// A drop is written as the letter of the dropped piece, which is omitted
+/// for pawns, followed by an `@` and the mandatory target square.
+#[derive(Debug, Eq, PartialEq, Clone)]
+struct DropMove {
+    piece: PieceKind,
+    target: (char, char),
+}
+
 /// Represents a SAN literal denoting a normal pawn move.
 ///
 /// ## Rough Specification
@@ -176,6 +803,18 @@ struct AbbreviatedPawnMove {
     promotion_piece: Option<PieceKind>,
 }
 
+/// The part of a source square given by a SAN literal
+/// to tell its move apart from other candidate moves.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum Disambiguation {
+    /// The index of the source file, e.g. `0` in `Nbd2` or `exd5`.
+    File(u8),
+    /// The index of the source rank, e.g. `0` in `R1a3`.
+    Rank(u8),
+    /// The whole source square, e.g. `h4` in `Qh4e1`.
+    Square(Square),
+}
+
 /// Describes the optional field
 /// used to disambiguate potentially
 /// ambiguous moves from one another.
@@ -186,6 +825,18 @@ enum DisambiguationField {
     SourceSquare((char, char)),
 }
 
+impl From<&DisambiguationField> for Disambiguation {
+    fn from(field: &DisambiguationField) -> Self {
+        match *field {
+            DisambiguationField::FileLetter(file) => Disambiguation::File(file_index(file)),
+            DisambiguationField::RankDigit(rank) => Disambiguation::Rank(rank_index(rank)),
+            DisambiguationField::SourceSquare(square) => {
+                Disambiguation::Square(square_from_chars(square))
+            }
+        }
+    }
+}
+
 /// Describes the traditional
 /// suffix annotation used to
 /// describe the qualitative
@@ -195,18 +846,30 @@ enum DisambiguationField {
 /// to the exclamation mark (!) and
 /// the word hook corresponds to the
 /// question mark (?).
-#[derive(Debug, PartialEq, Eq, Clone)]
-enum SuffixAnnotation {
-    Bang,     // good move
-    Hook,     // mistake
-    BangBang, // brilliant move
-    BangHook, // interesting move (ambiguous value)
-    HookBang, // dubious move (potentially negative value)
-    HookHook, // blunder
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum SuffixAnnotation {
+    /// `!`, a good move.
+    Bang,
+    /// `?`, a mistake.
+    Hook,
+    /// `!!`, a brilliant move.
+    BangBang,
+    /// `!?`, an interesting move of ambiguous value.
+    BangHook,
+    /// `?!`, a dubious move of potentially negative value.
+    HookBang,
+    /// `??`, a blunder.
+    HookHook,
 }
 
 type SanResult<'a, T> = IResult<&'a str, T, VerboseError<&'a str>>;
 
+/// The context attached by [`san_literal`] to the unparsed tail of a literal.
+const TRAILING_GARBAGE: &str = "Found trailing garbage.";
+
+/// The context attached by [`gate`] to an invalid gating suffix.
+const INVALID_GATE: &str = "Found an invalid gating suffix.";
+
 /// Parses the pattern \[?!\]?\[?!\]?.
 fn annotation(source: &str) -> SanResult<Option<SuffixAnnotation>> {
     let mut annotation = pair(opt(one_of("!?")), opt(one_of("!?")));
@@ -241,21 +904,9 @@ fn checkmate(source: &str) -> SanResult<bool> {
         .map(|(tail, symbol)| (tail, symbol == "#"))
 }
 
-/// Parses a piece of the form [KQBNR].
-fn piece(source: &str) -> SanResult<PieceKind> {
-    one_of("KQBNR").parse(source).map(|(tail, piece)| {
-        (
-            tail,
-            match piece {
-                'K' => PieceKind::King,
-                'Q' => PieceKind::Queen,
-                'B' => PieceKind::Bishop,
-                'N' => PieceKind::Knight,
-                'R' => PieceKind::Rook,
-                _ => unreachable!(),
-            },
-        )
-    })
+/// Parses a piece letter of the given `locale`, e.g. [KQBNR] in English.
+fn piece(locale: SanLocale, source: &str) -> SanResult<PieceKind> {
+    map_opt(anychar, |letter| locale.from_char(letter)).parse(source)
 }
 
 /// Parses a single file character of the form [abcdefgh]
@@ -291,33 +942,26 @@ fn capture(source: &str) -> SanResult<char> {
     capture.parse(source)
 }
 
-/// Parses the "=[RNBQ]" segment that can appear at the end of a pawn move.
-fn promotion(source: &str) -> SanResult<PieceKind> {
-    let promotion_piece = one_of("RNBQ");
+/// Parses the "=[RNBQ]" segment that can appear at the end of a pawn move,
+/// with the piece letters of the given `locale`.
+fn promotion(locale: SanLocale, source: &str) -> SanResult<PieceKind> {
+    let promotion_piece = map_opt(anychar, |letter| {
+        locale
+            .from_char(letter)
+            .filter(|&kind| kind != PieceKind::King)
+    });
     let mut promotion = preceded(tag("="), cut(promotion_piece));
-    promotion.parse(source).map(|(tail, piece)| {
-        (
-            tail,
-            match piece {
-                'R' => PieceKind::Rook,
-                'N' => PieceKind::Knight,
-                'B' => PieceKind::Bishop,
-                'Q' => PieceKind::Queen,
-                _ => unreachable!(),
-            },
-        )
-    })
+    promotion.parse(source)
 }
 
 /// Parses a move of the form \[abcdefgh\]\[capture\]?\[abcefgh\]\[promotion\]?.
-fn abbreviated_pawn_move(source: &str) -> SanResult<SanData> {
-    let mut abbrev_move =
-        tuple((
-            one_of("abcdefgh"),
-            opt(capture),
-            one_of("abcdefgh"),
-            opt(promotion),
-        ));
+fn abbreviated_pawn_move(locale: SanLocale, source: &str) -> SanResult<SanData> {
+    let mut abbrev_move = tuple((
+        one_of("abcdefgh"),
+        opt(capture),
+        one_of("abcdefgh"),
+        opt(|source| promotion(locale, source)),
+    ));
     abbrev_move
         .parse(source)
         .map(|(tail, (source, capture, target, promotion))| {
@@ -334,8 +978,12 @@ fn abbreviated_pawn_move(source: &str) -> SanResult<SanData> {
 }
 
 /// Parses a pawn move of the form ([abcdefgh]x)?(target)(promotion)?.
-fn pawn_move(source: &str) -> SanResult<SanData> {
-    let mut pawn_move = tuple((opt(pair(file, capture)), target, opt(promotion)));
+fn pawn_move(locale: SanLocale, source: &str) -> SanResult<SanData> {
+    let mut pawn_move = tuple((
+        opt(pair(file, capture)),
+        target,
+        opt(|source| promotion(locale, source)),
+    ));
     pawn_move
         .parse(source)
         .map(|(tail, (file_capture_block, target, promotion))| {
@@ -360,8 +1008,8 @@ fn castle_move(source: &str) -> SanResult<SanData> {
             tail,
             SanData::CastleMove(match castle {
                 // the order here is load-bearing
-                "O-O-O" | "0-0-0" => CastleMove::QueenSide,
-                "O-O" | "0-0" => CastleMove::KingSide,
+                "O-O-O" | "0-0-0" => CastleSide::QueenSide,
+                "O-O" | "0-0" => CastleSide::KingSide,
                 _ => unreachable!(),
             }),
         )
@@ -369,14 +1017,14 @@ fn castle_move(source: &str) -> SanResult<SanData> {
 }
 
 /// Parses a normal (non-pawn) move with the form [piece][disambiguation_field]?[capture]?[target].
-fn normal_move(source: &str) -> SanResult<SanData> {
-    let unambiguous_normal_move =
-        tuple((
-            piece,
-            success::<&str, Option<_>, _>(None),
-            opt(capture),
-            target,
-        ));
+fn normal_move(locale: SanLocale, source: &str) -> SanResult<SanData> {
+    let piece = |source| piece(locale, source);
+    let unambiguous_normal_move = tuple((
+        piece,
+        success::<&str, Option<_>, _>(None),
+        opt(capture),
+        target,
+    ));
 
     let normal_move = tuple((piece, disambiguation_field, opt(capture), target));
     alt((normal_move, unambiguous_normal_move))
@@ -394,22 +1042,82 @@ fn normal_move(source: &str) -> SanResult<SanData> {
         })
 }
 
-/// Parses a complete SAN literal.
-fn san_literal(source: &str) -> SanResult<San> {
-    let san_literal =
-        tuple((
-            alt((castle_move, pawn_move, abbreviated_pawn_move, normal_move)),
-            opt(permutation((opt(check), opt(checkmate)))),
-            annotation,
-            rest,
-        ));
+/// Returns the kind of piece which `letter` drops in the given `locale`, which
+/// is a pawn for a `P` which does not denote another piece.
+fn drop_piece(locale: SanLocale, letter: char) -> Option<PieceKind> {
+    match locale.from_char(letter) {
+        Some(PieceKind::King) => None,
+        None if letter == 'P' => Some(PieceKind::Pawn),
+        kind => kind,
+    }
+}
+
+/// Parses a piece drop with the form [piece]?@(target), which is only a move
+/// of [`SanVariant::Crazyhouse`].
+fn drop_move(locale: SanLocale, variant: SanVariant, source: &str) -> SanResult<'_, SanData> {
+    if variant != SanVariant::Crazyhouse {
+        return fail(source);
+    }
+
+    let piece = map_opt(anychar, |letter| drop_piece(locale, letter));
+    let mut drop_move = pair(opt(piece), preceded(tag("@"), target));
+    drop_move.parse(source).map(|(tail, (piece, target))| {
+        (
+            tail,
+            SanData::DropMove(DropMove {
+                piece: piece.unwrap_or(PieceKind::Pawn),
+                target,
+            }),
+        )
+    })
+}
+
+/// Parses a gating suffix with the form /[HE](target)?.
+fn gate(source: &str) -> SanResult<'_, Gate> {
+    let piece = map_opt(anychar, |letter| match letter {
+        'H' => Some(GatedPiece::Hawk),
+        'E' => Some(GatedPiece::Elephant),
+        _ => None,
+    });
+
+    let mut gate = context(
+        INVALID_GATE,
+        preceded(tag("/"), cut(pair(piece, opt(target)))),
+    );
+    gate.parse(source).map(|(tail, (piece, square))| {
+        (
+            tail,
+            Gate {
+                piece,
+                square: square.map(square_from_chars),
+            },
+        )
+    })
+}
+
+/// Parses a complete SAN literal of the given `variant`, with the piece letters
+/// of the given `locale`.
+fn san_literal(locale: SanLocale, variant: SanVariant, source: &str) -> SanResult<San> {
+    let san_literal = tuple((
+        alt((
+            castle_move,
+            |source| drop_move(locale, variant, source),
+            |source| pawn_move(locale, source),
+            |source| abbreviated_pawn_move(locale, source),
+            |source| normal_move(locale, source),
+        )),
+        cond(variant == SanVariant::Seirawan, opt(gate)),
+        opt(permutation((opt(check), opt(checkmate)))),
+        annotation,
+        rest,
+    ));
 
     let mut san_parser = complete(san_literal);
-    let (tail, (data, check_state, annotation, rest)) = san_parser.parse(source)?;
+    let (tail, (data, gate, check_state, annotation, rest)) = san_parser.parse(source)?;
 
     if rest.len() > 0 {
         let empty_err = VerboseError { errors: Vec::new() };
-        let err = VerboseError::add_context(rest, "Found trailing garbage.", empty_err);
+        let err = VerboseError::add_context(rest, TRAILING_GARBAGE, empty_err);
         return Err(nom::Err::Failure(err));
     }
 
@@ -417,6 +1125,7 @@ fn san_literal(source: &str) -> SanResult<San> {
         tail,
         San {
             data,
+            gate: gate.flatten(),
             annotation,
             is_check: check_state.is_some_and(|(check, _)| check.is_some()),
             is_checkmate: check_state.is_some_and(|(_, checkmate)| checkmate.is_some()),
@@ -424,10 +1133,327 @@ fn san_literal(source: &str) -> SanResult<San> {
     ))
 }
 
+/// Describes the invalid `rest` of a literal whose leading fields were parsed.
+fn trailing_error(rest: &str) -> ParseError<'_> {
+    match rest.chars().next() {
+        Some(symbol @ '+') => ParseError::InvalidCheckField(symbol),
+        Some(symbol @ '#') => ParseError::InvalidCheckmateField(symbol),
+        Some('!' | '?') => ParseError::InvalidAnnotationSuffixField(rest),
+        _ => ParseError::TrailingGarbage(rest),
+    }
+}
+
+/// Describes why [`san_literal`] rejected `source`, by finding the first of its
+/// mandatory fields which is invalid.
+fn diagnose(locale: SanLocale, variant: SanVariant, source: &str) -> ParseError<'_> {
+    let mut chars = source.chars();
+    let Some(first) = chars.next() else {
+        return ParseError::InvalidLiteralLength(0);
+    };
+
+    // the piece of a drop precedes its `@`, and the target square follows it
+    if let (SanVariant::Crazyhouse, Some((piece, target))) = (variant, source.split_once('@')) {
+        return match piece.chars().next() {
+            Some(letter) if piece.len() > 1 || drop_piece(locale, letter).is_none() => {
+                ParseError::InvalidDropPiece(letter)
+            }
+            _ => ParseError::InvalidTargetSquare(target),
+        };
+    }
+
+    let rest = chars.as_str();
+    match first {
+        'O' | '0' => ParseError::InvalidCastlingField(source),
+        'a'..='h' => match rest.chars().next() {
+            // the target square parsed, so only the promotion could have failed
+            Some('1'..='8' | 'a'..='h') => promotion_error(&rest[1..]),
+            Some('x' | 'X' | '×' | ':') => {
+                let target = &rest[rest.chars().next().map_or(0, char::len_utf8)..];
+                match target.as_bytes() {
+                    [b'a'..=b'h', b'1'..=b'8', ..] => promotion_error(&target[2..]),
+                    [b'a'..=b'h', ..] => promotion_error(&target[1..]),
+                    _ => ParseError::InvalidTargetSquare(target),
+                }
+            }
+            Some(symbol @ ('-' | '/')) => ParseError::InvalidCaptureField(symbol),
+            _ => ParseError::InvalidTargetSquare(source),
+        },
+        _ if locale.from_char(first).is_some() => {
+            // skip the disambiguation field, which the target square might also begin
+            let fields = rest.trim_start_matches(|c| matches!(c, 'a'..='h' | '1'..='8'));
+            match fields.chars().next() {
+                Some(symbol @ ('-' | '/')) => ParseError::InvalidCaptureField(symbol),
+                _ => ParseError::InvalidTargetSquare(rest),
+            }
+        }
+        _ => ParseError::InvalidLeadingPiece(first),
+    }
+}
+
+/// Describes an invalid promotion field at the start of `rest`.
+fn promotion_error(rest: &str) -> ParseError<'_> {
+    match rest.starts_with('=') {
+        true => ParseError::InvalidPromotionField(rest),
+        false => ParseError::Unknown,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn san_literal(source: &str) -> SanResult<San> {
+        super::san_literal(SanLocale::ENGLISH, SanVariant::Standard, source)
+    }
+
+    fn promotion(source: &str) -> SanResult<PieceKind> {
+        super::promotion(SanLocale::ENGLISH, source)
+    }
+
+    fn normal_move(source: &str) -> SanResult<SanData> {
+        super::normal_move(SanLocale::ENGLISH, source)
+    }
+
+    #[test]
+    fn san_literals_are_resolved_against_boards() {
+        use crate::core::Validate;
+        use crate::io::Fen;
+
+        let resolve = |fen: &str, literal: &str| {
+            let board = Fen::try_from(fen).unwrap().to_standard_board();
+            board
+                .validate_san(San::try_from(literal).unwrap())
+                .map(Move::from)
+        };
+
+        let mv = |source: &str, target: &str| {
+            Move::from((
+                Square::try_from(source).unwrap(),
+                Square::try_from(target).unwrap(),
+            ))
+        };
+
+        let start = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+        assert_eq!(resolve(start, "e4").unwrap(), mv("e2", "e4"));
+        assert_eq!(resolve(start, "Nf3").unwrap(), mv("g1", "f3"));
+        assert!(resolve(start, "e5").is_err());
+        assert!(resolve(start, "Nd4").is_err());
+
+        let open = "r3k2r/8/8/3pP3/8/8/8/R3K2R w KQkq d6 0 1";
+        assert_eq!(resolve(open, "O-O").unwrap(), mv("e1", "g1"));
+        assert_eq!(resolve(open, "0-0-0").unwrap(), mv("e1", "c1"));
+        assert_eq!(resolve(open, "exd6").unwrap(), mv("e5", "d6"));
+        assert_eq!(resolve(open, "Rab1").unwrap(), mv("a1", "b1"));
+        assert!(resolve(open, "Nc3").is_err());
+
+        let promotion = "8/4P3/8/8/8/8/k7/4K3 w - - 0 1";
+        let queen = mv("e7", "e8").with_promotion(PieceKind::Queen);
+        assert_eq!(resolve(promotion, "e8=Q").unwrap(), queen);
+        assert_eq!(resolve(promotion, "e8").unwrap(), queen);
+        assert_eq!(
+            resolve(promotion, "e8=N").unwrap(),
+            mv("e7", "e8").with_promotion(PieceKind::Knight)
+        );
+        assert!(resolve(start, "e4=Q").is_err());
+    }
+
+    #[test]
+    fn moves_are_formatted_as_san() {
+        use crate::core::Validate;
+        use crate::io::Fen;
+
+        let format = |fen: &str, source: &str, target: &str| {
+            let board = Fen::try_from(fen).unwrap().to_standard_board();
+            let candidate = Move::from((
+                Square::try_from(source).unwrap(),
+                Square::try_from(target).unwrap(),
+            ));
+            San::format(&board, board.validate(candidate).unwrap())
+        };
+
+        let open = "r3k2r/8/8/3pP3/8/8/8/R3K2R w KQkq d6 0 1";
+        assert_eq!(format(open, "e1", "g1"), "O-O");
+        assert_eq!(format(open, "e1", "c1"), "O-O-O");
+        assert_eq!(format(open, "e5", "d6"), "exd6");
+        assert_eq!(format(open, "a1", "b1"), "Rb1");
+        assert_eq!(format(open, "h1", "h8"), "Rxh8+");
+
+        let knights = "4k3/8/8/8/8/2N1N3/8/2N1K3 w - - 0 1";
+        assert_eq!(format(knights, "c3", "d5"), "Ncd5");
+        assert_eq!(format(knights, "c3", "b5"), "Nb5");
+        assert_eq!(format(knights, "c3", "e2"), "N3e2");
+        assert_eq!(format(knights, "e3", "d1"), "Ned1");
+        assert_eq!(format(knights, "c1", "d3"), "Nd3");
+
+        let promotion = "8/4P3/8/8/8/8/k7/4K3 w - - 0 1";
+        assert_eq!(format(promotion, "e7", "e8"), "e8=Q");
+        let board = Fen::try_from(promotion).unwrap().to_standard_board();
+        let knight = Move::from((
+            Square::try_from("e7").unwrap(),
+            Square::try_from("e8").unwrap(),
+        ))
+        .with_promotion(PieceKind::Knight);
+        assert_eq!(
+            San::format(&board, board.validate(knight).unwrap()),
+            "e8=N"
+        );
+        assert_eq!(
+            format("6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1", "a1", "a8"),
+            "Ra8#"
+        );
+    }
+
+    #[test]
+    fn parse_errors_describe_the_invalid_field() {
+        let error = |source| San::try_from(source).unwrap_err();
+
+        assert_eq!(error("e"), ParseError::InvalidLiteralLength(1));
+        assert_eq!(error("Qa1xb2+#!?!!!"), ParseError::InvalidLiteralLength(13));
+        assert_eq!(error("Zf3"), ParseError::InvalidLeadingPiece('Z'));
+        assert_eq!(error("Nz3"), ParseError::InvalidTargetSquare("z3"));
+        assert_eq!(error("e9"), ParseError::InvalidTargetSquare("e9"));
+        assert_eq!(error("exz5"), ParseError::InvalidTargetSquare("z5"));
+        assert_eq!(error("N-f3"), ParseError::InvalidCaptureField('-'));
+        assert_eq!(error("e-d5"), ParseError::InvalidCaptureField('-'));
+        assert_eq!(error("e8=K"), ParseError::InvalidPromotionField("=K"));
+        assert_eq!(error("exd8="), ParseError::InvalidPromotionField("="));
+        assert_eq!(error("O-X"), ParseError::InvalidCastlingField("O-X"));
+        assert_eq!(error("Qh5++"), ParseError::InvalidCheckField('+'));
+        assert_eq!(error("Qh5##"), ParseError::InvalidCheckmateField('#'));
+        assert_eq!(
+            error("Nf3!!?"),
+            ParseError::InvalidAnnotationSuffixField("?")
+        );
+        assert_eq!(error("Nf3 e5"), ParseError::TrailingGarbage(" e5"));
+        assert_eq!(
+            error("Zf3").to_string(),
+            "Expected one of 'O', 'K', 'Q', 'B', 'R', 'N' or a file; got Z"
+        );
+    }
+
+    #[test]
+    fn localized_san_is_parsed_and_formatted() {
+        use crate::core::Validate;
+
+        let board = Board::default();
+        let knight = Move::from((
+            Square::try_from("g1").unwrap(),
+            Square::try_from("f3").unwrap(),
+        ));
+        let legal_move = board.validate(knight).unwrap();
+
+        for (locale, literal) in [
+            (SanLocale::ENGLISH, "Nf3"),
+            (SanLocale::GERMAN, "Sf3"),
+            (SanLocale::FRENCH, "Cf3"),
+            (SanLocale::DUTCH, "Pf3"),
+        ] {
+            assert_eq!(San::format_localized(&board, legal_move, locale), literal);
+            let san = San::parse_localized(literal, locale).unwrap();
+            assert_eq!(board.validate_san(san).unwrap(), legal_move);
+        }
+
+        // each locale only accepts its own letters
+        assert!(San::parse_localized("Nf3", SanLocale::GERMAN).is_err());
+        assert!(San::parse_localized("e8=D", SanLocale::GERMAN).is_ok());
+        assert!(San::parse_localized("e8=K", SanLocale::GERMAN).is_err());
+    }
+
+    #[test]
+    fn variant_san_is_parsed_and_written() {
+        use crate::core::Validate;
+
+        let square = |name| Square::try_from(name).unwrap();
+        let crazyhouse =
+            |source| San::parse_variant(source, SanLocale::ENGLISH, SanVariant::Crazyhouse);
+        let seirawan =
+            |source| San::parse_variant(source, SanLocale::ENGLISH, SanVariant::Seirawan);
+
+        let knight = crazyhouse("N@f3+").unwrap();
+        assert!(knight.is_drop() && knight.is_check() && !knight.is_capture());
+        assert_eq!(knight.piece(), PieceKind::Knight);
+        assert_eq!(knight.target(), Some(square("f3")));
+        assert_eq!(
+            knight,
+            San::piece_drop(PieceKind::Knight, square("f3")).with_check()
+        );
+        assert_eq!(knight.to_string(), "N@f3+");
+
+        // pawns are dropped without a letter, though a `P` is also accepted
+        let pawn = San::piece_drop(PieceKind::Pawn, square("e4"));
+        assert_eq!(pawn.to_string(), "@e4");
+        assert_eq!(crazyhouse("@e4").unwrap(), pawn);
+        assert_eq!(crazyhouse("P@e4").unwrap(), pawn);
+        let german = San::parse_variant("S@f3", SanLocale::GERMAN, SanVariant::Crazyhouse);
+        assert_eq!(
+            german.unwrap().to_localized_string(SanLocale::GERMAN),
+            "S@f3"
+        );
+
+        assert_eq!(crazyhouse("K@e4"), Err(ParseError::InvalidDropPiece('K')));
+        assert_eq!(
+            crazyhouse("N@z9"),
+            Err(ParseError::InvalidTargetSquare("z9"))
+        );
+        assert!(San::try_from("N@f3").is_err());
+        assert!(seirawan("N@f3").is_err());
+
+        let hawk = Gate {
+            piece: GatedPiece::Hawk,
+            square: None,
+        };
+        let gated = seirawan("Nf3/H").unwrap();
+        assert_eq!(gated.gate(), Some(hawk));
+        assert_eq!(gated.to_string(), "Nf3/H");
+
+        let castle = seirawan("O-O/Eh1+").unwrap();
+        let elephant = Gate {
+            piece: GatedPiece::Elephant,
+            square: Some(square("h1")),
+        };
+        assert_eq!(
+            castle,
+            San::castling(CastleSide::KingSide)
+                .with_gate(elephant)
+                .with_check()
+        );
+        assert_eq!(castle.to_string(), "O-O/Eh1+");
+        assert_eq!(seirawan("Nf3/X"), Err(ParseError::InvalidGatingField("/X")));
+        assert_eq!(
+            San::try_from("Nf3/H"),
+            Err(ParseError::TrailingGarbage("/H"))
+        );
+
+        // a standard board can neither drop nor gate pieces
+        let board = Board::default();
+        assert!(board.validate_san(knight).is_err());
+        assert!(board.validate_san(gated).is_err());
+        let nf3 = board.validate_san(seirawan("Nf3").unwrap()).unwrap();
+        assert_eq!(
+            San::from_legal_move(&board, nf3)
+                .with_gate(hawk)
+                .to_string(),
+            "Nf3/H"
+        );
+
+        // in Chess960, the rook decides the side, wherever the king stands
+        assert_eq!(
+            CastleSide::of_rook(square("b1"), square("c1")),
+            CastleSide::KingSide
+        );
+        assert_eq!(
+            CastleSide::of_rook(square("g1"), square("a1")),
+            CastleSide::QueenSide
+        );
+    }
+
+    #[test]
+    fn written_literals_are_parsed_back() {
+        for literal in ["Nbxd7+!?", "Qh4e1#", "exf8=R", "dxe=Q", "O-O-O??", "R1a3"] {
+            assert_eq!(San::try_from(literal).unwrap().to_string(), literal);
+        }
+    }
+
     #[test]
     fn basic_san_parsing() {
         san_literal("e5").unwrap();
@@ -555,4 +1581,38 @@ mod tests {
         San::try_from("Bc3+").expect("should be a valid move");
         San::try_from("axb5").expect("should be a valid move");
     }
+
+    #[test]
+    fn san_accessors_expose_the_parsed_fields() {
+        let square = |name| Square::try_from(name).unwrap();
+
+        let san = San::try_from("Nbxd7+!?").unwrap();
+        assert_eq!(san.piece(), PieceKind::Knight);
+        assert_eq!(san.target(), Some(square("d7")));
+        assert_eq!(san.disambiguation(), Some(Disambiguation::File(1)));
+        assert!(san.is_capture() && san.is_check() && !san.is_checkmate());
+        assert_eq!(san.annotation(), Some(SuffixAnnotation::BangHook));
+        assert_eq!(san.castle(), None);
+
+        let san = San::try_from("Qh4e1#").unwrap();
+        assert_eq!(
+            san.disambiguation(),
+            Some(Disambiguation::Square(square("h4")))
+        );
+        assert!(!san.is_capture() && san.is_checkmate());
+
+        let san = San::try_from("exf8=R").unwrap();
+        assert_eq!(san.piece(), PieceKind::Pawn);
+        assert_eq!(san.disambiguation(), Some(Disambiguation::File(4)));
+        assert_eq!(san.promotion(), Some(PieceKind::Rook));
+
+        let san = San::try_from("ed").unwrap();
+        assert_eq!(san.target(), None);
+        assert!(san.is_capture());
+
+        let san = San::try_from("O-O-O??").unwrap();
+        assert_eq!(san.piece(), PieceKind::King);
+        assert_eq!(san.castle(), Some(CastleSide::QueenSide));
+        assert_eq!(san.annotation(), Some(SuffixAnnotation::HookHook));
+    }
 }
@@ -0,0 +1,246 @@
+use thiserror::Error;
+
+use crate::core::{Move as _, Process as _, Validate as _};
+use crate::standard::{Board, LegalMove, Move, PieceKind, Square};
+
+/// The alphabet of TCN, in which the first 64 characters denote the squares
+/// from a1 to h8 and the rest denote promotions and drops.
+const ALPHABET: &[u8] =
+    b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789!?{~}(^)[_]@#$,./&-*++=";
+
+/// The index in [`ALPHABET`] of the first character denoting a promotion.
+const PROMOTION_OFFSET: usize = 64;
+
+/// The index in [`ALPHABET`] of the first character denoting a drop, following
+/// the three characters of each promotion piece.
+const DROP_OFFSET: usize = PROMOTION_OFFSET + 3 * PROMOTION_PIECES.len();
+
+/// The promotion pieces of TCN, in the order of their characters.
+const PROMOTION_PIECES: [PieceKind; 4] = [
+    PieceKind::Queen,
+    PieceKind::Knight,
+    PieceKind::Rook,
+    PieceKind::Bishop,
+];
+
+/// Results when a TCN string cannot be decoded.
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum TcnError {
+    /// Results when the string does not consist of two-character moves.
+    #[error("TCN strings must have an even length; got {0} characters")]
+    InvalidLength(usize),
+    /// Results when a character is not part of the TCN alphabet.
+    #[error("invalid TCN character {character:?} in move {index}")]
+    InvalidCharacter {
+        /// The index of the move containing the character.
+        index: usize,
+        /// The invalid character.
+        character: char,
+    },
+    /// Results when a move drops a piece, which only occurs in variants.
+    #[error("move {0} drops a piece, which is not supported")]
+    Drop(usize),
+    /// Results when a move is not legal in the position it is played from.
+    #[error("move {0} is illegal")]
+    IllegalMove(usize),
+}
+
+/// Decodes a TCN string, as used by chess.com to record games, into its moves.
+///
+/// Each move is encoded by two characters: one for the source square, and one
+/// for either the target square or, for promotions, the promotion piece and the
+/// direction of the pawn.
+///
+/// The moves are not checked for legality; use [`replay`] for that.
+pub fn decode(tcn: &str) -> Result<Vec<Move>, TcnError> {
+    let pairs = tcn.as_bytes().chunks_exact(2);
+    if !pairs.remainder().is_empty() {
+        return Err(TcnError::InvalidLength(tcn.len()));
+    }
+
+    pairs
+        .enumerate()
+        .map(|(index, pair)| decode_move(index, pair[0], pair[1]))
+        .collect()
+}
+
+/// Decodes a TCN string and validates its moves, in order, from `board`.
+pub fn replay(board: &Board, tcn: &str) -> Result<Vec<LegalMove>, TcnError> {
+    let mut board = *board;
+    let mut legal_moves = Vec::new();
+
+    for (index, candidate) in decode(tcn)?.into_iter().enumerate() {
+        let legal_move = board
+            .validate(candidate)
+            .map_err(|_| TcnError::IllegalMove(index))?;
+
+        board = board.process(legal_move);
+        legal_moves.push(legal_move);
+    }
+
+    Ok(legal_moves)
+}
+
+/// Encodes `moves` as a TCN string.
+pub fn encode(moves: &[LegalMove]) -> String {
+    let mut tcn = String::with_capacity(2 * moves.len());
+    for &legal_move in moves {
+        let source = legal_move.source();
+        let target = legal_move.target();
+
+        tcn.push(ALPHABET[usize::from(source)] as char);
+        let target_index = match legal_move.promotion() {
+            Some(piece) => {
+                let piece_index = PROMOTION_PIECES.iter().position(|&kind| kind == piece);
+                PROMOTION_OFFSET
+                    + 3 * piece_index.unwrap_or(0)
+                    + (target.file() + 1 - source.file()) as usize
+            }
+            None => usize::from(target),
+        };
+
+        tcn.push(ALPHABET[target_index] as char);
+    }
+
+    tcn
+}
+
+/// Decodes the move at `index`, given by the characters `first` and `second`.
+fn decode_move(index: usize, first: u8, second: u8) -> Result<Move, TcnError> {
+    let position = |byte: u8| {
+        ALPHABET
+            .iter()
+            .position(|&character| character == byte)
+            .ok_or(TcnError::InvalidCharacter {
+                index,
+                character: byte as char,
+            })
+    };
+
+    let source = position(first)?;
+    let target = position(second)?;
+
+    if source >= DROP_OFFSET {
+        return Err(TcnError::Drop(index));
+    }
+
+    if source >= PROMOTION_OFFSET {
+        return Err(TcnError::InvalidCharacter {
+            index,
+            character: first as char,
+        });
+    }
+
+    let mut promotion = None;
+    let target = match target {
+        target if target < PROMOTION_OFFSET => target,
+        target if target < DROP_OFFSET => {
+            promotion = Some(PROMOTION_PIECES[(target - PROMOTION_OFFSET) / 3]);
+
+            // white pawns promote from the seventh rank, and black pawns from the second
+            let forward = match source / 8 {
+                6 => Some(source + 8),
+                1 => Some(source - 8),
+                _ => None,
+            };
+
+            let file_offset = (target - PROMOTION_OFFSET) % 3;
+            forward
+                .and_then(|forward| {
+                    (forward + file_offset)
+                        .checked_sub(1)
+                        .filter(|target| target / 8 == forward / 8)
+                })
+                .ok_or(TcnError::InvalidCharacter {
+                    index,
+                    character: second as char,
+                })?
+        }
+        _ => {
+            return Err(TcnError::InvalidCharacter {
+                index,
+                character: second as char,
+            })
+        }
+    };
+
+    let candidate = Move::from((Square::at(source as u8), Square::at(target as u8)));
+    Ok(match promotion {
+        Some(piece) => candidate.with_promotion(piece),
+        None => candidate,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::Fen;
+    use crate::standard::r#move::mv;
+
+    #[test]
+    fn games_are_decoded_and_replayed() {
+        // 1. e4 e5 2. Nf3 Nc6 3. Bc4
+        let tcn = "mC0Kgv5QfA";
+        let moves = decode(tcn).unwrap();
+        assert_eq!(
+            moves,
+            vec![
+                mv("e2", "e4"),
+                mv("e7", "e5"),
+                mv("g1", "f3"),
+                mv("b8", "c6"),
+                mv("f1", "c4"),
+            ]
+        );
+
+        let legal_moves = replay(&Board::default(), tcn).unwrap();
+        assert_eq!(encode(&legal_moves), tcn);
+        assert_eq!(
+            replay(&Board::default(), "mCmC"),
+            Err(TcnError::IllegalMove(1))
+        );
+    }
+
+    #[test]
+    fn promotions_and_invalid_strings_are_handled() {
+        // the pawn on b7 promotes on a8, b8, and c8 respectively
+        let queen = |source, target| mv(source, target).with_promotion(PieceKind::Queen);
+        assert_eq!(decode("X{").unwrap(), vec![queen("b7", "a8")]);
+        assert_eq!(decode("X~").unwrap(), vec![queen("b7", "b8")]);
+        assert_eq!(decode("X}").unwrap(), vec![queen("b7", "c8")]);
+        assert_eq!(decode("j~").unwrap(), vec![queen("b2", "b1")]);
+        assert_eq!(
+            decode("X^").unwrap(),
+            vec![mv("b7", "b8").with_promotion(PieceKind::Knight)]
+        );
+
+        let board = Fen::try_from("2r5/1P6/8/8/8/8/k7/4K3 w - - 0 1")
+            .unwrap()
+            .to_standard_board();
+        for tcn in ["X}", "X)", "X^", "X_", "X#"] {
+            let legal_moves = replay(&board, tcn).unwrap();
+            assert_eq!(encode(&legal_moves), tcn);
+        }
+
+        assert_eq!(
+            decode("I{").unwrap_err(),
+            TcnError::InvalidCharacter {
+                index: 0,
+                character: '{'
+            }
+        );
+        assert_eq!(decode("mC0"), Err(TcnError::InvalidLength(3)));
+        assert!(matches!(
+            decode("m "),
+            Err(TcnError::InvalidCharacter { index: 0, .. })
+        ));
+        assert_eq!(decode("&m"), Err(TcnError::Drop(0)));
+        assert_eq!(decode(",m"), Err(TcnError::Drop(0)));
+
+        // the last promotion character is a capture to the right promoting to a bishop
+        assert_eq!(
+            decode("0$"),
+            Ok(vec![mv("e7", "f8").with_promotion(PieceKind::Bishop)])
+        );
+    }
+}
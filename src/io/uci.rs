@@ -0,0 +1,984 @@
+use std::str::{FromStr, SplitWhitespace};
+
+use thiserror::Error;
+
+use super::fen::{Fen, FenOptions};
+use crate::standard::{Game, Move, PieceKind, Square};
+
+/// Provides a trait for engines and a loop driving them over UCI.
+pub mod engine;
+
+/// The keywords which may start a field of an `info` line.
+const INFO_KEYWORDS: [&str; 18] = [
+    "depth",
+    "seldepth",
+    "time",
+    "nodes",
+    "pv",
+    "multipv",
+    "score",
+    "currmove",
+    "currmovenumber",
+    "hashfull",
+    "nps",
+    "tbhits",
+    "sbhits",
+    "cpuload",
+    "string",
+    "refutation",
+    "currline",
+    "wdl",
+];
+
+/// The keywords which may start a field of a `go` command.
+const GO_KEYWORDS: [&str; 12] = [
+    "searchmoves",
+    "ponder",
+    "wtime",
+    "btime",
+    "winc",
+    "binc",
+    "movestogo",
+    "depth",
+    "nodes",
+    "mate",
+    "movetime",
+    "infinite",
+];
+
+/// The keywords which may start a field of an `option` declaration.
+const OPTION_KEYWORDS: [&str; 6] = ["name", "type", "default", "min", "max", "var"];
+
+/// Results when a line of UCI cannot be parsed.
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum UciError {
+    /// Results when the line is empty.
+    #[error("expected a UCI command, got an empty line")]
+    Empty,
+    /// Results when the line does not start with a known command.
+    #[error("unknown UCI command: {0}")]
+    UnknownCommand(String),
+    /// Results when a field is missing its value.
+    #[error("missing a value for {0}")]
+    MissingValue(&'static str),
+    /// Results when the value of a field is invalid.
+    #[error("invalid value {value:?} for {field}")]
+    InvalidValue {
+        /// The name of the field.
+        field: &'static str,
+        /// The invalid value.
+        value: String,
+    },
+    /// Results when a move is not written in coordinate notation, e.g. `e7e8q`.
+    #[error("invalid UCI move: {0}")]
+    InvalidMove(String),
+    /// Results when a `position` command gives an invalid FEN string or position.
+    #[error("invalid FEN position {fen:?}: {reason}")]
+    InvalidFen {
+        /// The FEN string as it appeared in the command.
+        fen: String,
+        /// A description of why the position is invalid.
+        reason: String,
+    },
+    /// Results when a move of a `position` command cannot be played.
+    #[error("illegal move {literal} at ply {ply}")]
+    IllegalMove {
+        /// The index of the move in the command.
+        ply: usize,
+        /// The move as it appeared in the command.
+        literal: String,
+    },
+}
+
+/// The evaluation of a position reported by an engine, from the
+/// perspective of the side to move.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Score {
+    /// An evaluation in hundredths of a pawn.
+    Centipawns(i32),
+    /// A forced mate in the given number of moves, negative if the side to move is mated.
+    Mate(i32),
+}
+
+/// Marks a [`Score`] which is only a bound on the true evaluation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ScoreBound {
+    /// The true evaluation is at least the score.
+    Lower,
+    /// The true evaluation is at most the score.
+    Upper,
+}
+
+/// The fields of an `info` line, each of which is optional.
+///
+/// Fields which are not recognised, such as `wdl`, are ignored.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Info {
+    /// The search depth, in plies.
+    pub depth: Option<u32>,
+    /// The selective search depth, in plies.
+    pub seldepth: Option<u32>,
+    /// The index of the line in multi-PV mode, starting from 1.
+    pub multipv: Option<u32>,
+    /// The evaluation of the position.
+    pub score: Option<Score>,
+    /// Whether the score is only a bound.
+    pub bound: Option<ScoreBound>,
+    /// The number of nodes searched.
+    pub nodes: Option<u64>,
+    /// The number of nodes searched per second.
+    pub nps: Option<u64>,
+    /// The time searched, in milliseconds.
+    pub time: Option<u64>,
+    /// The fill of the hash table, in permille.
+    pub hashfull: Option<u32>,
+    /// The number of tablebase hits.
+    pub tbhits: Option<u64>,
+    /// The move currently being searched.
+    pub currmove: Option<Move>,
+    /// The index of the move currently being searched, starting from 1.
+    pub currmovenumber: Option<u32>,
+    /// The principal variation, which may be empty.
+    pub pv: Vec<Move>,
+    /// A free-form message, which takes up the rest of the line.
+    pub string: Option<String>,
+}
+
+/// The result of a search, as reported by a `bestmove` line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BestMove {
+    /// The best move, or `None` if the engine reported the null move `0000`,
+    /// as it does when there are no legal moves.
+    pub best: Option<Move>,
+    /// The reply which the engine would like to ponder on.
+    pub ponder: Option<Move>,
+}
+
+/// The type and bounds of an [`EngineOption`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum OptionKind {
+    /// A boolean option.
+    Check {
+        /// The default value.
+        default: bool,
+    },
+    /// An integer option within a range.
+    Spin {
+        /// The default value.
+        default: i64,
+        /// The least allowed value.
+        min: i64,
+        /// The greatest allowed value.
+        max: i64,
+    },
+    /// An option taking one of a list of strings.
+    Combo {
+        /// The default value.
+        default: String,
+        /// The allowed values.
+        vars: Vec<String>,
+    },
+    /// An option without a value, which triggers an action when set.
+    Button,
+    /// A free-form string option, whose default may be empty.
+    String {
+        /// The default value.
+        default: String,
+    },
+}
+
+/// An option declared by an engine, which may be set with `setoption`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct EngineOption {
+    /// The name of the option, which may contain spaces.
+    pub name: String,
+    /// The type and bounds of the option.
+    pub kind: OptionKind,
+}
+
+/// The parameters of a `go` command, which starts a search.
+///
+/// Times are given in milliseconds, and every limit is optional; a search
+/// without any limits should be treated as `infinite`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct GoParams {
+    /// The moves to which the search is restricted, or all moves if empty.
+    pub searchmoves: Vec<Move>,
+    /// Whether to search in pondering mode, on the move the engine expects.
+    pub ponder: bool,
+    /// The time left on white's clock.
+    pub wtime: Option<u64>,
+    /// The time left on black's clock.
+    pub btime: Option<u64>,
+    /// White's increment per move.
+    pub winc: Option<u64>,
+    /// Black's increment per move.
+    pub binc: Option<u64>,
+    /// The number of moves until the next time control.
+    pub movestogo: Option<u32>,
+    /// The greatest depth to search, in plies.
+    pub depth: Option<u32>,
+    /// The greatest number of nodes to search.
+    pub nodes: Option<u64>,
+    /// Search for a mate in this many moves.
+    pub mate: Option<u32>,
+    /// The exact time to search.
+    pub movetime: Option<u64>,
+    /// Whether to search until a `stop` command.
+    pub infinite: bool,
+}
+
+impl FromStr for GoParams {
+    type Err = UciError;
+
+    /// Parses a `go` command, e.g. `go wtime 60000 btime 60000 movestogo 40`.
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let mut tokens = line.split_whitespace();
+        match tokens.next() {
+            Some("go") => (),
+            Some(command) => return Err(UciError::UnknownCommand(command.to_string())),
+            None => return Err(UciError::Empty),
+        }
+
+        let mut params = GoParams::default();
+        while let Some(keyword) = tokens.next() {
+            match keyword {
+                "searchmoves" => {
+                    params.searchmoves = join(&mut tokens, &GO_KEYWORDS)
+                        .split_whitespace()
+                        .map(parse_move)
+                        .collect::<Result<_, _>>()?;
+                }
+                "ponder" => params.ponder = true,
+                "wtime" => params.wtime = Some(value(&mut tokens, "wtime")?),
+                "btime" => params.btime = Some(value(&mut tokens, "btime")?),
+                "winc" => params.winc = Some(value(&mut tokens, "winc")?),
+                "binc" => params.binc = Some(value(&mut tokens, "binc")?),
+                "movestogo" => params.movestogo = Some(value(&mut tokens, "movestogo")?),
+                "depth" => params.depth = Some(value(&mut tokens, "depth")?),
+                "nodes" => params.nodes = Some(value(&mut tokens, "nodes")?),
+                "mate" => params.mate = Some(value(&mut tokens, "mate")?),
+                "movetime" => params.movetime = Some(value(&mut tokens, "movetime")?),
+                "infinite" => params.infinite = true,
+                other => {
+                    return Err(UciError::InvalidValue {
+                        field: "go",
+                        value: other.to_string(),
+                    })
+                }
+            }
+        }
+
+        Ok(params)
+    }
+}
+
+impl std::fmt::Display for GoParams {
+    /// Writes `self` as a `go` command, which [`GoParams::from_str`] parses back.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "go")?;
+        if !self.searchmoves.is_empty() {
+            write!(f, " searchmoves")?;
+            for candidate in &self.searchmoves {
+                write!(f, " {candidate}")?;
+            }
+        }
+
+        if self.ponder {
+            write!(f, " ponder")?;
+        }
+
+        let limits = [
+            ("wtime", self.wtime),
+            ("btime", self.btime),
+            ("winc", self.winc),
+            ("binc", self.binc),
+            ("movestogo", self.movestogo.map(u64::from)),
+            ("depth", self.depth.map(u64::from)),
+            ("nodes", self.nodes),
+            ("mate", self.mate.map(u64::from)),
+            ("movetime", self.movetime),
+        ];
+
+        for (keyword, limit) in limits {
+            if let Some(limit) = limit {
+                write!(f, " {keyword} {limit}")?;
+            }
+        }
+
+        if self.infinite {
+            write!(f, " infinite")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A line sent by an engine to the GUI.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EngineMessage {
+    /// The name of the engine, from `id name`.
+    Name(String),
+    /// The author of the engine, from `id author`.
+    Author(String),
+    /// The engine has sent all of its options, from `uciok`.
+    UciOk,
+    /// The engine is ready to receive commands, from `readyok`.
+    ReadyOk,
+    /// The result of a search.
+    BestMove(BestMove),
+    /// Information about an ongoing search.
+    Info(Info),
+    /// The declaration of an option.
+    Option(EngineOption),
+}
+
+impl FromStr for EngineMessage {
+    type Err = UciError;
+
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let mut tokens = line.split_whitespace();
+
+        match tokens.next() {
+            None => Err(UciError::Empty),
+            Some("uciok") => Ok(Self::UciOk),
+            Some("readyok") => Ok(Self::ReadyOk),
+            Some("id") => match tokens.next() {
+                Some("name") => Ok(Self::Name(join(&mut tokens, &[]))),
+                Some("author") => Ok(Self::Author(join(&mut tokens, &[]))),
+                _ => Err(UciError::MissingValue("id")),
+            },
+            Some("bestmove") => parse_best_move(tokens).map(Self::BestMove),
+            Some("info") => parse_info(tokens).map(Self::Info),
+            Some("option") => parse_option(tokens).map(Self::Option),
+            Some(command) => Err(UciError::UnknownCommand(command.to_string())),
+        }
+    }
+}
+
+impl std::fmt::Display for EngineMessage {
+    /// Writes `self` as the line which [`EngineMessage::from_str`] parses back.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Name(name) => write!(f, "id name {name}"),
+            Self::Author(author) => write!(f, "id author {author}"),
+            Self::UciOk => write!(f, "uciok"),
+            Self::ReadyOk => write!(f, "readyok"),
+            Self::BestMove(best_move) => write!(f, "{best_move}"),
+            Self::Info(info) => write!(f, "{info}"),
+            Self::Option(option) => write!(f, "{option}"),
+        }
+    }
+}
+
+impl std::fmt::Display for BestMove {
+    /// Writes `self` as a `bestmove` line, using `0000` if there is no best move.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.best {
+            Some(best) => write!(f, "bestmove {best}")?,
+            None => write!(f, "bestmove 0000")?,
+        }
+
+        match self.ponder {
+            Some(ponder) => write!(f, " ponder {ponder}"),
+            None => Ok(()),
+        }
+    }
+}
+
+impl std::fmt::Display for Info {
+    /// Writes `self` as an `info` line, with the free-form string last.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "info")?;
+
+        let counts = [
+            ("depth", self.depth.map(u64::from)),
+            ("seldepth", self.seldepth.map(u64::from)),
+            ("multipv", self.multipv.map(u64::from)),
+        ];
+        for (keyword, count) in counts {
+            if let Some(count) = count {
+                write!(f, " {keyword} {count}")?;
+            }
+        }
+
+        match self.score {
+            Some(Score::Centipawns(score)) => write!(f, " score cp {score}")?,
+            Some(Score::Mate(moves)) => write!(f, " score mate {moves}")?,
+            None => (),
+        }
+
+        match self.bound {
+            Some(ScoreBound::Lower) => write!(f, " lowerbound")?,
+            Some(ScoreBound::Upper) => write!(f, " upperbound")?,
+            None => (),
+        }
+
+        let counts = [
+            ("nodes", self.nodes),
+            ("nps", self.nps),
+            ("hashfull", self.hashfull.map(u64::from)),
+            ("tbhits", self.tbhits),
+            ("time", self.time),
+        ];
+        for (keyword, count) in counts {
+            if let Some(count) = count {
+                write!(f, " {keyword} {count}")?;
+            }
+        }
+
+        if let Some(currmove) = self.currmove {
+            write!(f, " currmove {currmove}")?;
+        }
+
+        if let Some(number) = self.currmovenumber {
+            write!(f, " currmovenumber {number}")?;
+        }
+
+        if !self.pv.is_empty() {
+            write!(f, " pv")?;
+            for candidate in &self.pv {
+                write!(f, " {candidate}")?;
+            }
+        }
+
+        match &self.string {
+            Some(string) => write!(f, " string {string}"),
+            None => Ok(()),
+        }
+    }
+}
+
+impl std::fmt::Display for EngineOption {
+    /// Writes `self` as an `option` line, using `<empty>` for an empty string default.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "option name {} type ", self.name)?;
+        match &self.kind {
+            OptionKind::Check { default } => write!(f, "check default {default}"),
+            OptionKind::Spin { default, min, max } => {
+                write!(f, "spin default {default} min {min} max {max}")
+            }
+            OptionKind::Combo { default, vars } => {
+                write!(f, "combo default {default}")?;
+                for var in vars {
+                    write!(f, " var {var}")?;
+                }
+
+                Ok(())
+            }
+            OptionKind::Button => write!(f, "button"),
+            OptionKind::String { default } if default.is_empty() => {
+                write!(f, "string default <empty>")
+            }
+            OptionKind::String { default } => write!(f, "string default {default}"),
+        }
+    }
+}
+
+/// Parses a move in the coordinate notation of UCI, e.g. `e2e4` or `e7e8q`.
+///
+/// The move is not checked for legality, and the null move `0000` is rejected.
+pub fn parse_move(literal: &str) -> Result<Move, UciError> {
+    let invalid = || UciError::InvalidMove(literal.to_string());
+    let square = |range| {
+        literal
+            .get(range)
+            .and_then(|name| Square::try_from(name).ok())
+            .ok_or_else(invalid)
+    };
+
+    let candidate = Move::from((square(0..2)?, square(2..4)?));
+    match literal.get(4..) {
+        Some("") => Ok(candidate),
+        Some("q") => Ok(candidate.with_promotion(PieceKind::Queen)),
+        Some("r") => Ok(candidate.with_promotion(PieceKind::Rook)),
+        Some("b") => Ok(candidate.with_promotion(PieceKind::Bishop)),
+        Some("n") => Ok(candidate.with_promotion(PieceKind::Knight)),
+        _ => Err(invalid()),
+    }
+}
+
+/// Parses a `position` command, e.g. `position startpos moves e2e4 e7e5` or
+/// `position fen <fen> moves ...`, into the [`Game`] it describes, validating
+/// each of the moves in turn.
+///
+/// The halfmove clock of a FEN string is not bounded, since some GUIs do not
+/// adjudicate draws by the fifty-move rule.
+pub fn parse_position(line: &str) -> Result<Game, UciError> {
+    let mut tokens = line.split_whitespace();
+    match tokens.next() {
+        Some("position") => (),
+        Some(command) => return Err(UciError::UnknownCommand(command.to_string())),
+        None => return Err(UciError::Empty),
+    }
+
+    let mut game = match tokens.next() {
+        Some("startpos") => Game::new(),
+        Some("fen") => {
+            let fen = join(&mut tokens, &["moves"]);
+            let invalid = |reason: String| UciError::InvalidFen {
+                fen: fen.clone(),
+                reason,
+            };
+
+            let board = Fen::parse_with(&fen, FenOptions::lenient())
+                .map_err(|err| invalid(err.to_string()))?
+                .to_standard_board();
+            board
+                .validate_setup()
+                .map_err(|err| invalid(err.to_string()))?;
+            Game::from_board(board)
+        }
+        Some(other) => {
+            return Err(UciError::InvalidValue {
+                field: "position",
+                value: other.to_string(),
+            })
+        }
+        None => return Err(UciError::MissingValue("position")),
+    };
+
+    match tokens.next() {
+        Some("moves") | None => (),
+        Some(other) => {
+            return Err(UciError::InvalidValue {
+                field: "position",
+                value: other.to_string(),
+            })
+        }
+    }
+
+    for (ply, literal) in tokens.enumerate() {
+        let illegal = || UciError::IllegalMove {
+            ply,
+            literal: literal.to_string(),
+        };
+
+        let candidate = parse_move(literal).map_err(|_| illegal())?;
+        game.play(candidate).map_err(|_| illegal())?;
+    }
+
+    Ok(game)
+}
+
+/// Parses the value following the keyword `field`.
+fn value<T: FromStr>(tokens: &mut SplitWhitespace, field: &'static str) -> Result<T, UciError> {
+    let token = tokens.next().ok_or(UciError::MissingValue(field))?;
+    token.parse().map_err(|_| UciError::InvalidValue {
+        field,
+        value: token.to_string(),
+    })
+}
+
+/// Joins the tokens up to, but excluding, the first of the given `keywords`.
+fn join(tokens: &mut SplitWhitespace, keywords: &[&str]) -> String {
+    let mut words = Vec::new();
+    while let Some(token) = tokens.clone().next() {
+        if keywords.contains(&token) {
+            break;
+        }
+
+        words.push(token);
+        tokens.next();
+    }
+
+    words.join(" ")
+}
+
+/// Parses the fields of a `bestmove` line, e.g. `e2e4 ponder e7e5`.
+fn parse_best_move(mut tokens: SplitWhitespace) -> Result<BestMove, UciError> {
+    let best = match tokens.next() {
+        None => return Err(UciError::MissingValue("bestmove")),
+        Some("0000" | "(none)") => None,
+        Some(literal) => Some(parse_move(literal)?),
+    };
+
+    let ponder = match tokens.next() {
+        Some("ponder") => {
+            let literal = tokens.next().ok_or(UciError::MissingValue("ponder"))?;
+            Some(parse_move(literal)?)
+        }
+        _ => None,
+    };
+
+    Ok(BestMove { best, ponder })
+}
+
+/// Parses the fields of an `info` line, e.g. `depth 20 score cp 35 pv e2e4 e7e5`.
+fn parse_info(mut tokens: SplitWhitespace) -> Result<Info, UciError> {
+    let mut info = Info::default();
+
+    while let Some(keyword) = tokens.next() {
+        match keyword {
+            "depth" => info.depth = Some(value(&mut tokens, "depth")?),
+            "seldepth" => info.seldepth = Some(value(&mut tokens, "seldepth")?),
+            "multipv" => info.multipv = Some(value(&mut tokens, "multipv")?),
+            "nodes" => info.nodes = Some(value(&mut tokens, "nodes")?),
+            "nps" => info.nps = Some(value(&mut tokens, "nps")?),
+            "time" => info.time = Some(value(&mut tokens, "time")?),
+            "hashfull" => info.hashfull = Some(value(&mut tokens, "hashfull")?),
+            "tbhits" => info.tbhits = Some(value(&mut tokens, "tbhits")?),
+            "currmovenumber" => info.currmovenumber = Some(value(&mut tokens, "currmovenumber")?),
+            "currmove" => {
+                let literal = tokens.next().ok_or(UciError::MissingValue("currmove"))?;
+                info.currmove = Some(parse_move(literal)?);
+            }
+            "score" => {
+                info.score = Some(match tokens.next() {
+                    Some("cp") => Score::Centipawns(value(&mut tokens, "score cp")?),
+                    Some("mate") => Score::Mate(value(&mut tokens, "score mate")?),
+                    _ => return Err(UciError::MissingValue("score")),
+                });
+
+                info.bound = match tokens.clone().next() {
+                    Some("lowerbound") => Some(ScoreBound::Lower),
+                    Some("upperbound") => Some(ScoreBound::Upper),
+                    _ => None,
+                };
+
+                if info.bound.is_some() {
+                    tokens.next();
+                }
+            }
+            "pv" => {
+                info.pv = join(&mut tokens, &INFO_KEYWORDS)
+                    .split_whitespace()
+                    .map(parse_move)
+                    .collect::<Result<_, _>>()?;
+            }
+            "string" => info.string = Some(join(&mut tokens, &[])),
+            // the values of unknown fields are skipped along with them
+            _ => (),
+        }
+    }
+
+    Ok(info)
+}
+
+/// Parses the fields of an `option` line, e.g. `name Hash type spin default 16 min 1 max 1024`.
+fn parse_option(mut tokens: SplitWhitespace) -> Result<EngineOption, UciError> {
+    let mut name = None;
+    let mut kind = None;
+    let mut default = None;
+    let mut min = None;
+    let mut max = None;
+    let mut vars = Vec::new();
+
+    while let Some(keyword) = tokens.next() {
+        let field = join(&mut tokens, &OPTION_KEYWORDS);
+        match keyword {
+            "name" => name = Some(field),
+            "type" => kind = Some(field),
+            "default" => default = Some(field),
+            "min" => min = Some(field),
+            "max" => max = Some(field),
+            "var" => vars.push(field),
+            _ => {
+                return Err(UciError::InvalidValue {
+                    field: "option",
+                    value: keyword.to_string(),
+                })
+            }
+        }
+    }
+
+    let name = name.ok_or(UciError::MissingValue("option name"))?;
+    let default = default.map(|value| match value.as_str() {
+        "<empty>" => String::new(),
+        _ => value,
+    });
+    let integer = |value: Option<String>, field| {
+        let value = value.ok_or(UciError::MissingValue(field))?;
+        value
+            .parse()
+            .map_err(|_| UciError::InvalidValue { field, value })
+    };
+
+    let kind = match kind.as_deref() {
+        Some("check") => OptionKind::Check {
+            default: match default.as_deref() {
+                Some("true") => true,
+                Some("false") => false,
+                _ => {
+                    return Err(UciError::InvalidValue {
+                        field: "option default",
+                        value: default.unwrap_or_default(),
+                    })
+                }
+            },
+        },
+        Some("spin") => OptionKind::Spin {
+            default: integer(default, "option default")?,
+            min: integer(min, "option min")?,
+            max: integer(max, "option max")?,
+        },
+        Some("combo") => OptionKind::Combo {
+            default: default.ok_or(UciError::MissingValue("option default"))?,
+            vars,
+        },
+        Some("button") => OptionKind::Button,
+        Some("string") => OptionKind::String {
+            default: default.unwrap_or_default(),
+        },
+        Some(other) => {
+            return Err(UciError::InvalidValue {
+                field: "option type",
+                value: other.to_string(),
+            })
+        }
+        None => return Err(UciError::MissingValue("option type")),
+    };
+
+    Ok(EngineOption { name, kind })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn moves_are_parsed_in_coordinate_notation() {
+        let promotion = parse_move("e7e8n").unwrap();
+        assert_eq!(promotion.to_string(), "e7e8n");
+        assert_eq!(parse_move("e2e4").unwrap().to_string(), "e2e4");
+
+        for invalid in ["", "e2", "e2e9", "e7e8k", "e2e4q5", "0000"] {
+            assert_eq!(
+                parse_move(invalid),
+                Err(UciError::InvalidMove(invalid.to_string()))
+            );
+        }
+    }
+
+    #[test]
+    fn position_commands_are_replayed() {
+        use crate::standard::Board;
+
+        let board = |fen: &str| Fen::try_from(fen).unwrap().to_standard_board();
+        let game = parse_position("position startpos moves e2e4 e7e5 g1f3").unwrap();
+        assert_eq!(game.start(), &Board::default());
+        assert_eq!(game.moves().len(), 3);
+        assert_eq!(
+            game.board(),
+            &board("rnbqkbnr/pppp1ppp/8/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R b KQkq - 1 2")
+        );
+
+        let castles =
+            parse_position("position fen r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 120 80 moves e1g1")
+                .unwrap();
+        assert_eq!(
+            castles.board(),
+            &board("r3k2r/8/8/8/8/8/8/R4RK1 b kq - 1 80")
+        );
+        assert!(parse_position("position startpos")
+            .unwrap()
+            .moves()
+            .is_empty());
+
+        assert_eq!(
+            parse_position("position startpos moves e2e4 e2e4"),
+            Err(UciError::IllegalMove {
+                ply: 1,
+                literal: "e2e4".to_string()
+            })
+        );
+        assert!(matches!(
+            parse_position("position fen 8/8/8/8/8/8/8/8 w - - 0 1"),
+            Err(UciError::InvalidFen { .. })
+        ));
+        assert_eq!(
+            parse_position("go infinite"),
+            Err(UciError::UnknownCommand("go".to_string()))
+        );
+    }
+
+    #[test]
+    fn go_commands_are_parsed_and_formatted() {
+        let params: GoParams = "go wtime 300000 btime 295000 winc 2000 binc 2000 movestogo 40"
+            .parse()
+            .unwrap();
+        assert_eq!(params.wtime, Some(300000));
+        assert_eq!(params.btime, Some(295000));
+        assert_eq!(params.winc, Some(2000));
+        assert_eq!(params.movestogo, Some(40));
+        assert!(!params.infinite);
+        assert_eq!(
+            params.to_string(),
+            "go wtime 300000 btime 295000 winc 2000 binc 2000 movestogo 40"
+        );
+
+        let params = GoParams {
+            searchmoves: vec![parse_move("e2e4").unwrap(), parse_move("d2d4").unwrap()],
+            depth: Some(12),
+            infinite: true,
+            ..GoParams::default()
+        };
+        assert_eq!(
+            params.to_string(),
+            "go searchmoves e2e4 d2d4 depth 12 infinite"
+        );
+        assert_eq!(params.to_string().parse(), Ok(params));
+        assert_eq!("go".parse(), Ok(GoParams::default()));
+
+        assert_eq!(
+            "go movetime soon".parse::<GoParams>(),
+            Err(UciError::InvalidValue {
+                field: "movetime",
+                value: "soon".to_string()
+            })
+        );
+        assert_eq!(
+            "go fast".parse::<GoParams>(),
+            Err(UciError::InvalidValue {
+                field: "go",
+                value: "fast".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn engine_messages_are_formatted() {
+        for line in [
+            "id name konig 0.1",
+            "uciok",
+            "bestmove e7e8q ponder a2a1",
+            "bestmove 0000",
+            "info depth 12 seldepth 20 multipv 2 score mate -4 lowerbound nodes 100 nps 50 \
+             hashfull 7 tbhits 1 time 2000 currmove e2e4 currmovenumber 1 pv e2e4 e7e5 string hi",
+            "option name Hash type spin default 16 min 1 max 1024",
+            "option name Style type combo default Normal var Solid var Normal",
+            "option name SyzygyPath type string default <empty>",
+            "option name Clear Hash type button",
+            "option name Ponder type check default true",
+        ] {
+            let message: EngineMessage = line.parse().unwrap();
+            assert_eq!(message.to_string(), line);
+        }
+    }
+
+    #[test]
+    fn info_lines_are_parsed() {
+        let line = "info depth 22 seldepth 31 multipv 1 score cp -35 upperbound nodes 1405382 \
+                    nps 1250339 hashfull 512 tbhits 0 time 1124 pv e2e4 e7e5 g1f3";
+        let Ok(EngineMessage::Info(info)) = line.parse() else {
+            panic!("expected an info line");
+        };
+
+        assert_eq!(info.depth, Some(22));
+        assert_eq!(info.seldepth, Some(31));
+        assert_eq!(info.multipv, Some(1));
+        assert_eq!(info.score, Some(Score::Centipawns(-35)));
+        assert_eq!(info.bound, Some(ScoreBound::Upper));
+        assert_eq!(info.nodes, Some(1405382));
+        assert_eq!(info.nps, Some(1250339));
+        assert_eq!(info.time, Some(1124));
+        assert_eq!(
+            info.pv,
+            [
+                parse_move("e2e4").unwrap(),
+                parse_move("e7e5").unwrap(),
+                parse_move("g1f3").unwrap()
+            ]
+        );
+
+        // unknown fields are skipped, and the string takes up the rest of the line
+        let line = "info depth 5 wdl 300 600 100 score mate -3 string mate found, pv follows";
+        let Ok(EngineMessage::Info(info)) = line.parse() else {
+            panic!("expected an info line");
+        };
+        assert_eq!(info.score, Some(Score::Mate(-3)));
+        assert_eq!(info.string.as_deref(), Some("mate found, pv follows"));
+        assert!(info.pv.is_empty());
+
+        assert_eq!(
+            "info depth deep".parse::<EngineMessage>(),
+            Err(UciError::InvalidValue {
+                field: "depth",
+                value: "deep".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn best_moves_and_handshakes_are_parsed() {
+        assert_eq!(
+            "bestmove e2e4 ponder e7e5".parse(),
+            Ok(EngineMessage::BestMove(BestMove {
+                best: Some(parse_move("e2e4").unwrap()),
+                ponder: Some(parse_move("e7e5").unwrap()),
+            }))
+        );
+        assert_eq!(
+            "bestmove 0000".parse(),
+            Ok(EngineMessage::BestMove(BestMove {
+                best: None,
+                ponder: None,
+            }))
+        );
+        assert_eq!(
+            "id name Stockfish 16".parse(),
+            Ok(EngineMessage::Name("Stockfish 16".to_string()))
+        );
+        assert_eq!("  uciok ".parse(), Ok(EngineMessage::UciOk));
+        assert_eq!("readyok".parse(), Ok(EngineMessage::ReadyOk));
+        assert_eq!("".parse::<EngineMessage>(), Err(UciError::Empty));
+        assert_eq!(
+            "go".parse::<EngineMessage>(),
+            Err(UciError::UnknownCommand("go".to_string()))
+        );
+    }
+
+    #[test]
+    fn options_are_parsed() {
+        let option = |line: &str| match line.parse() {
+            Ok(EngineMessage::Option(option)) => option,
+            other => panic!("expected an option, got {other:?}"),
+        };
+
+        assert_eq!(
+            option("option name Hash type spin default 16 min 1 max 33554432"),
+            EngineOption {
+                name: "Hash".to_string(),
+                kind: OptionKind::Spin {
+                    default: 16,
+                    min: 1,
+                    max: 33554432
+                },
+            }
+        );
+        assert_eq!(
+            option("option name UCI_AnalyseMode type check default false").kind,
+            OptionKind::Check { default: false }
+        );
+        assert_eq!(
+            option("option name Clear Hash type button"),
+            EngineOption {
+                name: "Clear Hash".to_string(),
+                kind: OptionKind::Button,
+            }
+        );
+        assert_eq!(
+            option("option name SyzygyPath type string default <empty>").kind,
+            OptionKind::String {
+                default: String::new()
+            }
+        );
+        assert_eq!(
+            option("option name Style type combo default Normal var Solid var Normal var Risky")
+                .kind,
+            OptionKind::Combo {
+                default: "Normal".to_string(),
+                vars: vec![
+                    "Solid".to_string(),
+                    "Normal".to_string(),
+                    "Risky".to_string()
+                ],
+            }
+        );
+
+        assert_eq!(
+            "option name Hash type spin default 16".parse::<EngineMessage>(),
+            Err(UciError::MissingValue("option min"))
+        );
+    }
+}
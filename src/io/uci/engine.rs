@@ -0,0 +1,278 @@
+use std::io::{self, BufRead, Write};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{mpsc, Arc};
+
+use super::{parse_position, BestMove, EngineMessage, EngineOption, GoParams, Info};
+use crate::standard::Game;
+
+/// A chess engine which can be driven over UCI by [`run`].
+///
+/// Only [`UciEngine::set_position`] and [`UciEngine::search`] must be implemented;
+/// the driver takes care of the protocol, so an engine never sees UCI text.
+pub trait UciEngine {
+    /// Returns the name of the engine, as sent in `id name`.
+    fn name(&self) -> &str;
+
+    /// Returns the author of the engine, as sent in `id author`.
+    fn author(&self) -> &str {
+        "unknown"
+    }
+
+    /// Returns the options which the GUI may set with `setoption`.
+    fn options(&self) -> Vec<EngineOption> {
+        Vec::new()
+    }
+
+    /// Sets the option called `name` to `value`, which is `None` for buttons.
+    fn set_option(&mut self, _name: &str, _value: Option<&str>) {}
+
+    /// Prepares for a new game, e.g. by clearing the hash table.
+    fn new_game(&mut self) {}
+
+    /// Sets the position to be searched, along with the moves leading to it.
+    fn set_position(&mut self, game: Game);
+
+    /// Searches the current position within the limits of `params`.
+    ///
+    /// The search should send its progress with [`SearchContext::info`], and return
+    /// as soon as [`SearchContext::is_stopped`] is `true`, which happens when the GUI
+    /// sends `stop` or `quit`.
+    fn search(&mut self, params: &GoParams, context: &mut SearchContext<'_>) -> BestMove;
+}
+
+/// The connection to the GUI during a [`UciEngine::search`].
+pub struct SearchContext<'a> {
+    /// The number of `stop` and `quit` commands read so far.
+    stops: &'a AtomicUsize,
+    /// The number of those commands which were handled before the search started.
+    handled: usize,
+    output: &'a mut dyn Write,
+    error: Option<io::Error>,
+}
+
+impl SearchContext<'_> {
+    /// Returns `true` if the GUI has asked the search to stop.
+    pub fn is_stopped(&self) -> bool {
+        self.stops.load(Ordering::Relaxed) > self.handled
+    }
+
+    /// Sends `info` to the GUI.
+    ///
+    /// Errors are reported by [`run`] once the search returns.
+    pub fn info(&mut self, info: Info) {
+        if self.error.is_none() {
+            self.error = send(self.output, EngineMessage::Info(info)).err();
+        }
+    }
+}
+
+/// Drives `engine` with the UCI commands read from `input`, writing its replies to
+/// `output`, until `quit` is received or `input` ends.
+///
+/// The input is read on a separate thread, so that `stop` can interrupt a search;
+/// any other commands are handled once the search has returned. Unknown commands
+/// are ignored, as UCI requires, and invalid `position` commands are reported with
+/// `info string`.
+pub fn run<E, R, W>(engine: &mut E, input: R, mut output: W) -> io::Result<()>
+where
+    E: UciEngine + ?Sized,
+    R: BufRead + Send + 'static,
+    W: Write,
+{
+    // a search is stopped by any `stop` or `quit` read after its `go`, which the
+    // reader counts before the command reaches the loop below
+    let stops = Arc::new(AtomicUsize::new(0));
+    let mut handled = 0;
+    let (sender, receiver) = mpsc::channel();
+
+    let reader_stops = Arc::clone(&stops);
+    std::thread::spawn(move || {
+        for line in input.lines() {
+            let Ok(line) = line else { break };
+            if matches!(line.trim(), "stop" | "quit") {
+                reader_stops.fetch_add(1, Ordering::Relaxed);
+            }
+
+            if sender.send(line).is_err() {
+                break;
+            }
+        }
+    });
+
+    for line in receiver {
+        let (command, arguments) = line
+            .trim()
+            .split_once(char::is_whitespace)
+            .unwrap_or((line.trim(), ""));
+
+        match command {
+            "uci" => {
+                send(&mut output, EngineMessage::Name(engine.name().to_string()))?;
+                send(
+                    &mut output,
+                    EngineMessage::Author(engine.author().to_string()),
+                )?;
+                for option in engine.options() {
+                    send(&mut output, EngineMessage::Option(option))?;
+                }
+                send(&mut output, EngineMessage::UciOk)?;
+            }
+            "isready" => send(&mut output, EngineMessage::ReadyOk)?,
+            "ucinewgame" => engine.new_game(),
+            "setoption" => {
+                let arguments = arguments.trim_start();
+                if let Some(arguments) = arguments.strip_prefix("name ") {
+                    match arguments.split_once(" value ") {
+                        Some((name, value)) => engine.set_option(name.trim(), Some(value.trim())),
+                        None => engine.set_option(arguments.trim(), None),
+                    }
+                }
+            }
+            "position" => match parse_position(&line) {
+                Ok(game) => engine.set_position(game),
+                Err(err) => send(
+                    &mut output,
+                    EngineMessage::Info(Info {
+                        string: Some(err.to_string()),
+                        ..Info::default()
+                    }),
+                )?,
+            },
+            "go" => {
+                let params = line.parse::<GoParams>().unwrap_or_default();
+                let mut context = SearchContext {
+                    stops: &stops,
+                    handled,
+                    output: &mut output,
+                    error: None,
+                };
+                let best_move = engine.search(&params, &mut context);
+                if let Some(err) = context.error {
+                    return Err(err);
+                }
+
+                send(&mut output, EngineMessage::BestMove(best_move))?;
+            }
+            "stop" => handled += 1,
+            "quit" => break,
+            // `ponderhit` has no effect outside of a search
+            _ => (),
+        }
+    }
+
+    Ok(())
+}
+
+/// Drives `engine` over the standard input and output, as a UCI engine binary does.
+pub fn run_stdio<E: UciEngine + ?Sized>(engine: &mut E) -> io::Result<()> {
+    run(engine, io::BufReader::new(io::stdin()), io::stdout())
+}
+
+/// Writes `message` to `output` as a line, flushing it so that the GUI receives it.
+fn send(output: &mut dyn Write, message: EngineMessage) -> io::Result<()> {
+    writeln!(output, "{message}")?;
+    output.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::uci::{OptionKind, Score};
+    use crate::standard::Move;
+
+    /// Plays the first legal move, reporting a fixed score, and waits for `stop`
+    /// before replying to `go infinite`.
+    #[derive(Default)]
+    struct FirstMove {
+        game: Game,
+        hash: Option<String>,
+        new_games: usize,
+    }
+
+    impl UciEngine for FirstMove {
+        fn name(&self) -> &str {
+            "first move"
+        }
+
+        fn options(&self) -> Vec<EngineOption> {
+            vec![EngineOption {
+                name: "Hash".to_string(),
+                kind: OptionKind::Spin {
+                    default: 16,
+                    min: 1,
+                    max: 64,
+                },
+            }]
+        }
+
+        fn set_option(&mut self, name: &str, value: Option<&str>) {
+            if name == "Hash" {
+                self.hash = value.map(str::to_string);
+            }
+        }
+
+        fn new_game(&mut self) {
+            self.new_games += 1;
+        }
+
+        fn set_position(&mut self, game: Game) {
+            self.game = game;
+        }
+
+        fn search(&mut self, params: &GoParams, context: &mut SearchContext<'_>) -> BestMove {
+            while params.infinite && !context.is_stopped() {
+                std::thread::yield_now();
+            }
+
+            let best = self.game.board().legal_moves().next().map(Move::from);
+            context.info(Info {
+                depth: params.depth,
+                score: Some(Score::Centipawns(0)),
+                pv: best.into_iter().collect(),
+                ..Info::default()
+            });
+
+            BestMove { best, ponder: None }
+        }
+    }
+
+    #[test]
+    fn engines_are_driven_over_uci() {
+        let input = "uci\n\
+                     setoption name Hash value 32\n\
+                     isready\n\
+                     ucinewgame\n\
+                     position startpos moves e2e4 e7e5\n\
+                     go depth 3\n\
+                     position startpos moves e2e5\n\
+                     position fen 7k/5Q2/6K1/8/8/8/8/8 b - - 0 1\n\
+                     go infinite\n\
+                     quit\n\
+                     isready\n";
+
+        let mut engine = FirstMove::default();
+        let mut output = Vec::new();
+        run(&mut engine, io::Cursor::new(input), &mut output).unwrap();
+
+        let output = String::from_utf8(output).unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(
+            lines,
+            [
+                "id name first move",
+                "id author unknown",
+                "option name Hash type spin default 16 min 1 max 64",
+                "uciok",
+                "readyok",
+                "info depth 3 score cp 0 pv b1a3",
+                "bestmove b1a3",
+                "info string illegal move e2e5 at ply 0",
+                "info score cp 0",
+                "bestmove 0000",
+            ]
+        );
+
+        assert_eq!(engine.hash.as_deref(), Some("32"));
+        assert_eq!(engine.new_games, 1);
+    }
+}
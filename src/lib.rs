@@ -9,5 +9,10 @@
 #![feature(slice_flatten)]
 
 pub mod core;
+mod error;
 pub mod io;
 pub mod standard;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+pub use error::Error;
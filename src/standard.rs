@@ -1,6 +1,12 @@
 //! A concrete implementation of standard chess.
 
-mod bitboard;
+mod attacks;
+
+/// Defines the [`AttackMap`] of the pieces of one side of a [`Board`].
+pub mod attack_map;
+
+/// Defines a [`BitBoard`] and related concepts.
+pub mod bitboard;
 
 /// Defines a [`Board`] and related concepts.
 pub mod board;
@@ -11,14 +17,103 @@ pub mod square;
 /// Defines a [`Move`] and [`LegalMove`].
 pub mod r#move;
 
+/// Defines legal move generation for a [`Board`].
+pub mod movegen;
+
+/// Defines a fixed-capacity [`MoveList`] for move generation.
+pub mod movelist;
+
+/// Defines move ordering heuristics for the legal moves of a [`Board`].
+pub mod ordering;
+
+/// Defines perft, the counting of legal move trees, for a [`Board`].
+pub mod perft;
+
+/// Defines a [`Mailbox0x88`] board as an alternative representation to a [`Board`].
+pub mod mailbox;
+
+/// Defines the [`Material`] held by each side on a [`Board`].
+pub mod material;
+
+/// Defines the detection of dead positions on a [`Board`].
+pub mod dead;
+
+/// Defines mobility metrics for the pieces on a [`Board`].
+pub mod mobility;
+
+/// Defines [`KingSafety`] metrics for a [`Board`].
+pub mod king_safety;
+
+/// Defines NNUE input feature extraction for a [`Board`].
+pub mod nnue;
+
+/// Defines a [`PawnStructure`] analysis of a [`Board`].
+pub mod pawns;
+
+/// Defines an encoding of a [`Board`] as a stack of one-hot planes.
+pub mod planes;
+
+/// Defines the [`board!`](crate::board) macro and the [`Diagram`](diagram::Diagram) it expands to.
+pub mod diagram;
+
+/// Defines the [`MoveEffects`] of processing a move, e.g. for animating it.
+pub mod effects;
+
+/// Defines a [`Game`] and its export to PGN.
+pub mod game;
+
+/// Defines the [`Termination`] of a [`Game`] and its adjudication.
+pub mod adjudication;
+
 /// Defines a [`Piece`] and related concepts.
 pub mod piece;
 
+/// Defines random playouts of a [`Game`].
+#[cfg(feature = "rand")]
+pub mod playout;
+
+/// Defines a [`Puzzle`] and the verification of answers to it.
+pub mod puzzle;
+
+/// Defines the [`Threat`]s against the pieces on a [`Board`], and which of them are hanging.
+pub mod threats;
+
+/// Defines a common interface for probing endgame tablebases.
+pub mod tablebase;
+
+/// Defines Polyglot-compatible Zobrist hashing for a [`Board`].
+pub mod zobrist;
+
+/// Defines a [`PositionIndex`] keyed by Zobrist hashes.
+pub mod position_index;
+
+pub use adjudication::Termination;
+pub use attack_map::AttackMap;
+pub use bitboard::BitBoard;
+pub use board::ApplyMovesError;
 pub use board::Board;
 pub use board::CastlingPermissions;
+pub use board::CastlingRight;
+pub use board::SetupError;
+pub use effects::MoveEffects;
+pub use game::{Game, Outcome};
+pub use king_safety::KingSafety;
+pub use mailbox::Mailbox0x88;
+pub use material::Material;
+pub use material::MaterialSignature;
+pub use movelist::MoveList;
+pub use pawns::PawnStructure;
 pub use piece::Color;
+pub use piece::ParsePieceError;
 pub use piece::Piece;
 pub use piece::PieceKind;
+pub use position_index::PositionIndex;
+pub use puzzle::Puzzle;
 pub use r#move::LegalMove;
 pub use r#move::Move;
+pub use r#move::MoveKind;
+pub use square::File;
+pub use square::Rank;
 pub use square::Square;
+pub use square::SquareColor;
+pub use threats::{Threat, ThreatKind};
@@ -0,0 +1,257 @@
+use super::{
+    board::Board,
+    game::{Game, Outcome},
+    piece::Color,
+    tablebase::{ProbeError, Tablebase, Wdl},
+};
+use crate::core::Standard as _;
+
+/// The reasons for which a [`Game`] may end, either on the board or by
+/// the adjudication of a tournament director.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Termination {
+    /// The side to move is checkmated.
+    Checkmate {
+        /// The side which delivered checkmate.
+        winner: Color,
+    },
+    /// The side to move has no legal moves but is not in check.
+    Stalemate,
+    /// Neither side can checkmate by any sequence of legal moves.
+    DeadPosition,
+    /// The current position has occurred the given number of times.
+    Repetition(usize),
+    /// The game has reached its limit of moves without ending.
+    MoveLimit,
+    /// A [`Tablebase`] knows the value of the current position.
+    Tablebase {
+        /// The side which wins with perfect play, or `None` if the position is drawn.
+        winner: Option<Color>,
+    },
+    /// One side has been ahead on evaluation for long enough to be declared the winner.
+    Evaluation {
+        /// The side which was ahead.
+        winner: Color,
+    },
+}
+
+impl Termination {
+    /// Returns the side which won, or `None` if the game was drawn.
+    pub fn winner(&self) -> Option<Color> {
+        match *self {
+            Self::Checkmate { winner } | Self::Evaluation { winner } => Some(winner),
+            Self::Tablebase { winner } => winner,
+            Self::Stalemate | Self::DeadPosition | Self::Repetition(_) | Self::MoveLimit => None,
+        }
+    }
+
+    /// Returns the PGN result token of the termination, e.g. `"1-0"`.
+    pub fn result(&self) -> &'static str {
+        match self.winner() {
+            Some(Color::White) => "1-0",
+            Some(Color::Black) => "0-1",
+            None => "1/2-1/2",
+        }
+    }
+}
+
+impl From<Outcome> for Termination {
+    fn from(value: Outcome) -> Self {
+        match value {
+            Outcome::Checkmate { winner } => Self::Checkmate { winner },
+            Outcome::Stalemate => Self::Stalemate,
+        }
+    }
+}
+
+impl Game {
+    /// Returns the [`Termination`] of the game if it has ended on the board,
+    /// by checkmate, stalemate, or a [dead position](Board::is_dead_position).
+    pub fn termination(&self) -> Option<Termination> {
+        match self.outcome() {
+            Some(outcome) => Some(outcome.into()),
+            None => self
+                .board()
+                .is_dead_position()
+                .then_some(Termination::DeadPosition),
+        }
+    }
+
+    /// Returns the number of times the current position has occurred in the game,
+    /// compared as by [`Board::same_position`]; this is 1 if it has not been repeated.
+    pub fn repetitions(&self) -> usize {
+        self.positions()
+            .filter(|board| board.same_position(self.board()))
+            .count()
+    }
+
+    /// Declares a draw if the current position has occurred at least `count` times,
+    /// e.g. 3 under the usual rule, or 5 for the automatic draw of FIDE Article 9.6.1.
+    pub fn adjudicate_repetition(&self, count: usize) -> Option<Termination> {
+        let repetitions = self.repetitions();
+        (repetitions >= count).then_some(Termination::Repetition(repetitions))
+    }
+
+    /// Declares a draw if at least `max_plies` moves have been played.
+    pub fn adjudicate_move_limit(&self, max_plies: usize) -> Option<Termination> {
+        (self.moves().len() >= max_plies).then_some(Termination::MoveLimit)
+    }
+
+    /// Declares the result which `tablebase` gives for the current position.
+    ///
+    /// Wins which the fifty-move rule turns into draws are declared draws. If the
+    /// position cannot be probed, e.g. because it has too many pieces, the
+    /// [`ProbeError`] is returned and the game should go on.
+    pub fn adjudicate_tablebase<T: Tablebase + ?Sized>(
+        &self,
+        tablebase: &T,
+    ) -> Result<Termination, ProbeError> {
+        let side = self.board().side_to_move();
+        let winner = match tablebase.probe_wdl(self.board())? {
+            Wdl::Win => Some(side),
+            Wdl::Loss => Some(side.opposite()),
+            Wdl::CursedWin | Wdl::Draw | Wdl::BlessedLoss => None,
+        };
+
+        Ok(Termination::Tablebase { winner })
+    }
+
+    /// Declares a win for the side which `evaluate` has put ahead by at least
+    /// `threshold` after each of the last `moves` moves.
+    ///
+    /// The evaluation is called on the position after each of those moves, and
+    /// should score it from the perspective of the side to move, as engines do.
+    /// Nothing is declared before `moves` moves have been played, or if `moves` is 0.
+    pub fn adjudicate_evaluation<F: FnMut(&Board) -> i32>(
+        &self,
+        mut evaluate: F,
+        threshold: i32,
+        moves: usize,
+    ) -> Option<Termination> {
+        if moves == 0 || self.moves().len() < moves {
+            return None;
+        }
+
+        // scores are taken from white's perspective, so that they can be compared
+        let start = self.moves().len() - moves;
+        let mut scores =
+            self.annotated_moves()
+                .skip(start)
+                .map(|(_, board)| match board.side_to_move() {
+                    Color::White => evaluate(&board),
+                    Color::Black => -evaluate(&board),
+                });
+
+        let first = scores.next()?;
+        let winner = match first {
+            score if score >= threshold => Color::White,
+            score if score <= -threshold => Color::Black,
+            _ => return None,
+        };
+
+        scores
+            .all(|score| match winner {
+                Color::White => score >= threshold,
+                Color::Black => score <= -threshold,
+            })
+            .then_some(Termination::Evaluation { winner })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::Fen;
+    use crate::standard::r#move::mv;
+    use crate::standard::tablebase::retrograde;
+
+    fn game(fen: &str) -> Game {
+        Game::from_board(Fen::try_from(fen).unwrap().to_standard_board())
+    }
+
+    #[test]
+    fn repetitions_and_move_limits_are_adjudicated() {
+        let mut game = Game::new();
+        assert_eq!(game.repetitions(), 1);
+
+        for _ in 0..2 {
+            for (source, target) in [("g1", "f3"), ("g8", "f6"), ("f3", "g1"), ("f6", "g8")] {
+                game.play(mv(source, target)).unwrap();
+            }
+        }
+
+        assert_eq!(game.repetitions(), 3);
+        assert_eq!(
+            game.adjudicate_repetition(3),
+            Some(Termination::Repetition(3))
+        );
+        assert_eq!(game.adjudicate_repetition(5), None);
+
+        assert_eq!(game.adjudicate_move_limit(8), Some(Termination::MoveLimit));
+        assert_eq!(game.adjudicate_move_limit(9), None);
+        assert_eq!(Termination::MoveLimit.result(), "1/2-1/2");
+        assert_eq!(game.termination(), None);
+    }
+
+    #[test]
+    fn terminations_on_the_board_include_dead_positions() {
+        let mut fools_mate = Game::new();
+        for (source, target) in [("f2", "f3"), ("e7", "e5"), ("g2", "g4"), ("d8", "h4")] {
+            fools_mate.play(mv(source, target)).unwrap();
+        }
+
+        let termination = fools_mate.termination().unwrap();
+        assert_eq!(termination.winner(), Some(Color::Black));
+        assert_eq!(termination.result(), fools_mate.result());
+
+        let bare = game("4k3/8/8/8/8/8/8/4KB2 w - - 0 1");
+        assert_eq!(bare.termination(), Some(Termination::DeadPosition));
+    }
+
+    #[test]
+    fn tablebase_results_are_adjudicated() {
+        let tablebase = retrograde::RetrogradeTablebase::generate(&[retrograde::Ending::KQvK]);
+
+        let won = game("8/8/8/4k3/8/8/8/KQ6 b - - 0 1");
+        assert_eq!(
+            won.adjudicate_tablebase(&tablebase).unwrap(),
+            Termination::Tablebase {
+                winner: Some(Color::White)
+            }
+        );
+
+        // the queen is lost at once
+        let drawn = game("8/8/8/8/8/8/1k6/2Q4K b - - 0 1");
+        assert_eq!(
+            drawn.adjudicate_tablebase(&tablebase).unwrap(),
+            Termination::Tablebase { winner: None }
+        );
+
+        assert!(Game::new().adjudicate_tablebase(&tablebase).is_err());
+    }
+
+    #[test]
+    fn evaluations_are_adjudicated_over_consecutive_moves() {
+        let material = |board: &Board| {
+            let side = board.side_to_move();
+            i32::from(board.material(side).value())
+                - i32::from(board.material(side.opposite()).value())
+        };
+
+        // white wins a knight, and keeps it for the following moves
+        let mut game = game("4k3/8/8/3n4/8/8/8/3RK3 w - - 0 1");
+        assert_eq!(game.adjudicate_evaluation(material, 3, 1), None);
+
+        for (source, target) in [("d1", "d5"), ("e8", "e7"), ("d5", "d1")] {
+            game.play(mv(source, target)).unwrap();
+        }
+
+        let white = Some(Termination::Evaluation {
+            winner: Color::White,
+        });
+        assert_eq!(game.adjudicate_evaluation(material, 5, 3), white);
+        assert_eq!(game.adjudicate_evaluation(material, 6, 3), None);
+        assert_eq!(game.adjudicate_evaluation(material, 5, 4), None);
+        assert_eq!(game.adjudicate_evaluation(material, 5, 0), None);
+    }
+}
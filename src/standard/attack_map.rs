@@ -0,0 +1,112 @@
+use super::{attacks, bitboard::BitBoard, board::Board, piece::Color, Square};
+use crate::core::Piece as _;
+
+/// The squares attacked by each piece of one side of a [`Board`], and the
+/// pieces of that side attacking each square.
+///
+/// A piece attacks a square if it could capture an enemy piece there, which
+/// for pawns means only their diagonal captures. Squares holding friendly pieces
+/// are attacked too, in the sense that those pieces are defended; pins and
+/// checks are ignored, as are the pieces which would attack a square only
+/// once another has captured on it (x-rays).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AttackMap {
+    color: Color,
+    attacks: [BitBoard; 64],
+    attackers: [BitBoard; 64],
+}
+
+impl AttackMap {
+    /// Returns the color whose pieces are described.
+    pub fn color(&self) -> Color {
+        self.color
+    }
+
+    /// Returns the squares attacked by the piece on `square`, which are
+    /// empty if it does not hold a piece of [`AttackMap::color`].
+    pub fn attacks_from(&self, square: Square) -> BitBoard {
+        self.attacks[usize::from(square)]
+    }
+
+    /// Returns the squares of the pieces attacking `square`.
+    ///
+    /// If `square` holds a piece of [`AttackMap::color`], these are its defenders.
+    pub fn attackers_of(&self, square: Square) -> BitBoard {
+        self.attackers[usize::from(square)]
+    }
+
+    /// Returns the number of pieces attacking `square`.
+    pub fn count(&self, square: Square) -> u32 {
+        self.attackers_of(square).count()
+    }
+
+    /// Returns every square attacked by at least one piece.
+    pub fn attacked(&self) -> BitBoard {
+        self.attacks
+            .iter()
+            .fold(BitBoard::EMPTY, |attacked, attacks| attacked | attacks)
+    }
+
+    /// Returns the squares of the pieces of [`AttackMap::color`] which at least one piece defends.
+    pub fn defended(&self, board: &Board) -> BitBoard {
+        self.attacked() & board.occupancy(self.color)
+    }
+}
+
+impl Board {
+    /// Returns the [`AttackMap`] of the pieces of the given `color`.
+    pub fn attack_map(&self, color: Color) -> AttackMap {
+        let mut map = AttackMap {
+            color,
+            attacks: [BitBoard::EMPTY; 64],
+            attackers: [BitBoard::EMPTY; 64],
+        };
+
+        let occupied = self.occupied();
+        for source in self.occupancy(color).squares() {
+            let Some(piece) = self[source] else {
+                continue;
+            };
+
+            let targets = attacks::attacks(color, piece.kind(), source, occupied);
+            map.attacks[usize::from(source)] = targets;
+            for target in targets.squares() {
+                map.attackers[usize::from(target)] |= BitBoard::from_square(source);
+            }
+        }
+
+        map
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::Fen;
+
+    #[test]
+    fn attack_maps_record_attacks_and_defences() {
+        let board = Fen::try_from("4k3/8/8/3p4/4N3/8/3PB3/4K2R w K - 0 1")
+            .unwrap()
+            .to_standard_board();
+        let square = |name| Square::try_from(name).unwrap();
+        let white = board.attack_map(Color::White);
+
+        assert_eq!(white.attacks_from(square("e4")).count(), 8);
+        assert_eq!(white.attacks_from(square("d2")).count(), 2);
+        assert!(white.attacks_from(square("d5")).is_empty());
+
+        // the pawn on d2 is defended by the king and the knight, but not by the bishop
+        let defenders = BitBoard::from_square(square("e1")) | BitBoard::from_square(square("e4"));
+        assert_eq!(white.attackers_of(square("d2")), defenders);
+        assert_eq!(white.count(square("e2")), 1);
+
+        // the pawn on d5 attacks the knight on e4, which is undefended
+        let black = board.attack_map(Color::Black);
+        let attackers = black.attackers_of(square("e4"));
+        assert_eq!(attackers, BitBoard::from_square(square("d5")));
+        assert_eq!(white.count(square("e4")), 0);
+        assert!(!white.defended(&board).contains(square("e4")));
+        assert!(white.attacked().contains(square("h8")));
+    }
+}
@@ -0,0 +1,244 @@
+//! Attack generation for the standard pieces.
+//!
+//! The leaping pieces (knights, kings, and pawns) have their attacks
+//! computed by shifting a single-bit board and masking off the squares
+//! that would otherwise wrap around the edges of the board, while the
+//! sliding pieces walk outward along their rays until they leave the
+//! board or hit an occupied square. Every function here is a `const fn`,
+//! and the attacks of the leaping pieces are tabulated at compile time.
+
+use super::{
+    bitboard::BitBoard,
+    piece::{Color, PieceKind},
+    Square,
+};
+
+const FILE_A: u64 = 0x0101_0101_0101_0101;
+const FILE_B: u64 = FILE_A << 1;
+const FILE_G: u64 = FILE_A << 6;
+const FILE_H: u64 = FILE_A << 7;
+
+const ROOK_DIRECTIONS: [(i8, i8); 4] = [(0, 1), (0, -1), (1, 0), (-1, 0)];
+const BISHOP_DIRECTIONS: [(i8, i8); 4] = [(1, 1), (1, -1), (-1, 1), (-1, -1)];
+
+/// Evaluates the `const fn` named by `$mask` on each square, in order from a1 to h8.
+macro_rules! tabulate {
+    ($mask:ident) => {{
+        let mut table = [BitBoard::EMPTY; 64];
+        let mut index = 0;
+        while index < 64 {
+            table[index] = BitBoard::from_bits($mask(1 << index));
+            index += 1;
+        }
+
+        table
+    }};
+}
+
+/// The squares attacked by a knight on each square.
+static KNIGHT_ATTACKS: [BitBoard; 64] = tabulate!(knight_mask);
+
+/// The squares attacked by a king on each square.
+static KING_ATTACKS: [BitBoard; 64] = tabulate!(king_mask);
+
+/// The squares attacked by a pawn of each color on each square, white first.
+static PAWN_ATTACKS: [[BitBoard; 64]; 2] = [tabulate!(white_pawn_mask), tabulate!(black_pawn_mask)];
+
+/// Returns the squares attacked by a knight on `square`.
+pub(crate) const fn knight(square: Square) -> BitBoard {
+    KNIGHT_ATTACKS[(square.rank() * 8 + square.file()) as usize]
+}
+
+/// Returns the squares attacked by a king on `square`.
+pub(crate) const fn king(square: Square) -> BitBoard {
+    KING_ATTACKS[(square.rank() * 8 + square.file()) as usize]
+}
+
+/// Returns the squares attacked (not pushed to) by a pawn
+/// of the given `color` standing on `square`.
+pub(crate) const fn pawn(color: Color, square: Square) -> BitBoard {
+    let color = match color {
+        Color::White => 0,
+        Color::Black => 1,
+    };
+
+    PAWN_ATTACKS[color][(square.rank() * 8 + square.file()) as usize]
+}
+
+/// Returns the squares attacked by a knight on the single square set in `b`.
+const fn knight_mask(b: u64) -> u64 {
+    ((b << 17) & !FILE_A)
+        | ((b << 15) & !FILE_H)
+        | ((b << 10) & !(FILE_A | FILE_B))
+        | ((b << 6) & !(FILE_G | FILE_H))
+        | ((b >> 17) & !FILE_H)
+        | ((b >> 15) & !FILE_A)
+        | ((b >> 10) & !(FILE_G | FILE_H))
+        | ((b >> 6) & !(FILE_A | FILE_B))
+}
+
+/// Returns the squares attacked by a king on the single square set in `b`.
+const fn king_mask(b: u64) -> u64 {
+    (b << 8)
+        | (b >> 8)
+        | ((b << 1) & !FILE_A)
+        | ((b >> 1) & !FILE_H)
+        | ((b << 9) & !FILE_A)
+        | ((b << 7) & !FILE_H)
+        | ((b >> 7) & !FILE_A)
+        | ((b >> 9) & !FILE_H)
+}
+
+/// Returns the squares attacked by a white pawn on the single square set in `b`.
+const fn white_pawn_mask(b: u64) -> u64 {
+    ((b << 7) & !FILE_H) | ((b << 9) & !FILE_A)
+}
+
+/// Returns the squares attacked by a black pawn on the single square set in `b`.
+const fn black_pawn_mask(b: u64) -> u64 {
+    ((b >> 9) & !FILE_H) | ((b >> 7) & !FILE_A)
+}
+
+/// Returns the squares attacked by a rook on `square`, given
+/// the set of `occupied` squares which block its movement.
+pub(crate) const fn rook(square: Square, occupied: BitBoard) -> BitBoard {
+    slide(square, occupied, &ROOK_DIRECTIONS)
+}
+
+/// Returns the squares attacked by a bishop on `square`, given
+/// the set of `occupied` squares which block its movement.
+pub(crate) const fn bishop(square: Square, occupied: BitBoard) -> BitBoard {
+    slide(square, occupied, &BISHOP_DIRECTIONS)
+}
+
+/// Returns the squares attacked by a queen on `square`, given
+/// the set of `occupied` squares which block its movement.
+pub(crate) const fn queen(square: Square, occupied: BitBoard) -> BitBoard {
+    BitBoard::from_bits(rook(square, occupied).bits() | bishop(square, occupied).bits())
+}
+
+/// Returns the squares attacked by a piece of the given `color`
+/// and `kind` on `square`, given the set of `occupied` squares.
+pub(crate) const fn attacks(
+    color: Color,
+    kind: PieceKind,
+    square: Square,
+    occupied: BitBoard,
+) -> BitBoard {
+    match kind {
+        PieceKind::Pawn => pawn(color, square),
+        PieceKind::Knight => knight(square),
+        PieceKind::Bishop => bishop(square, occupied),
+        PieceKind::Rook => rook(square, occupied),
+        PieceKind::Queen => queen(square, occupied),
+        PieceKind::King => king(square),
+    }
+}
+
+/// Returns the squares strictly between `a` and `b` if they share a rank, file,
+/// or diagonal, and the empty set otherwise.
+pub(crate) const fn between(a: Square, b: Square) -> BitBoard {
+    let file_delta = b.file() as i8 - a.file() as i8;
+    let rank_delta = b.rank() as i8 - a.rank() as i8;
+    let is_aligned = file_delta == 0 || rank_delta == 0 || file_delta.abs() == rank_delta.abs();
+
+    if (file_delta == 0 && rank_delta == 0) || !is_aligned {
+        return BitBoard::EMPTY;
+    }
+
+    let (file_step, rank_step) = (file_delta.signum(), rank_delta.signum());
+    let (mut f, mut r) = (a.file() as i8 + file_step, a.rank() as i8 + rank_step);
+    let mut squares = 0u64;
+
+    while f != b.file() as i8 || r != b.rank() as i8 {
+        squares |= 1u64 << (r * 8 + f);
+        f += file_step;
+        r += rank_step;
+    }
+
+    BitBoard::from_bits(squares)
+}
+
+/// Walks outward from `square` along each of the given `directions`,
+/// stopping at (and including) the first occupied square on each ray.
+const fn slide(square: Square, occupied: BitBoard, directions: &[(i8, i8); 4]) -> BitBoard {
+    let file = square.file() as i8;
+    let rank = square.rank() as i8;
+    let mut attacks = 0u64;
+
+    let mut direction = 0;
+    while direction < directions.len() {
+        let (file_step, rank_step) = directions[direction];
+        let (mut f, mut r) = (file + file_step, rank + rank_step);
+        while 0 <= f && f < 8 && 0 <= r && r < 8 {
+            let bit = 1u64 << (r * 8 + f);
+            attacks |= bit;
+
+            if occupied.bits() & bit != 0 {
+                break;
+            }
+
+            f += file_step;
+            r += rank_step;
+        }
+
+        direction += 1;
+    }
+
+    BitBoard::from_bits(attacks)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn square(name: &str) -> Square {
+        Square::try_from(name).unwrap()
+    }
+
+    #[test]
+    fn leaper_attacks_do_not_wrap_around_the_board() {
+        assert_eq!(knight(square("a1")).squares().count(), 2);
+        assert_eq!(knight(square("h8")).squares().count(), 2);
+        assert_eq!(knight(square("d4")).squares().count(), 8);
+        assert_eq!(king(square("a1")).squares().count(), 3);
+        assert_eq!(king(square("e4")).squares().count(), 8);
+        assert_eq!(pawn(Color::White, square("a2")).squares().count(), 1);
+        assert!(pawn(Color::White, square("a2")).contains(square("b3")));
+        assert!(pawn(Color::Black, square("h7")).contains(square("g6")));
+    }
+
+    #[test]
+    fn attacks_are_const_evaluable() {
+        const KNIGHT_ON_E4: BitBoard = knight(Square::at(28));
+        const ROOK_ON_A1: BitBoard = rook(Square::at(0), BitBoard::EMPTY);
+        const A1_TO_H8: BitBoard = between(Square::at(0), Square::at(63));
+
+        assert_eq!(KNIGHT_ON_E4, knight(square("e4")));
+        assert_eq!(ROOK_ON_A1.count(), 14);
+        assert_eq!(A1_TO_H8.count(), 6);
+    }
+
+    #[test]
+    fn sliding_attacks_stop_at_blockers() {
+        let blockers = BitBoard::from_square(square("d6")) | BitBoard::from_square(square("f4"));
+        let attacks = rook(square("d4"), blockers);
+
+        assert!(attacks.contains(square("d5")));
+        assert!(attacks.contains(square("d6")));
+        assert!(!attacks.contains(square("d7")));
+        assert!(attacks.contains(square("f4")));
+        assert!(!attacks.contains(square("g4")));
+        assert!(attacks.contains(square("a4")));
+        assert!(attacks.contains(square("d1")));
+        assert_eq!(bishop(square("a1"), BitBoard::EMPTY).squares().count(), 7);
+    }
+
+    #[test]
+    fn between_is_exclusive_and_requires_alignment() {
+        assert_eq!(between(square("a1"), square("a4")).squares().count(), 2);
+        assert_eq!(between(square("h8"), square("b2")).squares().count(), 5);
+        assert!(between(square("e4"), square("e5")).is_empty());
+        assert!(between(square("a1"), square("b3")).is_empty());
+    }
+}
@@ -1,9 +1,103 @@
-use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Not};
+use std::ops::{
+    BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Not, Shl, ShlAssign, Shr,
+    ShrAssign,
+};
+
+use super::Square;
 
 /// A `BitBoard` wraps a `u64` to provide
 /// a nice API.
+///
+/// The default bitboard is [`BitBoard::EMPTY`]. With the `bytemuck` feature, a
+/// bitboard is plain old data with the layout of its `u64`, and so slices of them
+/// can be cast to and from bytes, e.g. when memory-mapped from a binary file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[repr(transparent)]
 pub struct BitBoard(u64);
 
+// SAFETY: `BitBoard` is a `repr(transparent)` wrapper of a `u64`,
+// and every bit pattern of a `u64` is a valid bitboard.
+#[cfg(feature = "bytemuck")]
+unsafe impl bytemuck::Zeroable for BitBoard {}
+
+// SAFETY: as above; a `u64` has no padding and no interior mutability.
+#[cfg(feature = "bytemuck")]
+unsafe impl bytemuck::Pod for BitBoard {}
+
+impl BitBoard {
+    /// The bitboard with no bits set.
+    pub const EMPTY: BitBoard = BitBoard(0);
+
+    /// The bitboard with every bit set.
+    pub const FULL: BitBoard = BitBoard(u64::MAX);
+
+    /// Constructs a bitboard directly from its underlying `u64`,
+    /// in which bit 0 is a1, bit 7 is h1, and bit 63 is h8.
+    ///
+    /// Like the other constructors, this is a `const fn`:
+    ///
+    /// ```
+    /// use konig::standard::BitBoard;
+    ///
+    /// const BACK_RANKS: BitBoard = BitBoard::from_bits(BitBoard::rank(0).bits() | BitBoard::rank(7).bits());
+    /// assert_eq!(BACK_RANKS.count(), 16);
+    /// ```
+    pub const fn from_bits(bits: u64) -> Self {
+        BitBoard(bits)
+    }
+
+    /// Constructs a bitboard with exactly one bit set, at `square`.
+    pub const fn from_square(square: Square) -> Self {
+        BitBoard(1 << (square.rank() * 8 + square.file()))
+    }
+
+    /// Constructs the bitboard of every square on the given `file`, where 0 is the a-file.
+    pub const fn file(file: u8) -> Self {
+        BitBoard(0x0101_0101_0101_0101 << file)
+    }
+
+    /// Constructs the bitboard of every square on the given `rank`, where 0 is the first rank.
+    pub const fn rank(rank: u8) -> Self {
+        BitBoard(0xff << (8 * rank))
+    }
+
+    /// Returns the underlying `u64`.
+    pub const fn bits(self) -> u64 {
+        self.0
+    }
+
+    /// Returns `true` if the bit corresponding to `square` is set.
+    pub const fn contains(self, square: Square) -> bool {
+        self.0 & Self::from_square(square).0 != 0
+    }
+
+    /// Returns `true` if no bits are set.
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Returns the number of set bits.
+    pub const fn count(self) -> u32 {
+        self.0.count_ones()
+    }
+
+    /// Returns an iterator over the squares whose bits are set,
+    /// in ascending order from a1 to h8.
+    pub fn squares(self) -> impl Iterator<Item = Square> {
+        let mut bits = self.0;
+        std::iter::from_fn(move || {
+            if bits == 0 {
+                return None;
+            }
+
+            let index = bits.trailing_zeros() as u8;
+            bits &= bits - 1; // clear the lowest set bit
+                              // trailing_zeros on a nonzero u64 is always at most 63
+            Some(unsafe { Square::new_unchecked(index) })
+        })
+    }
+}
+
 impl BitAnd for BitBoard {
     type Output = BitBoard;
 
@@ -13,6 +107,40 @@ impl BitAnd for BitBoard {
     }
 }
 
+/// Implements a binary operator `$op` of `BitBoard` for every combination of
+/// values and references, from its implementation on values.
+macro_rules! forward_ref_binop {
+    ($op:ident, $method:ident) => {
+        impl $op<&BitBoard> for BitBoard {
+            type Output = BitBoard;
+
+            fn $method(self, rhs: &BitBoard) -> Self::Output {
+                self.$method(*rhs)
+            }
+        }
+
+        impl $op<BitBoard> for &BitBoard {
+            type Output = BitBoard;
+
+            fn $method(self, rhs: BitBoard) -> Self::Output {
+                (*self).$method(rhs)
+            }
+        }
+
+        impl $op<&BitBoard> for &BitBoard {
+            type Output = BitBoard;
+
+            fn $method(self, rhs: &BitBoard) -> Self::Output {
+                (*self).$method(*rhs)
+            }
+        }
+    };
+}
+
+forward_ref_binop!(BitAnd, bitand);
+forward_ref_binop!(BitOr, bitor);
+forward_ref_binop!(BitXor, bitxor);
+
 impl BitAndAssign for BitBoard {
     fn bitand_assign(&mut self, rhs: Self) {
         self.0 &= rhs.0;
@@ -49,6 +177,50 @@ impl BitXorAssign for BitBoard {
     }
 }
 
+/// Shifts every bit towards h8 by `rhs` places, discarding those shifted off
+/// the board; shifting by 8 moves each square one rank up.
+impl Shl<u32> for BitBoard {
+    type Output = BitBoard;
+
+    fn shl(self, rhs: u32) -> Self::Output {
+        BitBoard(self.0 << rhs)
+    }
+}
+
+impl ShlAssign<u32> for BitBoard {
+    fn shl_assign(&mut self, rhs: u32) {
+        self.0 <<= rhs;
+    }
+}
+
+/// Shifts every bit towards a1 by `rhs` places, discarding those shifted off
+/// the board; shifting by 8 moves each square one rank down.
+impl Shr<u32> for BitBoard {
+    type Output = BitBoard;
+
+    fn shr(self, rhs: u32) -> Self::Output {
+        BitBoard(self.0 >> rhs)
+    }
+}
+
+impl ShrAssign<u32> for BitBoard {
+    fn shr_assign(&mut self, rhs: u32) {
+        self.0 >>= rhs;
+    }
+}
+
+impl From<u64> for BitBoard {
+    fn from(bits: u64) -> Self {
+        BitBoard(bits)
+    }
+}
+
+impl From<BitBoard> for u64 {
+    fn from(board: BitBoard) -> Self {
+        board.0
+    }
+}
+
 impl FromIterator<bool> for BitBoard {
     fn from_iter<T: IntoIterator<Item = bool>>(iter: T) -> Self {
         let mut board = 0;
@@ -85,6 +257,14 @@ impl Not for BitBoard {
     }
 }
 
+impl Not for &BitBoard {
+    type Output = BitBoard;
+
+    fn not(self) -> Self::Output {
+        BitBoard(!self.0)
+    }
+}
+
 /// An [`Iterator`] over the bits of a [`BitBoard`].
 ///
 /// Using a mask instead of an index slightly reduces
@@ -118,3 +298,47 @@ impl<'a> Iterator for BitBoardIterator<'a> {
         Some(result)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    #[test]
+    fn bitboards_behave_as_values() {
+        let first = BitBoard::rank(0);
+        let second = BitBoard::from(0xff00);
+        assert_eq!(first << 8, second);
+        assert_eq!(second >> 8, first);
+        assert_eq!(first & second, BitBoard::default());
+
+        // bits shifted off either end of the board are discarded
+        let mut shifted = BitBoard::FULL;
+        shifted <<= 56;
+        shifted >>= 60;
+        assert_eq!(shifted.bits(), 0xf);
+
+        // references combine without being dereferenced first
+        let boards = [first, second, BitBoard::file(0)];
+        let union = boards
+            .iter()
+            .fold(BitBoard::EMPTY, |union, board| union | board);
+        let flipped: Vec<BitBoard> = boards.iter().map(|board| !board).collect();
+        assert_eq!(u64::from(union), 0x0101_0101_0101_ffff);
+        assert_eq!(flipped[0], BitBoard::from(!0xff));
+
+        let distinct: HashSet<BitBoard> = boards.into_iter().chain([first]).collect();
+        assert_eq!(distinct.len(), 3);
+    }
+
+    #[cfg(feature = "bytemuck")]
+    #[test]
+    fn bitboards_are_cast_from_bytes() {
+        let words = [0xff_u64, u64::MAX];
+        let bytes: &[u8] = bytemuck::cast_slice(&words);
+        let boards: &[BitBoard] = bytemuck::cast_slice(bytes);
+        assert_eq!(boards, [BitBoard::rank(0), BitBoard::FULL]);
+        assert_eq!(bytemuck::bytes_of(&boards[0]), 0xff_u64.to_ne_bytes());
+    }
+}
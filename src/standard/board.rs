@@ -1,15 +1,74 @@
 use std::slice::ChunksExact;
 
+use thiserror::Error;
+
 use super::{
-    piece::Color,
-    r#move::{IllegalMoveError, LegalMove, Move},
-    Square,
+    attacks,
+    bitboard::BitBoard,
+    movegen,
+    piece::{Color, PieceKind},
+    r#move::{IllegalMoveError, LegalMove, Move, MoveKind},
+    zobrist, Square,
+};
+
+use crate::{
+    core,
+    core::{r#move::WrapMove, Move as _, Piece as _, Position},
+    io::{
+        fen::Fen,
+        notation::{Lan, Notation as _},
+        uci,
+    },
+    standard::piece::Piece,
 };
 
-use crate::{core, core::Position, io::fen::Fen, standard::piece::Piece};
+/// Results when a [`Board`] describes a position which could not arise in a game.
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum SetupError {
+    /// Results when a side does not have exactly one king.
+    #[error("{0} has {1} kings; expected exactly one")]
+    KingCount(Color, u32),
+    /// Results when a pawn stands on the first or eighth rank.
+    #[error("there is a pawn on the back rank at {0}")]
+    PawnOnBackRank(Square),
+    /// Results when the side which is not to move is in check.
+    #[error("the side which is not to move is in check")]
+    OpponentInCheck,
+    /// Results when the en passant target square does not lie behind
+    /// a pawn which has just moved two squares.
+    #[error("{0} is not a plausible en passant target square")]
+    InvalidEnPassantSquare(Square),
+    /// Results when a side may castle even though its king or
+    /// the relevant rook is not on its initial square.
+    #[error("{0} may castle, but its king or rook has moved")]
+    InvalidCastlingRights(Color),
+}
+
+/// Results when a move of a sequence cannot be applied with [`Board::apply_moves`].
+#[derive(Debug, Error)]
+pub enum ApplyMovesError {
+    /// Results when a move is written in neither UCI nor LAN.
+    #[error("move {ply} is not written in UCI or LAN: {literal}")]
+    InvalidNotation {
+        /// The index of the move in the sequence.
+        ply: usize,
+        /// The move as it was written.
+        literal: String,
+    },
+    /// Results when a move is illegal in the position reached by the moves before it.
+    #[error("move {ply} ({literal}) is illegal: {reason}")]
+    IllegalMove {
+        /// The index of the move in the sequence.
+        ply: usize,
+        /// The move as it was written.
+        literal: String,
+        /// Why the move is illegal.
+        reason: IllegalMoveError,
+    },
+}
 
 /// Represents the possible castling permissions described by a FEN string.
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
 pub struct CastlingPermissions {
     /// Whether or not castling on the bottom-right is allowed.
     pub white_king_side: bool,
@@ -21,6 +80,60 @@ pub struct CastlingPermissions {
     pub black_queen_side: bool,
 }
 
+/// One of the four castling rights which make up a set of [`CastlingPermissions`].
+///
+/// The rights are declared, and ordered, as they are written in FEN: `KQkq`.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
+pub enum CastlingRight {
+    /// White may castle on the king side.
+    WhiteKingSide,
+    /// White may castle on the queen side.
+    WhiteQueenSide,
+    /// Black may castle on the king side.
+    BlackKingSide,
+    /// Black may castle on the queen side.
+    BlackQueenSide,
+}
+
+impl CastlingRight {
+    /// Every castling right, in the order in which they are written in FEN.
+    pub const ALL: [CastlingRight; 4] = [
+        Self::WhiteKingSide,
+        Self::WhiteQueenSide,
+        Self::BlackKingSide,
+        Self::BlackQueenSide,
+    ];
+
+    /// Returns the color of the side holding this right.
+    pub fn color(self) -> Color {
+        match self {
+            Self::WhiteKingSide | Self::WhiteQueenSide => Color::White,
+            Self::BlackKingSide | Self::BlackQueenSide => Color::Black,
+        }
+    }
+
+    /// Returns the character representing this right in FEN, one of `KQkq`.
+    pub fn to_fen_char(self) -> char {
+        match self {
+            Self::WhiteKingSide => 'K',
+            Self::WhiteQueenSide => 'Q',
+            Self::BlackKingSide => 'k',
+            Self::BlackQueenSide => 'q',
+        }
+    }
+
+    /// Returns the right represented by `symbol` in FEN, if it is one of `KQkq`.
+    pub fn from_fen_char(symbol: char) -> Option<Self> {
+        match symbol {
+            'K' => Some(Self::WhiteKingSide),
+            'Q' => Some(Self::WhiteQueenSide),
+            'k' => Some(Self::BlackKingSide),
+            'q' => Some(Self::BlackQueenSide),
+            _ => None,
+        }
+    }
+}
+
 impl CastlingPermissions {
     /// Convienience function for the empty set of castling permissions.
     pub fn none() -> CastlingPermissions {
@@ -31,6 +144,83 @@ impl CastlingPermissions {
             black_queen_side: false,
         }
     }
+
+    /// Returns `true` if the given `right` is granted.
+    pub fn contains(&self, right: CastlingRight) -> bool {
+        match right {
+            CastlingRight::WhiteKingSide => self.white_king_side,
+            CastlingRight::WhiteQueenSide => self.white_queen_side,
+            CastlingRight::BlackKingSide => self.black_king_side,
+            CastlingRight::BlackQueenSide => self.black_queen_side,
+        }
+    }
+
+    /// Grants or revokes the given `right`.
+    pub fn set(&mut self, right: CastlingRight, granted: bool) {
+        let field = match right {
+            CastlingRight::WhiteKingSide => &mut self.white_king_side,
+            CastlingRight::WhiteQueenSide => &mut self.white_queen_side,
+            CastlingRight::BlackKingSide => &mut self.black_king_side,
+            CastlingRight::BlackQueenSide => &mut self.black_queen_side,
+        };
+
+        *field = granted;
+    }
+
+    /// Returns an iterator over the granted rights, in the order `KQkq`.
+    pub fn iter(&self) -> impl Iterator<Item = CastlingRight> + '_ {
+        CastlingRight::ALL
+            .into_iter()
+            .filter(|&right| self.contains(right))
+    }
+
+    /// Returns `true` if any right is granted.
+    pub fn any(&self) -> bool {
+        self.iter().next().is_some()
+    }
+
+    /// Returns `true` if no right is granted.
+    pub fn is_empty(&self) -> bool {
+        !self.any()
+    }
+
+    /// Parses the castling field of a FEN string: either `-`, or the characters of
+    /// the granted rights from `KQkq`, each at most once and in any order.
+    ///
+    /// ```
+    /// use konig::standard::CastlingPermissions;
+    ///
+    /// let permissions = CastlingPermissions::from_fen_chars("Kq").unwrap();
+    /// assert!(permissions.white_king_side && permissions.black_queen_side);
+    /// assert_eq!(permissions.to_fen_string(), "Kq");
+    /// assert!(CastlingPermissions::from_fen_chars("KK").is_none());
+    /// ```
+    pub fn from_fen_chars(field: &str) -> Option<Self> {
+        let mut permissions = Self::none();
+        if field == "-" {
+            return Some(permissions);
+        }
+
+        for symbol in field.chars() {
+            let right = CastlingRight::from_fen_char(symbol)?;
+            if permissions.contains(right) {
+                return None;
+            }
+
+            permissions.set(right, true);
+        }
+
+        permissions.any().then_some(permissions)
+    }
+
+    /// Returns the castling field of a FEN string describing `self`, which
+    /// lists the granted rights in the order `KQkq`, or is `-` if there are none.
+    pub fn to_fen_string(&self) -> String {
+        match self.any() {
+            true => self.iter().map(CastlingRight::to_fen_char).collect(),
+            false => String::from("-"),
+        }
+    }
 }
 
 impl Default for CastlingPermissions {
@@ -65,11 +255,80 @@ impl Default for BoardState {
     }
 }
 
+/// Redundant bitboard views of the pieces on a [`Board`], which are kept in sync
+/// with its mailbox so that occupancy and attack queries need not scan it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct BitBoards {
+    /// The squares occupied by the pieces of each color, indexed by [`color_slot`].
+    colors: [BitBoard; 2],
+    /// The squares occupied by each kind of piece of each color,
+    /// indexed by [`color_slot`] and then by [`PieceKind`].
+    pieces: [[BitBoard; 6]; 2],
+    /// The square of the king of each color, if it is on the board.
+    kings: [Option<Square>; 2],
+    /// The number of each kind of piece of each color,
+    /// indexed by [`color_slot`] and then by [`PieceKind`].
+    counts: [[u8; 6]; 2],
+}
+
+impl BitBoards {
+    /// Computes the bitboards describing the mailbox `pieces`.
+    fn from_pieces(pieces: &[Option<Piece>; 64]) -> Self {
+        let mut bitboards = Self {
+            colors: [BitBoard::EMPTY; 2],
+            pieces: [[BitBoard::EMPTY; 6]; 2],
+            kings: [None; 2],
+            counts: [[0; 6]; 2],
+        };
+
+        for (index, piece) in pieces.iter().enumerate() {
+            if let Some(piece) = *piece {
+                bitboards.toggle(piece, Square::at(index as u8));
+            }
+        }
+
+        bitboards
+    }
+
+    /// Adds `piece` on `square` if it is absent, and removes it if it is present.
+    fn toggle(&mut self, piece: Piece, square: Square) {
+        let bit = BitBoard::from_square(square);
+        let color = color_slot(piece.color());
+        let kind = piece.kind() as usize;
+        self.colors[color] ^= bit;
+        self.pieces[color][kind] ^= bit;
+
+        match self.pieces[color][kind].contains(square) {
+            true => self.counts[color][kind] += 1,
+            false => self.counts[color][kind] -= 1,
+        }
+
+        if piece.kind() == PieceKind::King {
+            self.kings[color] = self.pieces[color][kind].squares().next();
+        }
+    }
+}
+
+/// Returns the index of `color` in the arrays of a [`BitBoards`].
+fn color_slot(color: Color) -> usize {
+    match color {
+        Color::White => 0,
+        Color::Black => 1,
+    }
+}
+
 /// Represents a standard 8x8 chess board.
+///
+/// The pieces are stored both as a mailbox, for cheap lookups by square, and as
+/// bitboards, for cheap occupancy and attack queries; every change to the pieces
+/// goes through [`Board::set`], which keeps the two in sync. The [Zobrist
+/// hash](Board::zobrist) of the position is maintained alongside them.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Board {
     pieces: [Option<Piece>; 64],
+    bitboards: BitBoards,
     state: BoardState,
+    key: u64,
 }
 
 impl core::Position for Board {
@@ -105,7 +364,22 @@ impl core::Validate for Board {
     type ValidationError = IllegalMoveError;
 
     fn validate(&self, candidate: Self::Move) -> Result<Self::LegalMove, Self::ValidationError> {
-        todo!()
+        let (source, target) = candidate.as_pair();
+
+        match self[source] {
+            Some(piece) if piece.color() == self.state.side_to_move => (),
+            _ => return Err(IllegalMoveError::InvalidSource(source)),
+        }
+
+        let Some(candidate) = movegen::classify(self, candidate) else {
+            return Err(IllegalMoveError::InvalidTarget(target));
+        };
+
+        if !movegen::is_legal(self, candidate) {
+            return Err(IllegalMoveError::Check(candidate));
+        }
+
+        Ok(LegalMove::wrap(self, candidate))
     }
 
     fn validate_san(
@@ -115,87 +389,39 @@ impl core::Validate for Board {
     where
         Self: core::Standard + Sized,
     {
-        todo!()
+        let mut matching = self
+            .legal_moves()
+            .filter(|&legal_move| candidate.describes(self, legal_move.into()));
+
+        match (matching.next(), matching.next()) {
+            (Some(legal_move), None) => Ok(legal_move),
+            _ => Err(IllegalMoveError::InvalidSan),
+        }
     }
 }
 
 impl core::Process for Board {
     fn process(&self, candidate: Self::LegalMove) -> Self {
-        todo!()
+        self.make(candidate.into())
+    }
+}
+
+impl core::ProcessNull for Board {
+    /// Passes the turn to the other side and clears the en passant target square.
+    ///
+    /// Since a [`Board`] does not track the move clocks, nothing else changes.
+    fn process_null(&self) -> Self {
+        let mut board = *self;
+        board.state.side_to_move = board.state.side_to_move.opposite();
+        board.state.en_passant_square = None;
+        board.key ^= self.state_key() ^ board.state_key();
+        board
     }
 }
 
 impl Default for Board {
     fn default() -> Self {
-        Self {
-            pieces: [
-                Some(Piece::WhiteRook),
-                Some(Piece::WhiteKnight),
-                Some(Piece::WhiteBishop),
-                Some(Piece::WhiteQueen),
-                Some(Piece::WhiteKing),
-                Some(Piece::WhiteBishop),
-                Some(Piece::WhiteKnight),
-                Some(Piece::WhiteRook),
-                Some(Piece::WhitePawn),
-                Some(Piece::WhitePawn),
-                Some(Piece::WhitePawn),
-                Some(Piece::WhitePawn),
-                Some(Piece::WhitePawn),
-                Some(Piece::WhitePawn),
-                Some(Piece::WhitePawn),
-                Some(Piece::WhitePawn),
-                None,
-                None,
-                None,
-                None,
-                None,
-                None,
-                None,
-                None,
-                None,
-                None,
-                None,
-                None,
-                None,
-                None,
-                None,
-                None,
-                None,
-                None,
-                None,
-                None,
-                None,
-                None,
-                None,
-                None,
-                None,
-                None,
-                None,
-                None,
-                None,
-                None,
-                None,
-                None,
-                Some(Piece::BlackPawn),
-                Some(Piece::BlackPawn),
-                Some(Piece::BlackPawn),
-                Some(Piece::BlackPawn),
-                Some(Piece::BlackPawn),
-                Some(Piece::BlackPawn),
-                Some(Piece::BlackPawn),
-                Some(Piece::BlackPawn),
-                Some(Piece::BlackRook),
-                Some(Piece::BlackKnight),
-                Some(Piece::BlackBishop),
-                Some(Piece::BlackQueen),
-                Some(Piece::BlackKing),
-                Some(Piece::BlackBishop),
-                Some(Piece::BlackKnight),
-                Some(Piece::BlackRook),
-            ],
-            state: BoardState::default(),
-        }
+        Self::starting_position()
     }
 }
 
@@ -228,7 +454,7 @@ impl From<Fen> for Board {
             en_passant_square: value.en_passant_square().map(Into::into),
         };
 
-        Self { pieces, state }
+        Self::new(pieces, state)
     }
 }
 
@@ -251,6 +477,632 @@ impl<'a> Board {
     }
 }
 
+impl Board {
+    /// Returns the starting position of standard chess.
+    ///
+    /// Unlike parsing [`FEN_STARTING_POSITION`](crate::io::fen::FEN_STARTING_POSITION),
+    /// this costs nothing at runtime, and so it can be used in `const` contexts:
+    ///
+    /// ```
+    /// use konig::standard::Board;
+    ///
+    /// const START: Board = Board::starting_position();
+    /// assert_eq!(START, Board::default());
+    /// ```
+    pub const fn starting_position() -> Self {
+        const WHITE_BACK_RANK: [Piece; 8] = [
+            Piece::WhiteRook,
+            Piece::WhiteKnight,
+            Piece::WhiteBishop,
+            Piece::WhiteQueen,
+            Piece::WhiteKing,
+            Piece::WhiteBishop,
+            Piece::WhiteKnight,
+            Piece::WhiteRook,
+        ];
+
+        const BLACK_BACK_RANK: [Piece; 8] = [
+            Piece::BlackRook,
+            Piece::BlackKnight,
+            Piece::BlackBishop,
+            Piece::BlackQueen,
+            Piece::BlackKing,
+            Piece::BlackBishop,
+            Piece::BlackKnight,
+            Piece::BlackRook,
+        ];
+
+        let mut pieces = [None; 64];
+        let mut file = 0;
+        while file < 8 {
+            pieces[file] = Some(WHITE_BACK_RANK[file]);
+            pieces[8 + file] = Some(Piece::WhitePawn);
+            pieces[48 + file] = Some(Piece::BlackPawn);
+            pieces[56 + file] = Some(BLACK_BACK_RANK[file]);
+            file += 1;
+        }
+
+        // the white pieces of each kind, which are mirrored to give the black pieces
+        const WHITE_PIECES: [u64; 6] = [0xff00, 0x81, 0x42, 0x24, 0x08, 0x10];
+        let mut piece_boards = [[BitBoard::EMPTY; 6]; 2];
+        let mut kind = 0;
+        while kind < 6 {
+            piece_boards[0][kind] = BitBoard::from_bits(WHITE_PIECES[kind]);
+            piece_boards[1][kind] = BitBoard::from_bits(WHITE_PIECES[kind].swap_bytes());
+            kind += 1;
+        }
+
+        Self {
+            pieces,
+            bitboards: BitBoards {
+                colors: [
+                    BitBoard::from_bits(0xffff),
+                    BitBoard::from_bits(0xffff << 48),
+                ],
+                pieces: piece_boards,
+                kings: [Some(Square::at(4)), Some(Square::at(60))],
+                counts: [[8, 2, 2, 2, 1, 1]; 2],
+            },
+            state: BoardState {
+                side_to_move: Color::White,
+                castling_rights: CastlingPermissions {
+                    white_king_side: true,
+                    white_queen_side: true,
+                    black_king_side: true,
+                    black_queen_side: true,
+                },
+                en_passant_square: None,
+            },
+            // the Polyglot key of the starting position
+            key: 0x463b96181691fc9c,
+        }
+    }
+
+    /// Constructs a board with the given `pieces` and `state`, computing its bitboards and key.
+    fn new(pieces: [Option<Piece>; 64], state: BoardState) -> Self {
+        let mut board = Self {
+            bitboards: BitBoards::from_pieces(&pieces),
+            pieces,
+            state,
+            key: 0,
+        };
+
+        board.key = board.compute_zobrist();
+        board
+    }
+
+    /// Places `piece` on the square at `index`, replacing whatever was
+    /// there, and updates the bitboards and key to match.
+    fn set(&mut self, index: usize, piece: Option<Piece>) {
+        let square = Square::at(index as u8);
+        if let Some(previous) = self.pieces[index] {
+            self.bitboards.toggle(previous, square);
+            self.key ^= zobrist::piece_key(previous, square);
+        }
+
+        if let Some(piece) = piece {
+            self.bitboards.toggle(piece, square);
+            self.key ^= zobrist::piece_key(piece, square);
+        }
+
+        self.pieces[index] = piece;
+    }
+
+    /// Returns the Zobrist hash of `self`, which is updated incrementally
+    /// as moves are made and unmade rather than recomputed.
+    ///
+    /// The hash is compatible with the Polyglot book format: in particular, the
+    /// en passant square only contributes to the hash if a pawn of the side to
+    /// move is actually in a position to capture en passant.
+    pub fn zobrist(&self) -> u64 {
+        self.key
+    }
+
+    /// Constructs a board with the given `pieces` and side to move,
+    /// no castling permissions, and no en passant target square.
+    pub(crate) fn from_pieces(pieces: [Option<Piece>; 64], side_to_move: Color) -> Self {
+        Self::new(
+            pieces,
+            BoardState {
+                side_to_move,
+                castling_rights: CastlingPermissions::none(),
+                en_passant_square: None,
+            },
+        )
+    }
+
+    /// Constructs a board from its constituent parts, without checking that they are consistent.
+    pub(crate) fn from_parts(
+        pieces: [Option<Piece>; 64],
+        side_to_move: Color,
+        castling_rights: CastlingPermissions,
+        en_passant_square: Option<Square>,
+    ) -> Self {
+        Self::new(
+            pieces,
+            BoardState {
+                side_to_move,
+                castling_rights,
+                en_passant_square,
+            },
+        )
+    }
+
+    /// Returns the set of squares occupied by any piece.
+    pub(crate) fn occupied(&self) -> BitBoard {
+        self.bitboards.colors[0] | self.bitboards.colors[1]
+    }
+
+    /// Returns the set of squares occupied by pieces of the given `color`.
+    pub(crate) fn occupancy(&self, color: Color) -> BitBoard {
+        self.bitboards.colors[color_slot(color)]
+    }
+
+    /// Returns the set of squares occupied by pieces of the given `color` and `kind`.
+    pub(crate) fn pieces(&self, color: Color, kind: PieceKind) -> BitBoard {
+        self.bitboards.pieces[color_slot(color)][kind as usize]
+    }
+
+    /// Returns the number of pieces of each kind of the given `color`, indexed by [`PieceKind`].
+    pub(crate) fn piece_counts(&self, color: Color) -> [u8; 6] {
+        self.bitboards.counts[color_slot(color)]
+    }
+
+    /// Returns the square of the king of the given `color`, if it is on the board.
+    ///
+    /// The squares of the kings are tracked as moves are made, so this is a
+    /// lookup rather than a scan of the board.
+    pub fn king_square(&self, color: Color) -> Option<Square> {
+        self.bitboards.kings[color_slot(color)]
+    }
+
+    /// Returns the set of squares holding pieces of color `by` which attack `square`.
+    pub(crate) fn attackers_to(&self, square: Square, by: Color) -> BitBoard {
+        let occupied = self.occupied();
+        let queens = self.pieces(by, PieceKind::Queen);
+
+        (attacks::pawn(by.opposite(), square) & self.pieces(by, PieceKind::Pawn))
+            | (attacks::knight(square) & self.pieces(by, PieceKind::Knight))
+            | (attacks::king(square) & self.pieces(by, PieceKind::King))
+            | (attacks::bishop(square, occupied) & (self.pieces(by, PieceKind::Bishop) | queens))
+            | (attacks::rook(square, occupied) & (self.pieces(by, PieceKind::Rook) | queens))
+    }
+
+    /// Returns `true` if any piece of color `by` attacks `square`.
+    ///
+    /// This is equivalent to checking whether the set of attackers of `square` is
+    /// empty, but it stops at the first attacker it finds, and only computes the
+    /// attacks of sliding pieces if the side has any. Whether the piece on `square`,
+    /// if any, could be captured is not considered, so this answers questions such
+    /// as "is this square safe for my king?"
+    pub fn is_attacked(&self, square: Square, by: Color) -> bool {
+        let leapers = (attacks::pawn(by.opposite(), square) & self.pieces(by, PieceKind::Pawn))
+            | (attacks::knight(square) & self.pieces(by, PieceKind::Knight))
+            | (attacks::king(square) & self.pieces(by, PieceKind::King));
+        if !leapers.is_empty() {
+            return true;
+        }
+
+        let occupied = self.occupied();
+        let queens = self.pieces(by, PieceKind::Queen);
+        let diagonal = self.pieces(by, PieceKind::Bishop) | queens;
+        if !diagonal.is_empty() && !(attacks::bishop(square, occupied) & diagonal).is_empty() {
+            return true;
+        }
+
+        let straight = self.pieces(by, PieceKind::Rook) | queens;
+        !straight.is_empty() && !(attacks::rook(square, occupied) & straight).is_empty()
+    }
+
+    /// Returns `true` if the king of the side to move is in check.
+    pub fn is_check(&self) -> bool {
+        let us = self.state.side_to_move;
+        self.king_square(us)
+            .is_some_and(|king| self.is_attacked(king, us.opposite()))
+    }
+
+    /// Returns `true` if playing `legal_move` would put the opposing king in check.
+    ///
+    /// This considers direct checks by the moving piece (or by the rook, when
+    /// castling, or by the promoted piece), as well as discovered checks by the
+    /// sliding pieces it uncovers, including through the pawn removed by an en
+    /// passant capture. Nothing is made and unmade, so this is cheap enough to
+    /// call for every candidate move, e.g. to order checks first in a search.
+    pub fn gives_check(&self, legal_move: &LegalMove) -> bool {
+        let us = self.state.side_to_move;
+        let (source, target) = legal_move.as_pair();
+        let (Some(king), Some(piece)) = (self.king_square(us.opposite()), self[source]) else {
+            return false;
+        };
+
+        let mut moved = BitBoard::from_square(source);
+        let mut occupied = (self.occupied() ^ moved) | BitBoard::from_square(target);
+        let (attacker, kind) = match legal_move.kind() {
+            MoveKind::EnPassant => {
+                let captured = Square::at(source.rank() * 8 + target.file());
+                occupied ^= BitBoard::from_square(captured);
+                (target, PieceKind::Pawn)
+            }
+            MoveKind::Castle => {
+                // the king never gives check itself, but the rook beside it may
+                let rank_offset = source.rank() * 8;
+                let (rook_source, rook_target) = match target.file() {
+                    6 => (Square::at(rank_offset + 7), Square::at(rank_offset + 5)),
+                    _ => (Square::at(rank_offset), Square::at(rank_offset + 3)),
+                };
+
+                moved |= BitBoard::from_square(rook_source);
+                occupied ^= BitBoard::from_square(rook_source) | BitBoard::from_square(rook_target);
+                (rook_target, PieceKind::Rook)
+            }
+            _ if piece.kind() == PieceKind::Pawn && matches!(target.rank(), 0 | 7) => {
+                let promotion = legal_move.promotion().unwrap_or(PieceKind::Queen);
+                (target, promotion)
+            }
+            _ => (target, piece.kind()),
+        };
+
+        if attacks::attacks(us, kind, attacker, occupied).contains(king) {
+            return true;
+        }
+
+        // the moving pieces are excluded, as their new squares were checked above
+        let queens = self.pieces(us, PieceKind::Queen);
+        let diagonal = (self.pieces(us, PieceKind::Bishop) | queens) & !moved;
+        let straight = (self.pieces(us, PieceKind::Rook) | queens) & !moved;
+        !(attacks::bishop(king, occupied) & diagonal).is_empty()
+            || !(attacks::rook(king, occupied) & straight).is_empty()
+    }
+
+    /// Returns `true` if `self` and `other` are the same position for the purposes of
+    /// the repetition rules: the same pieces stand on the same squares, the same side
+    /// is to move, and both sides have the same castling permissions and en passant
+    /// captures available.
+    ///
+    /// Unlike [`PartialEq`], an en passant target square only matters here if a pawn
+    /// can legally capture on it, following the FIDE Laws of Chess (article 9.2.3).
+    pub fn same_position(&self, other: &Board) -> bool {
+        self.pieces == other.pieces
+            && self.state.side_to_move == other.state.side_to_move
+            && self.state.castling_rights == other.state.castling_rights
+            && self.available_en_passant_square() == other.available_en_passant_square()
+    }
+
+    /// Returns the position with the other side to move and no en passant target
+    /// square, as if the side to move had passed.
+    ///
+    /// Unlike [`process_null`](core::ProcessNull::process_null), this checks that the
+    /// result is a position which could arise in a game, which answers questions such
+    /// as "what could I do if it were my move here?"; in particular, it fails with
+    /// [`SetupError::OpponentInCheck`] if the side to move is in check.
+    pub fn null_move(&self) -> Result<Board, SetupError> {
+        let board = core::ProcessNull::process_null(self);
+        board.validate_setup()?;
+        Ok(board)
+    }
+
+    /// Validates and plays each of `moves`, written in UCI (e.g. `e2e4`) or in LAN
+    /// (e.g. `Ng1-f3`), in order from `self`, and returns the final position.
+    ///
+    /// This is what a server does to replay the moves sent by a client. If a move
+    /// cannot be read or is illegal, the returned [`ApplyMovesError`] says which
+    /// one it was and why, and none of the moves are applied.
+    pub fn apply_moves<'a, I: IntoIterator<Item = &'a str>>(
+        &self,
+        moves: I,
+    ) -> Result<Board, ApplyMovesError> {
+        let mut board = *self;
+        for (ply, literal) in moves.into_iter().enumerate() {
+            let legal_move = match uci::parse_move(literal) {
+                Ok(candidate) => core::Validate::validate(&board, candidate),
+                Err(_) => match Lan::parse(literal) {
+                    Ok(parsed) => Lan::validate(parsed, &board),
+                    Err(_) => {
+                        return Err(ApplyMovesError::InvalidNotation {
+                            ply,
+                            literal: literal.to_string(),
+                        })
+                    }
+                },
+            };
+
+            let legal_move = legal_move.map_err(|reason| ApplyMovesError::IllegalMove {
+                ply,
+                literal: literal.to_string(),
+                reason,
+            })?;
+
+            board = board.make(legal_move.into());
+        }
+
+        Ok(board)
+    }
+
+    /// Returns the legal move which takes the pieces of `self` to those of `after`,
+    /// if there is exactly one.
+    ///
+    /// Only the placement of the pieces is compared, since that is all an electronic
+    /// board or a GUI can observe; castling, en passant and promotions are recognised
+    /// by where the pieces end up. This returns `None` if no legal move, or more than
+    /// one, produces the placement of `after`.
+    pub fn diff(&self, after: &Board) -> Option<Move> {
+        let mut matching = self
+            .legal_moves()
+            .filter(|&legal_move| self.make(legal_move.into()).pieces == after.pieces);
+
+        match (matching.next(), matching.next()) {
+            (Some(legal_move), None) => Some(legal_move.into()),
+            _ => None,
+        }
+    }
+
+    /// Returns the position seen from the other side of the board: each piece is
+    /// reflected across the middle of the board and changes color, and the side
+    /// to move, castling permissions and en passant target square follow suit.
+    ///
+    /// The result is equivalent to `self` with the roles of the sides exchanged,
+    /// which makes it useful for testing that evaluations are symmetric and for
+    /// augmenting datasets of positions.
+    pub fn mirrored(&self) -> Board {
+        let mut pieces = [None; 64];
+        for (index, piece) in self.pieces.iter().enumerate() {
+            pieces[index ^ 56] =
+                piece.map(|piece| Piece::new(piece.color().opposite(), piece.kind()));
+        }
+
+        let rights = self.state.castling_rights;
+        Board::new(
+            pieces,
+            BoardState {
+                side_to_move: self.state.side_to_move.opposite(),
+                castling_rights: CastlingPermissions {
+                    white_king_side: rights.black_king_side,
+                    white_queen_side: rights.black_queen_side,
+                    black_king_side: rights.white_king_side,
+                    black_queen_side: rights.white_queen_side,
+                },
+                en_passant_square: self
+                    .state
+                    .en_passant_square
+                    .map(|square| Square::at(usize::from(square) as u8 ^ 56)),
+            },
+        )
+    }
+
+    /// Returns the en passant target square if some pawn of the side to move can legally capture on it.
+    pub(crate) fn available_en_passant_square(&self) -> Option<Square> {
+        let square = self.state.en_passant_square?;
+        let us = self.state.side_to_move;
+        let capturers = attacks::pawn(us.opposite(), square) & self.pieces(us, PieceKind::Pawn);
+
+        capturers
+            .squares()
+            .map(|source| Move::from((source, square)).with_kind(MoveKind::EnPassant))
+            .any(|candidate| movegen::is_legal(self, candidate))
+            .then_some(square)
+    }
+
+    /// Checks that `self` describes a position which could arise in a game.
+    ///
+    /// This requires that each side has exactly one king, that no pawn is on the
+    /// first or eighth rank, that the side which is not to move is not in check,
+    /// that the en passant target square lies behind a pawn which could have just
+    /// moved two squares, and that any castling permissions are held by a king and
+    /// rook on their initial squares.
+    pub fn validate_setup(&self) -> Result<(), SetupError> {
+        for color in Color::ALL {
+            let kings = self.pieces(color, PieceKind::King).count();
+            if kings != 1 {
+                return Err(SetupError::KingCount(color, kings));
+            }
+        }
+
+        let pawns =
+            self.pieces(Color::White, PieceKind::Pawn) | self.pieces(Color::Black, PieceKind::Pawn);
+        let back_ranks = BitBoard::rank(0) | BitBoard::rank(7);
+        if let Some(square) = (pawns & back_ranks).squares().next() {
+            return Err(SetupError::PawnOnBackRank(square));
+        }
+
+        let them = self.state.side_to_move.opposite();
+        if self
+            .king_square(them)
+            .is_some_and(|king| self.is_attacked(king, them.opposite()))
+        {
+            return Err(SetupError::OpponentInCheck);
+        }
+
+        if let Some(square) = self.state.en_passant_square {
+            if !self.is_plausible_en_passant_square(square) {
+                return Err(SetupError::InvalidEnPassantSquare(square));
+            }
+        }
+
+        let rights = self.state.castling_rights;
+        let plausible = self.plausible_castling_rights();
+        if (rights.white_king_side && !plausible.white_king_side)
+            || (rights.white_queen_side && !plausible.white_queen_side)
+        {
+            return Err(SetupError::InvalidCastlingRights(Color::White));
+        }
+
+        if (rights.black_king_side && !plausible.black_king_side)
+            || (rights.black_queen_side && !plausible.black_queen_side)
+        {
+            return Err(SetupError::InvalidCastlingRights(Color::Black));
+        }
+
+        Ok(())
+    }
+
+    /// Returns `true` if `square` lies behind a pawn of the side which is not
+    /// to move that could have just moved two squares over it.
+    pub(crate) fn is_plausible_en_passant_square(&self, square: Square) -> bool {
+        // the pawn moved from `origin`, over `square`, to `pawn`
+        let them = self.state.side_to_move.opposite();
+        let index = usize::from(square) as u8;
+        let (rank, origin, pawn) = match them {
+            Color::White => (2, index.wrapping_sub(8), index + 8),
+            Color::Black => (5, index + 8, index.wrapping_sub(8)),
+        };
+
+        square.rank() == rank
+            && self[square].is_none()
+            && self[Square::at(origin)].is_none()
+            && self[Square::at(pawn)] == Some(Piece::new(them, PieceKind::Pawn))
+    }
+
+    /// Returns the castling permissions which are consistent with the placement of the
+    /// kings and rooks, i.e. those whose king and rook are still on their initial squares.
+    pub(crate) fn plausible_castling_rights(&self) -> CastlingPermissions {
+        let has = |index: u8, color: Color, kind: PieceKind| {
+            self[Square::at(index)] == Some(Piece::new(color, kind))
+        };
+
+        let white_king = has(4, Color::White, PieceKind::King);
+        let black_king = has(60, Color::Black, PieceKind::King);
+        CastlingPermissions {
+            white_king_side: white_king && has(7, Color::White, PieceKind::Rook),
+            white_queen_side: white_king && has(0, Color::White, PieceKind::Rook),
+            black_king_side: black_king && has(63, Color::Black, PieceKind::Rook),
+            black_queen_side: black_king && has(56, Color::Black, PieceKind::Rook),
+        }
+    }
+
+    /// Plays `candidate` on a copy of `self` and returns the result, without checking
+    /// that the move is legal or even pseudo-legal.
+    ///
+    /// Pawns reaching the final rank are promoted to the piece given by
+    /// [`Move::promotion`], or to a queen if there is none.
+    pub(crate) fn make(&self, candidate: Move) -> Board {
+        let mut board = *self;
+        let (source, target) = candidate.as_pair();
+        let Some(piece) = self[source] else {
+            return board;
+        };
+
+        let color = piece.color();
+        let source_index = usize::from(source);
+        let target_index = usize::from(target);
+        let kind = candidate
+            .kind()
+            .unwrap_or_else(|| movegen::kind_of(self, candidate));
+        board.set(source_index, None);
+
+        match kind {
+            // en passant captures remove a pawn from beside the target square
+            MoveKind::EnPassant => {
+                board.set(source.rank() as usize * 8 + target.file() as usize, None);
+                board.set(target_index, Some(piece));
+            }
+            MoveKind::Castle => {
+                // castling also moves the rook to the square the king crossed
+                let rank_offset = source.rank() as usize * 8;
+                let (rook_source, rook_target) = match target.file() {
+                    6 => (rank_offset + 7, rank_offset + 5),
+                    _ => (rank_offset, rank_offset + 3),
+                };
+
+                let rook = board.pieces[rook_source];
+                board.set(rook_source, None);
+                board.set(rook_target, rook);
+                board.set(target_index, Some(piece));
+            }
+            _ if piece.kind() == PieceKind::Pawn && matches!(target.rank(), 0 | 7) => {
+                let promotion = candidate.promotion().unwrap_or(PieceKind::Queen);
+                board.set(target_index, Some(Piece::new(color, promotion)));
+            }
+            _ => board.set(target_index, Some(piece)),
+        }
+
+        board.state.en_passant_square = match kind {
+            MoveKind::DoublePush => Some(Square::at((source_index + target_index) as u8 / 2)),
+            _ => None,
+        };
+
+        // moving the king, or moving to or from a corner, revokes the relevant permissions
+        let rights = &mut board.state.castling_rights;
+        if piece.kind() == PieceKind::King {
+            match color {
+                Color::White => {
+                    rights.white_king_side = false;
+                    rights.white_queen_side = false;
+                }
+                Color::Black => {
+                    rights.black_king_side = false;
+                    rights.black_queen_side = false;
+                }
+            }
+        }
+
+        for corner in [source_index, target_index] {
+            match corner {
+                0 => rights.white_queen_side = false,
+                7 => rights.white_king_side = false,
+                56 => rights.black_queen_side = false,
+                63 => rights.black_king_side = false,
+                _ => (),
+            }
+        }
+
+        board.state.side_to_move = color.opposite();
+        board.key ^= self.state_key() ^ board.state_key();
+        board
+    }
+
+    /// Takes back `legal_move`, which must be the last move played to reach `self`,
+    /// and returns the position in which it was played.
+    ///
+    /// The captured piece and the overwritten state are recorded by the [`LegalMove`],
+    /// so nothing needs to be recomputed.
+    pub fn unmake(&self, legal_move: LegalMove) -> Board {
+        let mut board = *self;
+        let (source, target) = legal_move.as_pair();
+        let target_index = usize::from(target);
+        let Some(piece) = self[target] else {
+            return board;
+        };
+
+        let color = piece.color();
+        let moved = match legal_move.promotion() {
+            Some(_) => Piece::new(color, PieceKind::Pawn),
+            None => piece,
+        };
+        board.set(usize::from(source), Some(moved));
+
+        match legal_move.kind() {
+            MoveKind::EnPassant => {
+                board.set(target_index, None);
+                board.set(
+                    source.rank() as usize * 8 + target.file() as usize,
+                    legal_move.captured(),
+                );
+            }
+            MoveKind::Castle => {
+                let rank_offset = source.rank() as usize * 8;
+                let (rook_source, rook_target) = match target.file() {
+                    6 => (rank_offset + 7, rank_offset + 5),
+                    _ => (rank_offset, rank_offset + 3),
+                };
+
+                let rook = board.pieces[rook_target];
+                board.set(rook_target, None);
+                board.set(rook_source, rook);
+                board.set(target_index, None);
+            }
+            _ => board.set(target_index, legal_move.captured()),
+        }
+
+        board.state = BoardState {
+            side_to_move: color,
+            castling_rights: legal_move.prior_castling_permissions(),
+            en_passant_square: legal_move.prior_en_passant_square(),
+        };
+        board.key ^= self.state_key() ^ board.state_key();
+        board
+    }
+}
+
 struct BoardIterator<'a> {
     board: &'a Board,
     index: usize, // alignment makes u8 and usize take the same space
@@ -316,6 +1168,42 @@ impl<'a> From<&'a Board> for BoardRankIterator<'a> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn moves_are_applied_in_sequence() {
+        let board = Board::default();
+        let mated = board
+            .apply_moves(["f2f3", "e7-e5", "g2g4", "Qd8-h4#"])
+            .unwrap();
+        assert!(mated.is_check() && !mated.has_legal_moves());
+
+        let castled = board.apply_moves("e2e4 e7e5 Ng1-f3 b8c6 Bf1-c4 g8f6 O-O".split(' '));
+        let castled = castled.unwrap();
+        assert_eq!(
+            castled[Square::try_from("g1").unwrap()],
+            Some(Piece::WhiteKing)
+        );
+
+        assert!(matches!(
+            board.apply_moves(["e2e4", "e2e4"]),
+            Err(ApplyMovesError::IllegalMove {
+                ply: 1,
+                reason: IllegalMoveError::InvalidSource(_),
+                ..
+            })
+        ));
+        assert!(matches!(
+            board.apply_moves(["e2e4", "e7-e6", "e4"]),
+            Err(ApplyMovesError::InvalidNotation { ply: 2, .. })
+        ));
+    }
+
+    #[test]
+    fn starting_position_is_consistent() {
+        let board = Board::starting_position();
+        assert_eq!(board, Board::new(board.pieces, board.state));
+        assert_eq!(board, Board::from(Fen::default()));
+    }
+
     #[test]
     fn standard_board_iterator_produces_correct_order() {
         let board = Board::default();
@@ -416,4 +1304,313 @@ mod tests {
         assert_eq!(board[j], Some(Piece::BlackRook));
         assert_eq!(board[k], None);
     }
+
+    #[test]
+    fn setups_are_validated() {
+        let setup = |fen: &str| Fen::try_from(fen).unwrap().to_validated_board().map(|_| ());
+        let square = |name: &str| Square::try_from(name).unwrap();
+
+        assert_eq!(setup(crate::io::fen::FEN_STARTING_POSITION), Ok(()));
+        assert_eq!(
+            setup("rnbqkbnr/ppp1pppp/8/8/3pP3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1"),
+            Ok(())
+        );
+
+        assert_eq!(
+            setup("8/8/8/8/8/8/8/4K3 w - - 0 1"),
+            Err(SetupError::KingCount(Color::Black, 0))
+        );
+        assert_eq!(
+            setup("4k3/8/8/8/8/8/8/4K1KP w - - 0 1"),
+            Err(SetupError::KingCount(Color::White, 2))
+        );
+        assert_eq!(
+            setup("4k3/8/8/8/8/8/8/4K2P w - - 0 1"),
+            Err(SetupError::PawnOnBackRank(square("h1")))
+        );
+        assert_eq!(setup("4k3/8/8/8/8/8/8/4K2R w - - 0 1"), Ok(()));
+        assert_eq!(
+            setup("4k2R/8/8/8/8/8/8/4K3 w - - 0 1"),
+            Err(SetupError::OpponentInCheck)
+        );
+        assert_eq!(
+            setup("rnbqkbnr/ppp1pppp/8/8/3pP3/8/PPPP1PPP/RNBQKBNR b KQkq d3 0 1"),
+            Err(SetupError::InvalidEnPassantSquare(square("d3")))
+        );
+        assert_eq!(
+            setup("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR b KQkq e3 0 1"),
+            Err(SetupError::InvalidEnPassantSquare(square("e3")))
+        );
+        assert_eq!(
+            setup("r3k3/8/8/8/8/8/8/R3K2R w KQkq - 0 1"),
+            Err(SetupError::InvalidCastlingRights(Color::Black))
+        );
+        assert_eq!(setup("r3k3/8/8/8/8/8/8/R3K2R w KQq - 0 1"), Ok(()));
+    }
+
+    #[test]
+    fn null_moves_pass_the_turn() {
+        use crate::core::{ProcessNull as _, Standard as _};
+
+        let board = Fen::try_from("rnbqkbnr/ppp1pppp/8/8/3pP3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1")
+            .unwrap()
+            .to_standard_board();
+        let passed = board.process_null();
+        assert_eq!(passed.side_to_move(), Color::White);
+        assert_eq!(passed.en_passant_target_square(), None);
+        assert_eq!(passed.castling_permissions(), board.castling_permissions());
+        assert!(passed.into_iter().eq(board.into_iter()));
+        assert_eq!(passed.process_null().side_to_move(), Color::Black);
+    }
+
+    #[test]
+    fn moves_are_inferred_from_diffs() {
+        use crate::core::{Process as _, Validate as _};
+
+        let mv = |source: &str, target: &str| -> Move {
+            (
+                Square::try_from(source).unwrap(),
+                Square::try_from(target).unwrap(),
+            )
+                .into()
+        };
+
+        for (fen, expected) in [
+            ("4k3/8/8/8/8/8/4P3/4K3 w - - 0 1", mv("e2", "e4")),
+            ("r3k2r/8/8/8/8/8/8/R3K2R b KQkq - 0 1", mv("e8", "c8")),
+            ("4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1", mv("e5", "d6")),
+            (
+                "4k3/1P6/8/8/8/8/8/4K3 w - - 0 1",
+                mv("b7", "b8").with_promotion(PieceKind::Knight),
+            ),
+        ] {
+            let before = Fen::try_from(fen).unwrap().to_standard_board();
+            let after = before.process(before.validate(expected).unwrap());
+            assert_eq!(before.diff(&after), Some(expected), "{fen}");
+        }
+
+        // a position is not reached from itself, nor from one two moves away
+        let start = Board::default();
+        assert_eq!(start.diff(&start), None);
+        let twice = start.make(mv("g1", "f3")).make(mv("g8", "f6"));
+        assert_eq!(start.diff(&twice), None);
+    }
+
+    #[test]
+    fn null_moves_are_validated() {
+        use crate::core::Standard as _;
+
+        let board = |fen| Fen::try_from(fen).unwrap().to_standard_board();
+
+        let passed = board("4k3/8/8/8/8/8/3p4/4K3 b - - 0 1").null_move();
+        assert_eq!(passed.map(|board| board.side_to_move()), Ok(Color::White));
+
+        // white is in check from the pawn, so it cannot pass
+        let checked = board("4k3/8/8/8/8/8/3p4/4K3 w - - 0 1");
+        assert_eq!(checked.null_move(), Err(SetupError::OpponentInCheck));
+    }
+
+    #[test]
+    fn moves_are_unmade() {
+        use crate::core::{Process as _, Standard as _, Validate as _};
+
+        for fen in [
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+            "rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq f6 0 1",
+            "n1n5/PPPk4/8/8/8/8/4Kppp/5N1N b - - 0 1",
+        ] {
+            let board = Fen::try_from(fen).unwrap().to_standard_board();
+            for legal_move in board.legal_moves() {
+                assert_eq!(board.process(legal_move).unmake(legal_move), board);
+            }
+        }
+
+        let board = Fen::try_from("rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq f6 0 1")
+            .unwrap()
+            .to_standard_board();
+        let square = |name: &str| Square::try_from(name).unwrap();
+        let en_passant = board.validate((square("e5"), square("f6")).into()).unwrap();
+        assert_eq!(en_passant.captured(), Some(Piece::BlackPawn));
+        assert_eq!(en_passant.prior_en_passant_square(), Some(square("f6")));
+        assert!(en_passant.is_capture());
+
+        let push = board.validate((square("d2"), square("d3")).into()).unwrap();
+        assert_eq!(push.captured(), None);
+        assert_eq!(
+            push.prior_castling_permissions(),
+            board.castling_permissions()
+        );
+    }
+
+    #[test]
+    fn bitboards_are_kept_in_sync() {
+        use crate::core::Process as _;
+
+        fn walk(board: &Board, depth: u8) {
+            assert_eq!(board.bitboards, BitBoards::from_pieces(&board.pieces));
+            if depth == 0 {
+                return;
+            }
+
+            for legal_move in board.legal_moves() {
+                let next = board.process(legal_move);
+                walk(&next, depth - 1);
+                assert_eq!(next.unmake(legal_move).bitboards, board.bitboards);
+            }
+        }
+
+        for fen in [
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+            "rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq f6 0 1",
+            "n1n5/PPPk4/8/8/8/8/4Kppp/5N1N b - - 0 1",
+        ] {
+            walk(&Fen::try_from(fen).unwrap().to_standard_board(), 2);
+        }
+
+        let board = Board::default();
+        assert_eq!(board.king_square(Color::Black), Square::try_from("e8").ok());
+        assert_eq!(board.occupied().count(), 32);
+        assert_eq!(board.mirrored().bitboards, board.bitboards);
+    }
+
+    #[test]
+    fn castling_permissions_are_iterated_and_converted() {
+        let all = CastlingPermissions::default();
+        assert!(all.iter().eq(CastlingRight::ALL));
+        assert_eq!(all.to_fen_string(), "KQkq");
+        assert!(CastlingPermissions::none().is_empty());
+        assert_eq!(CastlingPermissions::none().to_fen_string(), "-");
+
+        let black = CastlingPermissions::from_fen_chars("qk").unwrap();
+        assert!(black.any());
+        assert!(black.iter().all(|right| right.color() == Color::Black));
+        assert_eq!(black.to_fen_string(), "kq");
+
+        for invalid in ["", "KQkqK", "-K", "x"] {
+            assert_eq!(
+                CastlingPermissions::from_fen_chars(invalid),
+                None,
+                "{invalid}"
+            );
+        }
+    }
+
+    #[test]
+    fn king_squares_are_tracked() {
+        use crate::core::{Process as _, Validate as _};
+
+        let square = |name: &str| Square::try_from(name).unwrap();
+        let board = Fen::try_from("r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1")
+            .unwrap()
+            .to_standard_board();
+        let castled = board.process(board.validate((square("e1"), square("g1")).into()).unwrap());
+        assert_eq!(castled.king_square(Color::White), Some(square("g1")));
+        assert_eq!(castled.king_square(Color::Black), Some(square("e8")));
+
+        let kingless = Board::from_pieces([None; 64], Color::White);
+        assert_eq!(kingless.king_square(Color::White), None);
+    }
+
+    #[test]
+    fn attacked_squares_agree_with_attackers() {
+        let board = |fen: &str| Fen::try_from(fen).unwrap().to_standard_board();
+        let square = |name: &str| Square::try_from(name).unwrap();
+
+        let kiwipete =
+            board("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1");
+        for color in [Color::White, Color::Black] {
+            for index in 0..64 {
+                let sq = Square::at(index);
+                assert_eq!(
+                    kiwipete.is_attacked(sq, color),
+                    !kiwipete.attackers_to(sq, color).is_empty(),
+                    "{sq} by {color}"
+                );
+            }
+        }
+
+        // the rook attacks through the empty squares, but not past the king
+        let rook = board("4k3/8/8/8/8/8/8/R3K3 b - - 0 1");
+        assert!(rook.is_attacked(square("a8"), Color::White));
+        assert!(rook.is_attacked(square("d1"), Color::White));
+        assert!(!rook.is_attacked(square("g1"), Color::White));
+        assert!(!rook.is_attacked(square("b2"), Color::Black));
+    }
+
+    #[test]
+    fn checks_are_detected_without_making_moves() {
+        use crate::core::Process as _;
+
+        for fen in [
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+            // a discovered check by the bishop behind the knight
+            "4k3/8/8/8/8/2N5/1B6/4K3 w - - 0 1",
+            // an en passant capture uncovering the rook
+            "8/8/8/K2pP2k/8/8/8/8 w - d6 0 1",
+            "8/8/8/R2pP2k/8/8/8/4K3 w - d6 0 1",
+            // castling with the rook giving check
+            "5k2/8/8/8/8/8/8/4K2R w K - 0 1",
+            "3k4/8/8/8/8/8/8/R3K3 w Q - 0 1",
+            // promotions, including underpromotions and promotions with capture
+            "1n1k4/P7/8/8/8/8/8/4K3 w - - 0 1",
+            "4k3/8/8/8/8/8/p7/1R2K3 b - - 0 1",
+        ] {
+            let board = Fen::try_from(fen).unwrap().to_standard_board();
+            for legal_move in board.legal_moves() {
+                assert_eq!(
+                    board.gives_check(&legal_move),
+                    board.process(legal_move).is_check(),
+                    "{legal_move} in {fen}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn boards_are_mirrored() {
+        let board = |fen: &str| Fen::try_from(fen).unwrap().to_standard_board();
+
+        let kiwipete =
+            board("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K1R1 w Qkq - 0 1");
+        assert_eq!(
+            kiwipete.mirrored(),
+            board("r3k1r1/pppbbppp/2n2q1P/1P2p3/3pn3/BN2PNP1/P1PPQPB1/R3K2R b KQq - 0 1")
+        );
+        assert_eq!(kiwipete.mirrored().mirrored(), kiwipete);
+
+        let en_passant = board("rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq f6 0 1");
+        assert_eq!(
+            en_passant.mirrored(),
+            board("rnbqkbnr/pppp1ppp/8/8/3PpP2/8/PPP1P1PP/RNBQKBNR b KQkq f3 0 1")
+        );
+        assert_eq!(
+            en_passant.legal_moves().count(),
+            en_passant.mirrored().legal_moves().count()
+        );
+    }
+
+    #[test]
+    fn positions_are_compared_for_repetition() {
+        let board = |fen: &str| Fen::try_from(fen).unwrap().to_standard_board();
+
+        // no black pawn can capture on e3, so the en passant square is irrelevant
+        let pushed = board("rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1");
+        let placed = board("rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq - 0 1");
+        assert_ne!(pushed, placed);
+        assert!(pushed.same_position(&placed));
+
+        // the d4 pawn can capture on e3
+        let capturable = board("4k3/8/8/8/3pP3/8/8/4K3 b - e3 0 1");
+        let uncapturable = board("4k3/8/8/8/3pP3/8/8/4K3 b - - 0 1");
+        assert!(!capturable.same_position(&uncapturable));
+
+        // ...unless the capture would expose the king to the rook on h4
+        let pinned = board("8/8/8/8/k2pP2R/8/8/4K3 b - e3 0 1");
+        let unpinned = board("8/8/8/8/k2pP2R/8/8/4K3 b - - 0 1");
+        assert!(pinned.same_position(&unpinned));
+
+        assert!(!placed.same_position(&board(
+            "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b Kkq - 0 1"
+        )));
+    }
 }
@@ -0,0 +1,168 @@
+use super::{
+    attacks,
+    bitboard::BitBoard,
+    board::Board,
+    piece::{Color, PieceKind},
+};
+use crate::core::Standard as _;
+
+/// The light squares of the board, such as h1.
+const LIGHT_SQUARES: BitBoard = BitBoard::from_bits(0x55aa_55aa_55aa_55aa);
+
+impl Board {
+    /// Returns `true` if neither side has enough material to checkmate by any
+    /// sequence of legal moves: king against king, king and knight against king,
+    /// or any number of bishops which all stand on squares of the same color.
+    pub fn has_insufficient_material(&self) -> bool {
+        let count = |kind| {
+            self.pieces(Color::White, kind).count() + self.pieces(Color::Black, kind).count()
+        };
+
+        if count(PieceKind::Pawn) + count(PieceKind::Rook) + count(PieceKind::Queen) > 0 {
+            return false;
+        }
+
+        let bishops = self.pieces(Color::White, PieceKind::Bishop)
+            | self.pieces(Color::Black, PieceKind::Bishop);
+        match count(PieceKind::Knight) {
+            0 => (bishops & LIGHT_SQUARES).is_empty() || (bishops & !LIGHT_SQUARES).is_empty(),
+            1 => bishops.is_empty(),
+            _ => false,
+        }
+    }
+
+    /// Returns `true` if the position is dead, i.e. if no sequence of legal moves
+    /// can lead to checkmate by either side, in which case FIDE Article 5.2.2
+    /// draws the game immediately.
+    ///
+    /// Besides [insufficient material](Board::has_insufficient_material), this
+    /// recognises fortresses of kings and pawns in which every pawn is blocked,
+    /// no pawn can capture, and neither king can reach an enemy pawn which is not
+    /// defended by another pawn. The test is conservative: a position for which it
+    /// returns `true` is certainly dead, but some dead positions are not recognised.
+    pub fn is_dead_position(&self) -> bool {
+        self.has_insufficient_material() || self.is_blocked_fortress()
+    }
+
+    /// Returns `true` if only kings and pawns remain and the pawns can never move,
+    /// so that no check, and hence no checkmate, can ever arise.
+    fn is_blocked_fortress(&self) -> bool {
+        let white = self.pieces(Color::White, PieceKind::Pawn);
+        let black = self.pieces(Color::Black, PieceKind::Pawn);
+        let kings =
+            self.pieces(Color::White, PieceKind::King) | self.pieces(Color::Black, PieceKind::King);
+
+        // only the kings can move, and a king never gives check
+        if self.occupied() != white | black | kings || self.is_check() {
+            return false;
+        }
+
+        // a pawn which is blocked by another pawn stays blocked so long as no
+        // pawn captures, and so no pawn ever moves
+        let pawns = white | black;
+        if !((white << 8) & !pawns).is_empty() || !((black >> 8) & !pawns).is_empty() {
+            return false;
+        }
+
+        [Color::White, Color::Black].into_iter().all(|color| {
+            let (own, enemy) = match color {
+                Color::White => (white, black),
+                Color::Black => (black, white),
+            };
+
+            let own_attacks = pawn_attacks(color, own);
+            let enemy_attacks = pawn_attacks(color.opposite(), enemy);
+            let en_passant = self
+                .en_passant_target_square()
+                .filter(|_| self.side_to_move() == color)
+                .map_or(BitBoard::EMPTY, BitBoard::from_square);
+
+            // a king never steps onto a square attacked by an enemy pawn, so the
+            // squares it can reach are bounded by its own pawns and those attacks
+            let passable = !own & !enemy_attacks;
+            let region = flood(self.pieces(color, PieceKind::King), passable);
+
+            (own_attacks & (enemy | en_passant)).is_empty() && (region & enemy).is_empty()
+        })
+    }
+}
+
+/// Returns the squares attacked by the `pawns` of the given `color`.
+fn pawn_attacks(color: Color, pawns: BitBoard) -> BitBoard {
+    pawns.squares().fold(BitBoard::EMPTY, |attacked, square| {
+        attacked | attacks::pawn(color, square)
+    })
+}
+
+/// Returns the squares a king on `start` can reach by moving only through `passable` squares.
+fn flood(start: BitBoard, passable: BitBoard) -> BitBoard {
+    let mut region = start;
+    loop {
+        let grown = region.squares().fold(region, |grown, square| {
+            grown | (attacks::king(square) & passable)
+        });
+
+        if grown == region {
+            return region;
+        }
+
+        region = grown;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::Fen;
+
+    fn board(fen: &str) -> Board {
+        Fen::try_from(fen).unwrap().to_standard_board()
+    }
+
+    #[test]
+    fn insufficient_material_is_recognised() {
+        for fen in [
+            "4k3/8/8/8/8/8/8/4K3 w - - 0 1",
+            "4k3/8/8/8/8/8/8/4KN2 w - - 0 1",
+            // every bishop stands on a dark square
+            "3bk3/8/8/8/8/8/8/B1B1K3 b - - 0 1",
+        ] {
+            assert!(board(fen).has_insufficient_material(), "{fen}");
+            assert!(board(fen).is_dead_position(), "{fen}");
+        }
+
+        // each of these can be mated with the help of the other side
+        for fen in [
+            "4kb2/8/8/8/8/8/8/4KB2 w - - 0 1",
+            "4kn2/8/8/8/8/8/8/4KN2 w - - 0 1",
+            "4k3/8/8/8/8/8/8/3NKN2 w - - 0 1",
+            "4k3/8/8/8/8/8/8/4KR2 w - - 0 1",
+            "4k3/4p3/8/8/8/8/8/4K3 w - - 0 1",
+        ] {
+            assert!(!board(fen).has_insufficient_material(), "{fen}");
+            assert!(!board(fen).is_dead_position(), "{fen}");
+        }
+
+        assert!(!Board::default().is_dead_position());
+    }
+
+    #[test]
+    fn blocked_pawn_fortresses_are_dead() {
+        // neither king can get past the pawn chains
+        let fortress = board("8/8/3k4/1p1p1p1p/1P1P1P1P/3K4/8/8 w - - 0 1");
+        assert!(!fortress.has_insufficient_material());
+        assert!(fortress.is_dead_position());
+
+        // without the h-pawns, the white king can go round and take the f-pawn
+        let open = board("8/8/3k4/1p1p1p2/1P1P1P2/3K4/8/8 w - - 0 1");
+        assert!(!open.is_dead_position());
+
+        // the pawns on b4 and c5 can capture each other
+        let capture = board("8/8/3k4/1pp5/1PP5/3K4/8/8 w - - 0 1");
+        assert!(!capture.is_dead_position());
+
+        // a pawn can still move
+        let free = board("8/8/3k4/1p1p1p2/1P1P1P1P/3K4/8/8 w - - 0 1");
+        assert!(!free.is_dead_position());
+    }
+}
@@ -0,0 +1,322 @@
+use super::{
+    board::{Board, CastlingPermissions},
+    piece::{Color, Piece},
+    Square,
+};
+
+/// Writes a standard position inline as a diagram, checking it at compile time.
+///
+/// The squares are given rank by rank from the eighth rank down to the first, and
+/// from the a-file to the h-file, using the FEN letters for the pieces and `.` or
+/// `_` for empty squares. The ranks may optionally be separated by `/`. The diagram
+/// may be followed by a `;` and any of the options `side` (`White` or `Black`, by
+/// default `White`), `castling` (as in FEN, by default `-`), and `en_passant` (a
+/// square, by default none).
+///
+/// ```
+/// use konig::{board, standard::Board};
+///
+/// let start = board! {
+///     r n b q k b n r
+///     p p p p p p p p
+///     . . . . . . . .
+///     . . . . . . . .
+///     . . . . . . . .
+///     . . . . . . . .
+///     P P P P P P P P
+///     R N B Q K B N R;
+///     castling: KQkq
+/// };
+/// assert_eq!(start, Board::default());
+/// ```
+///
+/// Diagrams which could not arise in a game, for instance because a side does not
+/// have exactly one king, are rejected at compile time:
+///
+/// ```compile_fail
+/// # use konig::board;
+/// let two_kings = board! {
+///     . . . . k . . . / . . . . . . . . / . . . . . . . . / . . . . . . . .
+///     . . . . . . . . / . . . . . . . . / . . . . . . . . / K . . . K . . .
+/// };
+/// ```
+///
+/// The remaining [setup conditions](Board::validate_setup), which require attack
+/// generation, are checked when the board is constructed, and cause a panic.
+#[macro_export]
+macro_rules! board {
+    (@squares [$($square:expr,)*] / $($rest:tt)*) => {
+        $crate::board!(@squares [$($square,)*] $($rest)*)
+    };
+    (@squares [$($square:expr,)*] ; $($options:tt)*) => {
+        $crate::board!(@build [$($square,)*] $($options)*)
+    };
+    (@squares [$($square:expr,)*]) => {
+        $crate::board!(@build [$($square,)*])
+    };
+    (@squares [$($square:expr,)*] $next:tt $($rest:tt)*) => {
+        $crate::board!(@squares [$($square,)* $crate::board!(@square $next),] $($rest)*)
+    };
+    (@square .) => { None };
+    (@square _) => { None };
+    (@square P) => { Some($crate::standard::Piece::WhitePawn) };
+    (@square N) => { Some($crate::standard::Piece::WhiteKnight) };
+    (@square B) => { Some($crate::standard::Piece::WhiteBishop) };
+    (@square R) => { Some($crate::standard::Piece::WhiteRook) };
+    (@square Q) => { Some($crate::standard::Piece::WhiteQueen) };
+    (@square K) => { Some($crate::standard::Piece::WhiteKing) };
+    (@square p) => { Some($crate::standard::Piece::BlackPawn) };
+    (@square n) => { Some($crate::standard::Piece::BlackKnight) };
+    (@square b) => { Some($crate::standard::Piece::BlackBishop) };
+    (@square r) => { Some($crate::standard::Piece::BlackRook) };
+    (@square q) => { Some($crate::standard::Piece::BlackQueen) };
+    (@square k) => { Some($crate::standard::Piece::BlackKing) };
+    (@square $other:tt) => {
+        compile_error!(concat!("expected a piece letter, `.` or `_`, found `", stringify!($other), "`"))
+    };
+    (@build [$($square:expr,)*] $($key:ident : $value:tt),* $(,)?) => {{
+        const DIAGRAM: $crate::standard::diagram::Diagram =
+            $crate::standard::diagram::Diagram::new([$($square),*])
+                $(.with(stringify!($key), stringify!($value)))*
+                .validated();
+        DIAGRAM.to_board()
+    }};
+    ($($tokens:tt)+) => {
+        $crate::board!(@squares [] $($tokens)+)
+    };
+}
+
+/// A position written out square by square, as by the [`board!`](crate::board) macro.
+///
+/// Every method of a diagram is a `const fn`, so that the macro can check the
+/// diagram while compiling; invalid diagrams cause a panic, which is then
+/// reported as a compile error.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Diagram {
+    pieces: [Option<Piece>; 64],
+    side_to_move: Color,
+    castling_rights: CastlingPermissions,
+    en_passant_square: Option<Square>,
+}
+
+impl Diagram {
+    /// Constructs a diagram from its `squares`, given rank by rank from the eighth
+    /// rank down to the first, with white to move and no castling permissions.
+    pub const fn new(squares: [Option<Piece>; 64]) -> Self {
+        let mut pieces = [None; 64];
+        let mut index = 0;
+        while index < 64 {
+            pieces[(7 - index / 8) * 8 + index % 8] = squares[index];
+            index += 1;
+        }
+
+        Self {
+            pieces,
+            side_to_move: Color::White,
+            castling_rights: CastlingPermissions {
+                white_king_side: false,
+                white_queen_side: false,
+                black_king_side: false,
+                black_queen_side: false,
+            },
+            en_passant_square: None,
+        }
+    }
+
+    /// Sets the option called `key` to `value`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `key` is not `side`, `castling` or `en_passant`, or if `value` is invalid.
+    pub const fn with(mut self, key: &str, value: &str) -> Self {
+        if eq(key, "side") {
+            self.side_to_move = match value.as_bytes() {
+                b"White" | b"white" | b"w" => Color::White,
+                b"Black" | b"black" | b"b" => Color::Black,
+                _ => panic!("expected the side to be `White` or `Black`"),
+            };
+        } else if eq(key, "castling") {
+            self.castling_rights = parse_castling(value.as_bytes());
+        } else if eq(key, "en_passant") {
+            self.en_passant_square = Some(parse_square(value.as_bytes()));
+        } else {
+            panic!("expected one of the options `side`, `castling` or `en_passant`");
+        }
+
+        self
+    }
+
+    /// Checks that each side has exactly one king, that no pawn stands on
+    /// the first or eighth rank, and that the castling permissions are held
+    /// by kings and rooks on their initial squares.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any of these conditions does not hold.
+    pub const fn validated(self) -> Self {
+        let mut kings = [0; 2];
+        let mut index = 0;
+        while index < 64 {
+            match self.pieces[index] {
+                Some(Piece::WhiteKing) => kings[0] += 1,
+                Some(Piece::BlackKing) => kings[1] += 1,
+                Some(Piece::WhitePawn | Piece::BlackPawn) if index < 8 || index >= 56 => {
+                    panic!("there is a pawn on the first or eighth rank")
+                }
+                _ => (),
+            }
+
+            index += 1;
+        }
+
+        if kings[0] != 1 || kings[1] != 1 {
+            panic!("each side must have exactly one king");
+        }
+
+        let rights = self.castling_rights;
+        let white_king = self.holds(4, Piece::WhiteKing);
+        let black_king = self.holds(60, Piece::BlackKing);
+
+        if (rights.white_king_side && !(white_king && self.holds(7, Piece::WhiteRook)))
+            || (rights.white_queen_side && !(white_king && self.holds(0, Piece::WhiteRook)))
+            || (rights.black_king_side && !(black_king && self.holds(63, Piece::BlackRook)))
+            || (rights.black_queen_side && !(black_king && self.holds(56, Piece::BlackRook)))
+        {
+            panic!("a side may castle, but its king or rook has moved");
+        }
+
+        self
+    }
+
+    /// Returns `true` if `piece` stands on the square at `index`.
+    const fn holds(&self, index: usize, piece: Piece) -> bool {
+        match self.pieces[index] {
+            Some(occupant) => occupant as u8 == piece as u8,
+            None => false,
+        }
+    }
+
+    /// Constructs the [`Board`] described by the diagram.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the board does not pass [`Board::validate_setup`].
+    pub fn to_board(self) -> Board {
+        let board = Board::from_parts(
+            self.pieces,
+            self.side_to_move,
+            self.castling_rights,
+            self.en_passant_square,
+        );
+
+        if let Err(err) = board.validate_setup() {
+            panic!("invalid board! diagram: {err}");
+        }
+
+        board
+    }
+}
+
+/// Returns `true` if `a` and `b` are equal, in a `const` context.
+const fn eq(a: &str, b: &str) -> bool {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    if a.len() != b.len() {
+        return false;
+    }
+
+    let mut index = 0;
+    while index < a.len() {
+        if a[index] != b[index] {
+            return false;
+        }
+
+        index += 1;
+    }
+
+    true
+}
+
+/// Parses castling permissions written as in FEN.
+const fn parse_castling(value: &[u8]) -> CastlingPermissions {
+    let mut rights = CastlingPermissions {
+        white_king_side: false,
+        white_queen_side: false,
+        black_king_side: false,
+        black_queen_side: false,
+    };
+
+    if let b"-" = value {
+        return rights;
+    }
+
+    let mut index = 0;
+    while index < value.len() {
+        match value[index] {
+            b'K' => rights.white_king_side = true,
+            b'Q' => rights.white_queen_side = true,
+            b'k' => rights.black_king_side = true,
+            b'q' => rights.black_queen_side = true,
+            _ => panic!("expected the castling permissions to be `-` or some of `KQkq`"),
+        }
+
+        index += 1;
+    }
+
+    rights
+}
+
+/// Parses a square written as a file letter followed by a rank number.
+const fn parse_square(value: &[u8]) -> Square {
+    match value {
+        &[file @ b'a'..=b'h', rank @ b'1'..=b'8'] => Square::at((rank - b'1') * 8 + (file - b'a')),
+        _ => panic!("expected the en passant square to be a square, such as `e3`"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::Standard as _;
+    use crate::io::Fen;
+    use crate::standard::{Board, Color, Square};
+
+    #[test]
+    fn diagrams_are_expanded_to_boards() {
+        let start = board! {
+            r n b q k b n r / p p p p p p p p / . . . . . . . . / . . . . . . . .
+            . . . . . . . . / . . . . . . . . / P P P P P P P P / R N B Q K B N R;
+            side: White, castling: KQkq
+        };
+        assert_eq!(start, Board::default());
+
+        let en_passant = board! {
+            r n b q k b n r
+            p p p p _ p p p
+            _ _ _ _ _ _ _ _
+            _ _ _ _ _ _ _ _
+            _ _ _ _ P _ _ _
+            _ _ _ _ _ _ _ _
+            P P P P _ P P P
+            R N B Q K B N R;
+            side: Black,
+            castling: Kk,
+            en_passant: e3,
+        };
+        let fen =
+            Fen::try_from("rnbqkbnr/pppp1ppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b Kk e3 0 1").unwrap();
+        assert_eq!(en_passant, fen.to_standard_board());
+        assert_eq!(en_passant.side_to_move(), Color::Black);
+        assert_eq!(
+            en_passant.en_passant_target_square(),
+            Square::try_from("e3").ok()
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid board! diagram")]
+    fn diagrams_with_the_opponent_in_check_panic() {
+        board! {
+            . . . . k . . . / . . . . . . . . / . . . . . . . . / . . . . . . . .
+            . . . . . . . . / . . . . . . . . / . . . . . . . . / . . . . R . . K
+        };
+    }
+}
@@ -0,0 +1,110 @@
+use crate::core::{Move as _, Piece as _, Process as _};
+
+use super::{Board, LegalMove, MoveKind, Piece, PieceKind, Square};
+
+/// The changes which processing a [`LegalMove`] makes to the pieces of a [`Board`].
+///
+/// This describes each piece which appears, disappears or changes square,
+/// so that a GUI can animate a move without diffing the boards before and
+/// after it, which is ambiguous for castling and en passant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MoveEffects {
+    /// The piece which moved, as it stood on its source square.
+    pub piece: Piece,
+    /// The square from which the piece moved.
+    pub source: Square,
+    /// The square to which the piece moved.
+    pub target: Square,
+    /// The captured piece and the square it was removed from, which is
+    /// not the target square of an en passant capture.
+    pub capture: Option<(Piece, Square)>,
+    /// The source and target squares of the rook, if the move castles.
+    pub rook: Option<(Square, Square)>,
+    /// The piece which replaces the pawn on its target square, if the move promotes.
+    pub promotion: Option<Piece>,
+    /// Whether the move gives check.
+    pub check: bool,
+}
+
+impl Board {
+    /// Describes the [`MoveEffects`] of processing `legal_move`, which must be legal in `self`.
+    pub fn effects(&self, legal_move: LegalMove) -> MoveEffects {
+        self.process_with_effects(legal_move).1
+    }
+
+    /// Processes `legal_move` as [`Process::process`](crate::core::Process::process)
+    /// does, and also returns its [`MoveEffects`].
+    pub fn process_with_effects(&self, legal_move: LegalMove) -> (Board, MoveEffects) {
+        let (source, target) = (legal_move.source(), legal_move.target());
+        let piece = self[source].expect("a legal move has a piece on its source square");
+        let board = self.process(legal_move);
+
+        let capture = legal_move
+            .captured()
+            .map(|captured| match legal_move.kind() {
+                // the captured pawn stands beside the target square
+                MoveKind::EnPassant => (captured, Square::at(source.rank() * 8 + target.file())),
+                _ => (captured, target),
+            });
+
+        let rook = (legal_move.kind() == MoveKind::Castle).then(|| {
+            let rank_offset = source.rank() * 8;
+            match target.file() {
+                6 => (Square::at(rank_offset + 7), Square::at(rank_offset + 5)),
+                _ => (Square::at(rank_offset), Square::at(rank_offset + 3)),
+            }
+        });
+
+        let promotion = (piece.kind() == PieceKind::Pawn && matches!(target.rank(), 0 | 7))
+            .then(|| board[target])
+            .flatten();
+
+        let effects = MoveEffects {
+            piece,
+            source,
+            target,
+            capture,
+            rook,
+            promotion,
+            check: board.is_check(),
+        };
+
+        (board, effects)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::Validate as _;
+    use crate::io::{Fen, San};
+
+    fn effects(fen: &str, san: &str) -> MoveEffects {
+        let board = Fen::try_from(fen).unwrap().to_validated_board().unwrap();
+        let legal_move = board.validate_san(San::try_from(san).unwrap()).unwrap();
+        board.effects(legal_move)
+    }
+
+    #[test]
+    fn effects_describe_every_changed_square() {
+        let square = |name| Square::try_from(name).unwrap();
+
+        let castle = effects("r3k2r/8/8/8/8/8/8/R3K2R b KQkq - 0 1", "O-O-O");
+        assert_eq!(castle.piece, Piece::BlackKing);
+        assert_eq!((castle.source, castle.target), (square("e8"), square("c8")));
+        assert_eq!(castle.rook, Some((square("a8"), square("d8"))));
+        assert_eq!(castle.capture, None);
+        assert!(!castle.check);
+
+        let en_passant = effects("4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1", "exd6");
+        assert_eq!(en_passant.capture, Some((Piece::BlackPawn, square("d5"))));
+        assert_eq!(en_passant.rook, None);
+        assert!(!en_passant.check);
+
+        let promotion = effects("1n6/P2k4/8/8/8/8/8/4K3 w - - 0 1", "axb8=N+");
+        assert_eq!(promotion.piece, Piece::WhitePawn);
+        assert_eq!(promotion.capture, Some((Piece::BlackKnight, square("b8"))));
+        assert_eq!(promotion.promotion, Some(Piece::WhiteKnight));
+        assert!(promotion.check);
+    }
+}
@@ -0,0 +1,324 @@
+use std::io::{self, BufWriter, Write};
+
+use super::{
+    board::Board,
+    piece::Color,
+    r#move::{IllegalMoveError, LegalMove, Move},
+};
+use crate::{
+    core::{self, Process as _, Standard as _, Validate as _},
+    io::pgn::{self, Tags},
+};
+
+/// The line width used by [`Game::to_pgn`].
+pub const DEFAULT_LINE_WIDTH: usize = 80;
+
+/// The ways in which a game of standard chess can end on the board.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    /// The side to move is checkmated, and the other side has won.
+    Checkmate {
+        /// The side which delivered checkmate.
+        winner: Color,
+    },
+    /// The side to move has no legal moves but is not in check, and the game is drawn.
+    Stalemate,
+}
+
+/// A game of standard chess: a starting position and the moves played from it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Game {
+    start: Board,
+    moves: Vec<LegalMove>,
+    board: Board,
+}
+
+impl Default for Game {
+    fn default() -> Self {
+        Self::from_board(Board::default())
+    }
+}
+
+impl Game {
+    /// Constructs a game from the standard starting position.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Constructs a game starting from the position of `board`.
+    pub fn from_board(board: Board) -> Self {
+        Self {
+            start: board,
+            moves: Vec::new(),
+            board,
+        }
+    }
+
+    /// Returns the position in which the game started.
+    pub fn start(&self) -> &Board {
+        &self.start
+    }
+
+    /// Returns the current position of the game.
+    pub fn board(&self) -> &Board {
+        &self.board
+    }
+
+    /// Returns the moves played so far, in order.
+    pub fn moves(&self) -> &[LegalMove] {
+        &self.moves
+    }
+
+    /// Returns an iterator over the positions of the game, from the starting
+    /// position to the current one, so it yields one more board than there are moves.
+    pub fn positions(&self) -> impl Iterator<Item = Board> + '_ {
+        std::iter::once(self.start).chain(self.annotated_moves().map(|(_, board)| board))
+    }
+
+    /// Returns an iterator over the moves played so far, each with the position it
+    /// produced.
+    pub fn annotated_moves(&self) -> impl Iterator<Item = (LegalMove, Board)> + '_ {
+        self.moves.iter().scan(self.start, |board, &legal_move| {
+            *board = board.process(legal_move);
+            Some((legal_move, *board))
+        })
+    }
+
+    /// Validates `candidate` in the current position and plays it.
+    pub fn play(&mut self, candidate: Move) -> Result<LegalMove, IllegalMoveError> {
+        let legal_move = self.board.validate(candidate)?;
+        self.board = self.board.process(legal_move);
+        self.moves.push(legal_move);
+        Ok(legal_move)
+    }
+
+    /// Takes back the last move, returning it.
+    pub fn undo(&mut self) -> Option<LegalMove> {
+        let legal_move = self.moves.pop()?;
+        self.board = self.board.unmake(legal_move);
+        Some(legal_move)
+    }
+
+    /// Returns the outcome of the game if the side to move has no legal moves.
+    pub fn outcome(&self) -> Option<Outcome> {
+        if self.board.has_legal_moves() {
+            return None;
+        }
+
+        Some(match self.board.is_check() {
+            true => Outcome::Checkmate {
+                winner: self.board.side_to_move().opposite(),
+            },
+            false => Outcome::Stalemate,
+        })
+    }
+
+    /// Returns the PGN result token of the current position: the winner if the side
+    /// to move is checkmated, a draw if it is stalemated, and `*` otherwise.
+    pub fn result(&self) -> &'static str {
+        match self.outcome() {
+            None => "*",
+            Some(Outcome::Stalemate) => "1/2-1/2",
+            Some(Outcome::Checkmate {
+                winner: Color::White,
+            }) => "1-0",
+            Some(Outcome::Checkmate {
+                winner: Color::Black,
+            }) => "0-1",
+        }
+    }
+
+    /// Exports the game as PGN with the given `tags`, wrapping the
+    /// movetext at [`DEFAULT_LINE_WIDTH`] characters.
+    ///
+    /// See [`Game::to_pgn_with_width`] for how the tags are written.
+    pub fn to_pgn(&self, tags: &Tags) -> String {
+        self.to_pgn_with_width(tags, DEFAULT_LINE_WIDTH)
+    }
+
+    /// Exports the game as PGN with the given `tags`, wrapping the
+    /// movetext at `width` characters.
+    ///
+    /// The Seven Tag Roster is written first, with `?` standing in for any
+    /// missing tags, followed by the other `tags` in order. The `Result` tag
+    /// defaults to [`Game::result`], and the `SetUp` and `FEN` tags are added
+    /// if the game did not start from the standard position.
+    pub fn to_pgn_with_width(&self, tags: &Tags, width: usize) -> String {
+        let mut tags = tags.clone();
+        let result = tags
+            .get("Result")
+            .unwrap_or_else(|| self.result())
+            .to_string();
+        tags.insert("Result", result.as_str());
+
+        if self.start != Board::default() {
+            tags.insert("SetUp", "1");
+            tags.insert("FEN", crate::io::Fen::from(&self.start).to_string());
+        }
+
+        pgn::format_game(&self.start, &self.moves, &tags, &result, width)
+    }
+
+    /// Writes the game to `writer` as PGN, as exported by [`Game::to_pgn`],
+    /// followed by a blank line so that several games may be written in turn.
+    pub fn to_pgn_writer<W: Write>(&self, tags: &Tags, writer: W) -> io::Result<()> {
+        let mut writer = BufWriter::new(writer);
+        writer.write_all(self.to_pgn(tags).as_bytes())?;
+        writer.write_all(b"\n")?;
+        writer.flush()
+    }
+}
+
+impl core::Game for Game {
+    type Position = Board;
+    type Outcome = Outcome;
+
+    fn start(&self) -> &Board {
+        &self.start
+    }
+
+    fn position(&self) -> &Board {
+        &self.board
+    }
+
+    fn moves(&self) -> &[LegalMove] {
+        &self.moves
+    }
+
+    fn outcome(&self) -> Option<Outcome> {
+        Game::outcome(self)
+    }
+
+    fn play(&mut self, candidate: Move) -> Result<LegalMove, IllegalMoveError> {
+        Game::play(self, candidate)
+    }
+
+    fn undo(&mut self) -> Option<LegalMove> {
+        Game::undo(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::{Fen, PgnReader};
+    use crate::standard::r#move::mv;
+
+    #[test]
+    fn moves_are_played_and_taken_back() {
+        let mut game = Game::new();
+        game.play(mv("e2", "e4")).unwrap();
+        assert!(game.play(mv("e2", "e4")).is_err());
+        game.play(mv("e7", "e5")).unwrap();
+        assert_eq!(game.moves().len(), 2);
+
+        assert_eq!(Move::from(game.undo().unwrap()), mv("e7", "e5"));
+        assert_eq!(game.board(), &game.start().process(game.moves()[0]));
+        assert_eq!(game.result(), "*");
+    }
+
+    #[test]
+    fn positions_are_replayed() {
+        let mut game = Game::new();
+        for (source, target) in [("e2", "e4"), ("e7", "e5"), ("g1", "f3")] {
+            game.play(mv(source, target)).unwrap();
+        }
+
+        let positions: Vec<Board> = game.positions().collect();
+        assert_eq!(positions.len(), 4);
+        assert_eq!(positions.first(), Some(game.start()));
+        assert_eq!(positions.last(), Some(game.board()));
+
+        for (index, (legal_move, board)) in game.annotated_moves().enumerate() {
+            assert_eq!(legal_move, game.moves()[index]);
+            assert_eq!(board, positions[index].process(legal_move));
+            assert_eq!(board, positions[index + 1]);
+        }
+
+        assert_eq!(Game::new().positions().count(), 1);
+        assert_eq!(Game::new().annotated_moves().count(), 0);
+    }
+
+    #[test]
+    fn games_are_driven_through_the_core_trait() {
+        use crate::core::Game as _;
+
+        fn replay<G: core::Game<Position = Board>>(game: &G) -> Board {
+            game.moves()
+                .iter()
+                .fold(*game.start(), |board, &legal_move| {
+                    board.process(legal_move)
+                })
+        }
+
+        let mut game = Game::new();
+        for (source, target) in [("f2", "f3"), ("e7", "e5"), ("g2", "g4"), ("d8", "h4")] {
+            core::Game::play(&mut game, mv(source, target)).unwrap();
+        }
+
+        assert_eq!(game.ply_count(), 4);
+        assert_eq!(replay(&game), *game.position());
+        assert_eq!(
+            game.outcome(),
+            Some(Outcome::Checkmate {
+                winner: Color::Black
+            })
+        );
+        assert!(game.is_over());
+        assert!(!Game::new().is_over());
+    }
+
+    #[test]
+    fn games_are_exported_as_pgn() {
+        let mut game = Game::new();
+        for (source, target) in [("f2", "f3"), ("e7", "e5"), ("g2", "g4"), ("d8", "h4")] {
+            game.play(mv(source, target)).unwrap();
+        }
+
+        let mut tags = Tags::new();
+        tags.insert("Annotator", "konig");
+        tags.insert("White", "Fool");
+
+        let pgn = game.to_pgn(&tags);
+        assert_eq!(
+            pgn,
+            "[Event \"?\"]\n[Site \"?\"]\n[Date \"????.??.??\"]\n[Round \"?\"]\n\
+             [White \"Fool\"]\n[Black \"?\"]\n[Result \"0-1\"]\n[Annotator \"konig\"]\n\n\
+             1. f3 e5 2. g4 Qh4# 0-1\n"
+        );
+
+        // the export can be read back
+        let read = PgnReader::new(pgn.as_bytes()).next().unwrap().unwrap();
+        assert_eq!(read.legal_moves().unwrap(), game.moves());
+
+        // and several games can be written to and read from a stream
+        let mut file = Vec::new();
+        game.to_pgn_writer(&tags, &mut file).unwrap();
+        Game::new().to_pgn_writer(&Tags::new(), &mut file).unwrap();
+        let games: Vec<_> = PgnReader::from_reader(file.as_slice())
+            .map(Result::unwrap)
+            .collect();
+        assert_eq!(games.len(), 2);
+        assert_eq!(games[0].legal_moves().unwrap(), game.moves());
+        assert!(games[1].moves.is_empty());
+    }
+
+    #[test]
+    fn exports_respect_the_starting_position_and_width() {
+        let start = Fen::try_from("r3k2r/8/8/8/8/8/8/R3K2R b KQkq - 0 1")
+            .unwrap()
+            .to_standard_board();
+        let mut game = Game::from_board(start);
+        for (source, target) in [("e8", "c8"), ("e1", "g1"), ("d8", "d1"), ("f1", "d1")] {
+            game.play(mv(source, target)).unwrap();
+        }
+
+        let pgn = game.to_pgn_with_width(&Tags::new(), 12);
+        let movetext: Vec<&str> = pgn.split("\n\n").nth(1).unwrap().lines().collect();
+        assert_eq!(movetext, ["1... O-O-O", "2. O-O Rd1", "3. Rfxd1 *"]);
+        assert!(pgn.contains("[FEN \"r3k2r/8/8/8/8/8/8/R3K2R b KQkq - 0 1\"]"));
+
+        let read = PgnReader::new(pgn.as_bytes()).next().unwrap().unwrap();
+        assert_eq!(read.final_board().unwrap(), *game.board());
+    }
+}
@@ -0,0 +1,204 @@
+use super::{
+    attacks,
+    bitboard::BitBoard,
+    board::Board,
+    pawns::adjacent_files_mask,
+    piece::{Color, PieceKind},
+    Square,
+};
+use crate::core::Piece as _;
+
+/// A summary of how exposed the king of one side of a [`Board`] is.
+///
+/// The king zone is the king's own square together with every square adjacent
+/// to it, and the pawn shield is the set of friendly pawns standing on the one
+/// or two ranks directly in front of the king, on its own or adjacent files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KingSafety {
+    king: Square,
+    zone: BitBoard,
+    zone_attacks: u32,
+    zone_attackers: u32,
+    shield: BitBoard,
+    missing_shield_pawns: u32,
+    open_files: u32,
+    semi_open_files: u32,
+}
+
+impl KingSafety {
+    /// Computes the safety of the king of the given `color` on `board`,
+    /// returning `None` if there is no such king.
+    pub fn new(board: &Board, color: Color) -> Option<Self> {
+        let king = board.king_square(color)?;
+        let enemy = color.opposite();
+        let occupied = board.occupied();
+        let zone = BitBoard::from_square(king) | attacks::king(king);
+
+        let mut zone_attacks = 0;
+        let mut zone_attackers = 0;
+        for square in board.occupancy(enemy).squares() {
+            let kind = match board[square] {
+                Some(piece) if piece.kind() != PieceKind::King => piece.kind(),
+                _ => continue,
+            };
+
+            let attacked = (attacks::attacks(enemy, kind, square, occupied) & zone).count();
+            if attacked > 0 {
+                zone_attacks += attacked;
+                zone_attackers += 1;
+            }
+        }
+
+        let ours = board.pieces(color, PieceKind::Pawn);
+        let theirs = board.pieces(enemy, PieceKind::Pawn);
+        let files = BitBoard::file(king.file()) | adjacent_files_mask(king.file());
+        let shield = ours & files & shield_ranks(color, king.rank());
+
+        let mut missing_shield_pawns = 0;
+        let mut open_files = 0;
+        let mut semi_open_files = 0;
+        for file in king.file().saturating_sub(1)..=(king.file() + 1).min(7) {
+            let file = BitBoard::file(file);
+            if (shield & file).is_empty() {
+                missing_shield_pawns += 1;
+            }
+
+            match ((ours & file).is_empty(), (theirs & file).is_empty()) {
+                (true, true) => open_files += 1,
+                (true, false) => semi_open_files += 1,
+                _ => {}
+            }
+        }
+
+        Some(Self {
+            king,
+            zone,
+            zone_attacks,
+            zone_attackers,
+            shield,
+            missing_shield_pawns,
+            open_files,
+            semi_open_files,
+        })
+    }
+
+    /// Returns the square of the king.
+    pub fn king(&self) -> Square {
+        self.king
+    }
+
+    /// Returns the king zone, i.e. the king's square and every square adjacent to it.
+    pub fn zone(&self) -> BitBoard {
+        self.zone
+    }
+
+    /// Returns the total number of attacks on the king zone by opposing pieces
+    /// other than the king, counting each attacked square once per attacker.
+    pub fn zone_attacks(&self) -> u32 {
+        self.zone_attacks
+    }
+
+    /// Returns the number of opposing pieces which attack at least one square of the king zone.
+    pub fn zone_attackers(&self) -> u32 {
+        self.zone_attackers
+    }
+
+    /// Returns the friendly pawns forming the king's pawn shield.
+    pub fn shield(&self) -> BitBoard {
+        self.shield
+    }
+
+    /// Returns the number of files around the king without a pawn in its shield.
+    pub fn missing_shield_pawns(&self) -> u32 {
+        self.missing_shield_pawns
+    }
+
+    /// Returns the number of files around the king which hold no pawns at all.
+    pub fn open_files(&self) -> u32 {
+        self.open_files
+    }
+
+    /// Returns the number of files around the king which hold opposing pawns but no friendly ones.
+    pub fn semi_open_files(&self) -> u32 {
+        self.semi_open_files
+    }
+}
+
+impl Board {
+    /// Returns the [`KingSafety`] of the given `color`, or `None` if it has no king.
+    pub fn king_safety(&self, color: Color) -> Option<KingSafety> {
+        KingSafety::new(self, color)
+    }
+}
+
+/// Returns the one or two ranks directly in front of `rank`,
+/// from the perspective of the given `color`.
+fn shield_ranks(color: Color, rank: u8) -> BitBoard {
+    let ranks = match color {
+        Color::White => [rank.checked_add(1), rank.checked_add(2)],
+        Color::Black => [rank.checked_sub(1), rank.checked_sub(2)],
+    };
+
+    ranks
+        .into_iter()
+        .flatten()
+        .filter(|&rank| rank < 8)
+        .fold(BitBoard::EMPTY, |mask, rank| mask | BitBoard::rank(rank))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::Fen;
+
+    #[test]
+    fn starting_kings_are_safe() {
+        let board = Board::default();
+        for color in [Color::White, Color::Black] {
+            let safety = board.king_safety(color).unwrap();
+            assert_eq!(safety.zone().count(), 6);
+            assert_eq!(safety.zone_attacks(), 0);
+            assert_eq!(safety.shield().count(), 3);
+            assert_eq!(safety.missing_shield_pawns(), 0);
+            assert_eq!(safety.open_files(), 0);
+            assert_eq!(safety.semi_open_files(), 0);
+        }
+    }
+
+    #[test]
+    fn exposed_king_is_measured() {
+        // the queen on h4 hits h1, h2 and f2, and neither side has an h-pawn
+        let board = Fen::try_from("6k1/5pp1/8/8/7q/8/5PP1/6K1 w - - 0 1")
+            .unwrap()
+            .to_standard_board();
+
+        let white = board.king_safety(Color::White).unwrap();
+        assert_eq!(white.king(), Square::try_from("g1").unwrap());
+        assert_eq!(white.zone_attacks(), 3);
+        assert_eq!(white.zone_attackers(), 1);
+        assert_eq!(white.shield().count(), 2);
+        assert_eq!(white.missing_shield_pawns(), 1);
+        assert_eq!(white.open_files(), 1);
+
+        let black = board.king_safety(Color::Black).unwrap();
+        assert_eq!(black.zone_attacks(), 0);
+        assert_eq!(black.missing_shield_pawns(), 1);
+    }
+
+    #[test]
+    fn semi_open_files_are_counted_and_missing_kings_are_none() {
+        let board = Fen::try_from("7k/8/8/8/8/8/3p4/4K3 w - - 0 1")
+            .unwrap()
+            .to_standard_board();
+
+        let white = board.king_safety(Color::White).unwrap();
+        assert_eq!(white.semi_open_files(), 1);
+        assert_eq!(white.open_files(), 2);
+        assert_eq!(white.zone_attacks(), 1);
+
+        let empty = Fen::try_from("8/8/8/8/8/8/8/4K3 w - - 0 1")
+            .unwrap()
+            .to_standard_board();
+        assert_eq!(empty.king_safety(Color::Black), None);
+    }
+}
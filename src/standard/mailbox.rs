@@ -0,0 +1,700 @@
+use thiserror::Error;
+
+use super::{
+    board::{Board, CastlingPermissions},
+    movegen::PROMOTIONS,
+    piece::{Color, Piece, PieceKind},
+    r#move::Move,
+    Square,
+};
+use crate::{
+    core::{self, r#move, r#move::WrapMove, Move as _, Piece as _, Standard as _},
+    io::San,
+};
+
+/// The number of indices in a 0x88 board, half of which lie off the board.
+const SIZE: usize = 128;
+
+/// The offsets from a square to the squares a knight attacks.
+const KNIGHT_OFFSETS: [i16; 8] = [-33, -31, -18, -14, 14, 18, 31, 33];
+
+/// The offsets along which a bishop slides.
+const BISHOP_OFFSETS: [i16; 4] = [-17, -15, 15, 17];
+
+/// The offsets along which a rook slides.
+const ROOK_OFFSETS: [i16; 4] = [-16, -1, 1, 16];
+
+/// The offsets from a square to the squares a king attacks.
+const KING_OFFSETS: [i16; 8] = [-17, -16, -15, -1, 1, 15, 16, 17];
+
+/// Returns the 0x88 index of `square`.
+fn index_of(square: Square) -> usize {
+    square.rank() as usize * 16 + square.file() as usize
+}
+
+/// Returns the square at the 0x88 `index`, which must lie on the board.
+fn square_at(index: usize) -> Square {
+    Square::at(((index >> 4) * 8 + (index & 7)) as u8)
+}
+
+/// Returns the index `offset` away from `index`, or `None` if it lies off the board.
+///
+/// This is the point of the 0x88 layout: every index off the board, in either
+/// direction, has one of the bits of `0x88` set.
+fn step(index: usize, offset: i16) -> Option<usize> {
+    let target = index as i16 + offset;
+    (target >= 0 && target & 0x88 == 0).then_some(target as usize)
+}
+
+/// Results when a [`MailboxMove`] cannot be converted into a [`MailboxLegalMove`].
+#[derive(Debug, Error)]
+pub enum IllegalMailboxMoveError {
+    /// Results when a move is illegal because the friendly king is in check.
+    #[error("Invalid move {0:?}: the friendly king is in check.")]
+    Check(MailboxMove),
+    /// Results when a move is illegal because it has an invalid source index.
+    #[error("Invalid move source: {0}")]
+    InvalidSource(Square),
+    /// Results when a move is illegal because it has an invalid target index.
+    #[error("Invalid move target: {0}")]
+    InvalidTarget(Square),
+    /// Results when a SAN literal does not describe exactly one legal move.
+    #[error("Invalid SAN literal: it describes no unique legal move")]
+    InvalidSan,
+}
+
+impl r#move::IllegalMoveError for IllegalMailboxMoveError {
+    type Board = Mailbox0x88;
+    type Index = Square;
+    type Move = MailboxMove;
+    type LegalMove = MailboxLegalMove;
+}
+
+/// Represents a possible move on a [`Mailbox0x88`], including illegal moves.
+///
+/// This is a thin wrapper around a standard [`Move`], to which it can be freely converted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MailboxMove(Move);
+
+/// Represents a legal move on a [`Mailbox0x88`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MailboxLegalMove(MailboxMove);
+
+impl core::Move for MailboxMove {
+    type Board = Mailbox0x88;
+    type Index = Square;
+
+    fn source(&self) -> Self::Index {
+        self.0.source()
+    }
+
+    fn target(&self) -> Self::Index {
+        self.0.target()
+    }
+}
+
+impl core::Promote for MailboxMove {
+    type Kind = PieceKind;
+
+    fn promotion(&self) -> Option<Self::Kind> {
+        self.0.promotion()
+    }
+}
+
+impl core::Move for MailboxLegalMove {
+    type Board = Mailbox0x88;
+    type Index = Square;
+
+    fn source(&self) -> Self::Index {
+        self.0.source()
+    }
+
+    fn target(&self) -> Self::Index {
+        self.0.target()
+    }
+}
+
+impl core::LegalMove for MailboxLegalMove {
+    type Move = MailboxMove;
+}
+
+impl WrapMove for MailboxLegalMove {
+    fn wrap(_board: &Self::Board, value: Self::Move) -> Self {
+        Self(value)
+    }
+}
+
+impl From<Move> for MailboxMove {
+    fn from(value: Move) -> Self {
+        Self(value)
+    }
+}
+
+impl From<(Square, Square)> for MailboxMove {
+    fn from(value: (Square, Square)) -> Self {
+        Self(value.into())
+    }
+}
+
+impl From<MailboxMove> for Move {
+    fn from(value: MailboxMove) -> Self {
+        value.0
+    }
+}
+
+impl From<MailboxLegalMove> for MailboxMove {
+    fn from(value: MailboxLegalMove) -> Self {
+        value.0
+    }
+}
+
+impl From<MailboxLegalMove> for Move {
+    fn from(value: MailboxLegalMove) -> Self {
+        value.0 .0
+    }
+}
+
+/// A standard chess board stored as a 0x88 mailbox.
+///
+/// The 64 squares are laid out in a 128-element array with 16 indices per rank,
+/// of which only the first 8 lie on the board; an index is then off the board
+/// exactly when it has a bit of `0x88` set, which makes move generation by
+/// offsets cheap and simple. This is much slower than the [`BitBoard`](super::BitBoard)
+/// based generator of a [`Board`], but is independent of it, and so serves as a
+/// reference implementation against which the latter can be checked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Mailbox0x88 {
+    squares: [Option<Piece>; SIZE],
+    side_to_move: Color,
+    castling_rights: CastlingPermissions,
+    en_passant_square: Option<Square>,
+}
+
+impl Default for Mailbox0x88 {
+    fn default() -> Self {
+        Self::from(&Board::default())
+    }
+}
+
+impl From<&Board> for Mailbox0x88 {
+    fn from(value: &Board) -> Self {
+        let mut squares = [None; SIZE];
+        for index in 0..64 {
+            let square = Square::at(index);
+            squares[index_of(square)] = value[square];
+        }
+
+        Self {
+            squares,
+            side_to_move: value.side_to_move(),
+            castling_rights: value.castling_permissions(),
+            en_passant_square: value.en_passant_target_square(),
+        }
+    }
+}
+
+impl From<&Mailbox0x88> for Board {
+    fn from(value: &Mailbox0x88) -> Self {
+        let mut pieces = [None; 64];
+        for (index, piece) in pieces.iter_mut().enumerate() {
+            *piece = value[Square::at(index as u8)];
+        }
+
+        Board::from_parts(
+            pieces,
+            value.side_to_move,
+            value.castling_rights,
+            value.en_passant_square,
+        )
+    }
+}
+
+impl std::ops::Index<Square> for Mailbox0x88 {
+    type Output = Option<Piece>;
+
+    fn index(&self, index: Square) -> &Self::Output {
+        &self.squares[index_of(index)]
+    }
+}
+
+impl core::Position for Mailbox0x88 {
+    type Index = Square;
+    type Piece = Piece;
+
+    fn get_piece_at(&self, index: Self::Index) -> Option<&Self::Piece> {
+        self[index].as_ref()
+    }
+}
+
+impl core::Standard for Mailbox0x88 {
+    type Color = Color;
+
+    type CastlingPermissions = CastlingPermissions;
+
+    fn side_to_move(&self) -> Self::Color {
+        self.side_to_move
+    }
+
+    fn castling_permissions(&self) -> Self::CastlingPermissions {
+        self.castling_rights
+    }
+
+    fn en_passant_target_square(&self) -> Option<Self::Index> {
+        self.en_passant_square
+    }
+}
+
+impl core::Validate for Mailbox0x88 {
+    type Move = MailboxMove;
+    type LegalMove = MailboxLegalMove;
+    type ValidationError = IllegalMailboxMoveError;
+
+    fn validate(&self, candidate: Self::Move) -> Result<Self::LegalMove, Self::ValidationError> {
+        let (source, target) = candidate.as_pair();
+        let piece = match self[source] {
+            Some(piece) if piece.color() == self.side_to_move => piece,
+            _ => return Err(IllegalMailboxMoveError::InvalidSource(source)),
+        };
+
+        // a pawn reaching its last rank without a promotion piece promotes to a queen
+        let mut candidate = Move::from(candidate);
+        if piece.kind() == PieceKind::Pawn
+            && matches!(target.rank(), 0 | 7)
+            && candidate.promotion().is_none()
+        {
+            candidate = candidate.with_promotion(PieceKind::Queen);
+        }
+
+        if !self.pseudo_legal_moves().contains(&candidate) {
+            return Err(IllegalMailboxMoveError::InvalidTarget(target));
+        }
+
+        if !self.is_legal(candidate) {
+            return Err(IllegalMailboxMoveError::Check(candidate.into()));
+        }
+
+        Ok(MailboxLegalMove::wrap(self, candidate.into()))
+    }
+
+    fn validate_san(&self, candidate: San) -> Result<Self::LegalMove, Self::ValidationError>
+    where
+        Self: core::Standard + Sized,
+    {
+        let board = Board::from(self);
+        let mut matching = self
+            .legal_moves()
+            .filter(|&legal_move| candidate.describes(&board, legal_move.into()));
+
+        match (matching.next(), matching.next()) {
+            (Some(legal_move), None) => Ok(legal_move),
+            _ => Err(IllegalMailboxMoveError::InvalidSan),
+        }
+    }
+}
+
+impl core::Process for Mailbox0x88 {
+    fn process(&self, candidate: Self::LegalMove) -> Self {
+        self.make(candidate.into())
+    }
+}
+
+impl core::Keyed for Mailbox0x88 {
+    type Key = u64;
+
+    /// Returns the [Zobrist hash](Board::zobrist) of the equivalent [`Board`].
+    fn key(&self) -> u64 {
+        Board::from(self).zobrist()
+    }
+}
+
+impl Mailbox0x88 {
+    /// Returns an iterator over the legal moves in this position.
+    pub fn legal_moves(&self) -> impl Iterator<Item = MailboxLegalMove> + '_ {
+        self.pseudo_legal_moves()
+            .into_iter()
+            .filter(|&candidate| self.is_legal(candidate))
+            .map(|candidate| MailboxLegalMove::wrap(self, candidate.into()))
+    }
+
+    /// Returns the pseudo-legal moves in this position, i.e. the moves which obey
+    /// the movement rules of each piece but might leave the friendly king in check.
+    pub fn pseudo_legal_moves(&self) -> Vec<Move> {
+        let mut moves = Vec::new();
+        let us = self.side_to_move;
+
+        for from in (0..SIZE).filter(|index| index & 0x88 == 0) {
+            let Some(piece) = self.squares[from].filter(|piece| piece.color() == us) else {
+                continue;
+            };
+
+            match piece.kind() {
+                PieceKind::Pawn => self.generate_pawn(from, &mut moves),
+                PieceKind::Knight => {
+                    self.generate_offsets(from, &KNIGHT_OFFSETS, false, &mut moves)
+                }
+                PieceKind::Bishop => self.generate_offsets(from, &BISHOP_OFFSETS, true, &mut moves),
+                PieceKind::Rook => self.generate_offsets(from, &ROOK_OFFSETS, true, &mut moves),
+                PieceKind::Queen => {
+                    self.generate_offsets(from, &BISHOP_OFFSETS, true, &mut moves);
+                    self.generate_offsets(from, &ROOK_OFFSETS, true, &mut moves);
+                }
+                PieceKind::King => {
+                    self.generate_offsets(from, &KING_OFFSETS, false, &mut moves);
+                    self.generate_castling(from, &mut moves);
+                }
+            }
+        }
+
+        moves
+    }
+
+    /// Returns `true` if the king of the side to move is in check.
+    pub fn is_check(&self) -> bool {
+        let us = self.side_to_move;
+        self.king_index(us)
+            .is_some_and(|king| self.is_attacked(king, us.opposite()))
+    }
+
+    /// Returns `true` if playing the pseudo-legal `candidate` does not leave the friendly king in check.
+    fn is_legal(&self, candidate: Move) -> bool {
+        let us = self.side_to_move;
+        let after = self.make(candidate);
+        !after
+            .king_index(us)
+            .is_some_and(|king| after.is_attacked(king, us.opposite()))
+    }
+
+    /// Returns the index of the king of the given `color`, if there is one.
+    fn king_index(&self, color: Color) -> Option<usize> {
+        let king = Piece::new(color, PieceKind::King);
+        (0..SIZE).find(|&index| index & 0x88 == 0 && self.squares[index] == Some(king))
+    }
+
+    /// Returns `true` if the square at `index` is attacked by a piece of the given color.
+    fn is_attacked(&self, index: usize, by: Color) -> bool {
+        let is = |index: Option<usize>, kinds: &[PieceKind]| {
+            index
+                .and_then(|index| self.squares[index])
+                .is_some_and(|piece| piece.color() == by && kinds.contains(&piece.kind()))
+        };
+
+        // a pawn attacks the squares diagonally in front of it
+        let pawn_offsets: [i16; 2] = match by {
+            Color::White => [-15, -17],
+            Color::Black => [15, 17],
+        };
+
+        pawn_offsets
+            .iter()
+            .any(|&offset| is(step(index, offset), &[PieceKind::Pawn]))
+            || KNIGHT_OFFSETS
+                .iter()
+                .any(|&offset| is(step(index, offset), &[PieceKind::Knight]))
+            || KING_OFFSETS
+                .iter()
+                .any(|&offset| is(step(index, offset), &[PieceKind::King]))
+            || BISHOP_OFFSETS.iter().any(|&offset| {
+                is(
+                    self.first_occupied(index, offset),
+                    &[PieceKind::Bishop, PieceKind::Queen],
+                )
+            })
+            || ROOK_OFFSETS.iter().any(|&offset| {
+                is(
+                    self.first_occupied(index, offset),
+                    &[PieceKind::Rook, PieceKind::Queen],
+                )
+            })
+    }
+
+    /// Returns the index of the first occupied square from `index` in the direction of `offset`.
+    fn first_occupied(&self, mut index: usize, offset: i16) -> Option<usize> {
+        loop {
+            index = step(index, offset)?;
+            if self.squares[index].is_some() {
+                return Some(index);
+            }
+        }
+    }
+
+    /// Pushes the moves of the piece at `from` in the directions of `offsets` onto
+    /// `moves`, continuing in each direction until it is blocked if `slides` holds.
+    fn generate_offsets(&self, from: usize, offsets: &[i16], slides: bool, moves: &mut Vec<Move>) {
+        let source = square_at(from);
+
+        for &offset in offsets {
+            let mut index = from;
+            while let Some(to) = step(index, offset) {
+                match self.squares[to] {
+                    Some(piece) => {
+                        if piece.color() != self.side_to_move {
+                            moves.push(Move::from((source, square_at(to))));
+                        }
+
+                        break;
+                    }
+                    None => moves.push(Move::from((source, square_at(to)))),
+                }
+
+                if !slides {
+                    break;
+                }
+
+                index = to;
+            }
+        }
+    }
+
+    /// Pushes the moves of the pawn at `from` onto `moves`.
+    fn generate_pawn(&self, from: usize, moves: &mut Vec<Move>) {
+        let us = self.side_to_move;
+        let source = square_at(from);
+        let (forward, start_rank, last_rank): (i16, u8, u8) = match us {
+            Color::White => (16, 1, 7),
+            Color::Black => (-16, 6, 0),
+        };
+
+        let push = |moves: &mut Vec<Move>, to: usize| {
+            let candidate = Move::from((source, square_at(to)));
+            match square_at(to).rank() == last_rank {
+                true => moves.extend(PROMOTIONS.map(|kind| candidate.with_promotion(kind))),
+                false => moves.push(candidate),
+            }
+        };
+
+        let is_empty = |index: &usize| self.squares[*index].is_none();
+        if let Some(single) = step(from, forward).filter(is_empty) {
+            push(moves, single);
+
+            if source.rank() == start_rank {
+                if let Some(double) = step(single, forward).filter(is_empty) {
+                    push(moves, double);
+                }
+            }
+        }
+
+        for offset in [forward - 1, forward + 1] {
+            let Some(to) = step(from, offset) else {
+                continue;
+            };
+
+            match self.squares[to] {
+                Some(piece) if piece.color() != us => push(moves, to),
+                None if self.en_passant_square == Some(square_at(to)) => push(moves, to),
+                _ => (),
+            }
+        }
+    }
+
+    /// Pushes the castling moves of the king at `from` onto `moves`.
+    fn generate_castling(&self, from: usize, moves: &mut Vec<Move>) {
+        let us = self.side_to_move;
+        let rights = self.castling_rights;
+        let (king_side, queen_side, home) = match us {
+            Color::White => (rights.white_king_side, rights.white_queen_side, 0x04),
+            Color::Black => (rights.black_king_side, rights.black_queen_side, 0x74),
+        };
+
+        let them = us.opposite();
+        if from != home || !(king_side || queen_side) || self.is_attacked(from, them) {
+            return;
+        }
+
+        let rook = Some(Piece::new(us, PieceKind::Rook));
+        let is_empty = |index: usize| self.squares[index].is_none();
+        let source = square_at(from);
+
+        if king_side
+            && self.squares[from + 3] == rook
+            && is_empty(from + 1)
+            && is_empty(from + 2)
+            && !self.is_attacked(from + 1, them)
+        {
+            moves.push(Move::from((source, square_at(from + 2))));
+        }
+
+        if queen_side
+            && self.squares[from - 4] == rook
+            && is_empty(from - 1)
+            && is_empty(from - 2)
+            && is_empty(from - 3)
+            && !self.is_attacked(from - 1, them)
+        {
+            moves.push(Move::from((source, square_at(from - 2))));
+        }
+    }
+
+    /// Plays `candidate` on a copy of `self` and returns the result,
+    /// without checking that the move is legal or even pseudo-legal.
+    fn make(&self, candidate: Move) -> Self {
+        let mut next = *self;
+        let (source, target) = candidate.as_pair();
+        let (from, to) = (index_of(source), index_of(target));
+        let Some(piece) = self.squares[from] else {
+            return next;
+        };
+
+        let us = piece.color();
+        next.squares[from] = None;
+        next.en_passant_square = None;
+
+        match piece.kind() {
+            PieceKind::Pawn => {
+                // an en passant capture removes the pawn beside the source
+                if self.en_passant_square == Some(target)
+                    && self.squares[to].is_none()
+                    && source.file() != target.file()
+                {
+                    next.squares[(from & 0x70) | (to & 0x07)] = None;
+                }
+
+                if from.abs_diff(to) == 32 {
+                    next.en_passant_square = Some(square_at((from + to) / 2));
+                }
+
+                let kind = match target.rank() {
+                    0 | 7 => candidate.promotion().unwrap_or(PieceKind::Queen),
+                    _ => PieceKind::Pawn,
+                };
+
+                next.squares[to] = Some(Piece::new(us, kind));
+            }
+            PieceKind::King if source.file_distance(target) == 2 => {
+                // castling also moves the rook to the square the king crossed
+                let (rook_from, rook_to) = match to > from {
+                    true => (from + 3, from + 1),
+                    false => (from - 4, from - 1),
+                };
+
+                next.squares[rook_to] = next.squares[rook_from].take();
+                next.squares[to] = Some(piece);
+            }
+            _ => next.squares[to] = Some(piece),
+        }
+
+        let rights = &mut next.castling_rights;
+        if piece.kind() == PieceKind::King {
+            match us {
+                Color::White => {
+                    rights.white_king_side = false;
+                    rights.white_queen_side = false;
+                }
+                Color::Black => {
+                    rights.black_king_side = false;
+                    rights.black_queen_side = false;
+                }
+            }
+        }
+
+        for corner in [from, to] {
+            match corner {
+                0x00 => rights.white_queen_side = false,
+                0x07 => rights.white_king_side = false,
+                0x70 => rights.black_queen_side = false,
+                0x77 => rights.black_king_side = false,
+                _ => (),
+            }
+        }
+
+        next.side_to_move = us.opposite();
+        next
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{Process as _, Validate as _};
+    use crate::io::Fen;
+
+    const POSITIONS: [&str; 5] = [
+        "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+        "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+        "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+        "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1",
+        "rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq f6 0 1",
+    ];
+
+    fn perft(mailbox: &Mailbox0x88, depth: u8) -> u64 {
+        match depth {
+            0 => 1,
+            _ => mailbox
+                .legal_moves()
+                .map(|legal_move| perft(&mailbox.process(legal_move), depth - 1))
+                .sum(),
+        }
+    }
+
+    #[test]
+    fn indices_off_the_board_are_detected() {
+        assert_eq!(step(0x00, -1), None);
+        assert_eq!(step(0x07, 1), None);
+        assert_eq!(step(0x70, 16), None);
+        assert_eq!(step(0x77, 33), None);
+        assert_eq!(step(0x00, 33), Some(0x21));
+        assert_eq!(square_at(index_of(Square::at(42))), Square::at(42));
+    }
+
+    #[test]
+    fn boards_round_trip_through_mailboxes() {
+        for fen in POSITIONS {
+            let board = Fen::try_from(fen).unwrap().to_standard_board();
+            let mailbox = Mailbox0x88::from(&board);
+            assert_eq!(Board::from(&mailbox), board);
+            assert_eq!(mailbox.is_check(), board.is_check());
+        }
+
+        assert_eq!(Board::from(&Mailbox0x88::default()), Board::default());
+    }
+
+    #[test]
+    fn move_generation_agrees_with_the_board() {
+        for fen in POSITIONS {
+            let board = Fen::try_from(fen).unwrap().to_standard_board();
+            let mailbox = Mailbox0x88::from(&board);
+
+            for legal_move in board.legal_moves() {
+                let played = mailbox.validate(Move::from(legal_move).into()).unwrap();
+                assert_eq!(
+                    Board::from(&mailbox.process(played)),
+                    board.process(legal_move),
+                    "{fen}"
+                );
+            }
+
+            assert_eq!(
+                mailbox.legal_moves().count(),
+                board.legal_moves().count(),
+                "{fen}"
+            );
+        }
+    }
+
+    #[test]
+    fn perft_agrees_with_the_reference_counts() {
+        let mailbox =
+            |fen: &str| Mailbox0x88::from(&Fen::try_from(fen).unwrap().to_standard_board());
+        assert_eq!(perft(&mailbox(POSITIONS[0]), 3), 8902);
+        assert_eq!(perft(&mailbox(POSITIONS[1]), 2), 2039);
+        assert_eq!(perft(&mailbox(POSITIONS[2]), 3), 2812);
+        assert_eq!(perft(&mailbox(POSITIONS[3]), 2), 264);
+    }
+
+    #[test]
+    fn illegal_moves_are_rejected() {
+        let mailbox = Mailbox0x88::default();
+        let square = |name: &str| Square::try_from(name).unwrap();
+
+        assert!(matches!(
+            mailbox.validate((square("e7"), square("e5")).into()),
+            Err(IllegalMailboxMoveError::InvalidSource(_))
+        ));
+        assert!(matches!(
+            mailbox.validate((square("e2"), square("e5")).into()),
+            Err(IllegalMailboxMoveError::InvalidTarget(_))
+        ));
+
+        let e4 = mailbox.validate_san(San::try_from("e4").unwrap()).unwrap();
+        assert_eq!(Move::from(e4), Move::from((square("e2"), square("e4"))));
+    }
+}
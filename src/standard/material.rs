@@ -0,0 +1,320 @@
+use std::cmp::Ordering;
+
+use thiserror::Error;
+
+use super::{
+    board::Board,
+    piece::{Color, PieceKind},
+};
+
+/// The kinds of piece, in the order in which they index a [`Material`].
+const KINDS: [PieceKind; 6] = [
+    PieceKind::Pawn,
+    PieceKind::Rook,
+    PieceKind::Knight,
+    PieceKind::Bishop,
+    PieceKind::Queen,
+    PieceKind::King,
+];
+
+/// The kinds of piece and their letters, in the order in which they are written in
+/// a [`MaterialSignature`], which is also the order of decreasing value.
+const SIGNATURE_ORDER: [(PieceKind, char); 6] = [
+    (PieceKind::King, 'K'),
+    (PieceKind::Queen, 'Q'),
+    (PieceKind::Rook, 'R'),
+    (PieceKind::Bishop, 'B'),
+    (PieceKind::Knight, 'N'),
+    (PieceKind::Pawn, 'P'),
+];
+
+/// The greatest number of pieces one side may have in a [`MaterialSignature`].
+const MAX_SIDE_PIECES: u8 = 16;
+
+/// Results when a string is not a valid [`MaterialSignature`].
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+pub enum ParseSignatureError {
+    /// Results when the string does not contain exactly one `v` separating the two sides.
+    #[error("expected two sides separated by a single 'v'")]
+    Separator,
+    /// Results when a side contains a character which is not one of `KQRBNP`.
+    #[error("invalid piece letter {0:?}")]
+    InvalidPiece(char),
+    /// Results when a side does not have exactly one king.
+    #[error("a side has {0} kings; expected exactly one")]
+    KingCount(u8),
+    /// Results when a side has more than sixteen pieces.
+    #[error("a side has more than {MAX_SIDE_PIECES} pieces")]
+    TooManyPieces,
+}
+
+/// The number of pieces of each kind held by one side on a [`Board`].
+///
+/// A [`Board`] keeps these counts up to date as moves are made, so obtaining
+/// them with [`Board::material`] does not scan the board.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Material {
+    counts: [u8; 6],
+}
+
+impl Material {
+    /// Returns the number of pieces of the given `kind`.
+    pub fn count(&self, kind: PieceKind) -> u8 {
+        self.counts[kind as usize]
+    }
+
+    /// Returns the total number of pieces, including the king.
+    pub fn total(&self) -> u8 {
+        self.counts.iter().sum()
+    }
+
+    /// Returns the conventional value of the pieces in pawns, counting a knight
+    /// or bishop as 3, a rook as 5, a queen as 9, and the king as nothing.
+    pub fn value(&self) -> u16 {
+        KINDS
+            .iter()
+            .map(|&kind| {
+                let value = match kind {
+                    PieceKind::Pawn => 1,
+                    PieceKind::Knight | PieceKind::Bishop => 3,
+                    PieceKind::Rook => 5,
+                    PieceKind::Queen => 9,
+                    PieceKind::King => 0,
+                };
+
+                value * u16::from(self.count(kind))
+            })
+            .sum()
+    }
+
+    /// Returns an iterator over the kinds of piece present and their counts.
+    pub fn iter(&self) -> impl Iterator<Item = (PieceKind, u8)> + '_ {
+        KINDS
+            .iter()
+            .map(|&kind| (kind, self.count(kind)))
+            .filter(|&(_, count)| count > 0)
+    }
+
+    /// Compares the strength of `self` and `other`, first by [`value`](Material::value)
+    /// and then by the number of pieces of each kind, from the queens to the pawns.
+    fn strength(&self, other: &Material) -> Ordering {
+        self.value().cmp(&other.value()).then_with(|| {
+            SIGNATURE_ORDER
+                .iter()
+                .map(|&(kind, _)| self.count(kind).cmp(&other.count(kind)))
+                .find(|ordering| ordering.is_ne())
+                .unwrap_or(Ordering::Equal)
+        })
+    }
+}
+
+impl std::fmt::Display for Material {
+    /// Writes one letter per piece, from the king to the pawns, e.g. `KRPP`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for &(kind, letter) in &SIGNATURE_ORDER {
+            for _ in 0..self.count(kind) {
+                write!(f, "{letter}")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl std::str::FromStr for Material {
+    type Err = ParseSignatureError;
+
+    /// Parses one side of a [`MaterialSignature`], e.g. `KRPP`, with its letters in any order.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut material = Material::default();
+        for letter in s.chars() {
+            let (kind, _) = SIGNATURE_ORDER
+                .iter()
+                .find(|&&(_, symbol)| symbol == letter)
+                .ok_or(ParseSignatureError::InvalidPiece(letter))?;
+
+            if material.total() == MAX_SIDE_PIECES {
+                return Err(ParseSignatureError::TooManyPieces);
+            }
+
+            material.counts[*kind as usize] += 1;
+        }
+
+        match material.count(PieceKind::King) {
+            1 => Ok(material),
+            kings => Err(ParseSignatureError::KingCount(kings)),
+        }
+    }
+}
+
+/// The material of both sides of a position, written as in `KRPPvKR`.
+///
+/// This is the naming convention of endgame tablebases, and it classifies an
+/// ending regardless of which side holds which pieces: a signature describes
+/// the stronger side first, and [`matches`](MaterialSignature::matches) a board
+/// on which either color holds its first side.
+///
+/// ```
+/// # use konig::standard::MaterialSignature;
+/// # use konig::io::Fen;
+/// let rook_ending: MaterialSignature = "KRPPvKR".parse().unwrap();
+/// let board = Fen::try_from("8/8/4k3/8/1r6/8/PP2K3/R7 b - - 0 1")
+///     .unwrap()
+///     .to_standard_board();
+///
+/// assert_eq!(board.material_signature(), rook_ending);
+/// assert!(rook_ending.matches(&board));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MaterialSignature {
+    stronger: Material,
+    weaker: Material,
+}
+
+impl MaterialSignature {
+    /// Constructs the signature of the two sides `a` and `b`, in either order.
+    pub fn new(a: Material, b: Material) -> Self {
+        match a.strength(&b) {
+            Ordering::Less => Self {
+                stronger: b,
+                weaker: a,
+            },
+            _ => Self {
+                stronger: a,
+                weaker: b,
+            },
+        }
+    }
+
+    /// Returns the material of the stronger side, which is written first.
+    pub fn stronger(&self) -> Material {
+        self.stronger
+    }
+
+    /// Returns the material of the weaker side, which is written second.
+    pub fn weaker(&self) -> Material {
+        self.weaker
+    }
+
+    /// Returns the total number of pieces of both sides, including the kings.
+    pub fn total(&self) -> u8 {
+        self.stronger.total() + self.weaker.total()
+    }
+
+    /// Returns `true` if the pieces on `board` are exactly those of `self`,
+    /// held by either color.
+    pub fn matches(&self, board: &Board) -> bool {
+        board.material_signature() == *self
+    }
+}
+
+impl std::fmt::Display for MaterialSignature {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}v{}", self.stronger, self.weaker)
+    }
+}
+
+impl std::str::FromStr for MaterialSignature {
+    type Err = ParseSignatureError;
+
+    /// Parses a signature such as `KRPPvKR`, whose sides may be given in either order.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut sides = s.split('v');
+        match (sides.next(), sides.next(), sides.next()) {
+            (Some(a), Some(b), None) => Ok(Self::new(a.parse()?, b.parse()?)),
+            _ => Err(ParseSignatureError::Separator),
+        }
+    }
+}
+
+impl Board {
+    /// Returns the [`Material`] held by the given `color`.
+    pub fn material(&self, color: Color) -> Material {
+        Material {
+            counts: self.piece_counts(color),
+        }
+    }
+
+    /// Returns the [`MaterialSignature`] of this position, e.g. `KRPPvKR`.
+    pub fn material_signature(&self) -> MaterialSignature {
+        MaterialSignature::new(self.material(Color::White), self.material(Color::Black))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{Process as _, Validate as _};
+    use crate::io::Fen;
+    use crate::standard::Square;
+
+    #[test]
+    fn material_is_counted_and_updated() {
+        let start = Board::default().material(Color::White);
+        assert_eq!(start.count(PieceKind::Pawn), 8);
+        assert_eq!(start.count(PieceKind::King), 1);
+        assert_eq!(start.total(), 16);
+        assert_eq!(start.value(), 39);
+        assert_eq!(start, Board::default().material(Color::Black));
+
+        // a capturing underpromotion changes the material of both sides
+        let board = Fen::try_from("1r2k3/P7/8/8/8/8/8/4K3 w - - 0 1")
+            .unwrap()
+            .to_standard_board();
+        let candidate = (
+            Square::try_from("a7").unwrap(),
+            Square::try_from("b8").unwrap(),
+        );
+        let promotion = board
+            .validate(crate::standard::Move::from(candidate).with_promotion(PieceKind::Knight))
+            .unwrap();
+        let after = board.process(promotion);
+
+        let white: Vec<_> = after.material(Color::White).iter().collect();
+        assert_eq!(white, [(PieceKind::Knight, 1), (PieceKind::King, 1)]);
+        assert_eq!(after.material(Color::Black).total(), 1);
+        assert_eq!(after.unmake(promotion).material(Color::Black).value(), 5);
+    }
+
+    #[test]
+    fn material_signatures_are_canonical() {
+        let board = |fen: &str| Fen::try_from(fen).unwrap().to_standard_board();
+
+        assert_eq!(
+            Board::default().material_signature().to_string(),
+            "KQRRBBNNPPPPPPPPvKQRRBBNNPPPPPPPP"
+        );
+
+        // the stronger side is written first, whichever color it is
+        let signature = board("8/8/8/4k3/8/8/1p6/KR1N4 w - - 0 1").material_signature();
+        assert_eq!(signature.to_string(), "KRNvKP");
+        assert_eq!(signature.total(), 5);
+        let mirrored = board("kr1n4/1P6/8/8/4K3/8/8/8 w - - 0 1").material_signature();
+        assert_eq!(mirrored, signature);
+
+        let parsed: MaterialSignature = "KPvKNR".parse().unwrap();
+        assert_eq!(parsed, signature);
+        assert_eq!(parsed.weaker().count(PieceKind::Pawn), 1);
+
+        assert_eq!(
+            "KRvK".parse::<MaterialSignature>().unwrap().to_string(),
+            "KRvK"
+        );
+        assert_eq!(
+            "KRK".parse::<MaterialSignature>(),
+            Err(ParseSignatureError::Separator)
+        );
+        assert_eq!(
+            "KXvK".parse::<MaterialSignature>(),
+            Err(ParseSignatureError::InvalidPiece('X'))
+        );
+        assert_eq!(
+            "QvK".parse::<MaterialSignature>(),
+            Err(ParseSignatureError::KingCount(0))
+        );
+        assert_eq!(
+            "KPPPPPPPPPPPPPPPPvK".parse::<MaterialSignature>(),
+            Err(ParseSignatureError::TooManyPieces)
+        );
+    }
+}
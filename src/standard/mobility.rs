@@ -0,0 +1,125 @@
+use super::{
+    attacks,
+    bitboard::BitBoard,
+    board::Board,
+    piece::{Color, PieceKind},
+    Square,
+};
+use crate::core::Piece as _;
+
+/// The mobility of one side of a [`Board`], broken down by piece kind.
+///
+/// The mobility of a piece is the number of squares it could move to if pins
+/// and checks were ignored: for pieces other than pawns this is the number of
+/// squares they attack which are not occupied by friendly pieces, while pawns
+/// count the squares they can push to and the enemy pieces they can capture.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Mobility {
+    counts: [u32; 6],
+}
+
+impl Mobility {
+    /// Returns the combined mobility of every piece.
+    pub fn total(&self) -> u32 {
+        self.counts.iter().sum()
+    }
+
+    /// Returns the combined mobility of the pieces of the given `kind`.
+    pub fn of(&self, kind: PieceKind) -> u32 {
+        self.counts[kind as usize]
+    }
+}
+
+impl Board {
+    /// Returns the [`Mobility`] of the pieces of the given `color`.
+    pub fn mobility(&self, color: Color) -> Mobility {
+        let mut mobility = Mobility::default();
+        for square in self.occupancy(color).squares() {
+            if let (Some(piece), Some(count)) = (self[square], self.piece_mobility(square)) {
+                mobility.counts[piece.kind() as usize] += count;
+            }
+        }
+
+        mobility
+    }
+
+    /// Returns the mobility of the piece on `square`, or `None` if the square is empty.
+    ///
+    /// See [`Mobility`] for how this is counted.
+    pub fn piece_mobility(&self, square: Square) -> Option<u32> {
+        let piece = self[square]?;
+        let color = piece.color();
+        let occupied = self.occupied();
+
+        let targets = match piece.kind() {
+            PieceKind::Pawn => {
+                pawn_pushes(color, square, occupied)
+                    | (attacks::pawn(color, square) & self.occupancy(color.opposite()))
+            }
+            kind => attacks::attacks(color, kind, square, occupied) & !self.occupancy(color),
+        };
+
+        Some(targets.count())
+    }
+}
+
+/// Returns the empty squares to which a pawn of the given `color` on `square` could be pushed.
+fn pawn_pushes(color: Color, square: Square, occupied: BitBoard) -> BitBoard {
+    let (start_rank, last_rank) = match color {
+        Color::White => (1, 7),
+        Color::Black => (6, 0),
+    };
+
+    if square.rank() == last_rank {
+        return BitBoard::EMPTY;
+    }
+
+    let advance = |square: Square| match color {
+        Color::White => Square::at(usize::from(square) as u8 + 8),
+        Color::Black => Square::at(usize::from(square) as u8 - 8),
+    };
+
+    let single = advance(square);
+    if occupied.contains(single) {
+        return BitBoard::EMPTY;
+    }
+
+    let double = advance(single);
+    match square.rank() == start_rank && !occupied.contains(double) {
+        true => BitBoard::from_square(single) | BitBoard::from_square(double),
+        false => BitBoard::from_square(single),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::Fen;
+
+    #[test]
+    fn starting_mobility_matches_the_number_of_moves() {
+        let board = Board::default();
+        let mobility = board.mobility(Color::White);
+
+        assert_eq!(mobility.total(), 20);
+        assert_eq!(mobility.of(PieceKind::Pawn), 16);
+        assert_eq!(mobility.of(PieceKind::Knight), 4);
+        assert_eq!(mobility.of(PieceKind::Queen), 0);
+        assert_eq!(board.mobility(Color::Black), mobility);
+    }
+
+    #[test]
+    fn piece_mobility_counts_captures_but_not_friendly_squares() {
+        let board = Fen::try_from("4k3/8/8/3p4/3Q4/3P4/8/4K3 w - - 0 1")
+            .unwrap()
+            .to_standard_board();
+        let square = |name| Square::try_from(name).unwrap();
+
+        // the queen attacks 22 squares, but one of them holds a friendly pawn
+        assert_eq!(board.piece_mobility(square("d4")), Some(21));
+        // the blocked pawns can neither push nor capture
+        assert_eq!(board.piece_mobility(square("d3")), Some(0));
+        assert_eq!(board.piece_mobility(square("d5")), Some(0));
+        assert_eq!(board.piece_mobility(square("a1")), None);
+    }
+}
@@ -1,20 +1,28 @@
-use super::{board::Board, square::Square};
+use super::{
+    board::{Board, CastlingPermissions},
+    piece::{Piece, PieceKind},
+    square::Square,
+};
 use crate::core;
 use crate::core::r#move;
+use crate::core::{Piece as _, Standard as _};
 use thiserror::Error;
 
 /// Results when a [`Move`] cannot be converted into a [`LegalMove`]
 #[derive(Debug, Error)]
 pub enum IllegalMoveError {
     /// Results when a [`Move`] is illegal because the friendly king is in check.
-    #[error("Invalid move {0:?}: the friendly king is in check.")]
+    #[error("Invalid move {0}: the friendly king is in check.")]
     Check(Move),
     /// Results when a [`Move`] is illegal because it has an invalid source index.
-    #[error("Invalid move source: {0:?}")]
+    #[error("Invalid move source: {0}")]
     InvalidSource(Square),
     /// Results when a [`Move`] is illegal because it has an invalid target index.
-    #[error("Invalid move target: {0:?}")]
+    #[error("Invalid move target: {0}")]
     InvalidTarget(Square),
+    /// Results when a SAN literal does not describe exactly one legal move.
+    #[error("Invalid SAN literal: it describes no unique legal move")]
+    InvalidSan,
 }
 
 impl r#move::IllegalMoveError for IllegalMoveError {
@@ -24,19 +32,141 @@ impl r#move::IllegalMoveError for IllegalMoveError {
     type LegalMove = LegalMove;
 }
 
+/// The kinds of move distinguished by the rules of standard chess.
+///
+/// Promotions are described separately by [`Move::promotion`],
+/// and so a promotion is either a quiet move or a capture.
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Hash)]
+pub enum MoveKind {
+    /// A move to an empty square, other than a double push or castling.
+    Quiet,
+    /// A move capturing the piece on its target square.
+    Capture,
+    /// A pawn moving two squares from its initial rank.
+    DoublePush,
+    /// The king moving two squares towards a rook, which jumps over it.
+    Castle,
+    /// A pawn capturing a pawn which has just moved past it.
+    EnPassant,
+}
+
 /// Represents a possible move on a [`Board`],
 /// including illegal moves.
-#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+///
+/// A move constructed from a pair of squares carries no promotion piece, and
+/// promotes to a queen if it moves a pawn to its last rank; other promotions
+/// are described with [`Move::with_promotion`]. The [`MoveKind`] of a move is
+/// only known once it has been generated or validated on a [`Board`], and is
+/// ignored when comparing and hashing moves.
+///
+/// Moves are ordered by their source square, then their target square, and then
+/// their promotion piece, with moves carrying no promotion piece first. They are
+/// displayed in the coordinate notation used by UCI, e.g. `e2e4` or `e7e8q`.
+#[derive(Clone, Copy, Debug)]
 pub struct Move {
     /// The position to take a [piece](crate::standard::piece::StandardPiece) from.
     source: Square,
     /// The position to move a [piece](crate::standard::piece::StandardPiece) to.
     target: Square,
+    /// The kind of piece to which a pawn promotes, if any.
+    promotion: Option<PieceKind>,
+    /// The kind of the move, if it has been classified against a [`Board`].
+    kind: Option<MoveKind>,
+}
+
+impl PartialEq for Move {
+    fn eq(&self, other: &Self) -> bool {
+        self.source == other.source
+            && self.target == other.target
+            && self.promotion == other.promotion
+    }
+}
+
+impl Eq for Move {}
+
+impl std::hash::Hash for Move {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.source.hash(state);
+        self.target.hash(state);
+        self.promotion.hash(state);
+    }
+}
+
+impl PartialOrd for Move {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Move {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        (self.source, self.target, self.promotion).cmp(&(
+            other.source,
+            other.target,
+            other.promotion,
+        ))
+    }
+}
+
+impl std::fmt::Display for Move {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{}", self.source, self.target)?;
+
+        match self.promotion {
+            Some(PieceKind::Queen) => write!(f, "q"),
+            Some(PieceKind::Rook) => write!(f, "r"),
+            Some(PieceKind::Bishop) => write!(f, "b"),
+            Some(PieceKind::Knight) => write!(f, "n"),
+            Some(PieceKind::Pawn | PieceKind::King) | None => Ok(()),
+        }
+    }
+}
+
+impl Move {
+    /// Returns `self` promoting to a piece of the given `kind`.
+    pub fn with_promotion(self, kind: PieceKind) -> Self {
+        Self {
+            promotion: Some(kind),
+            ..self
+        }
+    }
+
+    /// Returns the kind of piece to which this move promotes, if it
+    /// was constructed with one or generated as a promotion.
+    pub fn promotion(&self) -> Option<PieceKind> {
+        self.promotion
+    }
+
+    /// Returns the kind of this move, if it has been generated or validated on a [`Board`].
+    pub fn kind(&self) -> Option<MoveKind> {
+        self.kind
+    }
+
+    /// Returns `self` classified as a move of the given `kind`.
+    pub(crate) fn with_kind(self, kind: MoveKind) -> Self {
+        Self {
+            kind: Some(kind),
+            ..self
+        }
+    }
 }
 
 /// Represents a legal move on a [`Board`].
-#[derive(PartialEq, Eq, Clone, Copy, Debug)]
-pub struct LegalMove(Move);
+///
+/// Along with the move itself, a legal move records the piece it captures and
+/// the parts of the state of the [`Board`] which it overwrites, so that it can
+/// be taken back with [`Board::unmake`]. It is displayed as the underlying [`Move`].
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Hash)]
+pub struct LegalMove {
+    /// The classified move.
+    mv: Move,
+    /// The piece captured by the move, if any.
+    captured: Option<Piece>,
+    /// The castling permissions before the move was played.
+    castling_rights: CastlingPermissions,
+    /// The en passant target square before the move was played.
+    en_passant_square: Option<Square>,
+}
 
 impl core::Move for Move {
     type Board = Board;
@@ -56,11 +186,11 @@ impl core::Move for LegalMove {
     type Index = Square;
 
     fn source(&self) -> Self::Index {
-        self.0.source
+        self.mv.source
     }
 
     fn target(&self) -> Self::Index {
-        self.0.target
+        self.mv.target
     }
 }
 
@@ -68,9 +198,82 @@ impl core::LegalMove for LegalMove {
     type Move = Move;
 }
 
+impl core::Promote for Move {
+    type Kind = PieceKind;
+
+    fn promotion(&self) -> Option<Self::Kind> {
+        self.promotion
+    }
+}
+
+impl core::Promote for LegalMove {
+    type Kind = PieceKind;
+
+    fn promotion(&self) -> Option<Self::Kind> {
+        self.mv.promotion
+    }
+}
+
 impl r#move::WrapMove for LegalMove {
-    fn wrap(value: Self::Move) -> Self {
-        Self(value)
+    fn wrap(board: &Self::Board, value: Self::Move) -> Self {
+        let captured = match value.kind {
+            Some(MoveKind::EnPassant) => {
+                Some(Piece::new(board.side_to_move().opposite(), PieceKind::Pawn))
+            }
+            _ => board[value.target],
+        };
+
+        Self {
+            mv: value,
+            captured,
+            castling_rights: board.castling_permissions(),
+            en_passant_square: board.en_passant_target_square(),
+        }
+    }
+}
+
+impl LegalMove {
+    /// Returns the kind of piece to which this move promotes, if it is a promotion.
+    pub fn promotion(&self) -> Option<PieceKind> {
+        self.mv.promotion
+    }
+
+    /// Returns the kind of this move.
+    pub fn kind(&self) -> MoveKind {
+        // legal moves are always classified during generation or validation
+        self.mv.kind.unwrap_or(MoveKind::Quiet)
+    }
+
+    /// Returns `true` if this move captures a piece, including en passant.
+    pub fn is_capture(&self) -> bool {
+        self.captured.is_some()
+    }
+
+    /// Returns the piece captured by this move, if any.
+    pub fn captured(&self) -> Option<Piece> {
+        self.captured
+    }
+
+    /// Returns the castling permissions of the position in which this move was played.
+    pub fn prior_castling_permissions(&self) -> CastlingPermissions {
+        self.castling_rights
+    }
+
+    /// Returns the en passant target square of the position in which this move was played.
+    pub fn prior_en_passant_square(&self) -> Option<Square> {
+        self.en_passant_square
+    }
+}
+
+impl std::fmt::Display for LegalMove {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.mv.fmt(f)
+    }
+}
+
+impl From<LegalMove> for Move {
+    fn from(value: LegalMove) -> Self {
+        value.mv
     }
 }
 
@@ -79,6 +282,54 @@ impl From<(Square, Square)> for Move {
         Self {
             source: value.0,
             target: value.1,
+            promotion: None,
+            kind: None,
         }
     }
 }
+
+/// Constructs the move from `source` to `target`, for use in tests.
+#[cfg(test)]
+pub(crate) fn mv(source: &str, target: &str) -> Move {
+    (
+        Square::try_from(source).unwrap(),
+        Square::try_from(target).unwrap(),
+    )
+        .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::{BTreeSet, HashSet};
+
+    #[test]
+    fn moves_are_hashed_ordered_and_displayed() {
+        let promotion = mv("e7", "e8").with_promotion(PieceKind::Knight);
+        assert_eq!(promotion.to_string(), "e7e8n");
+        assert_eq!(mv("g1", "f3").to_string(), "g1f3");
+
+        // the kind of a move does not affect its hash, as it does not affect equality
+        let hashed: HashSet<Move> = [
+            mv("e2", "e4"),
+            mv("e2", "e4").with_kind(MoveKind::DoublePush),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(hashed.len(), 1);
+
+        let sorted: Vec<String> = [
+            promotion,
+            mv("g1", "f3"),
+            mv("e7", "e8"),
+            mv("b1", "c3"),
+            mv("b1", "a3"),
+        ]
+        .into_iter()
+        .collect::<BTreeSet<_>>()
+        .iter()
+        .map(Move::to_string)
+        .collect();
+        assert_eq!(sorted, ["b1a3", "b1c3", "g1f3", "e7e8", "e7e8n"]);
+    }
+}
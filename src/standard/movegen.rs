@@ -0,0 +1,882 @@
+use std::iter::FusedIterator;
+
+use super::{
+    attacks,
+    bitboard::BitBoard,
+    board::Board,
+    movelist::MoveList,
+    piece::{Color, PieceKind},
+    r#move::{LegalMove, Move, MoveKind},
+    Square,
+};
+use crate::core::{r#move::WrapMove, Move as _, Piece as _, Standard};
+
+/// The pieces to which a pawn may promote, in the order they are generated.
+pub(crate) const PROMOTIONS: [PieceKind; 4] = [
+    PieceKind::Queen,
+    PieceKind::Knight,
+    PieceKind::Rook,
+    PieceKind::Bishop,
+];
+
+/// The number of killer moves that a [`LegalMoves`] iterator will hold.
+pub const KILLER_SLOTS: usize = 2;
+
+/// The stages through which a [`LegalMoves`] iterator progresses.
+///
+/// Each stage is only generated once the previous stage has been
+/// exhausted, so a consumer which stops early (e.g. a search that
+/// finds a cutoff among the captures) never pays for the later stages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Stage {
+    /// Captures and promotions which are not expected to lose material,
+    /// ordered by most valuable victim and then least valuable attacker.
+    GoodCaptures,
+    /// The killer moves supplied to the iterator, if they are legal quiet moves.
+    Killers,
+    /// The remaining non-capturing moves.
+    Quiets,
+    /// Captures which are expected to lose material, e.g. a queen taking a defended pawn.
+    BadCaptures,
+    /// The iterator has been exhausted.
+    Done,
+}
+
+/// A lazy iterator over the legal moves in a [`Board`], produced in [`Stage`]s.
+///
+/// Moves are generated one stage at a time, and the legality of each move is only
+/// checked as it is yielded. Construct one with [`Board::legal_moves`].
+#[derive(Debug, Clone)]
+pub struct LegalMoves<'a> {
+    board: &'a Board,
+    stage: Stage,
+    buffer: MoveList,
+    cursor: usize,
+    bad_captures: MoveList,
+    killers: [Option<Move>; KILLER_SLOTS],
+}
+
+impl Board {
+    /// Returns a lazy iterator over the legal moves in this position.
+    ///
+    /// The moves are yielded in the order described by [`Stage`].
+    pub fn legal_moves(&self) -> LegalMoves<'_> {
+        LegalMoves {
+            board: self,
+            stage: Stage::GoodCaptures,
+            buffer: MoveList::new(),
+            cursor: 0,
+            bad_captures: MoveList::new(),
+            killers: [None; KILLER_SLOTS],
+        }
+    }
+
+    /// Returns an iterator over the legal moves of the piece on `source`, which is
+    /// empty if there is no piece on `source` or it does not belong to the side to move.
+    ///
+    /// Only the moves of that piece are generated, so this is much cheaper than
+    /// filtering [`Board::legal_moves`] when, say, a user picks up a piece.
+    pub fn legal_moves_from(&self, source: Square) -> impl Iterator<Item = LegalMove> + '_ {
+        let mut moves = MoveList::new();
+        if self[source].is_some_and(|piece| piece.color() == self.side_to_move()) {
+            generate_from(self, &Context::new(self), source, Filter::All, &mut moves);
+        }
+
+        moves
+            .into_iter()
+            .filter(move |&candidate| is_legal(self, candidate))
+            .map(move |candidate| LegalMove::wrap(self, candidate))
+    }
+
+    /// Clears `moves` and fills it with the legal moves in this position.
+    ///
+    /// Unlike [`Board::legal_moves`], the moves are not ordered by [`Stage`], but
+    /// generation never allocates; a search can reuse one [`MoveList`] per ply.
+    pub fn generate_moves_into(&self, moves: &mut MoveList) {
+        moves.clear();
+        generate(self, Filter::All, moves);
+        moves.retain(|&candidate| is_legal(self, candidate));
+    }
+
+    /// Returns `true` if the side to move has at least one legal move.
+    ///
+    /// The pieces are tried one at a time, starting with the king, and the search stops
+    /// at the first legal move, so detecting checkmate and stalemate never generates,
+    /// orders or stores every move in the position.
+    pub fn has_legal_moves(&self) -> bool {
+        let context = Context::new(self);
+        let king = self.pieces(context.us, PieceKind::King);
+        // in double check, only the king may move
+        let others = match context.evasion_mask.is_empty() {
+            true => BitBoard::EMPTY,
+            false => self.occupancy(context.us) & !king,
+        };
+
+        let mut moves = MoveList::new();
+        king.squares().chain(others.squares()).any(|source| {
+            moves.clear();
+            generate_from(self, &context, source, Filter::All, &mut moves);
+            moves.iter().any(|&candidate| is_legal(self, candidate))
+        })
+    }
+
+    /// Returns an iterator over the pseudo-legal moves in this position, i.e. the moves
+    /// which obey the movement rules of each piece but might leave the friendly king in check.
+    /// When the side to move is in check, only the [evasions](Board::evasions) are produced.
+    ///
+    /// Use [`Board::is_legal`] to filter these moves, and [`Validate::validate`](crate::core::Validate::validate)
+    /// to convert a move into a [`LegalMove`].
+    pub fn pseudo_legal_moves(&self) -> impl Iterator<Item = Move> {
+        let mut moves = MoveList::new();
+        generate(self, Filter::All, &mut moves);
+        moves.into_iter()
+    }
+
+    /// Returns an iterator over the pseudo-legal check evasions in this position,
+    /// i.e. king moves, captures of the checking piece, and interpositions between
+    /// it and the king. If the side to move is not in check, this is empty.
+    ///
+    /// Every legal move in a position where the king is in check is an evasion,
+    /// and so [`Board::legal_moves`] and [`Board::pseudo_legal_moves`] use this
+    /// generator automatically when [`Board::is_check`] holds.
+    pub fn evasions(&self) -> impl Iterator<Item = Move> {
+        let mut moves = MoveList::new();
+        let context = Context::new(self);
+        if context.evasion_mask != BitBoard::FULL {
+            generate_evasions(self, &context, Filter::All, &mut moves);
+        }
+
+        moves.into_iter()
+    }
+
+    /// Returns `true` if `candidate` is a pseudo-legal move for the side to move.
+    pub fn is_pseudo_legal(&self, candidate: &Move) -> bool {
+        is_pseudo_legal(self, *candidate)
+    }
+
+    /// Returns `true` if the pseudo-legal `candidate` does not leave the friendly king in check.
+    ///
+    /// This is the cheap half of move validation, and assumes that `candidate` has already
+    /// been established as pseudo-legal (e.g. because it was drawn from [`Board::pseudo_legal_moves`]);
+    /// the result is meaningless for any other move.
+    pub fn is_legal(&self, candidate: &Move) -> bool {
+        is_legal(self, *candidate)
+    }
+}
+
+impl<'a> LegalMoves<'a> {
+    /// Sets the killer moves to be tried directly after the good captures.
+    ///
+    /// Killers which are not legal quiet moves in this position are silently
+    /// ignored, and those which are legal are not repeated in the quiet stage.
+    pub fn with_killers(mut self, killers: [Option<Move>; KILLER_SLOTS]) -> Self {
+        self.killers = killers;
+        self
+    }
+
+    /// Returns the current stage of the iterator, i.e. the stage
+    /// from which the most recently yielded move was drawn.
+    pub fn stage(&self) -> Stage {
+        self.stage
+    }
+
+    /// Fills the buffer with the moves of the current stage.
+    fn fill(&mut self) {
+        self.buffer.clear();
+        self.cursor = 0;
+
+        match self.stage {
+            Stage::GoodCaptures => {
+                let mut captures = MoveList::new();
+                generate(self.board, Filter::Noisy, &mut captures);
+                captures.sort_by_key(|&mv| std::cmp::Reverse(mvv_lva(self.board, mv)));
+
+                for mv in captures {
+                    match is_good_capture(self.board, mv) {
+                        true => self.buffer.push(mv),
+                        false => self.bad_captures.push(mv),
+                    }
+                }
+            }
+            Stage::Killers => {
+                // the killers are replaced by the quiet moves they describe, with their
+                // kinds, so that the quiet stage can recognise them among its own moves
+                let mut killers = [None; KILLER_SLOTS];
+                for (slot, killer) in self.killers.into_iter().flatten().enumerate() {
+                    let killer = classify(self.board, killer)
+                        .filter(|&killer| is_quiet(self.board, killer))
+                        .filter(|killer| !killers.contains(&Some(*killer)));
+
+                    if let Some(killer) = killer {
+                        killers[slot] = Some(killer);
+                        self.buffer.push(killer);
+                    }
+                }
+
+                self.killers = killers;
+            }
+            Stage::Quiets => {
+                generate(self.board, Filter::Quiet, &mut self.buffer);
+                let killers = self.killers;
+                self.buffer.retain(|mv| !killers.contains(&Some(*mv)));
+            }
+            Stage::BadCaptures => std::mem::swap(&mut self.buffer, &mut self.bad_captures),
+            Stage::Done => (),
+        }
+    }
+
+    /// Advances to the next stage and fills the buffer.
+    fn advance(&mut self) {
+        self.stage = match self.stage {
+            Stage::GoodCaptures => Stage::Killers,
+            Stage::Killers => Stage::Quiets,
+            Stage::Quiets => Stage::BadCaptures,
+            Stage::BadCaptures | Stage::Done => Stage::Done,
+        };
+
+        self.fill();
+    }
+}
+
+impl<'a> Iterator for LegalMoves<'a> {
+    type Item = LegalMove;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // the first stage is filled lazily on the first call
+        if self.stage == Stage::GoodCaptures && self.cursor == 0 && self.buffer.is_empty() {
+            self.fill();
+        }
+
+        loop {
+            while let Some(&candidate) = self.buffer.get(self.cursor) {
+                self.cursor += 1;
+                if is_legal(self.board, candidate) {
+                    return Some(LegalMove::wrap(self.board, candidate));
+                }
+            }
+
+            if self.stage == Stage::Done {
+                return None;
+            }
+
+            self.advance();
+        }
+    }
+}
+
+impl<'a> FusedIterator for LegalMoves<'a> {}
+
+/// Determines which moves a call to [`generate`] should produce.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Filter {
+    /// Captures (including en passant) and promotions.
+    Noisy,
+    /// Every move which is not noisy.
+    Quiet,
+    /// Every pseudo-legal move.
+    All,
+}
+
+/// The precomputed occupancy information shared while generating moves.
+struct Context {
+    us: Color,
+    enemies: BitBoard,
+    occupied: BitBoard,
+    /// The squares to which pieces other than the king may move. When the king is in
+    /// check this is limited to the checking piece and the squares between it and the
+    /// king, and when it is in double check only the king may move at all.
+    evasion_mask: BitBoard,
+}
+
+impl Context {
+    /// Constructs a context which restricts generation to check evasions when necessary.
+    fn new(board: &Board) -> Self {
+        let mut context = Self::unrestricted(board);
+        let Some(king) = board.king_square(context.us) else {
+            return context;
+        };
+
+        let mut checkers = board.attackers_to(king, context.us.opposite()).squares();
+        context.evasion_mask = match (checkers.next(), checkers.next()) {
+            (None, _) => BitBoard::FULL,
+            (Some(checker), None) => {
+                BitBoard::from_square(checker) | attacks::between(king, checker)
+            }
+            (Some(_), Some(_)) => BitBoard::EMPTY,
+        };
+
+        context
+    }
+
+    /// Constructs a context which generates every pseudo-legal move, even if the king is in check.
+    fn unrestricted(board: &Board) -> Self {
+        let us = board.side_to_move();
+        Self {
+            us,
+            enemies: board.occupancy(us.opposite()),
+            occupied: board.occupied(),
+            evasion_mask: BitBoard::FULL,
+        }
+    }
+}
+
+/// Pushes every pseudo-legal move for the side to move matching `filter` onto `out`.
+///
+/// If the side to move is in check, only the pseudo-legal evasions are produced; see [`generate_evasions`].
+pub(crate) fn generate(board: &Board, filter: Filter, out: &mut MoveList) {
+    let context = Context::new(board);
+    if context.evasion_mask != BitBoard::FULL {
+        return generate_evasions(board, &context, filter, out);
+    }
+
+    for source in board.occupancy(context.us).squares() {
+        generate_from(board, &context, source, filter, out);
+    }
+}
+
+/// Pushes the pseudo-legal moves matching `filter` which might escape check onto `out`,
+/// i.e. king moves, captures of the checking piece, and interpositions on its ray.
+///
+/// In double check, only the king is considered.
+fn generate_evasions(board: &Board, context: &Context, filter: Filter, out: &mut MoveList) {
+    let king = board.pieces(context.us, PieceKind::King);
+    let sources = match context.evasion_mask.is_empty() {
+        true => king,
+        false => board.occupancy(context.us),
+    };
+
+    for source in sources.squares() {
+        generate_from(board, context, source, filter, out);
+    }
+}
+
+/// Pushes every pseudo-legal move of the piece on `source` matching `filter` onto `out`.
+fn generate_from(
+    board: &Board,
+    context: &Context,
+    source: Square,
+    filter: Filter,
+    out: &mut MoveList,
+) {
+    let Some(piece) = board[source] else {
+        return;
+    };
+
+    let noisy = filter != Filter::Quiet;
+    let quiet = filter != Filter::Noisy;
+
+    if piece.kind() == PieceKind::Pawn {
+        return generate_pawn(board, context, source, noisy, quiet, out);
+    }
+
+    let attacks = attacks::attacks(context.us, piece.kind(), source, context.occupied);
+    let mut targets = BitBoard::EMPTY;
+    if noisy {
+        targets |= attacks & context.enemies;
+    }
+    if quiet {
+        targets |= attacks & !context.occupied;
+    }
+
+    // the king escapes check by moving, so only the other pieces are restricted
+    if piece.kind() != PieceKind::King {
+        targets &= context.evasion_mask;
+    }
+
+    out.extend(targets.squares().map(|target| {
+        let kind = match context.enemies.contains(target) {
+            true => MoveKind::Capture,
+            false => MoveKind::Quiet,
+        };
+
+        Move::from((source, target)).with_kind(kind)
+    }));
+
+    if quiet && piece.kind() == PieceKind::King {
+        generate_castling(board, context, source, out);
+    }
+}
+
+/// Pushes the pseudo-legal moves of the pawn on `source` onto `out`.
+fn generate_pawn(
+    board: &Board,
+    context: &Context,
+    source: Square,
+    noisy: bool,
+    quiet: bool,
+    out: &mut MoveList,
+) {
+    let (step, start_rank, last_rank): (i8, u8, u8) = match context.us {
+        Color::White => (8, 1, 7),
+        Color::Black => (-8, 6, 0),
+    };
+
+    // a pawn can only be on its last rank in a malformed position, and has nowhere to go
+    if source.rank() == last_rank {
+        return;
+    }
+
+    let index = usize::from(source) as i8;
+    let single = Square::at((index + step) as u8);
+    let mask = context.evasion_mask;
+
+    if !context.occupied.contains(single) {
+        // pushes to the last rank promote, and so are noisy
+        let is_promotion = single.rank() == last_rank;
+        if mask.contains(single) && ((is_promotion && noisy) || (!is_promotion && quiet)) {
+            push_pawn_move(out, source, single, MoveKind::Quiet, last_rank);
+        }
+
+        if quiet && source.rank() == start_rank {
+            let double = Square::at((index + 2 * step) as u8);
+            if !context.occupied.contains(double) && mask.contains(double) {
+                out.push(Move::from((source, double)).with_kind(MoveKind::DoublePush));
+            }
+        }
+    }
+
+    if noisy {
+        let attacks = attacks::pawn(context.us, source);
+        for target in (attacks & context.enemies & mask).squares() {
+            push_pawn_move(out, source, target, MoveKind::Capture, last_rank);
+        }
+
+        // an en passant capture can evade check either by interposing on the target
+        // square or by removing the checking pawn, which stands beside the source
+        if let Some(ep) = board.en_passant_target_square() {
+            let victim = Square::at(source.rank() * 8 + ep.file());
+            if attacks.contains(ep) && (mask.contains(ep) || mask.contains(victim)) {
+                out.push(Move::from((source, ep)).with_kind(MoveKind::EnPassant));
+            }
+        }
+    }
+}
+
+/// Pushes the pawn move from `source` to `target` onto `out`,
+/// or every promotion if `target` is on the `last_rank`.
+fn push_pawn_move(
+    out: &mut MoveList,
+    source: Square,
+    target: Square,
+    kind: MoveKind,
+    last_rank: u8,
+) {
+    let pawn_move = Move::from((source, target)).with_kind(kind);
+    match target.rank() == last_rank {
+        true => out.extend(PROMOTIONS.map(|piece| pawn_move.with_promotion(piece))),
+        false => out.push(pawn_move),
+    }
+}
+
+/// Pushes the pseudo-legal castling moves of the king on `source` onto `out`.
+///
+/// The king may not castle out of or through check, but whether it
+/// castles into check is left to the ordinary legality test.
+fn generate_castling(board: &Board, context: &Context, source: Square, out: &mut MoveList) {
+    let rights = board.castling_permissions();
+    let (king_side, queen_side, rank_offset) = match context.us {
+        Color::White => (rights.white_king_side, rights.white_queen_side, 0),
+        Color::Black => (rights.black_king_side, rights.black_queen_side, 56),
+    };
+
+    if usize::from(source) != rank_offset + 4 || !(king_side || queen_side) {
+        return;
+    }
+
+    let them = context.us.opposite();
+    if board.is_attacked(source, them) {
+        return;
+    }
+
+    let rook = Some(crate::standard::Piece::new(context.us, PieceKind::Rook));
+    let square = |file: usize| Square::at((rank_offset + file) as u8);
+    let is_empty = |file: usize| !context.occupied.contains(square(file));
+
+    if king_side
+        && board[square(7)] == rook
+        && is_empty(5)
+        && is_empty(6)
+        && !board.is_attacked(square(5), them)
+    {
+        out.push(Move::from((source, square(6))).with_kind(MoveKind::Castle));
+    }
+
+    if queen_side
+        && board[square(0)] == rook
+        && is_empty(1)
+        && is_empty(2)
+        && is_empty(3)
+        && !board.is_attacked(square(3), them)
+    {
+        out.push(Move::from((source, square(2))).with_kind(MoveKind::Castle));
+    }
+}
+
+/// Returns `true` if `candidate` is a pseudo-legal move for the side to move.
+pub(crate) fn is_pseudo_legal(board: &Board, candidate: Move) -> bool {
+    classify(board, candidate).is_some()
+}
+
+/// Returns `candidate` with its [`MoveKind`] if it is a pseudo-legal move for the
+/// side to move, and `None` otherwise. A pawn move to the last rank without a
+/// promotion piece is taken to promote to a queen.
+pub(crate) fn classify(board: &Board, candidate: Move) -> Option<Move> {
+    let source = candidate.source();
+    let piece = board[source].filter(|piece| piece.color() == board.side_to_move())?;
+
+    let candidate = match candidate.promotion() {
+        None if piece.kind() == PieceKind::Pawn && matches!(candidate.target().rank(), 0 | 7) => {
+            candidate.with_promotion(PieceKind::Queen)
+        }
+        _ => candidate,
+    };
+
+    let mut moves = MoveList::new();
+    let context = Context::unrestricted(board);
+    generate_from(board, &context, source, Filter::All, &mut moves);
+    moves.into_iter().find(|&mv| mv == candidate)
+}
+
+/// Returns the [`MoveKind`] of `candidate` in the position of `board`,
+/// assuming that it is pseudo-legal.
+pub(crate) fn kind_of(board: &Board, candidate: Move) -> MoveKind {
+    let (source, target) = candidate.as_pair();
+    let moving = board[source].map(|piece| piece.kind());
+
+    match moving {
+        Some(PieceKind::King) if source.file_distance(target) == 2 => MoveKind::Castle,
+        Some(PieceKind::Pawn) if source.rank_distance(target) == 2 => MoveKind::DoublePush,
+        Some(PieceKind::Pawn)
+            if board[target].is_none() && board.en_passant_target_square() == Some(target) =>
+        {
+            MoveKind::EnPassant
+        }
+        _ if board[target].is_some() => MoveKind::Capture,
+        _ => MoveKind::Quiet,
+    }
+}
+
+/// Returns `true` if playing the pseudo-legal `candidate` does not leave
+/// the friendly king in check.
+pub(crate) fn is_legal(board: &Board, candidate: Move) -> bool {
+    let us = board.side_to_move();
+    let after = board.make(candidate);
+    match after.king_square(us) {
+        Some(king) => !after.is_attacked(king, us.opposite()),
+        None => true,
+    }
+}
+
+/// Returns `true` if the pseudo-legal `candidate` neither captures nor promotes.
+pub(crate) fn is_quiet(board: &Board, candidate: Move) -> bool {
+    captured_kind(board, candidate).is_none() && candidate.promotion().is_none()
+}
+
+/// Returns the kind of the piece captured by the pseudo-legal `candidate`, if any.
+fn captured_kind(board: &Board, candidate: Move) -> Option<PieceKind> {
+    match candidate.kind() {
+        Some(MoveKind::EnPassant) => Some(PieceKind::Pawn),
+        _ => board[candidate.target()].map(|piece| piece.kind()),
+    }
+}
+
+/// Returns the conventional material value of a piece of the given `kind`, in pawns.
+pub(crate) fn value(kind: PieceKind) -> i16 {
+    match kind {
+        PieceKind::Pawn => 1,
+        PieceKind::Knight | PieceKind::Bishop => 3,
+        PieceKind::Rook => 5,
+        PieceKind::Queen => 9,
+        PieceKind::King => 10,
+    }
+}
+
+/// Returns the material which `candidate` immediately gains, ignoring recaptures.
+fn gain(board: &Board, candidate: Move) -> i16 {
+    let victim = captured_kind(board, candidate).map_or(0, value);
+    let promotion = match candidate.promotion() {
+        Some(kind) => value(kind) - value(PieceKind::Pawn),
+        None => 0,
+    };
+
+    victim + promotion
+}
+
+/// Scores a noisy move by most valuable victim, then least valuable attacker.
+pub(crate) fn mvv_lva(board: &Board, candidate: Move) -> i16 {
+    let attacker = board[candidate.source()].map_or(0, |piece| value(piece.kind()));
+    16 * gain(board, candidate) - attacker
+}
+
+/// Returns `true` if the noisy `candidate` is not expected to lose material,
+/// i.e. if it gains at least as much as the moving piece is worth, or if the
+/// moving piece is the king (which can never be recaptured).
+fn is_good_capture(board: &Board, candidate: Move) -> bool {
+    match board[candidate.source()] {
+        Some(piece) if piece.kind() == PieceKind::King => true,
+        Some(piece) => gain(board, candidate) >= value(piece.kind()),
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::Fen;
+    use crate::standard::perft::perft;
+
+    fn board(fen: &str) -> Board {
+        Fen::try_from(fen).unwrap().to_standard_board()
+    }
+
+    #[test]
+    fn perft_from_the_starting_position() {
+        let board = Board::default();
+        assert_eq!(perft(&board, 1), 20);
+        assert_eq!(perft(&board, 2), 400);
+        assert_eq!(perft(&board, 3), 8902);
+    }
+
+    #[test]
+    fn perft_with_castling_and_en_passant() {
+        // "kiwipete", from https://www.chessprogramming.org/Perft_Results
+        let kiwipete =
+            board("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1");
+        assert_eq!(perft(&kiwipete, 1), 48);
+        assert_eq!(perft(&kiwipete, 2), 2039);
+
+        let endgame = board("8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1");
+        assert_eq!(perft(&endgame, 1), 14);
+        assert_eq!(perft(&endgame, 2), 191);
+        assert_eq!(perft(&endgame, 3), 2812);
+    }
+
+    #[test]
+    fn perft_with_underpromotions() {
+        // "position 4", from https://www.chessprogramming.org/Perft_Results
+        let promotions = board("r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1");
+        assert_eq!(perft(&promotions, 1), 6);
+        assert_eq!(perft(&promotions, 2), 264);
+        assert_eq!(perft(&promotions, 3), 9467);
+    }
+
+    #[test]
+    fn generated_moves_are_classified() {
+        let board = board("r3k3/1P6/8/3pP3/8/8/8/4K2R w Kq d6 0 1");
+        let square = |name| Square::try_from(name).unwrap();
+        let find = |source, target, promotion: Option<PieceKind>| {
+            board
+                .legal_moves()
+                .find(|mv| {
+                    mv.as_pair() == (square(source), square(target)) && mv.promotion() == promotion
+                })
+                .unwrap()
+        };
+
+        assert_eq!(find("e1", "g1", None).kind(), MoveKind::Castle);
+        assert_eq!(find("e5", "d6", None).kind(), MoveKind::EnPassant);
+        assert_eq!(find("e5", "e6", None).kind(), MoveKind::Quiet);
+        assert_eq!(find("h1", "h8", None).kind(), MoveKind::Quiet);
+        assert!(find("e5", "d6", None).is_capture());
+
+        let underpromotion = find("b7", "a8", Some(PieceKind::Knight));
+        assert_eq!(underpromotion.kind(), MoveKind::Capture);
+        assert_eq!(
+            board.make(underpromotion.into())[square("a8")],
+            Some(crate::standard::Piece::WhiteKnight)
+        );
+        assert_eq!(
+            board
+                .legal_moves()
+                .filter(|mv| mv.source() == square("b7"))
+                .count(),
+            8
+        );
+
+        let double_push = Board::default()
+            .legal_moves()
+            .find(|mv| mv.as_pair() == (square("e2"), square("e4")))
+            .unwrap();
+        assert_eq!(double_push.kind(), MoveKind::DoublePush);
+    }
+
+    #[test]
+    fn pseudo_legal_moves_include_pinned_pieces() {
+        // the knight on e2 is pinned against its king by the rook on e7
+        let board = board("4k3/4r3/8/8/8/8/4N3/4K3 w - - 0 1");
+        let pseudo_legal: Vec<Move> = board.pseudo_legal_moves().collect();
+        let legal: Vec<Move> = pseudo_legal
+            .iter()
+            .copied()
+            .filter(|mv| board.is_legal(mv))
+            .collect();
+
+        assert_eq!(pseudo_legal.len(), 10);
+        assert_eq!(legal.len(), 4);
+        assert_eq!(legal.len(), board.legal_moves().count());
+        assert!(pseudo_legal.iter().all(|mv| board.is_pseudo_legal(mv)));
+
+        let square = |name| Square::try_from(name).unwrap();
+        assert!(!board.is_pseudo_legal(&Move::from((square("e2"), square("e4")))));
+    }
+
+    /// Generates the legal moves by filtering every pseudo-legal move, bypassing the evasion generator.
+    fn unrestricted_legal_moves(board: &Board) -> Vec<Move> {
+        let context = Context::unrestricted(board);
+        let mut moves = MoveList::new();
+        for source in board.occupancy(context.us).squares() {
+            generate_from(board, &context, source, Filter::All, &mut moves);
+        }
+
+        moves.retain(|&mv| is_legal(board, mv));
+        moves.to_vec()
+    }
+
+    #[test]
+    fn evasions_agree_with_unrestricted_generation() {
+        let positions = [
+            // a rook check which can be blocked or captured
+            "4k3/8/8/8/1b6/8/3N4/r3K2R w K - 0 1",
+            // a double check, where only the king may move
+            "4k3/8/8/8/7b/8/3n4/R3K3 w Q - 0 1",
+            // a pawn check which can only be removed by capturing en passant
+            "8/8/8/2k5/3Pp3/8/8/4K3 b - d3 0 1",
+            // a bishop check which can be blocked by capturing en passant
+            "K7/8/8/1Pp5/4b3/8/8/4k3 w - c6 0 1",
+        ];
+
+        for fen in positions {
+            let board = board(fen);
+            assert!(board.is_check(), "{fen}");
+
+            let mut expected = unrestricted_legal_moves(&board);
+            let mut actual: Vec<Move> = board.legal_moves().map(Move::from).collect();
+            expected.sort_by_key(|mv| (usize::from(mv.source()), usize::from(mv.target())));
+            actual.sort_by_key(|mv| (usize::from(mv.source()), usize::from(mv.target())));
+
+            assert_eq!(actual, expected, "{fen}");
+            assert!(board.evasions().count() <= board.pseudo_legal_moves().count());
+        }
+
+        // a double check leaves only king moves
+        let double_check = board("4k3/8/8/8/7b/8/3n4/R3K3 w Q - 0 1");
+        let king = Square::try_from("e1").unwrap();
+        assert!(double_check.evasions().all(|mv| mv.source() == king));
+        assert_eq!(Board::default().evasions().count(), 0);
+    }
+
+    #[test]
+    fn legal_moves_are_generated_per_square() {
+        let positions = [
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+            "8/8/8/2k5/3Pp3/8/8/4K3 b - d3 0 1",
+            "4k3/8/8/8/7b/8/3n4/R3K3 w Q - 0 1",
+        ];
+
+        for fen in positions {
+            let board = board(fen);
+            for index in 0..64 {
+                let source = Square::at(index);
+                let expected: Vec<LegalMove> = board
+                    .legal_moves()
+                    .filter(|mv| mv.source() == source)
+                    .collect();
+                let mut actual: Vec<LegalMove> = board.legal_moves_from(source).collect();
+                actual.sort_by_key(|mv| expected.iter().position(|other| other == mv));
+
+                assert_eq!(actual, expected, "{fen} {source:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn stages_are_yielded_in_order() {
+        // white can take a pawn with a pawn (good) or take a defended pawn with a queen (bad)
+        let board = board("4k3/8/2p5/3p4/4P3/8/8/3QK3 w - - 0 1");
+        let mut moves = board.legal_moves();
+        let mut stages = Vec::new();
+
+        while let Some(mv) = moves.next() {
+            stages.push((moves.stage(), Move::from(mv)));
+        }
+
+        let square = |name| Square::try_from(name).unwrap();
+        assert_eq!(
+            stages.first().unwrap().1,
+            Move::from((square("e4"), square("d5")))
+        );
+        assert_eq!(
+            stages.last().unwrap().1,
+            Move::from((square("d1"), square("d5")))
+        );
+        assert!(stages.windows(2).all(|pair| pair[0].0 <= pair[1].0));
+    }
+
+    #[test]
+    fn legal_killers_are_yielded_once_before_quiets() {
+        let square = |name| Square::try_from(name).unwrap();
+        let killer = Move::from((square("g1"), square("f3")));
+        let illegal = Move::from((square("e1"), square("e3")));
+        let moves: Vec<Move> = Board::default()
+            .legal_moves()
+            .with_killers([Some(illegal), Some(killer)])
+            .map(Move::from)
+            .collect();
+
+        assert_eq!(moves.len(), 20);
+        assert_eq!(moves[0], killer);
+        assert!(!moves.contains(&illegal));
+
+        // a killer given twice, and without the kind of a double push, is yielded once
+        let push = Move::from((square("e2"), square("e4")));
+        let moves: Vec<Move> = Board::default()
+            .legal_moves()
+            .with_killers([Some(push), Some(push)])
+            .map(Move::from)
+            .collect();
+
+        assert_eq!(moves.len(), 20);
+        assert_eq!(moves[0].as_pair(), push.as_pair());
+        assert!(moves[1..].iter().all(|mv| mv.as_pair() != push.as_pair()));
+    }
+
+    #[test]
+    fn moves_are_generated_into_a_reused_buffer() {
+        let mut buffer = MoveList::new();
+        for fen in [
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+            "4k3/4r3/8/8/8/8/4N3/4K3 w - - 0 1",
+            "7k/5Q2/6K1/8/8/8/8/8 b - - 0 1",
+        ] {
+            let board = board(fen);
+            board.generate_moves_into(&mut buffer);
+
+            let mut expected: Vec<Move> = board.legal_moves().map(Move::from).collect();
+            let mut generated = buffer.to_vec();
+            expected.sort();
+            generated.sort();
+            assert_eq!(generated, expected, "{fen}");
+        }
+    }
+
+    #[test]
+    fn legal_moves_are_detected_without_generating_them_all() {
+        for (fen, expected) in [
+            (
+                "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+                true,
+            ),
+            // checkmate and stalemate
+            ("7k/6Q1/6K1/8/8/8/8/8 b - - 0 1", false),
+            ("7k/5Q2/6K1/8/8/8/8/8 b - - 0 1", false),
+            // a double check, which only the king can escape by capturing
+            ("4k3/8/8/8/8/8/4r3/r3K1N1 w - - 0 1", true),
+            // a check which only the knight can escape, by blocking
+            ("4k3/8/8/8/8/4N3/6PP/r6K w - - 0 1", true),
+        ] {
+            let board = board(fen);
+            assert_eq!(board.has_legal_moves(), expected, "{fen}");
+            assert_eq!(board.legal_moves().next().is_some(), expected, "{fen}");
+        }
+    }
+}
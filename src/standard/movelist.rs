@@ -0,0 +1,221 @@
+use std::ops::{Deref, DerefMut};
+
+use super::{r#move::Move, square::Square};
+
+/// The number of moves a [`MoveList`] can hold, which exceeds the
+/// number of pseudo-legal moves in any reachable position.
+pub const MOVE_LIST_CAPACITY: usize = 256;
+
+/// A list of at most [`MOVE_LIST_CAPACITY`] moves, stored inline.
+///
+/// Move generation writes into a `MoveList` rather than a [`Vec`], so that
+/// generating the moves of a position never allocates; a search can keep one
+/// list per ply on the stack. The list dereferences to a slice of its moves,
+/// which provides iteration, indexing and sorting.
+#[derive(Clone, Copy)]
+pub struct MoveList {
+    moves: [Move; MOVE_LIST_CAPACITY],
+    len: usize,
+}
+
+impl MoveList {
+    /// Constructs an empty list.
+    pub fn new() -> Self {
+        // the unused slots are never read, so any move will do
+        let placeholder = Move::from((Square::at(0), Square::at(0)));
+        Self {
+            moves: [placeholder; MOVE_LIST_CAPACITY],
+            len: 0,
+        }
+    }
+
+    /// Appends `mv` to the end of the list.
+    ///
+    /// # Panics
+    /// Panics if the list already holds [`MOVE_LIST_CAPACITY`] moves.
+    pub fn push(&mut self, mv: Move) {
+        assert!(self.len < MOVE_LIST_CAPACITY, "move list is full");
+        self.moves[self.len] = mv;
+        self.len += 1;
+    }
+
+    /// Removes and returns the last move in the list, if any.
+    pub fn pop(&mut self) -> Option<Move> {
+        self.len = self.len.checked_sub(1)?;
+        Some(self.moves[self.len])
+    }
+
+    /// Removes every move from the list.
+    pub fn clear(&mut self) {
+        self.len = 0;
+    }
+
+    /// Removes the move at `index`, replacing it with the last move in the list.
+    ///
+    /// # Panics
+    /// Panics if `index` is out of bounds.
+    pub fn swap_remove(&mut self, index: usize) -> Move {
+        let mv = self[index];
+        self.len -= 1;
+        self.moves[index] = self.moves[self.len];
+        mv
+    }
+
+    /// Keeps only the moves for which `predicate` returns `true`, preserving their order.
+    pub fn retain(&mut self, mut predicate: impl FnMut(&Move) -> bool) {
+        let mut kept = 0;
+        for index in 0..self.len {
+            if predicate(&self.moves[index]) {
+                self.moves[kept] = self.moves[index];
+                kept += 1;
+            }
+        }
+
+        self.len = kept;
+    }
+
+    /// Returns the moves in the list as a slice.
+    pub fn as_slice(&self) -> &[Move] {
+        &self.moves[..self.len]
+    }
+
+    /// Returns the moves in the list as a mutable slice.
+    pub fn as_mut_slice(&mut self) -> &mut [Move] {
+        &mut self.moves[..self.len]
+    }
+}
+
+impl Default for MoveList {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Debug for MoveList {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.as_slice()).finish()
+    }
+}
+
+impl PartialEq for MoveList {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl Eq for MoveList {}
+
+impl Deref for MoveList {
+    type Target = [Move];
+
+    fn deref(&self) -> &Self::Target {
+        self.as_slice()
+    }
+}
+
+impl DerefMut for MoveList {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.as_mut_slice()
+    }
+}
+
+impl Extend<Move> for MoveList {
+    fn extend<T: IntoIterator<Item = Move>>(&mut self, iter: T) {
+        for mv in iter {
+            self.push(mv);
+        }
+    }
+}
+
+impl FromIterator<Move> for MoveList {
+    fn from_iter<T: IntoIterator<Item = Move>>(iter: T) -> Self {
+        let mut list = Self::new();
+        list.extend(iter);
+        list
+    }
+}
+
+impl IntoIterator for MoveList {
+    type Item = Move;
+    type IntoIter = IntoIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter {
+            list: self,
+            cursor: 0,
+        }
+    }
+}
+
+impl<'a> IntoIterator for &'a MoveList {
+    type Item = &'a Move;
+    type IntoIter = std::slice::Iter<'a, Move>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// An owning iterator over the moves of a [`MoveList`].
+#[derive(Debug, Clone)]
+pub struct IntoIter {
+    list: MoveList,
+    cursor: usize,
+}
+
+impl Iterator for IntoIter {
+    type Item = Move;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mv = self.list.get(self.cursor).copied()?;
+        self.cursor += 1;
+        Some(mv)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.list.len() - self.cursor;
+        (remaining, Some(remaining))
+    }
+}
+
+impl ExactSizeIterator for IntoIter {}
+
+impl std::iter::FusedIterator for IntoIter {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn move_lists_behave_like_vectors() {
+        let square = |name| Square::try_from(name).unwrap();
+        let mv = |source, target| Move::from((square(source), square(target)));
+
+        let mut list: MoveList = [mv("e2", "e4"), mv("d2", "d4"), mv("g1", "f3")]
+            .into_iter()
+            .collect();
+        assert_eq!(list.len(), 3);
+        assert_eq!(list[1], mv("d2", "d4"));
+
+        list.retain(|&candidate| candidate != mv("d2", "d4"));
+        assert_eq!(list.as_slice(), [mv("e2", "e4"), mv("g1", "f3")]);
+
+        list.sort();
+        assert_eq!(list.as_slice(), [mv("g1", "f3"), mv("e2", "e4")]);
+        assert_eq!(list.swap_remove(0), mv("g1", "f3"));
+        assert_eq!(list.pop(), Some(mv("e2", "e4")));
+        assert_eq!(list.pop(), None);
+        assert!(list.is_empty());
+
+        let full: MoveList = std::iter::repeat_n(mv("a1", "a2"), MOVE_LIST_CAPACITY).collect();
+        assert_eq!(full.into_iter().len(), MOVE_LIST_CAPACITY);
+    }
+
+    #[test]
+    #[should_panic(expected = "move list is full")]
+    fn full_move_lists_panic_on_push() {
+        let mut list = MoveList::new();
+        let mv = Move::from((Square::at(0), Square::at(1)));
+        list.extend(std::iter::repeat_n(mv, MOVE_LIST_CAPACITY + 1));
+    }
+}
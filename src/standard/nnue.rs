@@ -0,0 +1,250 @@
+use super::{
+    board::Board,
+    piece::{Color, Piece, PieceKind},
+    r#move::LegalMove,
+    Square,
+};
+use crate::core::Piece as _;
+
+/// The number of piece planes in a [`FeatureSet::HalfKP`] input,
+/// plus the unused leading feature kept for compatibility.
+const HALF_KP_PLANES: usize = 10 * 64 + 1;
+
+/// The number of piece planes in a [`FeatureSet::HalfKA`] input.
+const HALF_KA_PLANES: usize = 12 * 64;
+
+/// An NNUE input feature set, describing how the pieces on a
+/// [`Board`] are mapped onto the indices of active input features.
+///
+/// Both feature sets are computed from the perspective of one side, and
+/// pair the square of that side's king with the square and kind of every
+/// other piece. From black's perspective the board is oriented so that
+/// black plays "up" the board, and friendly pieces always precede opposing
+/// pieces of the same kind.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FeatureSet {
+    /// The HalfKP feature set, which excludes both kings from the piece planes.
+    ///
+    /// Indices are laid out as `1 + square + 64 * plane + 641 * king`, matching the
+    /// original Stockfish networks; from black's perspective squares are rotated.
+    HalfKP,
+    /// The HalfKA feature set, which includes both kings in the piece planes.
+    ///
+    /// Indices are laid out as `square + 64 * plane + 768 * king`;
+    /// from black's perspective squares are flipped vertically.
+    HalfKA,
+}
+
+/// Describes how to bring the active features of one perspective
+/// up to date after a move, as returned by [`FeatureSet::update`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FeatureUpdate {
+    /// The perspective's king moved, so every feature changed and the
+    /// accumulator must be rebuilt from the given active features.
+    Refresh(Vec<usize>),
+    /// Only the given features were added and removed.
+    Delta {
+        /// The features which became active.
+        added: Vec<usize>,
+        /// The features which stopped being active.
+        removed: Vec<usize>,
+    },
+}
+
+impl FeatureSet {
+    /// Returns the total number of input features in this feature set.
+    pub const fn dimensions(self) -> usize {
+        match self {
+            Self::HalfKP => 64 * HALF_KP_PLANES,
+            Self::HalfKA => 64 * HALF_KA_PLANES,
+        }
+    }
+
+    /// Returns the index of the feature for `piece` on `square`, from the
+    /// perspective of `perspective` with its king on `king`.
+    ///
+    /// Returns `None` if the feature set has no feature for `piece`,
+    /// which is the case for kings in [`FeatureSet::HalfKP`].
+    pub fn index(
+        self,
+        perspective: Color,
+        king: Square,
+        square: Square,
+        piece: Piece,
+    ) -> Option<usize> {
+        let plane = 2 * kind_index(piece.kind()) + usize::from(piece.color() != perspective);
+
+        match self {
+            Self::HalfKP if piece.kind() == PieceKind::King => None,
+            Self::HalfKP => {
+                let orient = |square: Square| match perspective {
+                    Color::White => usize::from(square),
+                    Color::Black => usize::from(square) ^ 63,
+                };
+
+                Some(1 + orient(square) + 64 * plane + HALF_KP_PLANES * orient(king))
+            }
+            Self::HalfKA => {
+                let orient = |square: Square| match perspective {
+                    Color::White => usize::from(square),
+                    Color::Black => usize::from(square) ^ 56,
+                };
+
+                Some(orient(square) + 64 * plane + HALF_KA_PLANES * orient(king))
+            }
+        }
+    }
+
+    /// Returns the indices of every active feature on `board` from the perspective
+    /// of `perspective`, in ascending order of their squares.
+    ///
+    /// If `perspective` has no king on `board`, no features are active.
+    pub fn active(self, board: &Board, perspective: Color) -> Vec<usize> {
+        let Some(king) = board.king_square(perspective) else {
+            return Vec::new();
+        };
+
+        board
+            .occupied()
+            .squares()
+            .filter_map(|square| {
+                let piece = board[square]?;
+                self.index(perspective, king, square, piece)
+            })
+            .collect()
+    }
+
+    /// Returns the changes to the active features of `perspective` caused by
+    /// playing `candidate` on `board`.
+    pub fn update(self, board: &Board, candidate: LegalMove, perspective: Color) -> FeatureUpdate {
+        let next = board.make(candidate.into());
+
+        let king = match (
+            board.king_square(perspective),
+            next.king_square(perspective),
+        ) {
+            (Some(before), Some(after)) if before == after => before,
+            _ => return FeatureUpdate::Refresh(self.active(&next, perspective)),
+        };
+
+        let mut added = Vec::new();
+        let mut removed = Vec::new();
+        for square in (board.occupied() | next.occupied()).squares() {
+            if board[square] == next[square] {
+                continue;
+            }
+
+            if let Some(index) =
+                board[square].and_then(|p| self.index(perspective, king, square, p))
+            {
+                removed.push(index);
+            }
+
+            if let Some(index) = next[square].and_then(|p| self.index(perspective, king, square, p))
+            {
+                added.push(index);
+            }
+        }
+
+        FeatureUpdate::Delta { added, removed }
+    }
+}
+
+impl Board {
+    /// Returns the indices of the active NNUE input features of `self` in the given
+    /// feature `set`, from the perspective of `perspective`.
+    ///
+    /// See [`FeatureSet::active`] for details.
+    pub fn nnue_features(&self, set: FeatureSet, perspective: Color) -> Vec<usize> {
+        set.active(self, perspective)
+    }
+}
+
+/// Returns the conventional NNUE ordering of piece kinds,
+/// which differs from the declaration order of [`PieceKind`].
+fn kind_index(kind: PieceKind) -> usize {
+    match kind {
+        PieceKind::Pawn => 0,
+        PieceKind::Knight => 1,
+        PieceKind::Bishop => 2,
+        PieceKind::Rook => 3,
+        PieceKind::Queen => 4,
+        PieceKind::King => 5,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::Fen;
+
+    fn square(name: &str) -> Square {
+        Square::try_from(name).unwrap()
+    }
+
+    #[test]
+    fn starting_features_are_counted_and_indexed() {
+        let board = Board::default();
+        let half_kp = board.nnue_features(FeatureSet::HalfKP, Color::White);
+        let half_ka = board.nnue_features(FeatureSet::HalfKA, Color::White);
+
+        assert_eq!(half_kp.len(), 30);
+        assert_eq!(half_ka.len(), 32);
+        assert!(half_kp
+            .iter()
+            .all(|&index| index < FeatureSet::HalfKP.dimensions()));
+        assert!(half_ka
+            .iter()
+            .all(|&index| index < FeatureSet::HalfKA.dimensions()));
+
+        // a friendly pawn on a2 with the king on e1
+        let index =
+            FeatureSet::HalfKP.index(Color::White, square("e1"), square("a2"), Piece::WhitePawn);
+        assert_eq!(index, Some(1 + 8 + 641 * 4));
+        // the same pawn seen by black, whose king on e8 is rotated onto d1
+        let index =
+            FeatureSet::HalfKP.index(Color::Black, square("e8"), square("a2"), Piece::WhitePawn);
+        assert_eq!(index, Some(1 + 55 + 64 + 641 * 3));
+        // the perspectives are mirror images of each other in the starting position
+        let mut black = board.nnue_features(FeatureSet::HalfKA, Color::Black);
+        let mut white = half_ka;
+        black.sort_unstable();
+        white.sort_unstable();
+        assert_eq!(black, white);
+    }
+
+    #[test]
+    fn updates_agree_with_recomputed_features() {
+        let fens = [
+            "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+            "rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq f6 0 1",
+            "r3k3/1P6/8/8/8/8/8/4K2R w Kq - 0 1",
+        ];
+
+        for fen in fens {
+            let board = Fen::try_from(fen).unwrap().to_standard_board();
+            for set in [FeatureSet::HalfKP, FeatureSet::HalfKA] {
+                for perspective in [Color::White, Color::Black] {
+                    for candidate in board.legal_moves() {
+                        let next = board.make(candidate.into());
+                        let mut expected = next.nnue_features(set, perspective);
+                        expected.sort_unstable();
+
+                        let mut actual = match set.update(&board, candidate, perspective) {
+                            FeatureUpdate::Refresh(features) => features,
+                            FeatureUpdate::Delta { added, removed } => {
+                                let mut features = board.nnue_features(set, perspective);
+                                features.retain(|index| !removed.contains(index));
+                                features.extend(added);
+                                features
+                            }
+                        };
+                        actual.sort_unstable();
+
+                        assert_eq!(actual, expected, "{fen}: {candidate:?}");
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,135 @@
+use std::{cmp::Reverse, iter::FusedIterator};
+
+use super::{
+    board::Board,
+    movegen::{is_quiet, mvv_lva},
+    r#move::{LegalMove, Move},
+};
+
+/// The bonus added to the score of a move which gives check, worth as much as
+/// winning a pawn with a pawn.
+pub const CHECK_BONUS: i16 = 16;
+
+/// Scores `candidate` for move ordering, so that better moves score higher.
+///
+/// Captures and promotions are scored by most valuable victim and then least
+/// valuable attacker (MVV-LVA), and always score above quiet moves, which score
+/// zero. Moves which give check gain a further [`CHECK_BONUS`].
+pub fn score(board: &Board, candidate: LegalMove) -> i16 {
+    let check = match board.gives_check(&candidate) {
+        true => CHECK_BONUS,
+        false => 0,
+    };
+
+    let candidate = Move::from(candidate);
+    let noisy = match is_quiet(board, candidate) {
+        true => 0,
+        // the worst capture, a king taking a pawn, still scores above zero
+        false => mvv_lva(board, candidate),
+    };
+
+    noisy + check
+}
+
+impl Board {
+    /// Returns the legal moves in this position, sorted from best to worst by [`score`].
+    ///
+    /// Moves with equal scores keep the order in which they were generated.
+    pub fn ordered_moves(&self) -> Vec<LegalMove> {
+        let mut moves: Vec<LegalMove> = self.legal_moves().collect();
+        moves.sort_by_cached_key(|&legal_move| Reverse(score(self, legal_move)));
+        moves
+    }
+
+    /// Returns a [`MovePicker`] over the legal moves in this position.
+    pub fn pick_moves(&self) -> MovePicker {
+        MovePicker {
+            moves: self
+                .legal_moves()
+                .map(|legal_move| (legal_move, score(self, legal_move)))
+                .collect(),
+            cursor: 0,
+        }
+    }
+}
+
+/// An iterator which selects the remaining legal move with the highest [`score`]
+/// each time it is advanced.
+///
+/// Since a search often stops after its first few moves, selecting moves one at a
+/// time is usually cheaper than sorting them all, as [`Board::ordered_moves`] does.
+/// Moves with equal scores are yielded in an unspecified order.
+#[derive(Debug, Clone)]
+pub struct MovePicker {
+    moves: Vec<(LegalMove, i16)>,
+    cursor: usize,
+}
+
+impl MovePicker {
+    /// Returns the score of the move which would be yielded next, if any remain.
+    pub fn peek_score(&self) -> Option<i16> {
+        self.moves[self.cursor..]
+            .iter()
+            .map(|&(_, score)| score)
+            .max()
+    }
+}
+
+impl Iterator for MovePicker {
+    type Item = LegalMove;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let remaining = &self.moves[self.cursor..];
+        let best = (0..remaining.len()).max_by_key(|&index| remaining[index].1)?;
+
+        self.moves.swap(self.cursor, self.cursor + best);
+        self.cursor += 1;
+        Some(self.moves[self.cursor - 1].0)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.moves.len() - self.cursor;
+        (remaining, Some(remaining))
+    }
+}
+
+impl ExactSizeIterator for MovePicker {}
+
+impl FusedIterator for MovePicker {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::Fen;
+
+    #[test]
+    fn moves_are_ordered_by_victim_attacker_and_check() {
+        // the pawn and knight can both take the rook, the queen can give check
+        // on the e-file, and the knight and queen can take the defended pawn
+        let board = Fen::try_from("4k3/8/2r3p1/1P3p2/3N4/8/8/5Q1K w - - 0 1")
+            .unwrap()
+            .to_standard_board();
+
+        let ordered: Vec<String> = board
+            .ordered_moves()
+            .iter()
+            .take(6)
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(ordered, ["b5c6", "d4c6", "f1e1", "f1e2", "d4f5", "f1f5"]);
+
+        let scores: Vec<i16> = board
+            .ordered_moves()
+            .into_iter()
+            .map(|legal_move| score(&board, legal_move))
+            .collect();
+        assert!(scores.windows(2).all(|pair| pair[0] >= pair[1]));
+
+        let mut picker = board.pick_moves();
+        assert_eq!(picker.len(), scores.len());
+        assert_eq!(picker.peek_score(), Some(scores[0]));
+        let picked: Vec<i16> = picker.by_ref().map(|mv| score(&board, mv)).collect();
+        assert_eq!(picked, scores);
+        assert_eq!(picker.peek_score(), None);
+    }
+}
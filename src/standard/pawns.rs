@@ -0,0 +1,226 @@
+use super::{
+    attacks,
+    bitboard::BitBoard,
+    board::Board,
+    piece::{Color, PieceKind},
+    Square,
+};
+
+/// The pawn structure of a [`Board`], describing the common
+/// strengths and weaknesses of each side's pawns as [`BitBoard`]s.
+///
+/// Each of the sets is a subset of the pawns of the corresponding color,
+/// and a single pawn may belong to several sets at once (e.g. a pawn can
+/// be both passed and isolated).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PawnStructure {
+    pawns: [BitBoard; 2],
+    passed: [BitBoard; 2],
+    isolated: [BitBoard; 2],
+    doubled: [BitBoard; 2],
+    backward: [BitBoard; 2],
+    connected: [BitBoard; 2],
+}
+
+impl PawnStructure {
+    /// Analyses the pawns on `board`.
+    pub fn new(board: &Board) -> Self {
+        let mut structure = Self {
+            pawns: [BitBoard::EMPTY; 2],
+            passed: [BitBoard::EMPTY; 2],
+            isolated: [BitBoard::EMPTY; 2],
+            doubled: [BitBoard::EMPTY; 2],
+            backward: [BitBoard::EMPTY; 2],
+            connected: [BitBoard::EMPTY; 2],
+        };
+
+        for color in Color::ALL {
+            let ours = board.pieces(color, PieceKind::Pawn);
+            let theirs = board.pieces(color.opposite(), PieceKind::Pawn);
+            let index = color as usize;
+            structure.pawns[index] = ours;
+
+            for square in ours.squares() {
+                let pawn = BitBoard::from_square(square);
+                let file = BitBoard::file(square.file());
+                let adjacent = adjacent_files_mask(square.file());
+                let ahead = ahead_mask(color, square.rank());
+                let is_isolated = (ours & adjacent).is_empty();
+
+                if (theirs & ahead & (file | adjacent)).is_empty()
+                    && (ours & ahead & file).is_empty()
+                {
+                    structure.passed[index] |= pawn;
+                }
+
+                if is_isolated {
+                    structure.isolated[index] |= pawn;
+                }
+
+                if !(ours & ahead & file).is_empty() {
+                    structure.doubled[index] |= pawn;
+                }
+
+                // defended by a friendly pawn, or standing beside one
+                let supporters = attacks::pawn(color.opposite(), square) | rank_neighbors(square);
+                if !(ours & supporters).is_empty() {
+                    structure.connected[index] |= pawn;
+                }
+
+                // every neighbouring pawn has advanced past it, and it cannot safely advance
+                let behind_or_level = !ahead_mask(color, square.rank());
+                let stop = stop_square(color, square);
+                let is_stop_attacked =
+                    stop.is_some_and(|stop| !(attacks::pawn(color, stop) & theirs).is_empty());
+
+                if !is_isolated
+                    && (ours & adjacent & behind_or_level).is_empty()
+                    && is_stop_attacked
+                {
+                    structure.backward[index] |= pawn;
+                }
+            }
+        }
+
+        structure
+    }
+
+    /// Returns every pawn of the given `color`.
+    pub fn pawns(&self, color: Color) -> BitBoard {
+        self.pawns[color as usize]
+    }
+
+    /// Returns the pawns of the given `color` with no opposing pawns in front of them on
+    /// their own or adjacent files, and which are not blocked by a friendly pawn.
+    pub fn passed(&self, color: Color) -> BitBoard {
+        self.passed[color as usize]
+    }
+
+    /// Returns the pawns of the given `color` with no friendly pawns on adjacent files.
+    pub fn isolated(&self, color: Color) -> BitBoard {
+        self.isolated[color as usize]
+    }
+
+    /// Returns the pawns of the given `color` with another friendly pawn in front
+    /// of them on the same file, i.e. every doubled pawn except the most advanced.
+    pub fn doubled(&self, color: Color) -> BitBoard {
+        self.doubled[color as usize]
+    }
+
+    /// Returns the pawns of the given `color` which have fallen behind every friendly
+    /// pawn on the adjacent files, and whose stop square is attacked by an opposing pawn.
+    pub fn backward(&self, color: Color) -> BitBoard {
+        self.backward[color as usize]
+    }
+
+    /// Returns the pawns of the given `color` which are either defended by a
+    /// friendly pawn or stand directly beside one on the same rank.
+    pub fn connected(&self, color: Color) -> BitBoard {
+        self.connected[color as usize]
+    }
+}
+
+impl Board {
+    /// Returns the [`PawnStructure`] of `self`.
+    pub fn pawn_structure(&self) -> PawnStructure {
+        PawnStructure::new(self)
+    }
+}
+
+/// Returns the mask of every square on the files beside the given `file`.
+pub(crate) fn adjacent_files_mask(file: u8) -> BitBoard {
+    let mut mask = BitBoard::EMPTY;
+    if file > 0 {
+        mask |= BitBoard::file(file - 1);
+    }
+    if file < 7 {
+        mask |= BitBoard::file(file + 1);
+    }
+
+    mask
+}
+
+/// Returns the mask of every square on the ranks strictly in front of
+/// `rank`, from the perspective of the given `color`.
+pub(crate) fn ahead_mask(color: Color, rank: u8) -> BitBoard {
+    let bits = match color {
+        Color::White if rank < 7 => u64::MAX << (8 * (rank + 1)),
+        Color::Black => (1u64 << (8 * rank)) - 1,
+        Color::White => 0,
+    };
+
+    BitBoard::from_bits(bits)
+}
+
+/// Returns the squares directly beside `square` on the same rank.
+fn rank_neighbors(square: Square) -> BitBoard {
+    adjacent_files_mask(square.file()) & BitBoard::rank(square.rank())
+}
+
+/// Returns the square directly in front of a pawn of the given `color` on `square`, if any.
+fn stop_square(color: Color, square: Square) -> Option<Square> {
+    let index = usize::from(square) as u8;
+    match color {
+        Color::White if square.rank() < 7 => Some(Square::at(index + 8)),
+        Color::Black if square.rank() > 0 => Some(Square::at(index - 8)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::Fen;
+
+    fn squares(names: &[&str]) -> BitBoard {
+        names
+            .iter()
+            .map(|&name| BitBoard::from_square(Square::try_from(name).unwrap()))
+            .fold(BitBoard::EMPTY, |acc, square| acc | square)
+    }
+
+    #[test]
+    fn starting_pawns_are_connected_and_nothing_else() {
+        let structure = Board::default().pawn_structure();
+        for color in [Color::White, Color::Black] {
+            assert_eq!(structure.pawns(color).count(), 8);
+            assert_eq!(structure.connected(color), structure.pawns(color));
+            assert!(structure.passed(color).is_empty());
+            assert!(structure.isolated(color).is_empty());
+            assert!(structure.doubled(color).is_empty());
+            assert!(structure.backward(color).is_empty());
+        }
+    }
+
+    #[test]
+    fn pawn_weaknesses_are_classified() {
+        // white has doubled a-pawns and four isolated pawns, of which a3 and h5 are passed;
+        // black's isolated c- and e-pawns are both held back by the pawn on d3
+        let board = Fen::try_from("4k3/8/8/2p1p2P/8/P2P4/P7/4K3 w - - 0 1")
+            .unwrap()
+            .to_standard_board();
+        let structure = board.pawn_structure();
+
+        assert_eq!(
+            structure.isolated(Color::White),
+            squares(&["a2", "a3", "d3", "h5"])
+        );
+        assert_eq!(structure.doubled(Color::White), squares(&["a2"]));
+        assert_eq!(structure.passed(Color::White), squares(&["a3", "h5"]));
+        assert_eq!(structure.isolated(Color::Black), squares(&["c5", "e5"]));
+        assert!(structure.passed(Color::Black).is_empty());
+    }
+
+    #[test]
+    fn backward_and_connected_pawns_are_classified() {
+        // the d3 pawn has been left behind by c4 and e4, and d4 is guarded by black's c5
+        let board = Fen::try_from("4k3/8/8/2p5/2P1P3/3P4/8/4K3 w - - 0 1")
+            .unwrap()
+            .to_standard_board();
+        let structure = board.pawn_structure();
+
+        assert_eq!(structure.backward(Color::White), squares(&["d3"]));
+        assert_eq!(structure.connected(Color::White), squares(&["c4", "e4"]));
+        assert!(structure.backward(Color::Black).is_empty());
+    }
+}
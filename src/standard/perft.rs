@@ -0,0 +1,150 @@
+use super::board::Board;
+
+/// The number of entries in a default [`PerftTable`].
+const DEFAULT_ENTRIES: usize = 1 << 16;
+
+/// Counts the leaf nodes of the legal move tree of `board` to the given `depth`.
+///
+/// Perft is the standard test of move generation, since its results for many
+/// positions are well known; see <https://www.chessprogramming.org/Perft_Results>.
+///
+/// Since the moves are generated legally, the leaves one ply from the frontier
+/// are counted in bulk as the number of legal moves, without making any of
+/// them. This removes the most numerous make calls of the whole tree, which
+/// typically makes perft several times faster, at the cost of no longer
+/// checking that the moves of the final ply can be made.
+pub fn perft(board: &Board, depth: u8) -> u64 {
+    count_children(board, depth, perft)
+}
+
+/// Counts the leaf nodes as [`perft`] does, caching the counts of the subtrees
+/// it visits in `table` so that transpositions are only counted once.
+///
+/// Subtrees are identified by the [Zobrist hash](Board::zobrist) of their root
+/// and their depth, so a hash collision could produce a wrong count; with 64-bit
+/// keys this is vanishingly unlikely at the depths perft can reach.
+pub fn perft_hashed(board: &Board, depth: u8, table: &mut PerftTable) -> u64 {
+    // shallow subtrees are cheaper to count in bulk than to look up
+    if depth <= 1 {
+        return perft(board, depth);
+    }
+
+    let key = board.zobrist();
+    if let Some(nodes) = table.get(key, depth) {
+        return nodes;
+    }
+
+    let nodes = count_children(board, depth, |child, depth| {
+        perft_hashed(child, depth, table)
+    });
+
+    table.insert(key, depth, nodes);
+    nodes
+}
+
+/// Counts the leaves of the tree of `board` to the given `depth`, using `count`
+/// for the subtrees of its children, except at depth 1, where the legal moves
+/// are counted in bulk.
+fn count_children(board: &Board, depth: u8, mut count: impl FnMut(&Board, u8) -> u64) -> u64 {
+    match depth {
+        0 => 1,
+        1 => board.legal_moves().count() as u64,
+        _ => board
+            .legal_moves()
+            .map(|legal_move| count(&board.make(legal_move.into()), depth - 1))
+            .sum(),
+    }
+}
+
+/// A single cached subtree of a [`PerftTable`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+struct PerftEntry {
+    key: u64,
+    depth: u8,
+    nodes: u64,
+}
+
+/// A fixed-size transposition table of perft subtree counts, for [`perft_hashed`].
+///
+/// Each position hashes to a single slot, and newer entries always replace
+/// older ones. Entries remain valid between calls, so a table may be reused to
+/// count several positions, or the same position to increasing depths.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PerftTable {
+    entries: Vec<PerftEntry>,
+}
+
+impl Default for PerftTable {
+    fn default() -> Self {
+        Self::new(DEFAULT_ENTRIES)
+    }
+}
+
+impl PerftTable {
+    /// Constructs an empty table with room for at least `entries` subtrees,
+    /// rounded up to a power of two.
+    pub fn new(entries: usize) -> Self {
+        Self {
+            entries: vec![PerftEntry::default(); entries.max(1).next_power_of_two()],
+        }
+    }
+
+    /// Returns the number of subtrees the table can hold.
+    pub fn capacity(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Removes every entry from the table.
+    pub fn clear(&mut self) {
+        self.entries.fill(PerftEntry::default());
+    }
+
+    /// Returns the slot of the subtree at `key`.
+    fn slot(&self, key: u64) -> usize {
+        (key as usize) & (self.entries.len() - 1)
+    }
+
+    /// Returns the cached count of the subtree at `key` to the given `depth`.
+    fn get(&self, key: u64, depth: u8) -> Option<u64> {
+        let entry = self.entries[self.slot(key)];
+        (entry.depth == depth && entry.key == key).then_some(entry.nodes)
+    }
+
+    /// Caches the count of the subtree at `key` to the given `depth`.
+    fn insert(&mut self, key: u64, depth: u8, nodes: u64) {
+        let slot = self.slot(key);
+        self.entries[slot] = PerftEntry { key, depth, nodes };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::Fen;
+
+    fn board(fen: &str) -> Board {
+        Fen::try_from(fen).unwrap().to_standard_board()
+    }
+
+    #[test]
+    fn hashed_perft_agrees_with_the_reference_counts() {
+        let mut table = PerftTable::new(1 << 12);
+        assert_eq!(table.capacity(), 4096);
+
+        let start = Board::default();
+        assert_eq!(perft_hashed(&start, 3, &mut table), 8902);
+        assert_eq!(perft_hashed(&start, 4, &mut table), 197281);
+
+        // "kiwipete", from https://www.chessprogramming.org/Perft_Results
+        let kiwipete =
+            board("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1");
+        table.clear();
+        assert_eq!(perft_hashed(&kiwipete, 3, &mut table), 97862);
+        assert_eq!(perft(&kiwipete, 2), 2039);
+
+        // a tiny table still gives correct counts, only more slowly
+        let mut tiny = PerftTable::new(0);
+        assert_eq!(tiny.capacity(), 1);
+        assert_eq!(perft_hashed(&start, 3, &mut tiny), 8902);
+    }
+}
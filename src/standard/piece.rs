@@ -1,7 +1,11 @@
 use crate::core;
+use thiserror::Error;
 
 /// Represents the standard set of chess pieces.
-#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+///
+/// Pieces are ordered as they are declared: every black piece precedes every
+/// white piece, and the pieces of each color are ordered as their [`PieceKind`]s.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, PartialOrd, Ord, Hash)]
 pub enum Piece {
     /// A black pawn.
     BlackPawn,
@@ -30,7 +34,9 @@ pub enum Piece {
 }
 
 /// Represents the standard set of chess piece colors.
-#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+///
+/// Colors are ordered as they are declared, so that black precedes white.
+#[derive(Debug, Eq, PartialEq, PartialOrd, Ord, Hash, Clone, Copy)]
 pub enum Color {
     /// The second-playing side.
     Black,
@@ -39,7 +45,9 @@ pub enum Color {
 }
 
 /// Represents the standard set of chess piece kinds.
-#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+///
+/// Kinds are ordered as they are declared: pawn, rook, knight, bishop, queen, king.
+#[derive(Debug, Eq, PartialEq, PartialOrd, Ord, Hash, Clone, Copy)]
 pub enum PieceKind {
     /// A pawn.
     Pawn,
@@ -55,6 +63,71 @@ pub enum PieceKind {
     King,
 }
 
+impl Color {
+    /// Both colors, in the order in which they move.
+    pub const ALL: [Color; 2] = [Color::White, Color::Black];
+
+    /// Returns the opposing color.
+    pub const fn opposite(self) -> Self {
+        match self {
+            Self::Black => Self::White,
+            Self::White => Self::Black,
+        }
+    }
+
+    /// Returns the color denoted by `symbol` in the side to move field of
+    /// a FEN string, i.e. `'w'` for white and `'b'` for black.
+    pub fn from_char(symbol: char) -> Option<Self> {
+        match symbol {
+            'w' => Some(Self::White),
+            'b' => Some(Self::Black),
+            _ => None,
+        }
+    }
+
+    /// Returns the character denoting `self` in the side to move
+    /// field of a FEN string, i.e. `'w'` or `'b'`.
+    pub fn to_char(self) -> char {
+        match self {
+            Self::White => 'w',
+            Self::Black => 'b',
+        }
+    }
+}
+
+impl PieceKind {
+    /// Returns the English SAN letter of `self`, or `None` for a pawn.
+    ///
+    /// Other languages are handled by [`SanLocale`](crate::io::SanLocale).
+    pub fn to_san_char(self) -> Option<char> {
+        crate::io::SanLocale::ENGLISH.to_char(self)
+    }
+
+    /// Returns the kind of piece denoted by the English SAN letter `letter`.
+    pub fn from_san_char(letter: char) -> Option<Self> {
+        crate::io::SanLocale::ENGLISH.from_char(letter)
+    }
+}
+
+impl std::ops::Not for Color {
+    type Output = Self;
+
+    /// Returns the [`opposite`](Color::opposite) color.
+    fn not(self) -> Self::Output {
+        self.opposite()
+    }
+}
+
+impl std::fmt::Display for Color {
+    /// Writes `self` in lowercase, i.e. as `white` or `black`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::White => write!(f, "white"),
+            Self::Black => write!(f, "black"),
+        }
+    }
+}
+
 impl core::Piece for Piece {
     type Color = Color;
     type Kind = PieceKind;
@@ -152,7 +225,69 @@ impl Into<char> for Piece {
     }
 }
 
+/// Results when a string cannot be parsed as a [`Piece`].
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum ParsePieceError {
+    /// Results when the string is not exactly one character long.
+    #[error("expected a single character, got {0} characters")]
+    Length(usize),
+    /// Results when the character is neither a FEN letter nor a chess symbol.
+    #[error("{0:?} is neither a FEN piece letter nor a Unicode chess symbol")]
+    Unknown(char),
+}
+
+impl std::str::FromStr for Piece {
+    type Err = ParsePieceError;
+
+    /// Parses a piece from either its FEN letter, e.g. `N`, or its Unicode
+    /// chess symbol, e.g. `♘`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        let (Some(symbol), None) = (chars.next(), chars.next()) else {
+            return Err(ParsePieceError::Length(s.chars().count()));
+        };
+
+        Piece::try_from(symbol)
+            .ok()
+            .or_else(|| Piece::from_utf8_chess_symbol(symbol))
+            .ok_or(ParsePieceError::Unknown(symbol))
+    }
+}
+
+impl std::fmt::Display for Piece {
+    /// Writes the FEN letter of `self`, or its Unicode chess
+    /// symbol if the alternate flag is given, as in `{:#}`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let symbol = match f.alternate() {
+            true => self.into_utf8_chess_symbol(),
+            false => (*self).into(),
+        };
+
+        write!(f, "{symbol}")
+    }
+}
+
 impl Piece {
+    /// Converts a UTF-8 chess symbol into the corresponding piece, if there is one;
+    /// this is the inverse of [`Piece::into_utf8_chess_symbol`].
+    pub fn from_utf8_chess_symbol(symbol: char) -> Option<Self> {
+        match symbol {
+            '\u{2654}' => Some(Self::WhiteKing),
+            '\u{2655}' => Some(Self::WhiteQueen),
+            '\u{2656}' => Some(Self::WhiteRook),
+            '\u{2657}' => Some(Self::WhiteBishop),
+            '\u{2658}' => Some(Self::WhiteKnight),
+            '\u{2659}' => Some(Self::WhitePawn),
+            '\u{265A}' => Some(Self::BlackKing),
+            '\u{265B}' => Some(Self::BlackQueen),
+            '\u{265C}' => Some(Self::BlackRook),
+            '\u{265D}' => Some(Self::BlackBishop),
+            '\u{265E}' => Some(Self::BlackKnight),
+            '\u{265F}' => Some(Self::BlackPawn),
+            _ => None,
+        }
+    }
+
     /// Converts `self` into the corresponding UTF-8 [`char`].
     pub fn into_utf8_chess_symbol(self) -> char {
         match self {
@@ -171,3 +306,60 @@ impl Piece {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn colors_are_converted_and_negated() {
+        for color in Color::ALL {
+            assert_eq!(Color::from_char(color.to_char()), Some(color));
+            assert_eq!(!!color, color);
+            assert_eq!(!color, color.opposite());
+        }
+
+        assert_eq!(Color::from_char('W'), None);
+        assert_eq!(Color::White.to_string(), "white");
+        assert_eq!(format!("{} to move", Color::Black), "black to move");
+    }
+
+    #[test]
+    fn piece_kinds_are_converted_to_and_from_san() {
+        assert_eq!(PieceKind::Knight.to_san_char(), Some('N'));
+        assert_eq!(PieceKind::Pawn.to_san_char(), None);
+        assert_eq!(PieceKind::from_san_char('Q'), Some(PieceKind::Queen));
+        assert_eq!(PieceKind::from_san_char('P'), None);
+        assert_eq!(PieceKind::from_san_char('n'), None);
+    }
+
+    #[test]
+    fn pieces_are_parsed_and_displayed() {
+        let pieces = [
+            Piece::BlackPawn,
+            Piece::BlackRook,
+            Piece::BlackKnight,
+            Piece::BlackBishop,
+            Piece::BlackQueen,
+            Piece::BlackKing,
+            Piece::WhitePawn,
+            Piece::WhiteRook,
+            Piece::WhiteKnight,
+            Piece::WhiteBishop,
+            Piece::WhiteQueen,
+            Piece::WhiteKing,
+        ];
+
+        for piece in pieces {
+            assert_eq!(piece.to_string().parse(), Ok(piece));
+            assert_eq!(format!("{piece:#}").parse(), Ok(piece));
+        }
+
+        assert_eq!("N".parse(), Ok(Piece::WhiteKnight));
+        assert_eq!("\u{265E}".parse(), Ok(Piece::BlackKnight));
+        assert_eq!(format!("{:#}", Piece::WhiteKing), "\u{2654}");
+        assert_eq!("x".parse::<Piece>(), Err(ParsePieceError::Unknown('x')));
+        assert_eq!("Nf3".parse::<Piece>(), Err(ParsePieceError::Length(3)));
+        assert_eq!("".parse::<Piece>(), Err(ParsePieceError::Length(0)));
+    }
+}
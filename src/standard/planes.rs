@@ -0,0 +1,109 @@
+use super::{board::Board, piece::Color};
+use crate::core::Standard as _;
+
+/// The number of 8×8 planes produced by [`Board::to_planes`].
+pub const PLANE_COUNT: usize = 18;
+
+/// The number of values produced by [`Board::to_planes`], i.e. `PLANE_COUNT * 64`.
+pub const PLANES_LEN: usize = PLANE_COUNT * 64;
+
+/// The index of the side to move plane, which is filled with ones if white is to move.
+pub const SIDE_TO_MOVE_PLANE: usize = 12;
+
+/// The index of the first castling plane; the four castling planes are ordered white
+/// king side, white queen side, black king side, black queen side.
+pub const CASTLING_PLANES: usize = 13;
+
+/// The index of the en passant plane, which has a one on the en passant target square.
+pub const EN_PASSANT_PLANE: usize = 17;
+
+impl Board {
+    /// Encodes `self` as a flat, channels-first stack of [`PLANE_COUNT`] 8×8 one-hot planes.
+    ///
+    /// The value for `square` on `plane` lives at `plane * 64 + square`, where squares are
+    /// indexed from a1 to h8 along ranks. The first 12 planes mark the squares of each
+    /// [`Piece`](super::Piece), in the order that the variants of [`Piece`](super::Piece)
+    /// are declared; the remaining planes are described by [`SIDE_TO_MOVE_PLANE`],
+    /// [`CASTLING_PLANES`] and [`EN_PASSANT_PLANE`]. Planes which describe a flag rather
+    /// than a set of squares are filled entirely with either zeroes or ones.
+    pub fn to_planes(&self) -> Vec<f32> {
+        let mut planes = vec![0.0; PLANES_LEN];
+        self.write_planes(&mut planes);
+        planes
+    }
+
+    /// Writes the encoding described by [`Board::to_planes`] into `planes`, which
+    /// allows a batch of positions to be encoded into a single preallocated buffer.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `planes` does not have a length of exactly [`PLANES_LEN`].
+    pub fn write_planes(&self, planes: &mut [f32]) {
+        assert_eq!(planes.len(), PLANES_LEN, "expected {PLANES_LEN} values");
+        planes.fill(0.0);
+
+        for square in self.occupied().squares() {
+            if let Some(piece) = self[square] {
+                planes[piece as usize * 64 + usize::from(square)] = 1.0;
+            }
+        }
+
+        let mut fill = |plane: usize, flag: bool| {
+            if flag {
+                planes[plane * 64..(plane + 1) * 64].fill(1.0);
+            }
+        };
+
+        let castling = self.castling_permissions();
+        fill(SIDE_TO_MOVE_PLANE, self.side_to_move() == Color::White);
+        fill(CASTLING_PLANES, castling.white_king_side);
+        fill(CASTLING_PLANES + 1, castling.white_queen_side);
+        fill(CASTLING_PLANES + 2, castling.black_king_side);
+        fill(CASTLING_PLANES + 3, castling.black_queen_side);
+
+        if let Some(square) = self.en_passant_target_square() {
+            planes[EN_PASSANT_PLANE * 64 + usize::from(square)] = 1.0;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::Fen;
+    use crate::standard::{Piece, Square};
+
+    #[test]
+    fn starting_position_planes() {
+        let planes = Board::default().to_planes();
+        let plane = |index: usize| &planes[index * 64..(index + 1) * 64];
+
+        assert_eq!(planes.len(), PLANES_LEN);
+        assert_eq!(plane(Piece::WhitePawn as usize).iter().sum::<f32>(), 8.0);
+        assert_eq!(plane(Piece::BlackKing as usize)[60], 1.0);
+        assert!(plane(SIDE_TO_MOVE_PLANE).iter().all(|&value| value == 1.0));
+        assert!(plane(CASTLING_PLANES + 3).iter().all(|&value| value == 1.0));
+        assert!(plane(EN_PASSANT_PLANE).iter().all(|&value| value == 0.0));
+        assert_eq!(planes.iter().sum::<f32>(), 32.0 + 5.0 * 64.0);
+    }
+
+    #[test]
+    fn flags_and_en_passant_are_encoded() {
+        let board = Fen::try_from("rnbqkbnr/ppp1pppp/8/8/3pP3/8/PPPP1PPP/RNBQKBNR b Kq e3 0 3")
+            .unwrap()
+            .to_standard_board();
+        let mut planes = [1.0; PLANES_LEN];
+        board.write_planes(&mut planes);
+        let plane = |index: usize| &planes[index * 64..(index + 1) * 64];
+
+        assert!(plane(SIDE_TO_MOVE_PLANE).iter().all(|&value| value == 0.0));
+        assert!(plane(CASTLING_PLANES).iter().all(|&value| value == 1.0));
+        assert!(plane(CASTLING_PLANES + 1).iter().all(|&value| value == 0.0));
+        assert!(plane(CASTLING_PLANES + 2).iter().all(|&value| value == 0.0));
+        assert!(plane(CASTLING_PLANES + 3).iter().all(|&value| value == 1.0));
+
+        let e3 = usize::from(Square::try_from("e3").unwrap());
+        assert_eq!(plane(EN_PASSANT_PLANE)[e3], 1.0);
+        assert_eq!(plane(EN_PASSANT_PLANE).iter().sum::<f32>(), 1.0);
+    }
+}
@@ -0,0 +1,125 @@
+use rand::{seq::SliceRandom, Rng};
+
+use super::{
+    game::{Game, Outcome},
+    piece::Color,
+    r#move::Move,
+};
+
+/// The outcomes of a batch of random playouts, as counted by [`Game::random_playouts`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct PlayoutSummary {
+    /// The number of playouts which white won by checkmate.
+    pub white_wins: u32,
+    /// The number of playouts which black won by checkmate.
+    pub black_wins: u32,
+    /// The number of playouts which ended in stalemate.
+    pub stalemates: u32,
+    /// The number of playouts which reached the ply limit without ending.
+    pub unfinished: u32,
+}
+
+impl PlayoutSummary {
+    /// Returns the total number of playouts.
+    pub fn total(&self) -> u32 {
+        self.white_wins + self.black_wins + self.stalemates + self.unfinished
+    }
+
+    /// Counts the result of a single playout.
+    fn record(&mut self, outcome: Option<Outcome>) {
+        match outcome {
+            Some(Outcome::Checkmate {
+                winner: Color::White,
+            }) => self.white_wins += 1,
+            Some(Outcome::Checkmate {
+                winner: Color::Black,
+            }) => self.black_wins += 1,
+            Some(Outcome::Stalemate) => self.stalemates += 1,
+            None => self.unfinished += 1,
+        }
+    }
+}
+
+impl Game {
+    /// Plays uniformly random legal moves until the game ends or `max_plies` moves
+    /// have been played, returning the [`Outcome`] if the game ended.
+    ///
+    /// The moves are added to the game, so the playout can be inspected afterwards;
+    /// clone the game first to keep the original. Draws by repetition, the fifty-move
+    /// rule or insufficient material are not recognised, so such playouts only stop
+    /// at the ply limit.
+    pub fn random_playout<R: Rng + ?Sized>(
+        &mut self,
+        rng: &mut R,
+        max_plies: usize,
+    ) -> Option<Outcome> {
+        for _ in 0..max_plies {
+            let moves: Vec<Move> = self.board().legal_moves().map(Move::from).collect();
+            let Some(&candidate) = moves.choose(rng) else {
+                break;
+            };
+
+            self.play(candidate)
+                .expect("generated moves should be legal");
+        }
+
+        self.outcome()
+    }
+
+    /// Runs `count` random playouts from the current position of the game, as by
+    /// [`Game::random_playout`], and counts their outcomes; the game itself is unchanged.
+    pub fn random_playouts<R: Rng + ?Sized>(
+        &self,
+        rng: &mut R,
+        max_plies: usize,
+        count: u32,
+    ) -> PlayoutSummary {
+        let mut summary = PlayoutSummary::default();
+        for _ in 0..count {
+            summary.record(self.clone().random_playout(rng, max_plies));
+        }
+
+        summary
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::SmallRng, SeedableRng};
+
+    use super::*;
+    use crate::io::Fen;
+    use crate::standard::Board;
+
+    #[test]
+    fn playouts_end_in_outcomes_or_at_the_ply_limit() {
+        let mut rng = SmallRng::seed_from_u64(1);
+
+        let mut game = Game::new();
+        let outcome = game.random_playout(&mut rng, 40);
+        assert!(game.moves().len() <= 40);
+        assert_eq!(outcome, game.outcome());
+        assert_eq!(outcome.is_some(), game.moves().len() < 40);
+
+        // no moves are played with a ply limit of zero
+        let open = Fen::try_from("6k1/5ppp/8/8/8/8/8/R5K1 b - - 0 1")
+            .unwrap()
+            .to_standard_board();
+        let summary = Game::from_board(open).random_playouts(&mut rng, 0, 8);
+        assert_eq!(summary.unfinished, 8);
+
+        // a game which has already ended is left as it is
+        let mated = Game::from_board(
+            Fen::try_from("R5k1/5ppp/8/8/8/8/8/6K1 b - - 0 1")
+                .unwrap()
+                .to_standard_board(),
+        );
+        let summary = mated.random_playouts(&mut rng, 10, 5);
+        assert_eq!(summary.white_wins, 5);
+        assert_eq!(summary.total(), 5);
+        assert!(mated.moves().is_empty());
+
+        let summary = Game::from_board(Board::default()).random_playouts(&mut rng, 2, 3);
+        assert_eq!(summary.unfinished, 3);
+    }
+}
@@ -0,0 +1,244 @@
+use std::collections::HashMap;
+
+use super::board::Board;
+use crate::{core::Standard as _, io::Fen};
+
+/// A map from positions to values of type `V`, keyed by the
+/// [Zobrist hash](Board::zobrist) of each position.
+///
+/// Since distinct positions may share a hash, every entry also stores a
+/// FEN description of its position, which is compared against on each
+/// lookup; colliding positions are kept side by side rather than
+/// overwriting each other. Two boards are considered to be the same
+/// position if they agree on the placement of the pieces, the side to
+/// move, the castling permissions, and any en passant capture which is
+/// actually available.
+///
+/// This is intended for building opening trees and deduplicating
+/// datasets, where many positions are reached by several move orders.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PositionIndex<V> {
+    buckets: HashMap<u64, Vec<(String, V)>>,
+    len: usize,
+}
+
+impl<V> Default for PositionIndex<V> {
+    fn default() -> Self {
+        Self {
+            buckets: HashMap::new(),
+            len: 0,
+        }
+    }
+}
+
+impl<V> PositionIndex<V> {
+    /// Constructs an empty [`PositionIndex`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of positions in the index.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the index contains no positions.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Associates `value` with the position of `board`, returning
+    /// the value previously associated with it, if any.
+    pub fn insert(&mut self, board: &Board, value: V) -> Option<V> {
+        self.insert_keyed(board.zobrist(), position_key(board), value)
+    }
+
+    /// Returns a reference to the value associated with the position of `board`.
+    pub fn get(&self, board: &Board) -> Option<&V> {
+        let key = position_key(board);
+        self.buckets
+            .get(&board.zobrist())?
+            .iter()
+            .find(|(fen, _)| *fen == key)
+            .map(|(_, value)| value)
+    }
+
+    /// Returns a mutable reference to the value associated with the position of `board`.
+    pub fn get_mut(&mut self, board: &Board) -> Option<&mut V> {
+        let key = position_key(board);
+        self.buckets
+            .get_mut(&board.zobrist())?
+            .iter_mut()
+            .find(|(fen, _)| *fen == key)
+            .map(|(_, value)| value)
+    }
+
+    /// Returns `true` if the position of `board` is in the index.
+    pub fn contains(&self, board: &Board) -> bool {
+        self.get(board).is_some()
+    }
+
+    /// Removes the position of `board` from the index, returning its value if it was present.
+    pub fn remove(&mut self, board: &Board) -> Option<V> {
+        let hash = board.zobrist();
+        let key = position_key(board);
+        let bucket = self.buckets.get_mut(&hash)?;
+        let index = bucket.iter().position(|(fen, _)| *fen == key)?;
+        let (_, value) = bucket.swap_remove(index);
+
+        if bucket.is_empty() {
+            self.buckets.remove(&hash);
+        }
+
+        self.len -= 1;
+        Some(value)
+    }
+
+    /// Moves every entry of `other` into `self`, using `combine` to merge the
+    /// values of positions which are present in both indices.
+    pub fn merge(&mut self, other: PositionIndex<V>, mut combine: impl FnMut(&mut V, V)) {
+        for (hash, bucket) in other.buckets {
+            for (key, value) in bucket {
+                let existing = self
+                    .buckets
+                    .get_mut(&hash)
+                    .and_then(|bucket| bucket.iter_mut().find(|(fen, _)| *fen == key));
+
+                match existing {
+                    Some((_, current)) => combine(current, value),
+                    None => {
+                        self.insert_keyed(hash, key, value);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Returns an iterator over the entries of the index, as pairs of Zobrist hashes and values.
+    ///
+    /// The iteration order is unspecified.
+    pub fn iter(&self) -> impl Iterator<Item = (u64, &V)> {
+        self.buckets
+            .iter()
+            .flat_map(|(&hash, bucket)| bucket.iter().map(move |(_, value)| (hash, value)))
+    }
+
+    /// Returns the number of hashes which are shared by more than one position in the index.
+    pub fn collisions(&self) -> usize {
+        self.buckets
+            .values()
+            .filter(|bucket| bucket.len() > 1)
+            .count()
+    }
+
+    /// Inserts `value` under the precomputed `hash` and `key`.
+    fn insert_keyed(&mut self, hash: u64, key: String, value: V) -> Option<V> {
+        let bucket = self.buckets.entry(hash).or_default();
+        match bucket.iter_mut().find(|(fen, _)| *fen == key) {
+            Some((_, current)) => Some(std::mem::replace(current, value)),
+            None => {
+                bucket.push((key, value));
+                self.len += 1;
+                None
+            }
+        }
+    }
+}
+
+impl<V> Extend<(Board, V)> for PositionIndex<V> {
+    fn extend<T: IntoIterator<Item = (Board, V)>>(&mut self, iter: T) {
+        for (board, value) in iter {
+            self.insert(&board, value);
+        }
+    }
+}
+
+impl<V> FromIterator<(Board, V)> for PositionIndex<V> {
+    fn from_iter<T: IntoIterator<Item = (Board, V)>>(iter: T) -> Self {
+        let mut index = Self::new();
+        index.extend(iter);
+        index
+    }
+}
+
+/// Returns the first four fields of the FEN string of `board`, with the en passant
+/// square omitted unless an en passant capture is actually available.
+fn position_key(board: &Board) -> String {
+    let fen = Fen::from(board).to_string();
+    let mut fields = fen.split(' ');
+    let placement = fields.next().unwrap_or_default();
+    let side_to_move = fields.next().unwrap_or_default();
+    let castling = fields.next().unwrap_or_default();
+    let en_passant = match board.en_passant_target_square() {
+        Some(square) if board.is_en_passant_capturable(square) => fields.next().unwrap_or("-"),
+        _ => "-",
+    };
+
+    format!("{placement} {side_to_move} {castling} {en_passant}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn board(fen: &str) -> Board {
+        Fen::try_from(fen).unwrap().to_standard_board()
+    }
+
+    #[test]
+    fn insert_lookup_and_remove() {
+        let mut index = PositionIndex::new();
+        let start = Board::default();
+        let e4 = board("rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1");
+
+        assert_eq!(index.insert(&start, 1), None);
+        assert_eq!(index.insert(&e4, 2), None);
+        assert_eq!(index.insert(&start, 3), Some(1));
+        assert_eq!(index.len(), 2);
+        assert_eq!(index.get(&start), Some(&3));
+
+        *index.get_mut(&e4).unwrap() += 10;
+        assert_eq!(index.remove(&e4), Some(12));
+        assert!(!index.contains(&e4));
+        assert_eq!(index.len(), 1);
+    }
+
+    #[test]
+    fn unavailable_en_passant_squares_are_ignored() {
+        let mut index = PositionIndex::new();
+        let with_square = board("rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1");
+        let without_square = board("rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq - 0 1");
+
+        index.insert(&with_square, ());
+        assert!(index.contains(&without_square));
+    }
+
+    #[test]
+    fn colliding_hashes_are_kept_apart() {
+        let mut index = PositionIndex::new();
+        let start = Board::default();
+        let other = board("8/8/8/8/8/8/8/4K2k w - - 0 1");
+
+        // simulate a collision by filing the second position under the first's hash
+        index.insert(&start, "start");
+        index.insert_keyed(start.zobrist(), position_key(&other), "other");
+
+        assert_eq!(index.len(), 2);
+        assert_eq!(index.collisions(), 1);
+        assert_eq!(index.get(&start), Some(&"start"));
+        assert_eq!(index.get(&other), None);
+    }
+
+    #[test]
+    fn merge_combines_shared_positions() {
+        let start = Board::default();
+        let e4 = board("rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1");
+        let mut first: PositionIndex<u32> = [(start, 1)].into_iter().collect();
+        let second: PositionIndex<u32> = [(start, 2), (e4, 5)].into_iter().collect();
+
+        first.merge(second, |current, value| *current += value);
+        assert_eq!(first.len(), 2);
+        assert_eq!(first.get(&start), Some(&3));
+        assert_eq!(first.get(&e4), Some(&5));
+    }
+}
@@ -0,0 +1,243 @@
+use super::{
+    board::Board,
+    r#move::{IllegalMoveError, LegalMove, Move},
+};
+use crate::{
+    core::{Process as _, Validate as _},
+    io::San,
+};
+
+/// A tactical puzzle: a starting position, the line which solves it,
+/// and a set of themes describing it (e.g. `"fork"` or `"mateIn2"`).
+///
+/// The solution alternates between the solving side and its opponent,
+/// starting with whichever side is to move in the starting position.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Puzzle {
+    start: Board,
+    solution: Vec<LegalMove>,
+    themes: Vec<String>,
+}
+
+/// The outcome of checking an answer against a [`Puzzle`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    /// The answer solves the puzzle.
+    Solved,
+    /// Every move of the answer is correct, but the solution continues
+    /// after the given number of plies.
+    Incomplete {
+        /// The number of plies in the answer.
+        plies: usize,
+    },
+    /// The move at the given (zero-indexed) ply is illegal or wrong.
+    Incorrect {
+        /// The index of the first wrong move in the answer.
+        ply: usize,
+    },
+}
+
+impl Puzzle {
+    /// Constructs a [`Puzzle`], checking that `solution` is a legal line from `start`.
+    pub fn new(
+        start: Board,
+        solution: &[Move],
+        themes: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Result<Self, IllegalMoveError> {
+        let mut board = start;
+        let mut legal_moves = Vec::with_capacity(solution.len());
+        for &candidate in solution {
+            let legal_move = board.validate(candidate)?;
+            board = board.process(legal_move);
+            legal_moves.push(legal_move);
+        }
+
+        Ok(Self {
+            start,
+            solution: legal_moves,
+            themes: themes.into_iter().map(Into::into).collect(),
+        })
+    }
+
+    /// Returns the starting position of the puzzle.
+    pub fn start(&self) -> &Board {
+        &self.start
+    }
+
+    /// Returns the moves of the solution, for both sides.
+    pub fn solution(&self) -> &[LegalMove] {
+        &self.solution
+    }
+
+    /// Returns the themes of the puzzle.
+    pub fn themes(&self) -> &[String] {
+        &self.themes
+    }
+
+    /// Returns `true` if the puzzle has the given `theme`.
+    pub fn has_theme(&self, theme: &str) -> bool {
+        self.themes.iter().any(|known| known == theme)
+    }
+
+    /// Checks `answer`, a line of moves for both sides, against the solution.
+    ///
+    /// Each move is checked in turn, and the answer may deviate from the
+    /// solution in two ways. First, the moves of either side may be played
+    /// in a different order, so long as every move was taken from that
+    /// side's part of the solution and, by the end of the answer, the
+    /// transposed moves have led back to the solution's position, compared as
+    /// by [`Board::same_position`]. Second, if the final move of the solution
+    /// delivers checkmate, then any move which delivers checkmate in its place
+    /// is accepted.
+    pub fn check(&self, answer: &[Move]) -> Verdict {
+        self.verify(answer.len(), |board, ply| board.validate(answer[ply]).ok())
+    }
+
+    /// Checks `answer`, a line of SAN literals for both sides, against the
+    /// solution; see [`Puzzle::check`] for the rules applied to each move.
+    pub fn check_san(&self, answer: &[San]) -> Verdict {
+        self.verify(answer.len(), |board, ply| {
+            board.validate_san(answer[ply].clone()).ok()
+        })
+    }
+
+    /// Walks an answer of `len` plies, where `resolve` produces the legal
+    /// move of each ply on the board it is played from.
+    fn verify(
+        &self,
+        len: usize,
+        mut resolve: impl FnMut(&Board, usize) -> Option<LegalMove>,
+    ) -> Verdict {
+        let mut expected = self.start;
+        let mut board = self.start;
+        // the ply at which the answer left the solution's line of positions
+        let mut transposed_from = None;
+
+        for ply in 0..len {
+            let Some(&solution_move) = self.solution.get(ply) else {
+                return Verdict::Incorrect { ply };
+            };
+
+            let Some(legal_move) = resolve(&board, ply) else {
+                return Verdict::Incorrect { ply };
+            };
+
+            expected = expected.process(solution_move);
+            board = board.process(legal_move);
+
+            if board.same_position(&expected) {
+                transposed_from = None;
+                continue;
+            }
+
+            let is_final = ply + 1 == self.solution.len();
+            let is_transposed = self
+                .solution
+                .iter()
+                .skip(ply % 2)
+                .step_by(2)
+                .any(|&candidate| Move::from(candidate) == Move::from(legal_move));
+
+            match is_final {
+                true if transposed_from.is_none() && is_mate(&expected) && is_mate(&board) => {}
+                false if is_transposed => {
+                    transposed_from.get_or_insert(ply);
+                }
+                _ => return Verdict::Incorrect { ply },
+            }
+        }
+
+        // a transposition only counts once it has led back to the solution's position
+        if let Some(ply) = transposed_from {
+            return Verdict::Incorrect { ply };
+        }
+
+        match len == self.solution.len() {
+            true => Verdict::Solved,
+            false => Verdict::Incomplete { plies: len },
+        }
+    }
+}
+
+/// Returns `true` if the side to move on `board` is checkmated.
+fn is_mate(board: &Board) -> bool {
+    board.is_check() && !board.has_legal_moves()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::Fen;
+    use crate::standard::r#move::mv;
+
+    fn board(fen: &str) -> Board {
+        Fen::try_from(fen).unwrap().to_standard_board()
+    }
+
+    #[test]
+    fn answers_are_checked_move_by_move() {
+        let line = [
+            mv("e2", "e4"),
+            mv("e7", "e5"),
+            mv("g1", "f3"),
+            mv("b8", "c6"),
+        ];
+        let puzzle = Puzzle::new(Board::default(), &line, ["opening"]).unwrap();
+        assert!(puzzle.has_theme("opening"));
+        assert_eq!(puzzle.solution().len(), 4);
+
+        assert_eq!(puzzle.check(&line), Verdict::Solved);
+        assert_eq!(puzzle.check(&line[..1]), Verdict::Incomplete { plies: 1 });
+        assert_eq!(
+            puzzle.check(&[mv("d2", "d4")]),
+            Verdict::Incorrect { ply: 0 }
+        );
+        assert_eq!(
+            puzzle.check(&[mv("e2", "e4"), mv("e7", "e6")]),
+            Verdict::Incorrect { ply: 1 }
+        );
+
+        // the same moves in another order reach the same position
+        let transposed = [
+            mv("g1", "f3"),
+            mv("b8", "c6"),
+            mv("e2", "e4"),
+            mv("e7", "e5"),
+        ];
+        assert_eq!(puzzle.check(&transposed), Verdict::Solved);
+        assert_eq!(
+            puzzle.check(&transposed[..2]),
+            Verdict::Incorrect { ply: 0 }
+        );
+
+        // each side plays its own moves, but the position is never the solution's
+        let scrambled = [
+            mv("e2", "e4"),
+            mv("b8", "c6"),
+            mv("g1", "f3"),
+            mv("e7", "e6"),
+        ];
+        assert_eq!(puzzle.check(&scrambled), Verdict::Incorrect { ply: 3 });
+
+        let illegal = [mv("e2", "e5")];
+        assert_eq!(puzzle.check(&illegal), Verdict::Incorrect { ply: 0 });
+        assert!(Puzzle::new(Board::default(), &illegal, ["broken"]).is_err());
+    }
+
+    #[test]
+    fn alternative_mates_are_accepted() {
+        let start = board("6k1/5ppp/8/8/8/8/5PPP/R2R2K1 w - - 0 1");
+        let puzzle = Puzzle::new(start, &[mv("d1", "d8")], ["mateIn1"]).unwrap();
+
+        assert_eq!(puzzle.check(&[mv("a1", "a8")]), Verdict::Solved);
+        assert_eq!(
+            puzzle.check(&[mv("d1", "d7")]),
+            Verdict::Incorrect { ply: 0 }
+        );
+
+        let answer = [San::try_from("Rdd8#").unwrap()];
+        assert_eq!(puzzle.check_san(&answer), Verdict::Solved);
+        let ambiguous = [San::try_from("Rc1").unwrap()];
+        assert_eq!(puzzle.check_san(&ambiguous), Verdict::Incorrect { ply: 0 });
+    }
+}
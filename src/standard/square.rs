@@ -5,8 +5,116 @@ use nonmax::NonMaxU8;
 
 use super::PieceKind;
 
+/// The color of a [`Square`] on the checkered board, as given by [`core::Colored`].
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum SquareColor {
+    /// A light square, such as h1.
+    Light,
+    /// A dark square, such as a1.
+    Dark,
+}
+
+/// A file of the board, from the a-file to the h-file.
+#[derive(Copy, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, Debug)]
+pub enum File {
+    /// The a-file.
+    A,
+    /// The b-file.
+    B,
+    /// The c-file.
+    C,
+    /// The d-file.
+    D,
+    /// The e-file.
+    E,
+    /// The f-file.
+    F,
+    /// The g-file.
+    G,
+    /// The h-file.
+    H,
+}
+
+impl File {
+    /// Every file, from the a-file to the h-file.
+    pub const ALL: [File; 8] = [
+        File::A,
+        File::B,
+        File::C,
+        File::D,
+        File::E,
+        File::F,
+        File::G,
+        File::H,
+    ];
+
+    /// Returns the file with the given `index`, where 0 is the a-file, if there is one.
+    pub const fn from_index(index: u8) -> Option<Self> {
+        match index {
+            0..=7 => Some(Self::ALL[index as usize]),
+            _ => None,
+        }
+    }
+
+    /// Returns the index of `self`, where 0 is the a-file.
+    pub const fn index(self) -> u8 {
+        self as u8
+    }
+}
+
+/// A rank of the board, from the first rank (white's back rank) to the eighth.
+#[derive(Copy, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, Debug)]
+pub enum Rank {
+    /// The first rank.
+    First,
+    /// The second rank.
+    Second,
+    /// The third rank.
+    Third,
+    /// The fourth rank.
+    Fourth,
+    /// The fifth rank.
+    Fifth,
+    /// The sixth rank.
+    Sixth,
+    /// The seventh rank.
+    Seventh,
+    /// The eighth rank.
+    Eighth,
+}
+
+impl Rank {
+    /// Every rank, from the first rank to the eighth.
+    pub const ALL: [Rank; 8] = [
+        Rank::First,
+        Rank::Second,
+        Rank::Third,
+        Rank::Fourth,
+        Rank::Fifth,
+        Rank::Sixth,
+        Rank::Seventh,
+        Rank::Eighth,
+    ];
+
+    /// Returns the rank with the given `index`, where 0 is the first rank, if there is one.
+    pub const fn from_index(index: u8) -> Option<Self> {
+        match index {
+            0..=7 => Some(Self::ALL[index as usize]),
+            _ => None,
+        }
+    }
+
+    /// Returns the index of `self`, where 0 is the first rank.
+    pub const fn index(self) -> u8 {
+        self as u8
+    }
+}
+
 /// Represents a specific square on a `StandardBoard`
-#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+///
+/// Squares are ordered by their index, i.e. from a1 to h8 rank by rank, so that
+/// a1 < h1 < a2 < h8.
+#[derive(Copy, Clone, Eq, PartialEq, PartialOrd, Ord, Hash, Debug)]
 pub struct Square(NonMaxU8);
 
 impl core::Index for Square {
@@ -20,6 +128,31 @@ impl core::Index for Square {
     }
 }
 
+impl core::Colored for Square {
+    type Color = SquareColor;
+
+    fn color(&self) -> Self::Color {
+        // a1 is dark, and the colors alternate along every rank and file
+        match (self.file() + self.rank()) % 2 {
+            0 => SquareColor::Dark,
+            _ => SquareColor::Light,
+        }
+    }
+}
+
+impl core::Coordinates for Square {
+    const FILES: usize = 8;
+    const RANKS: usize = 8;
+
+    fn coordinates(&self) -> (usize, usize) {
+        (self.file().into(), self.rank().into())
+    }
+
+    fn from_coordinates(file: usize, rank: usize) -> Option<Self> {
+        (file < 8 && rank < 8).then(|| Square::at((rank * 8 + file) as u8))
+    }
+}
+
 impl core::PieceMetric for Square {
     type PieceKind = PieceKind;
 
@@ -39,7 +172,7 @@ impl TryFrom<u8> for Square {
     fn try_from(value: u8) -> Result<Self, Self::Error> {
         match value {
             index @ 0..=63 => Ok(Self(index.try_into().unwrap())),
-            index @ _ => Err(IndexError::OutOfBounds(index)),
+            index @ _ => Err(IndexError::OutOfBounds { index, bound: 64 }),
         }
     }
 }
@@ -48,14 +181,12 @@ impl TryFrom<usize> for Square {
     type Error = IndexError<usize>;
 
     fn try_from(value: usize) -> Result<Self, Self::Error> {
-        let int: u8 = value
-            .try_into()
-            .map_err(|_err| IndexError::OutOfBounds(value))?;
+        let int: u8 = value.try_into().map_err(|_err| IndexError::OutOfBounds {
+            index: value,
+            bound: 64,
+        })?;
 
-        match Square::try_from(int) {
-            Ok(index) => Ok(index),
-            Err(_) => Err(IndexError::OutOfBounds(int as usize)),
-        }
+        Ok(Square::try_from(int)?)
     }
 }
 
@@ -87,34 +218,101 @@ impl<'a> TryFrom<&'a str> for Square {
     }
 }
 
-impl Into<String> for Square {
-    fn into(self) -> String {
-        let rank = ((self.0.get() / 8) + 49) as char;
-        let file = ((self.0.get() % 8) + 97) as char;
+impl From<Square> for String {
+    fn from(value: Square) -> Self {
+        value.to_string()
+    }
+}
 
-        [file, rank].iter().collect()
+impl std::fmt::Display for Square {
+    /// Writes `self` as a file letter followed by a rank number, e.g. `e4`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let rank = (self.rank() + b'1') as char;
+        let file = (self.file() + b'a') as char;
+        write!(f, "{file}{rank}")
     }
 }
 
 impl Square {
-    /// Attempts to construct a valid [`StandardIndex`]
-    /// using the given value, and panics if that fails.
+    /// Constructs the square on the given `file` and `rank`.
+    ///
+    /// Every pair of a file and a rank is a square, so this cannot fail:
     ///
-    /// Consider using `try_from(value: usize)` instead for
-    /// safer code.
+    /// ```
+    /// use konig::standard::{File, Rank, Square};
     ///
-    /// This should be treated as a utility function,
-    /// to avoid constantly writing `StandardIndex::try_from(val).unwrap()`.
-    pub(crate) fn new(value: u8) -> Self {
-        assert!(value <= 63);
-        unsafe { Self(NonMaxU8::new_unchecked(value)) }
+    /// const E4: Square = Square::new(File::E, Rank::Fourth);
+    /// assert_eq!(E4.to_string(), "e4");
+    /// ```
+    pub const fn new(file: File, rank: Rank) -> Self {
+        // SAFETY: both indices are at most 7, so the index is at most 63
+        unsafe { Self::new_unchecked(rank.index() * 8 + file.index()) }
+    }
+
+    /// Returns the square with the given `index`, counting from 0 (a1) to 63 (h8)
+    /// rank by rank, or `None` if `index` is at least 64.
+    pub const fn from_index(index: u8) -> Option<Self> {
+        match index {
+            // SAFETY: the index has just been checked
+            0..=63 => Some(unsafe { Self::new_unchecked(index) }),
+            _ => None,
+        }
+    }
+
+    /// Constructs the square with the given `index`, as in [`Square::from_index`],
+    /// without checking that it lies on the board.
+    ///
+    /// # Safety
+    /// `index` must be less than 64; other values produce a square which lies off
+    /// the board, and which may cause out-of-bounds accesses where it is used.
+    pub const unsafe fn new_unchecked(index: u8) -> Self {
+        Self(NonMaxU8::new_unchecked(index))
+    }
+
+    /// Constructs the square with the given `index`, and panics if it is at least 64.
+    ///
+    /// This is a shorthand for indices which are known to lie on the board,
+    /// to avoid constantly writing `Square::from_index(index).unwrap()`.
+    pub(crate) const fn at(index: u8) -> Self {
+        assert!(index <= 63);
+        unsafe { Self(NonMaxU8::new_unchecked(index)) }
+    }
+
+    /// Returns the file of `self` as a number from 0 (the a-file) to 7 (the h-file).
+    pub(crate) const fn file(self) -> u8 {
+        self.0.get() % 8
+    }
+
+    /// Returns the rank of `self` as a number from 0 (the first rank) to 7 (the eighth rank).
+    pub(crate) const fn rank(self) -> u8 {
+        self.0.get() / 8
+    }
+
+    /// Returns the number of files between `self` and `other`, e.g. 2 for c1 and e8.
+    pub const fn file_distance(self, other: Square) -> u8 {
+        self.file().abs_diff(other.file())
+    }
+
+    /// Returns the number of ranks between `self` and `other`, e.g. 7 for c1 and e8.
+    pub const fn rank_distance(self, other: Square) -> u8 {
+        self.rank().abs_diff(other.rank())
+    }
+
+    /// Returns `true` if `self` and `other` are distinct squares on a common
+    /// diagonal or anti-diagonal, i.e. a bishop could move between them on an
+    /// empty board.
+    pub const fn same_diagonal(self, other: Square) -> bool {
+        let files = self.file_distance(other);
+        files != 0 && files == self.rank_distance(other)
     }
 
-    /// Constructs a [`StandardIndex`] without performing
-    /// safety checks. The caller must ensure that the
-    /// value is less than 64.
-    pub(crate) unsafe fn new_unchecked(value: u8) -> Self {
-        Self(NonMaxU8::new_unchecked(value))
+    /// Returns `true` if `self` and `other` are distinct squares on a common rank,
+    /// file or diagonal, i.e. a queen could move between them on an empty board.
+    ///
+    /// Only pieces on a common line can pin, skewer or check along it.
+    pub const fn same_line(self, other: Square) -> bool {
+        let (files, ranks) = (self.file_distance(other), self.rank_distance(other));
+        (files == 0) != (ranks == 0) || self.same_diagonal(other)
     }
 }
 
@@ -130,7 +328,13 @@ mod tests {
 
         assert!(i.is_ok_and(|index| index == Square::try_from(0u8).unwrap()));
         assert!(j.is_ok_and(|index| index == Square::try_from(63u8).unwrap()));
-        assert!(k.is_err_and(|err| err == IndexError::OutOfBounds(64u8)));
+        assert_eq!(
+            k,
+            Err(IndexError::OutOfBounds {
+                index: 64u8,
+                bound: 64
+            })
+        );
     }
 
     #[test]
@@ -141,7 +345,26 @@ mod tests {
 
         assert!(i.is_ok_and(|index| index == Square::try_from(0usize).unwrap()));
         assert!(j.is_ok_and(|index| index == Square::try_from(63usize).unwrap()));
-        assert!(k.is_err_and(|err| err == IndexError::OutOfBounds(64usize)));
+        assert_eq!(
+            k,
+            Err(IndexError::OutOfBounds {
+                index: 64usize,
+                bound: 64
+            })
+        );
+        assert_eq!(
+            Square::try_from(300usize).unwrap_err().to_string(),
+            "Received an out-of-bounds index: 300 (expected less than 64)"
+        );
+
+        let owned: IndexError<String> = Square::try_from(300usize).unwrap_err().into();
+        assert_eq!(
+            owned,
+            IndexError::OutOfBounds {
+                index: String::from("300"),
+                bound: 64
+            }
+        );
     }
 
     #[test]
@@ -150,19 +373,122 @@ mod tests {
         let j = Square::try_from("d6").unwrap();
         let k = Square::try_from("h7").unwrap();
 
-        assert_eq!(i, Square::new(16));
-        assert_eq!(j, Square::new(43));
-        assert_eq!(k, Square::new(55));
+        assert_eq!(i, Square::at(16));
+        assert_eq!(j, Square::at(43));
+        assert_eq!(k, Square::at(55));
     }
 
     #[test]
     fn standard_index_into_string_is_correct() {
-        let a3: String = Square::new(16).into();
-        let d6: String = Square::new(43).into();
-        let h7: String = Square::new(55).into();
+        let a3: String = Square::at(16).into();
+        let d6: String = Square::at(43).into();
+        let h7: String = Square::at(55).into();
 
         assert_eq!(a3, String::from("a3"));
         assert_eq!(d6, String::from("d6"));
         assert_eq!(h7, String::from("h7"));
     }
+
+    #[test]
+    fn standard_index_is_ordered_from_a1_to_h8() {
+        let mut squares = ["h8", "a2", "h1", "a1"].map(|name| Square::try_from(name).unwrap());
+        squares.sort();
+
+        assert_eq!(squares, [0u8, 7, 8, 63].map(Square::at));
+        assert!(Square::try_from("h1").unwrap() < Square::try_from("a2").unwrap());
+    }
+
+    #[test]
+    fn standard_index_is_displayed_in_algebraic_notation() {
+        assert_eq!(Square::at(0).to_string(), "a1");
+        assert_eq!(Square::at(28).to_string(), "e4");
+        assert_eq!(format!("{}-{}", Square::at(12), Square::at(28)), "e2-e4");
+        assert_eq!(
+            crate::standard::board::SetupError::PawnOnBackRank(Square::at(7)).to_string(),
+            "there is a pawn on the back rank at h1"
+        );
+    }
+
+    #[test]
+    fn standard_index_distances_and_lines_are_correct() {
+        let square = |name| Square::try_from(name).unwrap();
+
+        assert_eq!(square("c1").file_distance(square("e8")), 2);
+        assert_eq!(square("c1").rank_distance(square("e8")), 7);
+        assert_eq!(square("h4").file_distance(square("a4")), 7);
+
+        assert!(square("a1").same_diagonal(square("h8")));
+        assert!(square("h1").same_diagonal(square("b7")));
+        assert!(!square("a1").same_diagonal(square("a1")));
+        assert!(!square("a1").same_diagonal(square("b3")));
+
+        assert!(square("d4").same_line(square("d8")));
+        assert!(square("d4").same_line(square("a4")));
+        assert!(square("d4").same_line(square("g7")));
+        assert!(!square("d4").same_line(square("e6")));
+        assert!(!square("d4").same_line(square("d4")));
+    }
+
+    #[test]
+    fn standard_index_public_constructors_are_correct() {
+        assert_eq!(Square::new(File::A, Rank::First), Square::at(0));
+        assert_eq!(Square::new(File::E, Rank::Fourth).to_string(), "e4");
+        assert_eq!(Square::new(File::H, Rank::Eighth), Square::at(63));
+
+        assert_eq!(Square::from_index(43), Some(Square::at(43)));
+        assert_eq!(Square::from_index(64), None);
+        assert_eq!(File::from_index(8), None);
+        assert_eq!(Rank::from_index(2).map(Rank::index), Some(2));
+
+        for (square, index) in (0..64).filter_map(Square::from_index).zip(0..) {
+            let file = File::from_index(square.file()).unwrap();
+            let rank = Rank::from_index(square.rank()).unwrap();
+            assert_eq!(Square::new(file, rank), Square::at(index));
+        }
+    }
+
+    #[test]
+    fn standard_index_coordinates_round_trip() {
+        use crate::core::Coordinates;
+
+        let square = |name| Square::try_from(name).unwrap();
+        assert_eq!(square("a1").coordinates(), (0, 0));
+        assert_eq!(square("e4").coordinates(), (4, 3));
+        assert_eq!(Square::from_coordinates(7, 7), Some(square("h8")));
+        assert_eq!(Square::from_coordinates(8, 0), None);
+        assert_eq!(
+            Square::try_from_coordinates(2, 9),
+            Err(IndexError::OffBoard {
+                file: 2,
+                rank: 9,
+                files: 8,
+                ranks: 8
+            })
+        );
+        for sq in (0..64u8).map(Square::at) {
+            let (file, rank) = sq.coordinates();
+            assert_eq!(Square::from_coordinates(file, rank), Some(sq));
+        }
+
+        assert_eq!(Square::chebyshev_distance(&square("b1"), &square("g3")), 5);
+        assert_eq!(Square::manhattan_distance(&square("b1"), &square("g3")), 7);
+    }
+
+    #[test]
+    fn standard_index_colors_alternate() {
+        use crate::core::Colored as _;
+
+        let color = |name| Square::try_from(name).unwrap().color();
+        assert_eq!(color("a1"), SquareColor::Dark);
+        assert_eq!(color("h1"), SquareColor::Light);
+        assert_eq!(color("d1"), SquareColor::Light);
+        assert_eq!(color("e1"), SquareColor::Dark);
+        assert_eq!(color("h8"), SquareColor::Dark);
+
+        let light = (0..64)
+            .map(Square::at)
+            .filter(|square| square.color() == SquareColor::Light)
+            .count();
+        assert_eq!(light, 32);
+    }
 }
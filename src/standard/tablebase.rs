@@ -0,0 +1,147 @@
+use thiserror::Error;
+
+use super::{
+    board::Board,
+    piece::{Color, PieceKind},
+};
+use crate::core::{Piece as _, Standard as _};
+
+/// Provides in-memory generation of tables for trivial endings.
+pub mod retrograde;
+
+/// Results when a [`Tablebase`] cannot answer a probe.
+#[derive(Debug, Error)]
+pub enum ProbeError {
+    /// Results when the position has more pieces than the tablebase covers.
+    #[error("the position has {0} pieces, which is more than the tablebase covers")]
+    TooManyPieces(u32),
+    /// Results when the position has castling rights, which tablebases do not encode.
+    #[error("tablebases cannot be probed for positions with castling rights")]
+    CastlingRights,
+    /// Results when the table for the material on the board is not available.
+    #[error("missing table for {0}")]
+    MissingTable(String),
+    /// Results when the tablebase does not support the requested kind of probe.
+    #[error("unsupported probe: {0}")]
+    Unsupported(&'static str),
+}
+
+/// The game-theoretic value of a position with perfect play, from the perspective
+/// of the side to move, as distinguished by the fifty-move rule.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Wdl {
+    /// The side to move loses.
+    Loss,
+    /// The side to move loses, but can hold a draw under the fifty-move rule.
+    BlessedLoss,
+    /// The position is drawn.
+    Draw,
+    /// The side to move wins, but only if the fifty-move rule is ignored.
+    CursedWin,
+    /// The side to move wins.
+    Win,
+}
+
+impl Wdl {
+    /// Returns the value of the position from the perspective of the other side.
+    pub fn flip(self) -> Self {
+        match self {
+            Self::Loss => Self::Win,
+            Self::BlessedLoss => Self::CursedWin,
+            Self::Draw => Self::Draw,
+            Self::CursedWin => Self::BlessedLoss,
+            Self::Win => Self::Loss,
+        }
+    }
+}
+
+/// A distance to the end of a won or lost position, in plies, as measured by a [`Tablebase`].
+///
+/// Distances are positive if the side to move wins and negative if it loses,
+/// and zero if the position is drawn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Distance {
+    /// The distance to the next capture or pawn move which preserves the result (DTZ).
+    Zeroing(i32),
+    /// The distance to mate (DTM).
+    Mate(i32),
+}
+
+/// An endgame oracle which knows the value of every position with few enough pieces.
+pub trait Tablebase {
+    /// Returns the largest number of pieces, including kings, of the positions covered.
+    fn max_pieces(&self) -> u32;
+
+    /// Returns the [`Wdl`] value of `board` for the side to move.
+    fn probe_wdl(&self, board: &Board) -> Result<Wdl, ProbeError>;
+
+    /// Returns the [`Distance`] to the end of `board` for the side to move.
+    fn probe_distance(&self, board: &Board) -> Result<Distance, ProbeError>;
+}
+
+/// Returns the material on `board` in the naming convention shared by tablebase files,
+/// e.g. `"KQvK"`, with the pieces of `first` before those of the other side.
+pub(crate) fn material_name(board: &Board, first: Color) -> String {
+    format!(
+        "{}v{}",
+        board.material(first),
+        board.material(first.opposite())
+    )
+}
+
+/// Checks the preconditions shared by every tablebase probe, returning the
+/// [`Wdl`] value directly if only the two kings remain.
+pub(crate) fn check_probe(board: &Board, max_pieces: u32) -> Result<Option<Wdl>, ProbeError> {
+    let pieces = board.occupied().count();
+    if pieces > max_pieces {
+        return Err(ProbeError::TooManyPieces(pieces));
+    }
+
+    if board.castling_permissions().any() {
+        return Err(ProbeError::CastlingRights);
+    }
+
+    let only_kings = board
+        .occupied()
+        .squares()
+        .all(|square| board[square].is_some_and(|piece| piece.kind() == PieceKind::King));
+
+    Ok(only_kings.then_some(Wdl::Draw))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::Fen;
+
+    #[test]
+    fn material_names_follow_tablebase_conventions() {
+        let board = Fen::try_from("8/8/8/4k3/8/8/1p6/KR1N4 w - - 0 1")
+            .unwrap()
+            .to_standard_board();
+
+        assert_eq!(material_name(&board, Color::White), "KRNvKP");
+        assert_eq!(material_name(&board, Color::Black), "KPvKRN");
+    }
+
+    #[test]
+    fn probes_are_checked_before_lookup() {
+        let bare = Fen::try_from("8/8/8/4k3/8/8/8/K7 b - - 0 1")
+            .unwrap()
+            .to_standard_board();
+        assert_eq!(check_probe(&bare, 5).unwrap(), Some(Wdl::Draw));
+
+        let castling = Fen::try_from("4k3/8/8/8/8/8/8/4K2R w K - 0 1")
+            .unwrap()
+            .to_standard_board();
+        assert!(matches!(
+            check_probe(&castling, 5),
+            Err(ProbeError::CastlingRights)
+        ));
+        assert!(matches!(
+            check_probe(&Board::default(), 7),
+            Err(ProbeError::TooManyPieces(32))
+        ));
+        assert_eq!(Wdl::CursedWin.flip(), Wdl::BlessedLoss);
+    }
+}
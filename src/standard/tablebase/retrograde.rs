@@ -0,0 +1,562 @@
+use super::{check_probe, material_name, Distance, ProbeError, Tablebase, Wdl};
+use crate::core::{Piece as _, Standard as _};
+use crate::standard::{
+    attacks,
+    bitboard::BitBoard,
+    board::Board,
+    piece::{Color, Piece, PieceKind},
+    Square,
+};
+
+/// The number of entries in a table: a side to move, two kings, and one extra piece.
+const TABLE_SIZE: usize = 2 * 64 * 64 * 64;
+
+/// Marks an entry whose value has not been determined yet.
+const UNKNOWN: i16 = i16::MIN;
+
+/// Marks an entry which is drawn.
+const DRAW: i16 = i16::MIN + 1;
+
+/// Marks an entry which does not describe a legal position.
+const INVALID: i16 = i16::MAX;
+
+/// An ending which can be generated by a [`RetrogradeTablebase`], named
+/// with the side holding the extra piece first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Ending {
+    /// King and queen against king.
+    KQvK,
+    /// King and rook against king.
+    KRvK,
+    /// King and pawn against king.
+    KPvK,
+}
+
+impl Ending {
+    /// Returns the kind of the extra piece held by the stronger side.
+    fn kind(self) -> PieceKind {
+        match self {
+            Self::KQvK => PieceKind::Queen,
+            Self::KRvK => PieceKind::Rook,
+            Self::KPvK => PieceKind::Pawn,
+        }
+    }
+
+    /// Returns the name of the ending, e.g. `"KQvK"`.
+    fn name(self) -> &'static str {
+        match self {
+            Self::KQvK => "KQvK",
+            Self::KRvK => "KRvK",
+            Self::KPvK => "KPvK",
+        }
+    }
+}
+
+/// The values of the moves available in an entry whose
+/// results lie outside of its own table.
+#[derive(Debug, Clone, Copy, Default)]
+struct External {
+    /// The shortest distance to mate among the moves which leave the opponent lost.
+    shortest_loss: Option<i16>,
+    /// The longest distance to mate among the moves which leave the opponent winning.
+    longest_win: Option<i16>,
+    /// Whether any move leads to a draw.
+    draw: bool,
+}
+
+/// An in-memory distance-to-mate table for a single [`Ending`], generated by retrograde
+/// analysis.
+///
+/// Entries store distances in plies from the perspective of the side to move: positive
+/// values count the plies to deliver mate, and other values count the plies until the
+/// side to move is mated. Pawns always promote to queens, since an underpromotion is
+/// never better for the side with the pawn in these endings, and so the tables only
+/// need to be generated for the endings which queens lead to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetrogradeTable {
+    ending: Ending,
+    values: Vec<i16>,
+}
+
+impl RetrogradeTable {
+    /// Generates the table for `ending`, which for [`Ending::KPvK`] requires the
+    /// table for [`Ending::KQvK`] to resolve promotions.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ending` is [`Ending::KPvK`] and `queens` is not a [`Ending::KQvK`] table.
+    pub fn generate(ending: Ending, queens: Option<&RetrogradeTable>) -> Self {
+        if ending == Ending::KPvK {
+            assert!(
+                queens.is_some_and(|table| table.ending == Ending::KQvK),
+                "generating KPvK requires the KQvK table"
+            );
+        }
+
+        let mut values = vec![INVALID; TABLE_SIZE];
+        let mut offsets = vec![0u32; TABLE_SIZE + 1];
+        let mut children = Vec::new();
+        let mut externals = vec![External::default(); TABLE_SIZE];
+
+        // enumerate the legal positions, resolving those without moves
+        for index in 0..TABLE_SIZE {
+            offsets[index] = children.len() as u32;
+            let entry = Entry::from_index(index);
+            if !entry.is_valid(ending) {
+                continue;
+            }
+
+            values[index] = UNKNOWN;
+            let external = &mut externals[index];
+            let any_moves = entry.successors(ending, |successor| match successor {
+                Successor::Entry(child) => children.push(child.index() as u32),
+                Successor::Capture => record(external, DRAW),
+                Successor::Promotion(child) => {
+                    // the value of a promotion is read from the queen table
+                    let value = queens.map_or(DRAW, |table| table.values[child.index()]);
+                    record(external, value);
+                }
+            });
+
+            if !any_moves {
+                values[index] = if entry.is_check(ending) { 0 } else { DRAW };
+            }
+        }
+
+        offsets[TABLE_SIZE] = children.len() as u32;
+
+        let longest_external = externals
+            .iter()
+            .flat_map(|external| [external.shortest_loss, external.longest_win])
+            .flatten()
+            .max()
+            .unwrap_or(0);
+
+        // in round n, assign every position which is won or lost in exactly n plies
+        let mut round: i16 = 1;
+        loop {
+            let mut changed = false;
+            for index in 0..TABLE_SIZE {
+                if values[index] != UNKNOWN {
+                    continue;
+                }
+
+                let external = externals[index];
+                let mut shortest_loss = external.shortest_loss;
+                let mut longest_win = external.longest_win;
+                let mut all_wins = !external.draw;
+
+                let range = offsets[index] as usize..offsets[index + 1] as usize;
+                for &child in &children[range] {
+                    match values[child as usize] {
+                        UNKNOWN | DRAW => all_wins = false,
+                        value if value <= 0 => {
+                            shortest_loss = Some(shortest_loss.map_or(-value, |d| d.min(-value)))
+                        }
+                        value => longest_win = Some(longest_win.map_or(value, |d| d.max(value))),
+                    }
+                }
+
+                if shortest_loss == Some(round - 1) {
+                    values[index] = round;
+                    changed = true;
+                } else if all_wins && longest_win.map_or(0, |d| d + 1) == round {
+                    values[index] = -round;
+                    changed = true;
+                }
+            }
+
+            if !changed && round > longest_external + 1 {
+                break;
+            }
+
+            round += 1;
+        }
+
+        for value in values.iter_mut().filter(|value| **value == UNKNOWN) {
+            *value = DRAW;
+        }
+
+        Self { ending, values }
+    }
+
+    /// Returns the [`Ending`] covered by the table.
+    pub fn ending(&self) -> Ending {
+        self.ending
+    }
+
+    /// Returns the value stored for `board`, or `None` if `board`
+    /// does not hold the material of the table.
+    fn value(&self, board: &Board) -> Option<i16> {
+        let strong = Color::ALL
+            .into_iter()
+            .find(|&color| material_name(board, color) == self.ending.name())?;
+
+        let mirrored;
+        let board = match strong {
+            Color::White => board,
+            Color::Black => {
+                mirrored = mirror(board);
+                &mirrored
+            }
+        };
+
+        let (_, index) = locate(board)?;
+        Some(self.values[index])
+    }
+}
+
+/// A collection of [`RetrogradeTable`]s, serving as a [`Tablebase`] for trivial
+/// endings without any external files.
+///
+/// Each table covers its ending with the extra piece on either side. Since the
+/// tables are computed in memory, generating them takes a moment; they are
+/// intended for testing, teaching, and validating other [`Tablebase`]s.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RetrogradeTablebase {
+    tables: Vec<RetrogradeTable>,
+}
+
+impl RetrogradeTablebase {
+    /// Generates the tables for every given ending, along with any
+    /// tables they depend on.
+    pub fn generate(endings: &[Ending]) -> Self {
+        let mut tablebase = Self::default();
+        let needs_queens = endings.contains(&Ending::KPvK);
+
+        for ending in [Ending::KQvK, Ending::KRvK, Ending::KPvK] {
+            if endings.contains(&ending) || (ending == Ending::KQvK && needs_queens) {
+                let queens = tablebase.table(Ending::KQvK);
+                let table = RetrogradeTable::generate(ending, queens);
+                tablebase.tables.push(table);
+            }
+        }
+
+        tablebase
+    }
+
+    /// Returns the table for `ending`, if it was generated.
+    pub fn table(&self, ending: Ending) -> Option<&RetrogradeTable> {
+        self.tables.iter().find(|table| table.ending == ending)
+    }
+
+    /// Returns the value stored for `board`, after checking that it can be probed.
+    fn probe(&self, board: &Board) -> Result<i16, ProbeError> {
+        if check_probe(board, 3)?.is_some() {
+            return Ok(DRAW);
+        }
+
+        let value = self
+            .tables
+            .iter()
+            .find_map(|table| table.value(board))
+            .ok_or_else(|| ProbeError::MissingTable(material_name(board, Color::White)))?;
+
+        match value {
+            INVALID => Err(ProbeError::Unsupported("probing an illegal position")),
+            value => Ok(value),
+        }
+    }
+}
+
+impl Tablebase for RetrogradeTablebase {
+    fn max_pieces(&self) -> u32 {
+        3
+    }
+
+    fn probe_wdl(&self, board: &Board) -> Result<Wdl, ProbeError> {
+        Ok(match self.probe(board)? {
+            DRAW => Wdl::Draw,
+            value if value > 0 => Wdl::Win,
+            _ => Wdl::Loss,
+        })
+    }
+
+    fn probe_distance(&self, board: &Board) -> Result<Distance, ProbeError> {
+        Ok(match self.probe(board)? {
+            DRAW => Distance::Mate(0),
+            value => Distance::Mate(value.into()),
+        })
+    }
+}
+
+/// Folds the value of a move leaving the table into `external`.
+fn record(external: &mut External, value: i16) {
+    match value {
+        UNKNOWN | DRAW | INVALID => external.draw = true,
+        value if value <= 0 => {
+            external.shortest_loss = Some(external.shortest_loss.map_or(-value, |d| d.min(-value)))
+        }
+        value => external.longest_win = Some(external.longest_win.map_or(value, |d| d.max(value))),
+    }
+}
+
+/// The placement of the pieces in a table entry, with white holding the extra piece.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Entry {
+    white_to_move: bool,
+    white_king: Square,
+    black_king: Square,
+    piece: Square,
+}
+
+/// A position reached by a move from an [`Entry`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Successor {
+    /// A position in the same table.
+    Entry(Entry),
+    /// A position where black has captured the extra piece.
+    Capture,
+    /// A position where white has promoted its pawn to a queen.
+    Promotion(Entry),
+}
+
+impl Entry {
+    /// Returns the entry described by `index`.
+    fn from_index(index: usize) -> Self {
+        let square = |shift: usize| Square::at(((index >> shift) & 63) as u8);
+        Self {
+            white_to_move: index >> 18 == 1,
+            white_king: square(12),
+            black_king: square(6),
+            piece: square(0),
+        }
+    }
+
+    /// Returns the index of `self` in its table.
+    fn index(self) -> usize {
+        (usize::from(self.white_to_move) << 18)
+            | (usize::from(self.white_king) << 12)
+            | (usize::from(self.black_king) << 6)
+            | usize::from(self.piece)
+    }
+
+    /// Returns the squares occupied by the pieces.
+    fn occupied(self) -> BitBoard {
+        BitBoard::from_square(self.white_king)
+            | BitBoard::from_square(self.black_king)
+            | BitBoard::from_square(self.piece)
+    }
+
+    /// Returns the squares attacked by the extra piece, given the `occupied` squares.
+    fn piece_attacks(self, ending: Ending, occupied: BitBoard) -> BitBoard {
+        attacks::attacks(Color::White, ending.kind(), self.piece, occupied)
+    }
+
+    /// Returns `true` if the black king is attacked by the extra piece.
+    fn is_check(self, ending: Ending) -> bool {
+        self.piece_attacks(ending, self.occupied())
+            .contains(self.black_king)
+    }
+
+    /// Returns `true` if `self` describes a legal position.
+    fn is_valid(self, ending: Ending) -> bool {
+        let is_distinct = self.white_king != self.black_king
+            && self.piece != self.white_king
+            && self.piece != self.black_king;
+        let is_pawn_on_back_rank = ending == Ending::KPvK && matches!(self.piece.rank(), 0 | 7);
+
+        // the side which just moved cannot have left its king in check
+        is_distinct
+            && !is_pawn_on_back_rank
+            && !attacks::king(self.white_king).contains(self.black_king)
+            && !(self.white_to_move && self.is_check(ending))
+    }
+
+    /// Calls `visit` with the successor of every legal move in `self`,
+    /// returning `true` if there were any.
+    fn successors(self, ending: Ending, mut visit: impl FnMut(Successor)) -> bool {
+        let occupied = self.occupied();
+        let mut any_moves = false;
+        let mut emit = |successor| {
+            any_moves = true;
+            visit(successor);
+        };
+
+        if self.white_to_move {
+            let next = Self {
+                white_to_move: false,
+                ..self
+            };
+
+            let king_targets =
+                attacks::king(self.white_king) & !occupied & !attacks::king(self.black_king);
+            for white_king in king_targets.squares() {
+                emit(Successor::Entry(Self { white_king, ..next }));
+            }
+
+            let piece_targets = match ending {
+                Ending::KPvK => pawn_pushes(self.piece, occupied),
+                _ => self.piece_attacks(ending, occupied) & !occupied,
+            };
+
+            for piece in piece_targets.squares() {
+                match ending == Ending::KPvK && piece.rank() == 7 {
+                    true => emit(Successor::Promotion(Self { piece, ..next })),
+                    false => emit(Successor::Entry(Self { piece, ..next })),
+                }
+            }
+        } else {
+            // the black king cannot shield a square from a slider by standing on its line
+            let without_king = occupied & !BitBoard::from_square(self.black_king);
+            let attacked =
+                attacks::king(self.white_king) | self.piece_attacks(ending, without_king);
+
+            let king_targets = attacks::king(self.black_king) & !attacked;
+            for black_king in king_targets.squares() {
+                match black_king == self.piece {
+                    true => emit(Successor::Capture),
+                    false => emit(Successor::Entry(Self {
+                        white_to_move: true,
+                        black_king,
+                        ..self
+                    })),
+                }
+            }
+        }
+
+        any_moves
+    }
+}
+
+/// Returns the squares to which a white pawn on `square` can be pushed.
+fn pawn_pushes(square: Square, occupied: BitBoard) -> BitBoard {
+    let single = BitBoard::from_bits(BitBoard::from_square(square).bits() << 8) & !occupied;
+    let double = BitBoard::from_bits((single & BitBoard::rank(2)).bits() << 8) & !occupied;
+    single | double
+}
+
+/// Returns the kind of white's extra piece on `board` and the index of the board in
+/// the table for that piece, or `None` if white has no extra piece or black has one.
+fn locate(board: &Board) -> Option<(PieceKind, usize)> {
+    let mut white_king = None;
+    let mut black_king = None;
+    let mut extra = None;
+
+    for square in board.occupied().squares() {
+        let piece = board[square]?;
+        match (piece.color(), piece.kind()) {
+            (Color::White, PieceKind::King) => white_king = Some(square),
+            (Color::Black, PieceKind::King) => black_king = Some(square),
+            (Color::White, kind) if extra.is_none() => extra = Some((kind, square)),
+            _ => return None,
+        }
+    }
+
+    let (kind, square) = extra?;
+    let side_to_move = match board.side_to_move() {
+        Color::Black => 0,
+        Color::White => 1,
+    };
+
+    let index = ((side_to_move * 64 + usize::from(white_king?)) * 64 + usize::from(black_king?))
+        * 64
+        + usize::from(square);
+    Some((kind, index))
+}
+
+/// Returns `board` flipped vertically with the colors of its pieces
+/// and the side to move swapped.
+fn mirror(board: &Board) -> Board {
+    let mut pieces = [None; 64];
+    for square in board.occupied().squares() {
+        if let Some(piece) = board[square] {
+            let flipped = usize::from(square) ^ 56;
+            pieces[flipped] = Some(Piece::new(piece.color().opposite(), piece.kind()));
+        }
+    }
+
+    let side_to_move = board.side_to_move().opposite();
+    Board::from_pieces(pieces, side_to_move)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::Fen;
+
+    fn board(fen: &str) -> Board {
+        Fen::try_from(fen).unwrap().to_standard_board()
+    }
+
+    #[test]
+    fn queen_and_pawn_endings_are_solved() {
+        let tablebase = RetrogradeTablebase::generate(&[Ending::KPvK]);
+        assert!(tablebase.table(Ending::KQvK).is_some());
+        assert!(tablebase.table(Ending::KRvK).is_none());
+
+        // black is already mated
+        let mated = board("k7/1Q6/1K6/8/8/8/8/8 b - - 0 1");
+        assert_eq!(tablebase.probe_wdl(&mated).unwrap(), Wdl::Loss);
+        assert_eq!(tablebase.probe_distance(&mated).unwrap(), Distance::Mate(0));
+
+        // mate in one, with the colors either way around
+        let mate_in_one = board("k7/7Q/1K6/8/8/8/8/8 w - - 0 1");
+        assert_eq!(
+            tablebase.probe_distance(&mate_in_one).unwrap(),
+            Distance::Mate(1)
+        );
+        let mirrored = board("8/8/8/8/8/1k6/7q/K7 b - - 0 1");
+        assert_eq!(
+            tablebase.probe_distance(&mirrored).unwrap(),
+            Distance::Mate(1)
+        );
+
+        // stalemate, and a queen which is simply lost
+        let stalemate = board("k7/2Q5/1K6/8/8/8/8/8 b - - 0 1");
+        assert_eq!(tablebase.probe_wdl(&stalemate).unwrap(), Wdl::Draw);
+        let hanging = board("8/8/8/8/8/8/1kQ5/7K b - - 0 1");
+        assert_eq!(tablebase.probe_wdl(&hanging).unwrap(), Wdl::Draw);
+
+        // the longest KQvK mate takes 10 moves
+        let longest = (0..TABLE_SIZE)
+            .map(|index| tablebase.table(Ending::KQvK).unwrap().values[index])
+            .filter(|&value| value != INVALID && value != DRAW)
+            .max()
+            .unwrap();
+        assert_eq!(longest, 19);
+
+        // a rook pawn with the defending king in front of it is a draw, and so
+        // is a centre pawn when the defending king can take the opposition
+        let rook_pawn = board("k7/8/8/8/8/8/P7/K7 w - - 0 1");
+        assert_eq!(tablebase.probe_wdl(&rook_pawn).unwrap(), Wdl::Draw);
+        let opposition = board("8/8/4k3/8/4P3/4K3/8/8 w - - 0 1");
+        assert_eq!(tablebase.probe_wdl(&opposition).unwrap(), Wdl::Draw);
+
+        // but a king on the sixth rank in front of its pawn always wins
+        let sixth_rank = board("4k3/8/4K3/4P3/8/8/8/8 b - - 0 1");
+        assert_eq!(tablebase.probe_wdl(&sixth_rank).unwrap(), Wdl::Loss);
+        let sixth_rank = board("4k3/8/4K3/4P3/8/8/8/8 w - - 0 1");
+        assert_eq!(tablebase.probe_wdl(&sixth_rank).unwrap(), Wdl::Win);
+
+        let rook = board("k7/8/8/8/8/8/8/KR6 w - - 0 1");
+        assert!(matches!(
+            tablebase.probe_wdl(&rook),
+            Err(ProbeError::MissingTable(_))
+        ));
+    }
+
+    #[test]
+    fn rook_endings_are_solved() {
+        let tablebase = RetrogradeTablebase::generate(&[Ending::KRvK]);
+        let table = tablebase.table(Ending::KRvK).unwrap();
+        assert_eq!(table.ending(), Ending::KRvK);
+
+        // the longest KRvK mate takes 16 moves
+        let longest = table
+            .values
+            .iter()
+            .copied()
+            .filter(|&value| value != INVALID && value != DRAW)
+            .max()
+            .unwrap();
+        assert_eq!(longest, 31);
+
+        let mate_in_one = board("k7/8/1K6/8/8/8/8/7R w - - 0 1");
+        assert_eq!(tablebase.probe_wdl(&mate_in_one).unwrap(), Wdl::Win);
+        assert_eq!(
+            tablebase.probe_distance(&mate_in_one).unwrap(),
+            Distance::Mate(1)
+        );
+    }
+}
@@ -0,0 +1,214 @@
+use super::{
+    attacks,
+    bitboard::BitBoard,
+    board::Board,
+    movegen::value,
+    piece::{Color, Piece, PieceKind},
+    Square,
+};
+use crate::core::{Piece as _, Standard as _};
+
+/// The kinds of piece in the order in which they join an exchange, least valuable first.
+const EXCHANGE_ORDER: [PieceKind; 6] = [
+    PieceKind::Pawn,
+    PieceKind::Knight,
+    PieceKind::Bishop,
+    PieceKind::Rook,
+    PieceKind::Queen,
+    PieceKind::King,
+];
+
+/// Why a piece is described by a [`Threat`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ThreatKind {
+    /// The piece is attacked by a piece worth less than itself, and so
+    /// loses material even if it is defended.
+    LesserAttacker,
+    /// The piece is attacked and not defended at all.
+    Undefended,
+    /// The piece is attacked by more pieces than defend it.
+    Underdefended,
+}
+
+/// A piece which the opponent of its side attacks without enough defence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Threat {
+    /// The threatened piece.
+    pub piece: Piece,
+    /// The square of the threatened piece.
+    pub square: Square,
+    /// The squares of the enemy pieces attacking it.
+    pub attackers: BitBoard,
+    /// Why the piece is threatened.
+    pub kind: ThreatKind,
+}
+
+impl Board {
+    /// Returns the squares of the pieces of the given `color` which the other side
+    /// wins material by capturing, i.e. those whose static exchange evaluation is
+    /// positive for their attackers.
+    ///
+    /// Kings are never hanging; pins are ignored, so a pinned attacker or defender
+    /// still takes part in the exchange.
+    pub fn hanging_pieces(&self, color: Color) -> BitBoard {
+        let attacked = self.attack_map(color.opposite()).attacked();
+        let targets = (attacked & self.occupancy(color)) & !self.pieces(color, PieceKind::King);
+        targets
+            .squares()
+            .filter(|&square| self.exchange(square, color.opposite()) > 0)
+            .fold(BitBoard::EMPTY, |hanging, square| {
+                hanging | BitBoard::from_square(square)
+            })
+    }
+
+    /// Returns the [`Threat`]s against the pieces of the side to move, in order
+    /// from a1 to h8, which is what it must answer before anything else.
+    ///
+    /// Each threatened piece is described once, by the first of the kinds of
+    /// [`ThreatKind`] which applies to it. Checks are not threats, since the
+    /// king is never captured.
+    pub fn threats(&self) -> Vec<Threat> {
+        let color = self.side_to_move();
+        let (attacks, defences) = (self.attack_map(color.opposite()), self.attack_map(color));
+
+        let mut threats = Vec::new();
+        for square in (attacks.attacked() & self.occupancy(color)).squares() {
+            let Some(piece) = self[square] else {
+                continue;
+            };
+
+            let attackers = attacks.attackers_of(square);
+            let defenders = defences.attackers_of(square);
+            let least_attacker = EXCHANGE_ORDER
+                .into_iter()
+                .find(|&kind| !(attackers & self.pieces(color.opposite(), kind)).is_empty());
+
+            let kind = match least_attacker {
+                _ if piece.kind() == PieceKind::King => continue,
+                Some(attacker) if value(attacker) < value(piece.kind()) => {
+                    ThreatKind::LesserAttacker
+                }
+                _ if defenders.is_empty() => ThreatKind::Undefended,
+                _ if attackers.count() > defenders.count() => ThreatKind::Underdefended,
+                _ => continue,
+            };
+
+            threats.push(Threat {
+                piece,
+                square,
+                attackers,
+                kind,
+            });
+        }
+
+        threats
+    }
+
+    /// Returns the static exchange evaluation of capturing the piece on `target`
+    /// with the pieces of color `by`: the material they win, in pawns, if both
+    /// sides keep capturing on `target` with their least valuable piece for as
+    /// long as it profits them. The first capture is always made.
+    ///
+    /// Sliding pieces behind the capturing pieces join the exchange as the pieces
+    /// in front of them are used up.
+    pub(crate) fn exchange(&self, target: Square, by: Color) -> i16 {
+        let Some(victim) = self[target] else {
+            return 0;
+        };
+
+        let mut occupied = self.occupied();
+        let mut gains = [0; 32];
+        gains[0] = value(victim.kind());
+
+        let mut depth = 0;
+        let mut side = by;
+        while let Some((source, kind)) = self.least_valuable_attacker(target, side, occupied) {
+            // what the other side gains by recapturing the piece which just captured
+            depth += 1;
+            gains[depth] = value(kind) - gains[depth - 1];
+            occupied ^= BitBoard::from_square(source);
+            side = side.opposite();
+        }
+
+        // each side may stop capturing instead of losing material by continuing
+        while depth > 1 {
+            depth -= 1;
+            gains[depth - 1] = -(-gains[depth - 1]).max(gains[depth]);
+        }
+
+        gains[0]
+    }
+
+    /// Returns the square and kind of the least valuable piece of color `by`
+    /// attacking `target` among the `occupied` squares, if there is one.
+    fn least_valuable_attacker(
+        &self,
+        target: Square,
+        by: Color,
+        occupied: BitBoard,
+    ) -> Option<(Square, PieceKind)> {
+        EXCHANGE_ORDER.into_iter().find_map(|kind| {
+            let attackers = attacks::attacks(by.opposite(), kind, target, occupied)
+                & self.pieces(by, kind)
+                & occupied;
+            attackers.squares().next().map(|square| (square, kind))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::Fen;
+
+    fn board(fen: &str) -> Board {
+        Fen::try_from(fen).unwrap().to_standard_board()
+    }
+
+    #[test]
+    fn exchanges_are_evaluated_with_x_rays() {
+        let square = |name| Square::try_from(name).unwrap();
+
+        // the knight takes a pawn defended by a pawn, and is lost for it
+        let defended = board("4k3/8/2p5/3p4/8/4N3/8/4K3 w - - 0 1");
+        assert_eq!(defended.exchange(square("d5"), Color::White), -2);
+
+        // the rooks win the pawn, since the second rook recaptures behind the first
+        let battery = board("3rk3/8/8/3p4/8/8/3R4/3RK3 w - - 0 1");
+        assert_eq!(battery.exchange(square("d5"), Color::White), 1);
+        assert_eq!(battery.exchange(square("d4"), Color::White), 0);
+    }
+
+    #[test]
+    fn hanging_pieces_and_threats_are_found() {
+        let square = |name| Square::try_from(name).unwrap();
+
+        // the knight on c3 is attacked by a pawn, the undefended bishop on g5 by a
+        // rook, and the rook on d1 by a rook, though it is defended twice; in turn,
+        // the bishop and the rook on d1 attack the rook on d8
+        let board = board("3rk1r1/8/8/6B1/1p6/2N5/8/3RK3 w - - 0 1");
+        assert_eq!(
+            board.hanging_pieces(Color::White),
+            BitBoard::from_square(square("c3")) | BitBoard::from_square(square("g5"))
+        );
+        assert_eq!(
+            board.hanging_pieces(Color::Black),
+            BitBoard::from_square(square("d8"))
+        );
+
+        let threats = board.threats();
+        let kinds: Vec<_> = threats
+            .iter()
+            .map(|threat| (threat.square, threat.kind))
+            .collect();
+        assert_eq!(
+            kinds,
+            [
+                (square("c3"), ThreatKind::LesserAttacker),
+                (square("g5"), ThreatKind::Undefended)
+            ]
+        );
+        assert_eq!(threats[0].piece, Piece::WhiteKnight);
+        assert_eq!(threats[0].attackers, BitBoard::from_square(square("b4")));
+    }
+}
@@ -0,0 +1,156 @@
+//! Bindings exposing the standard board to JavaScript through `wasm-bindgen`.
+//!
+//! Squares and moves cross the boundary as strings, so that a browser GUI
+//! can drive a game without mirroring any of this crate's types: positions
+//! are FEN strings, and moves are read in SAN or UCI and written in both.
+//!
+//! The crate is not built as a `cdylib` by default; see the README for how
+//! to build these bindings for `wasm32-unknown-unknown`.
+
+use wasm_bindgen::prelude::*;
+
+use crate::core::{Process as _, Standard as _};
+use crate::io::notation::{Notation, Uci};
+use crate::io::{Fen, San};
+use crate::standard::{Board, Color, LegalMove, Square};
+use crate::Error;
+
+/// A position of standard chess, exported to JavaScript as `Board`.
+#[wasm_bindgen(js_name = Board)]
+#[derive(Debug, Clone, Default)]
+pub struct JsBoard {
+    board: Board,
+}
+
+#[wasm_bindgen(js_class = Board)]
+impl JsBoard {
+    /// Constructs the starting position.
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Constructs the position described by `fen`, which must be reachable in a game.
+    #[wasm_bindgen(js_name = fromFen)]
+    pub fn from_fen(fen: &str) -> Result<JsBoard, JsError> {
+        let fen = Fen::try_from(fen).map_err(Error::from)?;
+        let board = fen.to_validated_board().map_err(Error::from)?;
+        Ok(Self { board })
+    }
+
+    /// Returns the FEN string of the position.
+    pub fn fen(&self) -> String {
+        Fen::from(&self.board).to_string()
+    }
+
+    /// Returns `"w"` or `"b"` for the side to move.
+    #[wasm_bindgen(js_name = sideToMove)]
+    pub fn side_to_move(&self) -> String {
+        match self.board.side_to_move() {
+            Color::White => String::from("w"),
+            Color::Black => String::from("b"),
+        }
+    }
+
+    /// Returns the FEN character of the piece on `square`, e.g. `"K"` on `"e1"`,
+    /// or `undefined` if the square is empty.
+    #[wasm_bindgen(js_name = pieceAt)]
+    pub fn piece_at(&self, square: &str) -> Result<Option<String>, JsError> {
+        let square = Square::try_from(square).map_err(Error::from)?;
+        Ok(self.board[square].map(|piece| piece.to_string()))
+    }
+
+    /// Returns the legal moves of the position in UCI notation, e.g. `"e2e4"`.
+    #[wasm_bindgen(js_name = legalMoves)]
+    pub fn legal_moves(&self) -> Vec<String> {
+        self.board
+            .legal_moves()
+            .map(|legal_move| Uci::format(&legal_move, &self.board))
+            .collect()
+    }
+
+    /// Returns the legal moves of the position in SAN, e.g. `"Nf3"`.
+    #[wasm_bindgen(js_name = legalMovesSan)]
+    pub fn legal_moves_san(&self) -> Vec<String> {
+        self.board
+            .legal_moves()
+            .map(|legal_move| San::format(&self.board, legal_move))
+            .collect()
+    }
+
+    /// Returns `true` if the side to move is in check.
+    #[wasm_bindgen(js_name = isCheck)]
+    pub fn is_check(&self) -> bool {
+        self.board.is_check()
+    }
+
+    /// Returns `true` if the side to move has been checkmated.
+    #[wasm_bindgen(js_name = isCheckmate)]
+    pub fn is_checkmate(&self) -> bool {
+        self.board.is_check() && !self.board.has_legal_moves()
+    }
+
+    /// Returns `true` if the side to move has no legal moves but is not in check.
+    #[wasm_bindgen(js_name = isStalemate)]
+    pub fn is_stalemate(&self) -> bool {
+        !self.board.is_check() && !self.board.has_legal_moves()
+    }
+
+    /// Plays `literal`, a move in SAN or UCI notation, and returns it in SAN.
+    ///
+    /// An error is thrown if the move is not legal, and the position is unchanged.
+    pub fn play(&mut self, literal: &str) -> Result<String, JsError> {
+        let legal_move = self.resolve(literal).ok_or_else(|| {
+            JsError::new(&format!("{literal} is not a legal move in {}", self.fen()))
+        })?;
+
+        let san = San::format(&self.board, legal_move);
+        self.board = self.board.process(legal_move);
+        Ok(san)
+    }
+}
+
+impl JsBoard {
+    /// Reads `literal` as a legal move in SAN, and then in UCI notation.
+    fn resolve(&self, literal: &str) -> Option<LegalMove> {
+        San::read(literal, &self.board).or_else(|| Uci::read(literal, &self.board))
+    }
+}
+
+impl From<Board> for JsBoard {
+    fn from(board: Board) -> Self {
+        Self { board }
+    }
+}
+
+impl From<JsBoard> for Board {
+    fn from(value: JsBoard) -> Self {
+        value.board
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // only the paths which never construct a `JsError` can run off the web
+
+    #[test]
+    fn boards_are_driven_by_strings() {
+        let mut board = JsBoard::new();
+        assert_eq!(board.legal_moves().len(), 20);
+        assert!(board.legal_moves_san().contains(&String::from("Nf3")));
+
+        for (literal, san) in [("f3", "f3"), ("e7e5", "e5"), ("g4", "g4"), ("Qh4", "Qh4#")] {
+            assert_eq!(board.play(literal).ok().as_deref(), Some(san));
+        }
+
+        assert!(board.is_checkmate());
+        assert!(!board.is_stalemate());
+        assert_eq!(board.side_to_move(), "w");
+        assert_eq!(board.piece_at("h4").ok().flatten().as_deref(), Some("q"));
+
+        let restored = JsBoard::from_fen(&board.fen()).ok().unwrap();
+        assert_eq!(restored.fen(), board.fen());
+    }
+}